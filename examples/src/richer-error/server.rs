@@ -26,7 +26,7 @@ impl Greeter for MyGreeter {
         let mut err_details = ErrorDetails::new();
 
         // Add error details conditionally
-        if name == "" {
+        if name.is_empty() {
             err_details.add_bad_request_violation("name", "name cannot be empty");
         } else if name.len() > 20 {
             err_details.add_bad_request_violation("name", "name is too long");
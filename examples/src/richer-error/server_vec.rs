@@ -26,7 +26,7 @@ impl Greeter for MyGreeter {
         let mut bad_request = BadRequest::new(vec![]);
 
         // Add violations conditionally
-        if name == "" {
+        if name.is_empty() {
             bad_request.add_violation("name", "name cannot be empty");
         } else if name.len() > 20 {
             bad_request.add_violation("name", "name is too long");
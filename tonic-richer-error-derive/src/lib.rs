@@ -0,0 +1,310 @@
+/*!
+Derive macro for the `tonic-richer-error` crate. Should be used through the
+`derive` feature of `tonic-richer-error`, which re-exports [`IntoStatus`].
+*/
+
+#![warn(missing_debug_implementations, missing_docs, rust_2018_idioms)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, LitInt, LitStr};
+
+/// Generates a `From<T> for tonic::Status` implementation, adding the error
+/// details described by the attributes of each enum variant (or of the
+/// struct). Attributes placed on the enum itself apply to every variant.
+///
+/// The following attributes are supported:
+/// - `#[code(InvalidArgument)]`: `tonic::Code` of the status. If absent, the
+///   code is inferred from the error details, as in
+///   `ErrorDetails::inferred_code`.
+/// - `#[message("...")]`: status message. If absent, the `Display`
+///   implementation of the type is used.
+/// - `#[retry_info(delay_secs = 5)]` or `#[retry_info(delay_ms = 500)]`
+/// - `#[quota_failure(subject = "...", description = "...")]`
+/// - `#[error_info(reason = "...", domain = "...")]`
+/// - `#[precondition_failure(type = "...", subject = "...", description = "...")]`
+/// - `#[bad_request(field = "...", description = "...")]`
+/// - `#[resource_info(resource_type = "...", resource_name = "...", owner = "...", description = "...")]`
+/// - `#[help(description = "...", url = "...")]`
+/// - `#[localized_message(locale = "...", message = "...")]`
+///
+/// Violation and link attributes can be repeated.
+#[proc_macro_derive(
+    IntoStatus,
+    attributes(
+        code,
+        message,
+        retry_info,
+        quota_failure,
+        error_info,
+        precondition_failure,
+        bad_request,
+        resource_info,
+        help,
+        localized_message
+    )
+)]
+pub fn derive_into_status(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Status code, message and details statements parsed from attributes.
+#[derive(Default)]
+struct StatusAttrs {
+    code: Option<Ident>,
+    message: Option<LitStr>,
+    details: Vec<TokenStream2>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let shared = parse_attrs(&input.attrs)?;
+
+    let arms = match &input.data {
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                let pattern = match variant.fields {
+                    Fields::Named(_) => quote!(#name::#ident { .. }),
+                    Fields::Unnamed(_) => quote!(#name::#ident(..)),
+                    Fields::Unit => quote!(#name::#ident),
+                };
+                let attrs = parse_attrs(&variant.attrs)?;
+                Ok(expand_arm(pattern, &shared, attrs))
+            })
+            .collect::<syn::Result<Vec<TokenStream2>>>()?,
+        Data::Struct(_) => vec![expand_arm(quote!(_), &shared, StatusAttrs::default())],
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "IntoStatus cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for ::tonic::Status #where_clause {
+            fn from(err: #name #ty_generics) -> Self {
+                let mut details = ::tonic_richer_error::ErrorDetails::new();
+
+                let (code, message): (
+                    ::core::option::Option<::tonic::Code>,
+                    ::std::string::String,
+                ) = match &err {
+                    #(#arms)*
+                };
+
+                match code {
+                    ::core::option::Option::Some(code) => {
+                        <::tonic::Status as ::tonic_richer_error::WithErrorDetails>::with_error_details(
+                            code, message, details,
+                        )
+                    }
+                    ::core::option::Option::None => details.into_status(message),
+                }
+            }
+        }
+    })
+}
+
+fn expand_arm(pattern: TokenStream2, shared: &StatusAttrs, attrs: StatusAttrs) -> TokenStream2 {
+    let code = match attrs.code.as_ref().or(shared.code.as_ref()) {
+        Some(code) => quote!(::core::option::Option::Some(::tonic::Code::#code)),
+        None => quote!(::core::option::Option::None),
+    };
+
+    let message = match attrs.message.as_ref().or(shared.message.as_ref()) {
+        Some(message) => quote!(::std::string::String::from(#message)),
+        None => quote!(::std::string::ToString::to_string(&err)),
+    };
+
+    let shared_details = &shared.details;
+    let details = &attrs.details;
+
+    quote! {
+        #pattern => {
+            #(#shared_details)*
+            #(#details)*
+            (#code, #message)
+        }
+    }
+}
+
+fn parse_attrs(attrs: &[Attribute]) -> syn::Result<StatusAttrs> {
+    let mut parsed = StatusAttrs::default();
+
+    for attr in attrs.iter() {
+        let path = attr.path();
+
+        if path.is_ident("code") {
+            parsed.code = Some(attr.parse_args::<Ident>()?);
+        } else if path.is_ident("message") {
+            parsed.message = Some(attr.parse_args::<LitStr>()?);
+        } else if path.is_ident("retry_info") {
+            parsed.details.push(parse_retry_info(attr)?);
+        } else if path.is_ident("quota_failure") {
+            let [subject, description] = parse_args(attr, ["subject", "description"], [])?;
+            parsed.details.push(quote! {
+                details.add_quota_failure_violation(#subject, #description);
+            });
+        } else if path.is_ident("error_info") {
+            let [reason, domain] = parse_args(attr, ["reason", "domain"], [])?;
+            parsed.details.push(quote! {
+                details.set_error_info(#reason, #domain, ::std::collections::BTreeMap::new());
+            });
+        } else if path.is_ident("precondition_failure") {
+            let [violation_type, subject, description] =
+                parse_args(attr, ["type", "subject", "description"], [])?;
+            parsed.details.push(quote! {
+                details.add_precondition_failure_violation(#violation_type, #subject, #description);
+            });
+        } else if path.is_ident("bad_request") {
+            let [field, description] = parse_args(attr, ["field", "description"], [])?;
+            parsed.details.push(quote! {
+                details.add_bad_request_violation(#field, #description);
+            });
+        } else if path.is_ident("resource_info") {
+            let [resource_type, resource_name, owner, description] = parse_args(
+                attr,
+                ["resource_type", "resource_name", "owner", "description"],
+                ["owner", "description"],
+            )?;
+            parsed.details.push(quote! {
+                details.set_resource_info(#resource_type, #resource_name, #owner, #description);
+            });
+        } else if path.is_ident("help") {
+            let [description, url] = parse_args(attr, ["description", "url"], [])?;
+            parsed.details.push(quote! {
+                details.add_help_link(#description, #url);
+            });
+        } else if path.is_ident("localized_message") {
+            let [locale, message] = parse_args(attr, ["locale", "message"], [])?;
+            parsed.details.push(quote! {
+                details.set_localized_message(#locale, #message);
+            });
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Parses `key = "value"` arguments, in the order of `keys`. Keys listed in
+/// `optional` default to an empty string.
+fn parse_args<const N: usize, const M: usize>(
+    attr: &Attribute,
+    keys: [&str; N],
+    optional: [&str; M],
+) -> syn::Result<[LitStr; N]> {
+    let mut values: [Option<LitStr>; N] = std::array::from_fn(|_| None);
+
+    attr.parse_nested_meta(|meta| {
+        let index = keys
+            .iter()
+            .position(|key| meta.path.is_ident(key))
+            .ok_or_else(|| meta.error(format!("expected one of: {}", keys.join(", "))))?;
+
+        values[index] = Some(meta.value()?.parse()?);
+
+        Ok(())
+    })?;
+
+    let mut missing = Vec::new();
+
+    let values = std::array::from_fn(|i| match values[i].take() {
+        Some(value) => value,
+        None => {
+            if !optional.contains(&keys[i]) {
+                missing.push(keys[i]);
+            }
+            LitStr::new("", Span::call_site())
+        }
+    });
+
+    if !missing.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            format!("missing arguments: {}", missing.join(", ")),
+        ));
+    }
+
+    Ok(values)
+}
+
+fn parse_retry_info(attr: &Attribute) -> syn::Result<TokenStream2> {
+    if let syn::Meta::Path(_) = attr.meta {
+        return Ok(quote! {
+            details.set_retry_info(::core::option::Option::None);
+        });
+    }
+
+    let mut delay = None;
+
+    attr.parse_nested_meta(|meta| {
+        let value: LitInt = meta.value()?.parse()?;
+
+        if meta.path.is_ident("delay_secs") {
+            delay = Some(quote!(::std::time::Duration::from_secs(#value)));
+        } else if meta.path.is_ident("delay_ms") {
+            delay = Some(quote!(::std::time::Duration::from_millis(#value)));
+        } else {
+            return Err(meta.error("expected one of: delay_secs, delay_ms"));
+        }
+
+        Ok(())
+    })?;
+
+    let delay = match delay {
+        Some(delay) => quote!(::core::option::Option::Some(#delay)),
+        None => quote!(::core::option::Option::None),
+    };
+
+    Ok(quote! {
+        details.set_retry_info(#delay);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::expand;
+
+    #[test]
+    fn reject_invalid_attrs() {
+        let input = syn::parse_quote! {
+            enum AppError {
+                #[bad_request(field = "email")]
+                InvalidEmail,
+            }
+        };
+
+        let err = match expand(input) {
+            Ok(_) => panic!("attribute without description was accepted"),
+            Err(err) => err,
+        };
+
+        assert!(
+            err.to_string().eq("missing arguments: description"),
+            "error differs from expected result"
+        );
+
+        let input = syn::parse_quote! {
+            #[error_info(domain = "example.local", reason = "SOME_REASON")]
+            enum AppError {
+                #[code(InvalidArgument)]
+                #[precondition_failure(type = "TOS", subject = "user", description = "terms")]
+                Terms,
+            }
+        };
+
+        assert!(expand(input).is_ok(), "valid attributes were rejected");
+    }
+}
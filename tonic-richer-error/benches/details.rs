@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use tonic::{Code, Status};
+use tonic_richer_error::{
+    pack_any, unpack_any, AnyMessage, BadRequest, DebugInfo, ErrorDetails, ErrorInfo, Help,
+    LocalizedMessage, PreconditionFailure, QuotaFailure, RequestInfo, ResourceInfo, RetryInfo,
+    WithErrorDetails,
+};
+
+/// Error details with a single bad request violation.
+fn small_details() -> ErrorDetails {
+    ErrorDetails::with_bad_request_violation("name", "is required")
+}
+
+/// Error details with a few details, as commonly returned by services.
+fn medium_details() -> ErrorDetails {
+    let mut metadata = BTreeMap::new();
+    metadata.insert("sku".to_string(), "A-1".to_string());
+
+    let mut err_details = ErrorDetails::new();
+
+    err_details
+        .set_retry_info(Some(Duration::from_secs(5)))
+        .set_error_info("INVALID_ITEMS", "shop.example.local", metadata)
+        .set_request_info("request-id", "")
+        .add_help_link("item rules", "https://shop.example.local/docs/items");
+
+    for index in 0..10 {
+        err_details.add_bad_request_violation(format!("items[{index}].sku"), "is invalid");
+    }
+
+    err_details
+}
+
+/// Error details with every standard detail and long lists.
+fn large_details() -> ErrorDetails {
+    let mut err_details = medium_details();
+
+    err_details
+        .set_debug_info(
+            (0..50).map(|index| format!("frame {index}")).collect(),
+            "details",
+        )
+        .set_resource_info(
+            "shop.example.local/Item",
+            "items/A-1",
+            "owner",
+            "description",
+        )
+        .set_localized_message("en-US", "some items are invalid");
+
+    for index in 0..100 {
+        err_details
+            .add_bad_request_violation(format!("lines[{index}].quantity"), "must be positive")
+            .add_quota_failure_violation(format!("project:{index}"), "quota exceeded")
+            .add_precondition_failure_violation("STOCK", format!("items/{index}"), "out of stock");
+    }
+
+    err_details
+}
+
+/// A named generator of error details.
+type DetailSet = (&'static str, fn() -> ErrorDetails);
+
+const DETAIL_SETS: [DetailSet; 3] = [
+    ("small", small_details),
+    ("medium", medium_details),
+    ("large", large_details),
+];
+
+fn gen_status(c: &mut Criterion) {
+    let mut group = c.benchmark_group("with_error_details");
+
+    for (name, details) in DETAIL_SETS {
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.iter_batched(
+                details,
+                |details| Status::with_error_details(Code::InvalidArgument, "bad request", details),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+fn extract_details(c: &mut Criterion) {
+    let mut group = c.benchmark_group("error_details");
+
+    for (name, details) in DETAIL_SETS {
+        let status = Status::with_error_details(Code::InvalidArgument, "bad request", details());
+
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.iter(|| status.error_details())
+        });
+    }
+
+    group.finish();
+}
+
+fn single_type_getters(c: &mut Criterion) {
+    let mut group = c.benchmark_group("single_type_getters");
+
+    for (name, details) in DETAIL_SETS {
+        let status = Status::with_error_details(Code::InvalidArgument, "bad request", details());
+
+        group.bench_function(BenchmarkId::new("get_details_bad_request", name), |b| {
+            b.iter(|| status.get_details_bad_request())
+        });
+        group.bench_function(BenchmarkId::new("get_details_error_info", name), |b| {
+            b.iter(|| status.get_details_error_info())
+        });
+        group.bench_function(BenchmarkId::new("get_details_retry_info", name), |b| {
+            b.iter(|| status.get_details_retry_info())
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmarks packing a detail into an `Any`, and unpacking it back.
+fn bench_any<T: AnyMessage>(c: &mut Criterion, name: &str, detail: Option<T>) {
+    let detail = match detail {
+        Some(detail) => detail,
+        None => return,
+    };
+
+    let any = pack_any(&detail);
+
+    c.bench_function(&format!("into_any/{name}"), |b| {
+        b.iter(|| pack_any(&detail))
+    });
+    c.bench_function(&format!("from_any/{name}"), |b| {
+        b.iter(|| unpack_any::<T>(&any))
+    });
+}
+
+fn per_detail_any(c: &mut Criterion) {
+    let details = large_details();
+
+    bench_any::<RetryInfo>(c, "retry_info", details.retry_info);
+    bench_any::<DebugInfo>(c, "debug_info", details.debug_info);
+    bench_any::<QuotaFailure>(c, "quota_failure", details.quota_failure);
+    bench_any::<ErrorInfo>(c, "error_info", details.error_info);
+    bench_any::<PreconditionFailure>(c, "precondition_failure", details.precondition_failure);
+    bench_any::<BadRequest>(c, "bad_request", details.bad_request);
+    bench_any::<RequestInfo>(c, "request_info", details.request_info);
+    bench_any::<ResourceInfo>(c, "resource_info", details.resource_info);
+    bench_any::<Help>(c, "help", details.help);
+    bench_any::<LocalizedMessage>(c, "localized_message", details.localized_message);
+}
+
+criterion_group!(
+    benches,
+    gen_status,
+    extract_details,
+    single_type_getters,
+    per_detail_any
+);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use prost::bytes::BytesMut;
+use tonic::{Code, Status};
+use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+
+fn bad_request_details() -> ErrorDetails {
+    let mut err_details = ErrorDetails::new();
+
+    for index in 0..10 {
+        err_details.add_bad_request_violation(format!("items[{index}].sku"), "is invalid");
+    }
+
+    err_details
+}
+
+fn encode_buffer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode_buffer");
+
+    group.bench_function("with_error_details", |b| {
+        b.iter_batched(
+            bad_request_details,
+            |details| Status::with_error_details(Code::InvalidArgument, "bad request", details),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+
+    let mut buf = BytesMut::new();
+
+    group.bench_function("with_error_details_into", |b| {
+        b.iter_batched(
+            bad_request_details,
+            |details| {
+                Status::with_error_details_into(
+                    Code::InvalidArgument,
+                    "bad request",
+                    details,
+                    &mut buf,
+                )
+            },
+            criterion::BatchSize::SmallInput,
+        )
+    });
+
+    #[cfg(feature = "buffer-pool")]
+    group.bench_function("with_error_details_pooled", |b| {
+        use tonic_richer_error::PooledStatusExt;
+
+        b.iter_batched(
+            bad_request_details,
+            |details| {
+                Status::with_error_details_pooled(Code::InvalidArgument, "bad request", details)
+            },
+            criterion::BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, encode_buffer);
+criterion_main!(benches);
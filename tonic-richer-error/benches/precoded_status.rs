@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use tonic::{Code, Status};
+use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+
+fn rate_limited_details() -> ErrorDetails {
+    let mut err_details = ErrorDetails::new();
+
+    err_details
+        .set_retry_info(Some(Duration::from_secs(1)))
+        .add_quota_failure_violation("clientip:127.0.0.1", "rate exceeded")
+        .set_error_info("RATE_LIMITED", "example.local", Default::default())
+        .add_help_link("rate limits", "https://example.local/docs/rate-limits");
+
+    err_details
+}
+
+fn precoded_status(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rate_limited_status");
+
+    group.bench_function("with_error_details", |b| {
+        b.iter(|| {
+            Status::with_error_details(
+                Code::ResourceExhausted,
+                "rate exceeded",
+                rate_limited_details(),
+            )
+        })
+    });
+
+    let precoded = rate_limited_details().freeze(Code::ResourceExhausted, "rate exceeded");
+
+    group.bench_function("precoded_to_status", |b| b.iter(|| precoded.to_status()));
+
+    group.finish();
+}
+
+criterion_group!(benches, precoded_status);
+criterion_main!(benches);
@@ -1,6 +1,12 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    prost_build::compile_protos(
-        &["proto/status.proto", "proto/error_details.proto"],
+    // Map fields are generated as `BTreeMap`s, so their entries are always
+    // encoded in key order, and encoding the same details yields the same bytes
+    prost_build::Config::new().btree_map(["."]).compile_protos(
+        &[
+            "proto/status.proto",
+            "proto/error_details.proto",
+            "proto/http.proto",
+        ],
         &["proto/"],
     )?;
     Ok(())
@@ -0,0 +1,268 @@
+use tonic::{Code, Status};
+
+use super::{ErrorDetails, WithErrorDetails};
+
+/// Codes ordered by precedence, used to choose the code of an
+/// [`AggregatedStatus`]. Errors the client must act on come first, followed
+/// by non-retryable server errors, and then by transient ones, so the
+/// aggregated status is only retryable if every failure is.
+const CODE_PRECEDENCE: [Code; 16] = [
+    Code::Unauthenticated,
+    Code::PermissionDenied,
+    Code::InvalidArgument,
+    Code::OutOfRange,
+    Code::NotFound,
+    Code::AlreadyExists,
+    Code::FailedPrecondition,
+    Code::DataLoss,
+    Code::Internal,
+    Code::Unimplemented,
+    Code::Unknown,
+    Code::ResourceExhausted,
+    Code::Aborted,
+    Code::Unavailable,
+    Code::DeadlineExceeded,
+    Code::Cancelled,
+];
+
+/// Collects the failed statuses of calls made in parallel, such as with
+/// `futures::future::join_all`, and combines them into a single
+/// `tonic::Status`. Each failure is tagged with the name of its source.
+///
+/// [`crate::BadRequest`] field violations are prefixed with the source, as
+/// `{source}.{field}`, and [`crate::QuotaFailure`] violation subjects as
+/// `{source}/{subject}`. Other details are merged as described in
+/// [`ErrorDetails::merge`]. If no code is set, the code of the most
+/// significant failure is used: authentication, authorization and request
+/// errors take precedence over server errors, which take precedence over
+/// transient errors such as `Code::Unavailable`.
+/// # Examples
+///
+/// ```
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{AggregatedStatus, ErrorDetails, WithErrorDetails};
+///
+/// let results: Vec<(&str, Result<(), Status>)> = vec![
+///     ("inventory", Err(Status::unavailable("inventory unavailable"))),
+///     ("pricing", Ok(())),
+///     (
+///         "shipping",
+///         Err(Status::with_error_details(
+///             Code::InvalidArgument,
+///             "invalid address",
+///             ErrorDetails::with_bad_request_violation("zip_code", "must have 5 digits"),
+///         )),
+///     ),
+/// ];
+///
+/// let status = results
+///     .into_iter()
+///     .fold(AggregatedStatus::new(), |aggregated, (source, result)| {
+///         aggregated.add_result(source, result)
+///     })
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(status.code(), Code::InvalidArgument);
+/// assert_eq!(
+///     status.get_details_bad_request().unwrap().field_violations[0].field,
+///     "shipping.zip_code"
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AggregatedStatus {
+    code: Option<Code>,
+    message: Option<String>,
+    failures: Vec<(String, Status)>,
+}
+
+impl AggregatedStatus {
+    /// Creates a new, empty [`AggregatedStatus`].
+    pub fn new() -> Self {
+        AggregatedStatus::default()
+    }
+
+    /// Sets the code of the aggregated status, instead of choosing it from
+    /// the failures.
+    pub fn code(mut self, code: Code) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the message of the aggregated status. Defaults to the messages
+    /// of the failures, prefixed with their sources, as
+    /// `{source}: {message}`, and separated by `; `.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Adds the failed status of a call to `source`. Statuses with
+    /// `Code::Ok` are ignored.
+    pub fn add(mut self, source: impl Into<String>, status: Status) -> Self {
+        if status.code() != Code::Ok {
+            self.failures.push((source.into(), status));
+        }
+        self
+    }
+
+    /// Adds the result of a call to `source`, if it failed.
+    pub fn add_result<T>(self, source: impl Into<String>, result: Result<T, Status>) -> Self {
+        match result {
+            Ok(_) => self,
+            Err(status) => self.add(source, status),
+        }
+    }
+
+    /// Returns `true` if no failures were added.
+    pub fn is_empty(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the number of failures added.
+    pub fn len(&self) -> usize {
+        self.failures.len()
+    }
+
+    /// Generates the aggregated `tonic::Status`. Returns `None` if no
+    /// failures were added.
+    pub fn build(self) -> Option<Status> {
+        if self.failures.is_empty() {
+            return None;
+        }
+
+        let code = self.code.unwrap_or_else(|| {
+            let codes = self.failures.iter().map(|(_, status)| status.code());
+
+            codes
+                .min_by_key(|code| {
+                    CODE_PRECEDENCE
+                        .iter()
+                        .position(|c| c == code)
+                        .unwrap_or(CODE_PRECEDENCE.len())
+                })
+                .unwrap_or(Code::Unknown)
+        });
+
+        let message = self.message.unwrap_or_else(|| {
+            self.failures
+                .iter()
+                .map(|(source, status)| format!("{source}: {}", status.message()))
+                .collect::<Vec<String>>()
+                .join("; ")
+        });
+
+        let mut details = ErrorDetails::new();
+
+        for (source, status) in &self.failures {
+            details.merge(prefixed_details(source, status.error_details()));
+        }
+
+        Some(Status::with_error_details(code, message, details))
+    }
+}
+
+/// Prefixes the bad request fields and quota failure subjects of the details
+/// of a failure with its source.
+fn prefixed_details(source: &str, mut details: ErrorDetails) -> ErrorDetails {
+    if source.is_empty() {
+        return details;
+    }
+
+    if let Some(bad_request) = details.bad_request.as_mut() {
+        for violation in &mut bad_request.field_violations {
+            violation.field = format!("{source}.{}", violation.field);
+        }
+    }
+
+    if let Some(quota_failure) = details.quota_failure.as_mut() {
+        for violation in &mut quota_failure.violations {
+            violation.subject = format!("{source}/{}", violation.subject);
+        }
+    }
+
+    details
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::AggregatedStatus;
+
+    #[test]
+    fn aggregate_statuses() {
+        assert!(
+            AggregatedStatus::new()
+                .add_result::<()>("inventory", Ok(()))
+                .add("pricing", Status::ok(""))
+                .build()
+                .is_none(),
+            "aggregated status generated without failures"
+        );
+
+        let status = AggregatedStatus::new()
+            .add("inventory", Status::unavailable("inventory unavailable"))
+            .add(
+                "quota",
+                Status::with_error_details(
+                    Code::ResourceExhausted,
+                    "quota exceeded",
+                    ErrorDetails::with_quota_failure_violation("clientip:127.0.0.1", "exceeded"),
+                ),
+            )
+            .add(
+                "shipping",
+                Status::with_error_details(
+                    Code::InvalidArgument,
+                    "invalid address",
+                    ErrorDetails::with_bad_request_violation("zip_code", "must have 5 digits"),
+                ),
+            )
+            .build();
+
+        let status = match status {
+            Some(status) => status,
+            None => panic!("aggregated status not generated for failures"),
+        };
+
+        let formatted = format!(
+            "{:?} {:?} {:?} {:?}",
+            status.code(),
+            status.message(),
+            status
+                .get_details_bad_request()
+                .map(|br| br.field_violations),
+            status.get_details_quota_failure().map(|qf| qf.violations)
+        );
+
+        println!("aggregated status -> {formatted}");
+
+        let expected = concat!(
+            "InvalidArgument ",
+            "\"inventory: inventory unavailable; quota: quota exceeded; shipping: invalid address\" ",
+            "Some([FieldViolation { field: \"shipping.zip_code\", description: \"must have 5 digits\", reason: \"\", localized_message: None }]) ",
+            "Some([QuotaViolation { subject: \"quota/clientip:127.0.0.1\", description: \"exceeded\", api_service: \"\", quota_metric: \"\", quota_id: \"\", quota_dimensions: {}, quota_value: 0, future_quota_value: None }])"
+        );
+
+        assert!(
+            formatted.eq(expected),
+            "aggregated status differs from expected result"
+        );
+
+        let status = AggregatedStatus::new()
+            .add("inventory", Status::unavailable("inventory unavailable"))
+            .add("pricing", Status::deadline_exceeded("timed out"))
+            .code(Code::Unavailable)
+            .message("dependencies unavailable")
+            .build();
+
+        assert!(
+            status.is_some_and(|status| status.code() == Code::Unavailable
+                && status.message() == "dependencies unavailable"),
+            "aggregated status with code and message differs from expected result"
+        );
+    }
+}
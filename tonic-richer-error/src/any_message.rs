@@ -0,0 +1,200 @@
+use prost::DecodeError;
+use prost_types::Any;
+
+use super::std_messages::*;
+use super::type_url_prefix::canonical_type_url;
+use super::{CustomErrorDetail, ErrorDetail, ErrorDetailType, IntoAny};
+
+/// Packs and unpacks error details as `google.protobuf.Any` messages.
+/// Implemented by the standard error message structs and by every
+/// [`CustomErrorDetail`] type, so the same packing logic used by this crate
+/// can be reused for third-party detail types.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::{AnyMessage, BadRequest};
+///
+/// let bad_request = BadRequest::with_violation("field", "description");
+///
+/// let any = bad_request.pack_any();
+///
+/// assert_eq!(any.type_url, "type.googleapis.com/google.rpc.BadRequest");
+/// assert_eq!(BadRequest::unpack_any(&any).unwrap(), bad_request);
+/// ```
+pub trait AnyMessage: ErrorDetailType + Sized {
+    /// Encodes the message as a `prost_types::Any`, with the type's
+    /// `TYPE_URL`.
+    fn pack_any(&self) -> Any;
+
+    /// Decodes the message from a `prost_types::Any`. If the `type_url` does
+    /// not match the type's `TYPE_URL`, or the value is malformed, a
+    /// `prost::DecodeError` is returned.
+    fn unpack_any(any: &Any) -> Result<Self, DecodeError> {
+        if canonical_type_url(&any.type_url) != Self::TYPE_URL {
+            return Err(DecodeError::new(format!(
+                "unexpected type URL {:?}, expected {:?}",
+                any.type_url,
+                Self::TYPE_URL
+            )));
+        }
+
+        Self::decode_detail(&any.value)
+    }
+}
+
+impl<T: CustomErrorDetail> AnyMessage for T {
+    fn pack_any(&self) -> Any {
+        self.to_any()
+    }
+}
+
+macro_rules! impl_any_message {
+    ($($detail:ty),*) => {
+        $(
+            impl AnyMessage for $detail {
+                fn pack_any(&self) -> Any {
+                    self.clone().into_any()
+                }
+            }
+        )*
+    };
+}
+
+impl_any_message!(
+    RetryInfo,
+    DebugInfo,
+    QuotaFailure,
+    ErrorInfo,
+    PreconditionFailure,
+    BadRequest,
+    RequestInfo,
+    ResourceInfo,
+    Help,
+    LocalizedMessage,
+    HttpRequest,
+    HttpResponse
+);
+
+/// Encodes a message as a `prost_types::Any`, as described in
+/// [`AnyMessage::pack_any`].
+pub fn pack_any<T: AnyMessage>(message: &T) -> Any {
+    message.pack_any()
+}
+
+/// Decodes a message from a `prost_types::Any`, as described in
+/// [`AnyMessage::unpack_any`].
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::{pack_any, unpack_any, ErrorInfo, RetryInfo};
+///
+/// let any = pack_any(&RetryInfo::new(None));
+///
+/// assert!(unpack_any::<RetryInfo>(&any).is_ok());
+/// assert!(unpack_any::<ErrorInfo>(&any).is_err());
+/// ```
+pub fn unpack_any<T: AnyMessage>(any: &Any) -> Result<T, DecodeError> {
+    T::unpack_any(any)
+}
+
+impl ErrorDetail {
+    /// Wraps a [`CustomErrorDetail`] at the [`ErrorDetail::Other`] variant,
+    /// so it can be sent in a vector of [`ErrorDetail`] enums, such as with
+    /// [`crate::WithErrorDetails::with_error_details_vec`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{CustomErrorDetail, ErrorDetail};
+    ///
+    /// #[derive(Clone, PartialEq, prost::Message)]
+    /// pub struct QuotaPlan {
+    ///     #[prost(string, tag = "1")]
+    ///     pub plan: String,
+    /// }
+    ///
+    /// impl CustomErrorDetail for QuotaPlan {
+    ///     const TYPE_URL: &'static str = "type.example.com/example.QuotaPlan";
+    /// }
+    ///
+    /// let detail = ErrorDetail::custom(&QuotaPlan { plan: "free".into() });
+    ///
+    /// assert!(matches!(detail, ErrorDetail::Other(_)));
+    /// ```
+    pub fn custom<T: CustomErrorDetail>(detail: &T) -> Self {
+        ErrorDetail::Other(detail.to_any())
+    }
+}
+
+#[cfg(all(test, feature = "tonic"))]
+mod tests {
+
+    use tonic::{Code, Status};
+
+    use super::super::{BadRequest, CustomErrorDetail, ErrorDetail, RequestInfo, WithErrorDetails};
+    use super::{pack_any, unpack_any};
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct QuotaPlan {
+        #[prost(string, tag = "1")]
+        plan: String,
+    }
+
+    impl CustomErrorDetail for QuotaPlan {
+        const TYPE_URL: &'static str = "type.example.com/example.QuotaPlan";
+    }
+
+    #[test]
+    fn pack_and_unpack_any() {
+        let plan = QuotaPlan {
+            plan: "free".into(),
+        };
+
+        let status = Status::with_error_details_vec(
+            Code::ResourceExhausted,
+            "quota",
+            vec![
+                BadRequest::with_violation("field", "description").into(),
+                ErrorDetail::custom(&plan),
+            ],
+        );
+
+        let formatted = format!("{:?}", status.get_details_custom::<QuotaPlan>());
+
+        println!("custom detail sent in vec -> {formatted}");
+
+        let expected = "Some(QuotaPlan { plan: \"free\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "custom detail sent in vec differs from expected result"
+        );
+
+        let any = pack_any(&plan);
+
+        assert!(
+            unpack_any::<QuotaPlan>(&any).ok() == Some(plan),
+            "unpacked custom detail differs from original"
+        );
+
+        let req_info = RequestInfo::new("request-id", "");
+
+        let any = pack_any(&req_info);
+
+        assert!(
+            unpack_any::<RequestInfo>(&any).ok() == Some(req_info),
+            "unpacked standard detail differs from original"
+        );
+
+        let err = match unpack_any::<QuotaPlan>(&any) {
+            Ok(_) => panic!("unpacked detail with mismatched type URL"),
+            Err(err) => err.to_string(),
+        };
+
+        println!("mismatched type URL -> {err}");
+
+        assert!(
+            err.contains("unexpected type URL \"type.googleapis.com/google.rpc.RequestInfo\""),
+            "mismatched type URL error differs from expected result"
+        );
+    }
+}
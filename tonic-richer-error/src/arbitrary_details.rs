@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use prost_types::Any;
+
+use super::RetryInfo;
+
+/// Generates a retry delay within [`RetryInfo::MAX_RETRY_DELAY`], so it is
+/// not clamped when encoded.
+pub(crate) fn retry_delay(u: &mut Unstructured<'_>) -> Result<Option<Duration>> {
+    if !bool::arbitrary(u)? {
+        return Ok(None);
+    }
+
+    let secs = u.int_in_range(0..=RetryInfo::MAX_RETRY_DELAY.as_secs())?;
+    let nanos = u.int_in_range(0..=999_999_999)?;
+
+    Ok(Some(Duration::new(secs, nanos)))
+}
+
+/// Generates unknown details, with type URLs that never match the standard
+/// error messages, so they are preserved as unknown when decoded.
+pub(crate) fn unknown_details(u: &mut Unstructured<'_>) -> Result<Vec<Any>> {
+    u.arbitrary_iter::<(u8, Vec<u8>)>()?
+        .map(|detail| {
+            let (id, value) = detail?;
+
+            Ok(Any {
+                type_url: format!("type.example.local/custom.Detail{id}"),
+                value,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use super::super::ErrorDetails;
+
+    #[test]
+    fn arbitrary_details_round_trip() {
+        for seed in 0..64u8 {
+            let data: Vec<u8> = (0..1024u32)
+                .map(|index| (index as u8).wrapping_mul(31).wrapping_add(seed))
+                .collect();
+
+            let err_details = match ErrorDetails::arbitrary(&mut Unstructured::new(&data)) {
+                Ok(err_details) => err_details,
+                Err(err) => panic!("Error generating arbitrary details: {:?}", err),
+            };
+
+            println!("arbitrary details -> {:?}", err_details);
+
+            let encoded = err_details.clone().encode_status(3, "invalid argument");
+
+            let decoded = match ErrorDetails::decode_status(&encoded) {
+                Ok(decoded) => decoded,
+                Err(err) => panic!("Error decoding arbitrary details: {:?}", err),
+            };
+
+            assert!(
+                decoded.eq(&err_details),
+                "decoded arbitrary details differ from original"
+            );
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+
+use super::{code_to_http, policy, retry_after_header, RichStatus};
+
+/// Converts the [`RichStatus`] into an HTTP response, so hybrid services can
+/// return the same errors from REST endpoints. The HTTP status code is
+/// mapped from the gRPC code, following `google.rpc.Code`, and the body is
+/// the canonical `google.rpc.Status` JSON representation, as described in
+/// [`crate::ErrorDetails::to_status_json`]. As when generating a
+/// `tonic::Status`, the global [`crate::ErrorDetailsPolicy`] is applied.
+/// [`crate::RetryInfo`] details also set the `Retry-After` header.
+/// # Examples
+///
+/// ```
+/// use axum::http::StatusCode;
+/// use axum::response::IntoResponse;
+/// use tonic_richer_error::RichStatus;
+///
+/// let response = RichStatus::for_reason("OUT_OF_STOCK", "shop.example.local")
+///     .message("item is out of stock")
+///     .details(|details| {
+///         details.add_precondition_failure_violation("STOCK", "items/A-1", "out of stock");
+///     })
+///     .into_response();
+///
+/// assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+/// ```
+impl IntoResponse for RichStatus {
+    fn into_response(self) -> Response {
+        let (code, message, mut details) = self.into_parts();
+
+        policy::apply_global(&mut details);
+
+        let retry_after = details.retry_info.as_ref().and_then(retry_after_header);
+
+        let body = details.to_status_json(code, message);
+
+        let mut response = (
+            code_to_http(code),
+            [(header::CONTENT_TYPE, "application/json")],
+            body,
+        )
+            .into_response();
+
+        if let Some(retry_after) = retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, retry_after);
+        }
+
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use axum::body::HttpBody;
+    use axum::http::{header, StatusCode};
+    use axum::response::IntoResponse;
+    use tonic::Code;
+
+    use super::super::{ErrorDetails, RichStatus};
+
+    #[tokio::test]
+    async fn rich_status_into_response() {
+        let response = RichStatus::for_reason("QUOTA_EXCEEDED", "example.local")
+            .message("quota exceeded")
+            .details(|details| {
+                details
+                    .add_quota_failure_violation("clientip:127.0.0.1", "rate exceeded")
+                    .set_retry_info(Some(Duration::from_secs(30)));
+            })
+            .into_response();
+
+        assert!(
+            response.status() == StatusCode::TOO_MANY_REQUESTS,
+            "response has unexpected HTTP status"
+        );
+
+        assert!(
+            response.headers()[header::CONTENT_TYPE] == "application/json",
+            "response has unexpected content type"
+        );
+
+        assert!(
+            response.headers()[header::RETRY_AFTER] == "30",
+            "response has unexpected Retry-After header"
+        );
+
+        let body = match response.into_body().data().await {
+            Some(Ok(body)) => body,
+            Some(Err(err)) => panic!("Error reading response body: {:?}", err),
+            None => panic!("response has no body"),
+        };
+
+        let json = String::from_utf8_lossy(&body);
+
+        println!("response body -> {json}");
+
+        let (code, message, err_details) = match ErrorDetails::from_status_json(&json) {
+            Ok(parsed) => parsed,
+            Err(err) => panic!("Error parsing response body: {:?}", err),
+        };
+
+        assert!(
+            code == Code::ResourceExhausted && message == "quota exceeded",
+            "response body has unexpected code or message"
+        );
+
+        assert!(
+            err_details.quota_failure.is_some()
+                && err_details.retry_info.is_some()
+                && err_details.error_info.is_some(),
+            "response body is missing error details"
+        );
+    }
+}
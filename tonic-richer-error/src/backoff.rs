@@ -0,0 +1,137 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use super::RetryInfo;
+
+/// Exponential backoff policy with jitter, used to generate the
+/// [`RetryInfo`] details sent to clients. The delay for a given attempt is
+/// `base * 2^attempt`, limited by `cap`, and reduced by a random fraction of
+/// up to `jitter` to spread out retries from multiple clients. Can be stored
+/// per client on the server side.
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tonic_richer_error::Backoff;
+///
+/// let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(10));
+///
+/// // Third consecutive failure of a client
+/// let retry_info = backoff.next_retry_info(2);
+///
+/// let delay = retry_info.retry_delay.unwrap();
+/// assert!(delay > Duration::from_millis(200) && delay <= Duration::from_millis(400));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Backoff {
+    base: Duration,
+    cap: Duration,
+    jitter: f64,
+}
+
+impl Backoff {
+    /// Default fraction of the delay that can be removed by jitter.
+    pub const DEFAULT_JITTER: f64 = 0.5;
+
+    /// Creates a new [`Backoff`], using [`Backoff::DEFAULT_JITTER`].
+    pub fn new(base: Duration, cap: Duration) -> Self {
+        Backoff {
+            base,
+            cap,
+            jitter: Backoff::DEFAULT_JITTER,
+        }
+    }
+
+    /// Sets the fraction of the delay that can be removed by jitter, clamped
+    /// to the `0.0..=1.0` range. A jitter of `0.0` disables randomization.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns the delay for the given attempt, starting at `0`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base
+            .checked_mul(2u32.saturating_pow(attempt))
+            .unwrap_or(self.cap)
+            .min(self.cap);
+
+        if self.jitter == 0.0 {
+            return delay;
+        }
+
+        delay.mul_f64(1.0 - self.jitter * random_fraction())
+    }
+
+    /// Generates the [`RetryInfo`] details for the given attempt, starting at
+    /// `0`.
+    pub fn next_retry_info(&self, attempt: u32) -> RetryInfo {
+        RetryInfo::new(Some(self.delay(attempt)))
+    }
+}
+
+/// Returns a pseudo-random value in the `0.0..1.0` range. Not suitable for
+/// cryptographic purposes.
+fn random_fraction() -> f64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use super::Backoff;
+
+    #[test]
+    fn backoff_delays() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1)).jitter(0.0);
+
+        let delays: Vec<Duration> = [0, 1, 2, 3, 4, 64]
+            .into_iter()
+            .map(|attempt| backoff.delay(attempt))
+            .collect();
+
+        println!("backoff delays -> {:?}", delays);
+
+        let expected = [100, 200, 400, 800, 1000, 1000].map(Duration::from_millis);
+
+        assert!(
+            delays.eq(&expected),
+            "backoff delays differ from expected result"
+        );
+
+        let backoff = backoff.jitter(0.5);
+
+        for attempt in 0..10 {
+            let delay = backoff.delay(3);
+
+            assert!(
+                delay > Duration::from_millis(400) && delay <= Duration::from_millis(800),
+                "jittered delay {:?} at attempt {} is out of range",
+                delay,
+                attempt
+            );
+        }
+
+        let formatted = format!(
+            "{:?}",
+            Backoff::new(Duration::from_secs(1), Duration::from_secs(1))
+                .jitter(0.0)
+                .next_retry_info(5)
+        );
+
+        assert!(
+            formatted.eq("RetryInfo { retry_delay: Some(1s) }"),
+            "RetryInfo from backoff differs from expected result"
+        );
+    }
+}
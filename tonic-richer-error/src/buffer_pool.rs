@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+
+use prost::bytes::BytesMut;
+use tonic::{Code, Status};
+
+use super::{ErrorDetails, WithErrorDetails};
+
+thread_local! {
+    static BUFFER: RefCell<BytesMut> = RefCell::new(BytesMut::new());
+}
+
+/// Extension trait for `tonic::Status`, generating statuses with error
+/// details encoded into a buffer owned by the current thread, as described
+/// in [`WithErrorDetails::with_error_details_into`]. High-QPS servers can
+/// use it to avoid a fresh allocation per error, without threading a buffer
+/// through their handlers.
+/// # Examples
+///
+/// ```
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{ErrorDetails, PooledStatusExt, WithErrorDetails};
+///
+/// let status = Status::with_error_details_pooled(
+///     Code::InvalidArgument,
+///     "bad request",
+///     ErrorDetails::with_bad_request_violation("field", "description"),
+/// );
+///
+/// assert!(status.get_details_bad_request().is_some());
+/// ```
+pub trait PooledStatusExt {
+    /// Generates a `tonic::Status` with error details obtained from an
+    /// [`ErrorDetails`] struct, encoded into the buffer of the current
+    /// thread.
+    fn with_error_details_pooled(
+        code: Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+    ) -> Status;
+}
+
+impl PooledStatusExt for Status {
+    fn with_error_details_pooled(
+        code: Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+    ) -> Status {
+        BUFFER.with(|buf| match buf.try_borrow_mut() {
+            Ok(mut buf) => Status::with_error_details_into(code, message, details, &mut buf),
+            // Only reachable if a status is generated while encoding another
+            Err(_) => Status::with_error_details(code, message, details),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::PooledStatusExt;
+
+    #[test]
+    fn gen_pooled_statuses() {
+        let err_details = ErrorDetails::with_bad_request_violation("field", "description");
+
+        let expected =
+            Status::with_error_details(Code::InvalidArgument, "bad request", err_details.clone());
+
+        for _ in 0..3 {
+            let status = Status::with_error_details_pooled(
+                Code::InvalidArgument,
+                "bad request",
+                err_details.clone(),
+            );
+
+            assert!(
+                status.details() == expected.details(),
+                "pooled status differs from status with error details"
+            );
+        }
+    }
+}
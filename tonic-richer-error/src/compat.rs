@@ -0,0 +1,174 @@
+//! Adapters for projects that depend on newer tonic and prost releases, whose
+//! `tonic::Status` and `prost_types::Any` types differ from the ones used by
+//! this crate. Each supported tonic version has its own module, enabled by the
+//! corresponding feature:
+//!
+//! - `tonic-010`: [`tonic_010`](self::tonic_010), for tonic 0.10 and prost 0.12.
+//! - `tonic-011`: [`tonic_011`](self::tonic_011), for tonic 0.11 and prost 0.12.
+//! - `tonic-012`: [`tonic_012`](self::tonic_012), for tonic 0.12 and prost 0.13.
+//!
+//! The adapters go through the encoded `google.rpc.Status`, so the error
+//! details are carried unchanged across versions. Status metadata is not
+//! converted.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "tonic-012")]
+//! # {
+//! use tonic_richer_error::compat::tonic_012::{check_error_details, with_error_details};
+//! use tonic_richer_error::ErrorDetails;
+//!
+//! let status = with_error_details(
+//!     tonic_012::Code::InvalidArgument,
+//!     "bad request",
+//!     ErrorDetails::with_bad_request_violation("field", "description"),
+//! );
+//!
+//! let err_details = check_error_details(&status).unwrap();
+//!
+//! assert!(err_details.bad_request.is_some());
+//! # }
+//! ```
+
+/// Generates the adapter functions for one tonic version, and the
+/// `prost_types` version it depends on.
+macro_rules! tonic_compat {
+    ($tonic:ident, $prost_types:ident) => {
+        use prost::DecodeError;
+
+        use super::super::status_ref::AnyRef;
+        use super::super::{Error, ErrorDetail, ErrorDetails, FromAny, IntoAny};
+
+        /// Generates a `tonic::Status` with error details obtained from an
+        /// [`ErrorDetails`] struct, as described in
+        /// [`ErrorDetails::encode_status`].
+        pub fn with_error_details(
+            code: $tonic::Code,
+            message: impl Into<String>,
+            details: ErrorDetails,
+        ) -> $tonic::Status {
+            let message: String = message.into();
+
+            let encoded = details.encode_status(code as i32, message.clone());
+
+            $tonic::Status::with_details(code, message, encoded.into())
+        }
+
+        /// Get an [`ErrorDetails`] struct from a `tonic::Status`. If some
+        /// `prost::DecodeError` occurs, an [`Error`] is returned.
+        pub fn check_error_details(status: &$tonic::Status) -> Result<ErrorDetails, Error> {
+            ErrorDetails::decode_status(status.details())
+        }
+
+        /// Converts an error detail into a `prost_types::Any`.
+        pub fn into_any(detail: impl Into<ErrorDetail>) -> $prost_types::Any {
+            let any = detail.into().into_any();
+
+            $prost_types::Any {
+                type_url: any.type_url,
+                value: any.value,
+            }
+        }
+
+        /// Converts a `prost_types::Any` into an [`ErrorDetail`]. Details with
+        /// unknown type URLs are kept at the [`ErrorDetail::Other`] variant.
+        pub fn from_any(any: &$prost_types::Any) -> Result<ErrorDetail, DecodeError> {
+            ErrorDetail::from_any(AnyRef {
+                type_url: &any.type_url,
+                value: &any.value,
+            })
+        }
+
+        /// Converts a `tonic::Status` of this version into one of the
+        /// version used by this crate, keeping its code, message and
+        /// details.
+        #[cfg(feature = "tonic")]
+        pub fn into_status(status: &$tonic::Status) -> tonic::Status {
+            tonic::Status::with_details(
+                tonic::Code::from_i32(status.code() as i32),
+                status.message(),
+                status.details().to_vec().into(),
+            )
+        }
+
+        /// Converts a `tonic::Status` of the version used by this crate into
+        /// one of this version, keeping its code, message and details.
+        #[cfg(feature = "tonic")]
+        pub fn from_status(status: &tonic::Status) -> $tonic::Status {
+            $tonic::Status::with_details(
+                $tonic::Code::from_i32(status.code() as i32),
+                status.message(),
+                status.details().to_vec().into(),
+            )
+        }
+    };
+}
+
+/// Adapters for tonic 0.10 and prost 0.12.
+#[cfg(feature = "tonic-010")]
+pub mod tonic_010 {
+    tonic_compat!(tonic_010, prost_types_012);
+}
+
+/// Adapters for tonic 0.11 and prost 0.12.
+#[cfg(feature = "tonic-011")]
+pub mod tonic_011 {
+    tonic_compat!(tonic_011, prost_types_012);
+}
+
+/// Adapters for tonic 0.12 and prost 0.13.
+#[cfg(feature = "tonic-012")]
+pub mod tonic_012 {
+    tonic_compat!(tonic_012, prost_types_013);
+}
+
+#[cfg(all(test, feature = "tonic", feature = "tonic-012"))]
+mod tests {
+
+    use super::super::{BadRequest, ErrorDetail, ErrorDetails, WithErrorDetails};
+    use super::tonic_012::{check_error_details, from_any, from_status, into_any, into_status};
+
+    #[test]
+    fn convert_tonic_012_statuses() {
+        let status = tonic::Status::with_error_details(
+            tonic::Code::InvalidArgument,
+            "bad request",
+            ErrorDetails::with_bad_request_violation("field", "description"),
+        );
+
+        let converted = from_status(&status);
+
+        let formatted = format!(
+            "{:?} {:?} {:?}",
+            converted.code(),
+            converted.message(),
+            check_error_details(&converted).map(|details| details.bad_request)
+        );
+
+        println!("tonic 0.12 status -> {formatted}");
+
+        let expected = "InvalidArgument \"bad request\" Ok(Some(BadRequest { field_violations: [FieldViolation { field: \"field\", description: \"description\", reason: \"\", localized_message: None }] }))";
+
+        assert!(
+            formatted.eq(expected),
+            "tonic 0.12 status differs from expected result"
+        );
+
+        let back = into_status(&converted);
+
+        assert!(
+            back.code() == status.code() && back.details() == status.details(),
+            "status converted back differs from original"
+        );
+
+        let bad_request = BadRequest::with_violation("field", "description");
+
+        let any = into_any(bad_request.clone());
+
+        assert!(
+            matches!(from_any(&any), Ok(ErrorDetail::BadRequest(decoded)) if decoded == bad_request),
+            "detail converted back differs from original"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Golden binary vectors of `google.rpc.Status` and functions to verify that
+//! encoded statuses round trip through this crate unchanged. The vectors are
+//! encoded by `protoc --encode`, with the reference C++ protobuf
+//! implementation, from the text format sources in the `vectors` directory,
+//! and cover every standard error message, map fields with several entries,
+//! and details with unknown type URLs.
+
+use prost::Message;
+
+use super::status_ref::StatusRef;
+use super::{decode_details_vec, pb, Error, ErrorDetail, IntoAny};
+
+/// A named golden binary vector, holding an encoded `google.rpc.Status`.
+#[derive(Clone, Copy, Debug)]
+pub struct Vector {
+    /// Name of the vector, describing the details it contains.
+    pub name: &'static str,
+
+    /// Encoded `google.rpc.Status`.
+    pub bytes: &'static [u8],
+}
+
+/// Golden binary vectors provided by this crate.
+pub const VECTORS: &[Vector] = &[
+    Vector {
+        name: "bad_request",
+        bytes: include_bytes!("../vectors/bad_request.bin"),
+    },
+    Vector {
+        name: "retry_quota",
+        bytes: include_bytes!("../vectors/retry_quota.bin"),
+    },
+    Vector {
+        name: "error_info",
+        bytes: include_bytes!("../vectors/error_info.bin"),
+    },
+    Vector {
+        name: "error_info_metadata",
+        bytes: include_bytes!("../vectors/error_info_metadata.bin"),
+    },
+    Vector {
+        name: "all_details",
+        bytes: include_bytes!("../vectors/all_details.bin"),
+    },
+    Vector {
+        name: "custom_detail",
+        bytes: include_bytes!("../vectors/custom_detail.bin"),
+    },
+];
+
+/// Decodes an encoded `google.rpc.Status` into [`crate::ErrorDetail`]s and
+/// encodes it back, keeping the order of the details. Map entries, such as
+/// [`crate::ErrorInfo`] metadata, are encoded in key order. The global
+/// [`crate::ErrorDetailsPolicy`] is not applied. If the status or some
+/// detail is malformed, an [`Error`] is returned.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::conformance::{roundtrip, VECTORS};
+///
+/// let bytes = roundtrip(VECTORS[0].bytes).unwrap();
+///
+/// assert_eq!(bytes, VECTORS[0].bytes);
+/// ```
+pub fn roundtrip(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    let (code, message, details) = decode(bytes)?;
+
+    let status = pb::Status {
+        code,
+        message,
+        details: details.into_iter().map(IntoAny::into_any).collect(),
+    };
+
+    Ok(status.encode_to_vec())
+}
+
+/// Asserts that an encoded `google.rpc.Status`, such as one produced by
+/// another gRPC implementation, round trips through this crate unchanged,
+/// as described in [`roundtrip`]. The original and round tripped statuses
+/// are compared after decoding, since encoders may write map entries in any
+/// order.
+///
+/// # Panics
+///
+/// Panics if the status can not be decoded, or if the round tripped status
+/// differs from the original one.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::conformance::{assert_roundtrip, VECTORS};
+///
+/// for vector in VECTORS {
+///     assert_roundtrip(vector.bytes);
+/// }
+/// ```
+pub fn assert_roundtrip(bytes: &[u8]) {
+    let decoded = |bytes: &[u8]| match decode(bytes) {
+        Ok(decoded) => decoded,
+        Err(err) => panic!("round trip failed to decode the status: {:?}", err),
+    };
+
+    let original = decoded(bytes);
+
+    let encoded = match roundtrip(bytes) {
+        Ok(encoded) => encoded,
+        Err(err) => panic!("round trip failed to decode the status: {:?}", err),
+    };
+
+    let round_tripped = decoded(&encoded);
+
+    assert!(
+        original == round_tripped,
+        "round trip changed the status\n  original: {:?}\n   round tripped: {:?}",
+        original,
+        round_tripped
+    );
+}
+
+/// Decodes the code, message and details of an encoded `google.rpc.Status`.
+fn decode(bytes: &[u8]) -> Result<(i32, String, Vec<ErrorDetail>), Error> {
+    let status = StatusRef::decode(bytes).map_err(Error::InvalidStatus)?;
+
+    let details = decode_details_vec(&status)?;
+
+    Ok((status.code, status.message.to_string(), details))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+
+    #[cfg(feature = "tonic")]
+    use tonic::{Code, Status};
+
+    use super::super::ErrorDetails;
+    #[cfg(feature = "tonic")]
+    use super::super::{ErrorDetail, WithErrorDetails};
+    use super::{assert_roundtrip, roundtrip, VECTORS};
+
+    #[test]
+    fn golden_vectors() {
+        for vector in VECTORS {
+            println!("round tripping vector -> {}", vector.name);
+
+            assert_roundtrip(vector.bytes);
+        }
+
+        assert!(
+            roundtrip(&[8, 3, 18, 1]).is_err(),
+            "truncated status was round tripped"
+        );
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn golden_vectors_in_status() {
+        let vector = VECTORS
+            .iter()
+            .find(|vector| vector.name == "custom_detail")
+            .expect("custom_detail vector is missing");
+
+        let status = Status::with_details(Code::NotFound, "not found", vector.bytes.into());
+
+        let details = status.get_error_details_vec();
+
+        let formatted = format!("{:?}", details);
+
+        println!("details decoded from vector -> {formatted}");
+
+        assert!(
+            matches!(
+                details.as_slice(),
+                [ErrorDetail::Other(_), ErrorDetail::ResourceInfo(_)]
+            ),
+            "details decoded from vector differ from expected result"
+        );
+
+        let all_details = status_details("all_details");
+
+        println!("details decoded from all_details vector -> {all_details}");
+
+        let expected = "ErrorDetails { retry_info: None, debug_info: Some(DebugInfo { stack_entries: [\"frame2\", \"frame1\"], detail: \"stack trace\" }), quota_failure: None, error_info: None, precondition_failure: Some(PreconditionFailure { violations: [PreconditionViolation { type: \"TOS\", subject: \"example.local\", description: \"terms of service not accepted\" }] }), bad_request: None, request_info: Some(RequestInfo { request_id: \"request-1\", serving_data: \"serving-data\" }), resource_info: Some(ResourceInfo { resource_type: \"book\", resource_name: \"shelves/1/books/2\", owner: \"user:1\", description: \"book not found\" }), help: Some(Help { links: [HelpLink { description: \"documentation\", url: \"https://example.local/docs\" }] }), localized_message: Some(LocalizedMessage { locale: \"pt-BR\", message: \"Requisição inválida\" }), unknown_details: [] }";
+
+        assert!(
+            all_details.eq(expected),
+            "details decoded from all_details vector differ from expected result"
+        );
+    }
+
+    #[test]
+    fn deterministic_map_encoding() {
+        let encode = || {
+            let metadata: BTreeMap<String, String> =
+                ["service", "consumer", "quota_limit", "region"]
+                    .into_iter()
+                    .map(|key| (key.to_string(), format!("{key}-value")))
+                    .collect();
+
+            ErrorDetails::with_error_info("RATE_LIMIT_EXCEEDED", "example.local", metadata)
+                .encode_status(8, "quota exceeded")
+        };
+
+        let expected = encode();
+
+        for _ in 0..50 {
+            let encoded = encode();
+
+            assert_roundtrip(&encoded);
+
+            assert!(
+                encoded == expected && roundtrip(&encoded).is_ok_and(|bytes| bytes == expected),
+                "status with several metadata entries was not encoded deterministically"
+            );
+        }
+    }
+
+    #[cfg(feature = "tonic")]
+    fn status_details(name: &str) -> String {
+        let vector = VECTORS
+            .iter()
+            .find(|vector| vector.name == name)
+            .expect("vector is missing");
+
+        let status = Status::with_details(Code::Unknown, "", vector.bytes.into());
+
+        format!("{:?}", status.error_details())
+    }
+}
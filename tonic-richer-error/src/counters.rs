@@ -0,0 +1,96 @@
+use prost_types::Any;
+
+use super::ErrorDetailKind;
+
+/// Name of the counter incremented for every detail added to a generated
+/// status.
+pub(crate) const EMITTED_TOTAL: &str = "richer_error_emitted_total";
+
+/// Name of the counter incremented for every detail extracted from a status.
+pub(crate) const DECODED_TOTAL: &str = "richer_error_decoded_total";
+
+/// Increments the [`EMITTED_TOTAL`] counter once per detail, labeled with the
+/// detail kind.
+pub(crate) fn details_emitted(details: &[Any]) {
+    for any in details {
+        increment(EMITTED_TOTAL, &any.type_url);
+    }
+}
+
+/// Increments the [`DECODED_TOTAL`] counter for a detail, labeled with the
+/// detail kind.
+pub(crate) fn detail_decoded(type_url: &str) {
+    increment(DECODED_TOTAL, type_url);
+}
+
+/// Increments a counter, labeled with the kind of the detail with the given
+/// type URL.
+fn increment(name: &'static str, type_url: &str) {
+    let kind = ErrorDetailKind::from_type_url(type_url);
+
+    metrics::counter!(name, "kind" => kind.as_str()).increment(1);
+}
+
+#[cfg(all(test, feature = "tonic"))]
+mod tests {
+
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::{DECODED_TOTAL, EMITTED_TOTAL};
+
+    #[test]
+    fn count_emitted_and_decoded_details() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        metrics::with_local_recorder(&recorder, || {
+            let mut err_details = ErrorDetails::new();
+
+            err_details
+                .add_bad_request_violation("field", "description")
+                .set_request_info("request-id", "");
+
+            let status =
+                Status::with_error_details(Code::InvalidArgument, "bad request", err_details);
+
+            let _ = status.error_details();
+            let _ = status.get_error_details_vec();
+        });
+
+        let mut counters: Vec<String> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let kind = key
+                    .key()
+                    .labels()
+                    .map(|label| label.value().to_string())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                let count = match value {
+                    DebugValue::Counter(count) => count,
+                    _ => 0,
+                };
+                format!("{}{{kind={kind}}} {count}", key.key().name())
+            })
+            .collect();
+
+        counters.sort();
+
+        let formatted = format!("{:?}", counters);
+
+        println!("detail counters -> {formatted}");
+
+        let expected = format!(
+            "[\"{DECODED_TOTAL}{{kind=bad_request}} 2\", \"{DECODED_TOTAL}{{kind=request_info}} 2\", \"{EMITTED_TOTAL}{{kind=bad_request}} 1\", \"{EMITTED_TOTAL}{{kind=request_info}} 1\"]"
+        );
+
+        assert!(
+            formatted.eq(&expected),
+            "detail counters differ from expected result"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::string::ToString;
+
+use prost::{DecodeError, Message};
+use prost_types::Any;
+
+use super::type_url_prefix::canonical_type_url;
+use super::ErrorDetails;
+
+/// Implemented by user-defined protobuf messages that can be sent as error
+/// details, alongside the standard error messages. The implementing type is
+/// usually generated by `prost-build`, with the `TYPE_URL` matching its
+/// fully-qualified protobuf name.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::CustomErrorDetail;
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// pub struct QuotaPlan {
+///     #[prost(string, tag = "1")]
+///     pub plan: String,
+/// }
+///
+/// impl CustomErrorDetail for QuotaPlan {
+///     const TYPE_URL: &'static str = "type.example.com/example.QuotaPlan";
+/// }
+/// ```
+pub trait CustomErrorDetail: Message + Default + Sized {
+    /// Type URL of the custom error message type.
+    const TYPE_URL: &'static str;
+
+    /// Encodes the custom error message as a `prost_types::Any`.
+    fn to_any(&self) -> Any {
+        Any {
+            type_url: Self::TYPE_URL.to_string(),
+            value: self.encode_to_vec(),
+        }
+    }
+
+    /// Decodes the custom error message from a `prost_types::Any`. The
+    /// `type_url` is not checked.
+    fn from_any(any: &Any) -> Result<Self, DecodeError> {
+        Self::decode(any.value.as_slice())
+    }
+}
+
+impl ErrorDetails {
+    /// Adds a user-defined error message to the [`ErrorDetails`]. It is
+    /// stored at the `unknown_details` field, and encoded alongside the
+    /// standard error messages. Can be chained with other `.set_` and
+    /// `.add_` [`ErrorDetails`] methods.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{CustomErrorDetail, ErrorDetails};
+    ///
+    /// # #[derive(Clone, PartialEq, prost::Message)]
+    /// # pub struct QuotaPlan {
+    /// #     #[prost(string, tag = "1")]
+    /// #     pub plan: String,
+    /// # }
+    /// # impl CustomErrorDetail for QuotaPlan {
+    /// #     const TYPE_URL: &'static str = "type.example.com/example.QuotaPlan";
+    /// # }
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details.add_custom(QuotaPlan { plan: "free".into() });
+    /// ```
+    pub fn add_custom<T: CustomErrorDetail>(&mut self, detail: T) -> &mut Self {
+        self.unknown_details.push(detail.to_any());
+        self
+    }
+
+    /// Returns the first user-defined error message of type `T` found at the
+    /// `unknown_details` field, if any. If some `prost::DecodeError` occurs,
+    /// returns `None`.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{CustomErrorDetail, ErrorDetails};
+    ///
+    /// # #[derive(Clone, PartialEq, prost::Message)]
+    /// # pub struct QuotaPlan {
+    /// #     #[prost(string, tag = "1")]
+    /// #     pub plan: String,
+    /// # }
+    /// # impl CustomErrorDetail for QuotaPlan {
+    /// #     const TYPE_URL: &'static str = "type.example.com/example.QuotaPlan";
+    /// # }
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details.add_custom(QuotaPlan { plan: "free".into() });
+    ///
+    /// assert_eq!(err_details.get_custom::<QuotaPlan>().unwrap().plan, "free");
+    /// ```
+    pub fn get_custom<T: CustomErrorDetail>(&self) -> Option<T> {
+        self.unknown_details
+            .iter()
+            .filter(|any| canonical_type_url(&any.type_url) == T::TYPE_URL)
+            .find_map(|any| T::from_any(any).ok())
+    }
+}
+
+#[cfg(all(test, feature = "tonic"))]
+mod tests {
+
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::CustomErrorDetail;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct QuotaPlan {
+        #[prost(string, tag = "1")]
+        plan: String,
+
+        #[prost(uint32, tag = "2")]
+        limit: u32,
+    }
+
+    impl CustomErrorDetail for QuotaPlan {
+        const TYPE_URL: &'static str = "type.example.com/example.QuotaPlan";
+    }
+
+    #[test]
+    fn custom_details() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .add_quota_failure_violation("clientip:<ip address>", "description")
+            .add_custom(QuotaPlan {
+                plan: "free".into(),
+                limit: 100,
+            });
+
+        let status = Status::with_error_details(Code::ResourceExhausted, "quota", err_details);
+
+        let formatted = format!(
+            "{:?} {:?}",
+            status.get_details_custom::<QuotaPlan>(),
+            status.error_details().get_custom::<QuotaPlan>()
+        );
+
+        println!("custom details -> {formatted}");
+
+        let expected =
+            "Some(QuotaPlan { plan: \"free\", limit: 100 }) Some(QuotaPlan { plan: \"free\", limit: 100 })";
+
+        assert!(
+            formatted.eq(expected),
+            "extracted custom details differ from expected result"
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error;
+
+use prost::DecodeError;
+
+/// Describes a detail that could not be decoded by
+/// [`crate::WithErrorDetails::get_error_details_lenient`]. If the
+/// `google.rpc.Status` itself is malformed, `type_url` is empty and `index`
+/// is the number of details read before the malformed part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetailDecodeError {
+    /// Position of the detail in the status `details` field.
+    pub index: usize,
+
+    /// Type URL of the detail.
+    pub type_url: String,
+
+    /// Error returned when decoding the detail.
+    pub source: DecodeError,
+}
+
+impl fmt::Display for DetailDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.type_url.is_empty() {
+            write!(
+                f,
+                "malformed status at detail {}: {}",
+                self.index, self.source
+            )
+        } else {
+            write!(
+                f,
+                "malformed detail {} ({}): {}",
+                self.index, self.type_url, self.source
+            )
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for DetailDecodeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Error returned when error details can not be extracted from a
+/// `google.rpc.Status`, describing what went wrong and which detail caused
+/// it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A detail could not be decoded.
+    Decode {
+        /// Type URL of the detail.
+        type_url: String,
+
+        /// Position of the detail in the status `details` field.
+        index: usize,
+
+        /// Error returned when decoding the detail.
+        source: DecodeError,
+    },
+
+    /// The `google.rpc.Status` itself is malformed.
+    InvalidStatus(DecodeError),
+}
+
+impl Error {
+    pub(crate) fn detail(index: usize, type_url: &str, source: DecodeError) -> Self {
+        Error::Decode {
+            type_url: type_url.to_string(),
+            index,
+            source,
+        }
+    }
+
+    /// Returns the underlying `prost::DecodeError`.
+    pub fn decode_error(&self) -> &DecodeError {
+        match self {
+            Error::Decode { source, .. } => source,
+            Error::InvalidStatus(source) => source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Decode {
+                type_url,
+                index,
+                source,
+            } => write!(f, "malformed detail {index} ({type_url}): {source}"),
+            Error::InvalidStatus(source) => write!(f, "malformed status: {source}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(self.decode_error())
+    }
+}
+
+impl From<DetailDecodeError> for Error {
+    fn from(err: DetailDecodeError) -> Self {
+        if err.type_url.is_empty() {
+            Error::InvalidStatus(err.source)
+        } else {
+            Error::Decode {
+                type_url: err.type_url,
+                index: err.index,
+                source: err.source,
+            }
+        }
+    }
+}
+
+impl From<Error> for DecodeError {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Decode { source, .. } => source,
+            Error::InvalidStatus(source) => source,
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+
+use prost_types::Any;
+
+use super::{ErrorDetails, IntoAny};
+
+#[cfg(feature = "std")]
+static GLOBAL_ORDER: RwLock<DetailOrder> = RwLock::new(DetailOrder::standard());
+
+/// Identifies one of the standard error messages stored in an
+/// [`ErrorDetails`] struct. Used to describe a [`DetailOrder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DetailKind {
+    /// Identifies [`crate::RetryInfo`] details.
+    RetryInfo,
+
+    /// Identifies [`crate::DebugInfo`] details.
+    DebugInfo,
+
+    /// Identifies [`crate::QuotaFailure`] details.
+    QuotaFailure,
+
+    /// Identifies [`crate::ErrorInfo`] details.
+    ErrorInfo,
+
+    /// Identifies [`crate::PreconditionFailure`] details.
+    PreconditionFailure,
+
+    /// Identifies [`crate::BadRequest`] details.
+    BadRequest,
+
+    /// Identifies [`crate::RequestInfo`] details.
+    RequestInfo,
+
+    /// Identifies [`crate::ResourceInfo`] details.
+    ResourceInfo,
+
+    /// Identifies [`crate::Help`] details.
+    Help,
+
+    /// Identifies [`crate::LocalizedMessage`] details.
+    LocalizedMessage,
+}
+
+impl DetailKind {
+    /// Order in which the details of an [`ErrorDetails`] struct are encoded
+    /// by default.
+    pub const STANDARD_ORDER: [DetailKind; 10] = [
+        DetailKind::RetryInfo,
+        DetailKind::DebugInfo,
+        DetailKind::QuotaFailure,
+        DetailKind::ErrorInfo,
+        DetailKind::PreconditionFailure,
+        DetailKind::BadRequest,
+        DetailKind::RequestInfo,
+        DetailKind::ResourceInfo,
+        DetailKind::Help,
+        DetailKind::LocalizedMessage,
+    ];
+}
+
+/// Describes the order in which the details of an [`ErrorDetails`] struct
+/// are encoded into a `tonic::Status`. Kinds that are not listed are encoded
+/// after the listed ones, in [`DetailKind::STANDARD_ORDER`]. Details with
+/// unknown type URLs are always encoded last.
+///
+/// The order can be passed to
+/// [`crate::WithErrorDetails::with_error_details_ordered`], or installed
+/// with [`DetailOrder::install`] to be used by
+/// [`crate::WithErrorDetails::with_error_details`].
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use tonic_richer_error::DetailOrder;
+///
+/// // Place `ErrorInfo` first, as the primary detail of every status
+/// DetailOrder::error_info_first().install();
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DetailOrder {
+    kinds: Vec<DetailKind>,
+}
+
+impl DetailOrder {
+    /// Creates a [`DetailOrder`] that encodes the details in
+    /// [`DetailKind::STANDARD_ORDER`].
+    pub const fn standard() -> Self {
+        DetailOrder { kinds: Vec::new() }
+    }
+
+    /// Creates a [`DetailOrder`] that encodes the given kinds first, in the
+    /// given order. Repeated kinds are ignored.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{DetailKind, DetailOrder};
+    ///
+    /// let order = DetailOrder::new([DetailKind::ErrorInfo, DetailKind::Help]);
+    ///
+    /// assert_eq!(order.kinds()[..3], [
+    ///     DetailKind::ErrorInfo,
+    ///     DetailKind::Help,
+    ///     DetailKind::RetryInfo,
+    /// ]);
+    /// ```
+    pub fn new(kinds: impl IntoIterator<Item = DetailKind>) -> Self {
+        let mut order = DetailOrder::standard();
+
+        for kind in kinds {
+            if !order.kinds.contains(&kind) {
+                order.kinds.push(kind);
+            }
+        }
+
+        order
+    }
+
+    /// Creates a [`DetailOrder`] that encodes [`crate::ErrorInfo`] details
+    /// first, followed by the remaining details in
+    /// [`DetailKind::STANDARD_ORDER`].
+    pub fn error_info_first() -> Self {
+        DetailOrder::new([DetailKind::ErrorInfo])
+    }
+
+    /// Returns the complete order in which the kinds are encoded.
+    pub fn kinds(&self) -> Vec<DetailKind> {
+        let mut kinds = self.kinds.clone();
+
+        for kind in DetailKind::STANDARD_ORDER {
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+
+        kinds
+    }
+
+    /// Installs the order globally, replacing the previously installed one.
+    #[cfg(feature = "std")]
+    pub fn install(self) {
+        match GLOBAL_ORDER.write() {
+            Ok(mut order) => *order = self,
+            Err(poisoned) => *poisoned.into_inner() = self,
+        }
+    }
+
+    /// Returns a copy of the globally installed order.
+    #[cfg(feature = "std")]
+    pub fn global() -> Self {
+        match GLOBAL_ORDER.read() {
+            Ok(order) => order.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Converts the details of an [`ErrorDetails`] struct into a vector of
+    /// `prost_types::Any`, following this order.
+    pub(crate) fn encode(&self, mut details: ErrorDetails) -> Vec<Any> {
+        let mut conv_details: Vec<Any> = Vec::with_capacity(10 + details.unknown_details.len());
+
+        for kind in self.kinds() {
+            let any = match kind {
+                DetailKind::RetryInfo => details.retry_info.take().map(IntoAny::into_any),
+                DetailKind::DebugInfo => details.debug_info.take().map(IntoAny::into_any),
+                DetailKind::QuotaFailure => details.quota_failure.take().map(IntoAny::into_any),
+                DetailKind::ErrorInfo => details.error_info.take().map(IntoAny::into_any),
+                DetailKind::PreconditionFailure => {
+                    details.precondition_failure.take().map(IntoAny::into_any)
+                }
+                DetailKind::BadRequest => details.bad_request.take().map(IntoAny::into_any),
+                DetailKind::RequestInfo => details.request_info.take().map(IntoAny::into_any),
+                DetailKind::ResourceInfo => details.resource_info.take().map(IntoAny::into_any),
+                DetailKind::Help => details.help.take().map(IntoAny::into_any),
+                DetailKind::LocalizedMessage => {
+                    details.localized_message.take().map(IntoAny::into_any)
+                }
+            };
+
+            conv_details.extend(any);
+        }
+
+        conv_details.extend(details.unknown_details);
+
+        conv_details
+    }
+}
+
+#[cfg(all(test, feature = "tonic"))]
+mod tests {
+
+    use std::time::Duration;
+    use tonic::{Code, Status};
+
+    use super::super::{
+        BadRequest, ErrorDetails, ErrorInfo, LocalizedMessage, RetryInfo, WithErrorDetails,
+    };
+    use super::{DetailKind, DetailOrder};
+
+    #[test]
+    fn ordered_details() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .add_bad_request_violation("field", "description")
+            .set_localized_message("en-US", "message for the user")
+            .set_error_info("SOME_INFO", "example.local", Default::default());
+
+        let order = DetailOrder::new([DetailKind::ErrorInfo, DetailKind::LocalizedMessage]);
+
+        let status = Status::with_error_details_ordered(
+            Code::InvalidArgument,
+            "bad request",
+            err_details,
+            &order,
+        );
+
+        let type_urls: Vec<&str> = status
+            .error_details_iter()
+            .map(|detail| detail.type_url)
+            .collect();
+
+        println!("ordered type URLs -> {:?}", type_urls);
+
+        let expected = [
+            ErrorInfo::TYPE_URL,
+            LocalizedMessage::TYPE_URL,
+            RetryInfo::TYPE_URL,
+            BadRequest::TYPE_URL,
+        ];
+
+        assert!(
+            type_urls.eq(&expected),
+            "ordered type URLs differ from expected result"
+        );
+
+        assert!(
+            DetailOrder::standard()
+                .kinds()
+                .eq(&DetailKind::STANDARD_ORDER),
+            "standard order differs from DetailKind::STANDARD_ORDER"
+        );
+
+        assert!(
+            DetailOrder::new([DetailKind::Help, DetailKind::Help])
+                .kinds()
+                .len()
+                == 10,
+            "repeated kinds were not ignored"
+        );
+    }
+}
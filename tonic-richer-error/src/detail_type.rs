@@ -0,0 +1,131 @@
+use prost::DecodeError;
+
+use super::std_messages::*;
+use super::{AnyRef, CustomErrorDetail, FromAny};
+
+/// Associates an error details type with its protobuf type URL. Implemented
+/// by the standard error message structs and by every [`CustomErrorDetail`]
+/// type. Used by [`crate::WithErrorDetails::has_detail`] and
+/// [`crate::RawDetail::downcast`].
+pub trait ErrorDetailType {
+    /// Type URL of the error details type.
+    const TYPE_URL: &'static str;
+
+    /// Decodes the error details from the `value` of a
+    /// `google.protobuf.Any`.
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError>
+    where
+        Self: Sized;
+}
+
+/// Decodes one of the standard error messages.
+fn decode_std<T: FromAny>(value: &[u8]) -> Result<T, DecodeError> {
+    T::from_any(AnyRef {
+        type_url: "",
+        value,
+    })
+}
+
+impl<T: CustomErrorDetail> ErrorDetailType for T {
+    const TYPE_URL: &'static str = <T as CustomErrorDetail>::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        T::decode(value)
+    }
+}
+
+impl ErrorDetailType for RetryInfo {
+    const TYPE_URL: &'static str = RetryInfo::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for DebugInfo {
+    const TYPE_URL: &'static str = DebugInfo::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for QuotaFailure {
+    const TYPE_URL: &'static str = QuotaFailure::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for ErrorInfo {
+    const TYPE_URL: &'static str = ErrorInfo::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for PreconditionFailure {
+    const TYPE_URL: &'static str = PreconditionFailure::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for BadRequest {
+    const TYPE_URL: &'static str = BadRequest::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for RequestInfo {
+    const TYPE_URL: &'static str = RequestInfo::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for ResourceInfo {
+    const TYPE_URL: &'static str = ResourceInfo::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for Help {
+    const TYPE_URL: &'static str = Help::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for LocalizedMessage {
+    const TYPE_URL: &'static str = LocalizedMessage::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for HttpRequest {
+    const TYPE_URL: &'static str = HttpRequest::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
+
+impl ErrorDetailType for HttpResponse {
+    const TYPE_URL: &'static str = HttpResponse::TYPE_URL;
+
+    fn decode_detail(value: &[u8]) -> Result<Self, DecodeError> {
+        decode_std(value)
+    }
+}
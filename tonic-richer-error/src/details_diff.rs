@@ -0,0 +1,338 @@
+use std::fmt;
+
+use super::{ErrorDetailKind, ErrorDetails};
+
+/// A single difference found by [`ErrorDetails::diff`]. Details and
+/// violations are described by their `Display` output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DetailChange {
+    /// A detail present only in the other [`ErrorDetails`].
+    Added {
+        /// Kind of the detail.
+        kind: ErrorDetailKind,
+        /// The added detail.
+        detail: String,
+    },
+
+    /// A detail present only in the original [`ErrorDetails`].
+    Removed {
+        /// Kind of the detail.
+        kind: ErrorDetailKind,
+        /// The removed detail.
+        detail: String,
+    },
+
+    /// A detail present in both [`ErrorDetails`], with different contents.
+    Changed {
+        /// Kind of the detail.
+        kind: ErrorDetailKind,
+        /// The original detail.
+        from: String,
+        /// The other detail.
+        to: String,
+    },
+
+    /// A violation present only in the other [`ErrorDetails`].
+    ViolationAdded {
+        /// Kind of the detail holding the violation.
+        kind: ErrorDetailKind,
+        /// The added violation.
+        violation: String,
+    },
+
+    /// A violation present only in the original [`ErrorDetails`].
+    ViolationRemoved {
+        /// Kind of the detail holding the violation.
+        kind: ErrorDetailKind,
+        /// The removed violation.
+        violation: String,
+    },
+}
+
+/// Formats as `+ kind: detail`, `- kind: detail`, `~ kind: from -> to`,
+/// `+ kind violation: violation` or `- kind violation: violation`.
+impl fmt::Display for DetailChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DetailChange::Added { kind, detail } => write!(f, "+ {kind}: {detail}"),
+            DetailChange::Removed { kind, detail } => write!(f, "- {kind}: {detail}"),
+            DetailChange::Changed { kind, from, to } => write!(f, "~ {kind}: {from} -> {to}"),
+            DetailChange::ViolationAdded { kind, violation } => {
+                write!(f, "+ {kind} violation: {violation}")
+            }
+            DetailChange::ViolationRemoved { kind, violation } => {
+                write!(f, "- {kind} violation: {violation}")
+            }
+        }
+    }
+}
+
+/// Differences between two [`ErrorDetails`], returned by
+/// [`ErrorDetails::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DetailsDiff {
+    /// The differences found, in the order of the [`ErrorDetails`] fields.
+    pub changes: Vec<DetailChange>,
+}
+
+impl DetailsDiff {
+    /// Returns `true` if no differences were found.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Formats as one change per line, or `no differences`.
+impl fmt::Display for DetailsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return f.write_str("no differences");
+        }
+
+        for (index, change) in self.changes.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{change}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ErrorDetails {
+    /// Compares the error details with `other`, describing the details that
+    /// were added, removed or changed. [`crate::QuotaFailure`],
+    /// [`crate::PreconditionFailure`] and [`crate::BadRequest`] details are
+    /// compared violation by violation, regardless of their order. Useful to
+    /// assert server error contracts in integration tests, printing precise
+    /// mismatches.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let expected = ErrorDetails::with_bad_request_violation("name", "is required");
+    ///
+    /// let mut actual = ErrorDetails::with_bad_request_violation("email", "is invalid");
+    ///
+    /// actual.set_request_info("request-id", "");
+    ///
+    /// let diff = expected.diff(&actual);
+    ///
+    /// assert_eq!(
+    ///     diff.to_string(),
+    ///     "- bad_request violation: name: is required\n\
+    ///      + bad_request violation: email: is invalid\n\
+    ///      + request_info: request request-id"
+    /// );
+    /// ```
+    pub fn diff(&self, other: &ErrorDetails) -> DetailsDiff {
+        let mut changes = Vec::new();
+
+        diff_detail(
+            &mut changes,
+            ErrorDetailKind::RetryInfo,
+            &self.retry_info,
+            &other.retry_info,
+        );
+        diff_detail(
+            &mut changes,
+            ErrorDetailKind::DebugInfo,
+            &self.debug_info,
+            &other.debug_info,
+        );
+
+        match (&self.quota_failure, &other.quota_failure) {
+            (Some(a), Some(b)) => diff_violations(
+                &mut changes,
+                ErrorDetailKind::QuotaFailure,
+                &a.violations,
+                &b.violations,
+            ),
+            (a, b) => diff_detail(&mut changes, ErrorDetailKind::QuotaFailure, a, b),
+        }
+
+        diff_detail(
+            &mut changes,
+            ErrorDetailKind::ErrorInfo,
+            &self.error_info,
+            &other.error_info,
+        );
+
+        match (&self.precondition_failure, &other.precondition_failure) {
+            (Some(a), Some(b)) => diff_violations(
+                &mut changes,
+                ErrorDetailKind::PreconditionFailure,
+                &a.violations,
+                &b.violations,
+            ),
+            (a, b) => diff_detail(&mut changes, ErrorDetailKind::PreconditionFailure, a, b),
+        }
+
+        match (&self.bad_request, &other.bad_request) {
+            (Some(a), Some(b)) => diff_violations(
+                &mut changes,
+                ErrorDetailKind::BadRequest,
+                &a.field_violations,
+                &b.field_violations,
+            ),
+            (a, b) => diff_detail(&mut changes, ErrorDetailKind::BadRequest, a, b),
+        }
+
+        diff_detail(
+            &mut changes,
+            ErrorDetailKind::RequestInfo,
+            &self.request_info,
+            &other.request_info,
+        );
+        diff_detail(
+            &mut changes,
+            ErrorDetailKind::ResourceInfo,
+            &self.resource_info,
+            &other.resource_info,
+        );
+        diff_detail(&mut changes, ErrorDetailKind::Help, &self.help, &other.help);
+        diff_detail(
+            &mut changes,
+            ErrorDetailKind::LocalizedMessage,
+            &self.localized_message,
+            &other.localized_message,
+        );
+
+        for any in self.unknown_details.iter() {
+            if !other.unknown_details.contains(any) {
+                changes.push(DetailChange::Removed {
+                    kind: ErrorDetailKind::Other,
+                    detail: any.type_url.clone(),
+                });
+            }
+        }
+
+        for any in other.unknown_details.iter() {
+            if !self.unknown_details.contains(any) {
+                changes.push(DetailChange::Added {
+                    kind: ErrorDetailKind::Other,
+                    detail: any.type_url.clone(),
+                });
+            }
+        }
+
+        DetailsDiff { changes }
+    }
+}
+
+/// Describes a detail, falling back to its `Debug` output when the `Display`
+/// output of both sides is the same, such as for violations that differ only
+/// in their reasons.
+fn describe<T: fmt::Display + fmt::Debug>(detail: &T, other: &T) -> String {
+    let described = detail.to_string();
+
+    if described == other.to_string() {
+        return format!("{detail:?}");
+    }
+
+    described
+}
+
+/// Compares two optional details of the same kind.
+fn diff_detail<T: PartialEq + fmt::Display + fmt::Debug>(
+    changes: &mut Vec<DetailChange>,
+    kind: ErrorDetailKind,
+    a: &Option<T>,
+    b: &Option<T>,
+) {
+    match (a, b) {
+        (Some(a), Some(b)) if a != b => changes.push(DetailChange::Changed {
+            kind,
+            from: describe(a, b),
+            to: describe(b, a),
+        }),
+        (Some(a), None) => changes.push(DetailChange::Removed {
+            kind,
+            detail: a.to_string(),
+        }),
+        (None, Some(b)) => changes.push(DetailChange::Added {
+            kind,
+            detail: b.to_string(),
+        }),
+        _ => {}
+    }
+}
+
+/// Compares two lists of violations, regardless of their order. Duplicated
+/// violations are matched one to one.
+fn diff_violations<T: PartialEq + fmt::Display>(
+    changes: &mut Vec<DetailChange>,
+    kind: ErrorDetailKind,
+    a: &[T],
+    b: &[T],
+) {
+    let mut unmatched: Vec<&T> = b.iter().collect();
+
+    for violation in a.iter() {
+        match unmatched.iter().position(|other| *other == violation) {
+            Some(index) => {
+                unmatched.remove(index);
+            }
+            None => changes.push(DetailChange::ViolationRemoved {
+                kind,
+                violation: violation.to_string(),
+            }),
+        }
+    }
+
+    for violation in unmatched {
+        changes.push(DetailChange::ViolationAdded {
+            kind,
+            violation: violation.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use super::super::ErrorDetails;
+
+    #[test]
+    fn diff_details() {
+        let mut expected = ErrorDetails::new();
+
+        expected
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .set_error_info("INVALID_FIELD", "example.local", Default::default())
+            .add_bad_request_violation("name", "is required")
+            .add_bad_request_violation("email", "is invalid");
+
+        let mut actual = ErrorDetails::new();
+
+        actual
+            .set_retry_info(Some(Duration::from_secs(10)))
+            .add_bad_request_violation("email", "is invalid")
+            .add_bad_request_violation("age", "must be positive")
+            .set_localized_message("en-US", "invalid request");
+
+        let diff = expected.diff(&actual);
+
+        println!("details diff ->\n{diff}");
+
+        let expected_diff = "~ retry_info: retry after 5s -> retry after 10s\n\
+            - error_info: INVALID_FIELD (example.local)\n\
+            - bad_request violation: name: is required\n\
+            + bad_request violation: age: must be positive\n\
+            + localized_message: en-US: invalid request";
+
+        assert!(
+            diff.to_string().eq(expected_diff),
+            "details diff differs from expected result"
+        );
+
+        assert!(
+            expected.diff(&expected.clone()).is_empty(),
+            "diff of equal details is not empty"
+        );
+    }
+}
@@ -0,0 +1,373 @@
+//! Human-readable `Display` implementations for the error details, so clients
+//! can show or log them without formatting the `Debug` output by hand.
+
+use std::fmt;
+
+use super::std_messages::*;
+use super::{ErrorDetail, ErrorDetailKind, ErrorDetails, FromAny};
+
+/// Writes the items separated by `"; "`.
+fn write_joined<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            f.write_str("; ")?;
+        }
+        write!(f, "{item}")?;
+    }
+
+    Ok(())
+}
+
+/// Formats as `retry after 5s`, or `retry delay not informed`.
+impl fmt::Display for RetryInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.retry_delay {
+            Some(delay) => write!(f, "retry after {delay:?}"),
+            None => f.write_str("retry delay not informed"),
+        }
+    }
+}
+
+/// Formats as `detail (stack: entry_a, entry_b)`.
+impl fmt::Display for DebugInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.detail)?;
+
+        if !self.stack_entries.is_empty() {
+            write!(f, " (stack: {})", self.stack_entries.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats as `subject: description`.
+impl fmt::Display for QuotaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.subject, self.description)
+    }
+}
+
+/// Formats as `subject_a: description; subject_b: description`.
+impl fmt::Display for QuotaFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_joined(f, &self.violations)
+    }
+}
+
+/// Formats as `REASON (domain) key_a=value, key_b=value`, with the metadata
+/// sorted by key.
+impl fmt::Display for ErrorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.reason, self.domain)?;
+
+        let mut metadata: Vec<_> = self.metadata.iter().collect();
+        metadata.sort();
+
+        for (index, (key, value)) in metadata.into_iter().enumerate() {
+            let separator = if index == 0 { " " } else { ", " };
+            write!(f, "{separator}{key}={value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats as `TYPE subject: description`.
+impl fmt::Display for PreconditionViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.r#type, self.subject, self.description)
+    }
+}
+
+/// Formats as `TYPE subject_a: description; TYPE subject_b: description`.
+impl fmt::Display for PreconditionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_joined(f, &self.violations)
+    }
+}
+
+/// Formats as `field: description`.
+impl fmt::Display for FieldViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.description)
+    }
+}
+
+/// Formats as `field_a: description; field_b: description`.
+impl fmt::Display for BadRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_joined(f, &self.field_violations)
+    }
+}
+
+/// Formats as `request request-id (serving data)`.
+impl fmt::Display for RequestInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "request {}", self.request_id)?;
+
+        if !self.serving_data.is_empty() {
+            write!(f, " ({})", self.serving_data)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats as `resource-type resource-name: description (owner: owner)`.
+impl fmt::Display for ResourceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.resource_type, self.resource_name)?;
+
+        if !self.description.is_empty() {
+            write!(f, ": {}", self.description)?;
+        }
+
+        if !self.owner.is_empty() {
+            write!(f, " (owner: {})", self.owner)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats as `description: url`.
+impl fmt::Display for HelpLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.description, self.url)
+    }
+}
+
+/// Formats as `description_a: url_a; description_b: url_b`.
+impl fmt::Display for Help {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_joined(f, &self.links)
+    }
+}
+
+/// Formats as `locale: message`.
+impl fmt::Display for LocalizedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.locale, self.message)
+    }
+}
+
+/// Formats as `key: value`.
+impl fmt::Display for HttpHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.value)
+    }
+}
+
+/// Formats as `METHOD uri`.
+impl fmt::Display for HttpRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.uri)
+    }
+}
+
+/// Formats as `status reason`.
+impl fmt::Display for HttpResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.status, self.reason)
+    }
+}
+
+/// Formats as `kind: detail`, where details with unknown type URLs are
+/// described by their type URL.
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.kind())?;
+
+        match self {
+            ErrorDetail::RetryInfo(detail) => write!(f, "{detail}"),
+            ErrorDetail::DebugInfo(detail) => write!(f, "{detail}"),
+            ErrorDetail::QuotaFailure(detail) => write!(f, "{detail}"),
+            ErrorDetail::ErrorInfo(detail) => write!(f, "{detail}"),
+            ErrorDetail::PreconditionFailure(detail) => write!(f, "{detail}"),
+            ErrorDetail::BadRequest(detail) => write!(f, "{detail}"),
+            ErrorDetail::RequestInfo(detail) => write!(f, "{detail}"),
+            ErrorDetail::ResourceInfo(detail) => write!(f, "{detail}"),
+            ErrorDetail::Help(detail) => write!(f, "{detail}"),
+            ErrorDetail::LocalizedMessage(detail) => write!(f, "{detail}"),
+            ErrorDetail::HttpRequest(detail) => write!(f, "{detail}"),
+            ErrorDetail::HttpResponse(detail) => write!(f, "{detail}"),
+            ErrorDetail::Other(any) => f.write_str(&any.type_url),
+        }
+    }
+}
+
+/// Formats as a multi-line summary, with one `kind: detail` line per detail,
+/// in the order of the [`ErrorDetails`] fields. Details with unknown type
+/// URLs come last.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::ErrorDetails;
+///
+/// let mut err_details = ErrorDetails::new();
+///
+/// err_details
+///     .add_bad_request_violation("field_a", "must be positive")
+///     .add_bad_request_violation("field_b", "must not be empty")
+///     .set_request_info("request-id", "");
+///
+/// assert_eq!(
+///     err_details.to_string(),
+///     "bad_request: field_a: must be positive; field_b: must not be empty\n\
+///      request_info: request request-id"
+/// );
+/// ```
+impl fmt::Display for ErrorDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: [(ErrorDetailKind, Option<&dyn fmt::Display>); 10] = [
+            (
+                ErrorDetailKind::RetryInfo,
+                self.retry_info.as_ref().map(|d| d as _),
+            ),
+            (
+                ErrorDetailKind::DebugInfo,
+                self.debug_info.as_ref().map(|d| d as _),
+            ),
+            (
+                ErrorDetailKind::QuotaFailure,
+                self.quota_failure.as_ref().map(|d| d as _),
+            ),
+            (
+                ErrorDetailKind::ErrorInfo,
+                self.error_info.as_ref().map(|d| d as _),
+            ),
+            (
+                ErrorDetailKind::PreconditionFailure,
+                self.precondition_failure.as_ref().map(|d| d as _),
+            ),
+            (
+                ErrorDetailKind::BadRequest,
+                self.bad_request.as_ref().map(|d| d as _),
+            ),
+            (
+                ErrorDetailKind::RequestInfo,
+                self.request_info.as_ref().map(|d| d as _),
+            ),
+            (
+                ErrorDetailKind::ResourceInfo,
+                self.resource_info.as_ref().map(|d| d as _),
+            ),
+            (ErrorDetailKind::Help, self.help.as_ref().map(|d| d as _)),
+            (
+                ErrorDetailKind::LocalizedMessage,
+                self.localized_message.as_ref().map(|d| d as _),
+            ),
+        ];
+
+        let mut first = true;
+
+        let mut write_line = |f: &mut fmt::Formatter<'_>, line: fmt::Arguments<'_>| {
+            if !first {
+                f.write_str("\n")?;
+            }
+            first = false;
+            f.write_fmt(line)
+        };
+
+        for (kind, detail) in lines {
+            if let Some(detail) = detail {
+                write_line(f, format_args!("{kind}: {detail}"))?;
+            }
+        }
+
+        for any in self.unknown_details.iter() {
+            match ErrorDetail::from_any(any.into()) {
+                Ok(detail) => write_line(f, format_args!("{detail}"))?,
+                Err(_) => write_line(
+                    f,
+                    format_args!(
+                        "{}: {}",
+                        ErrorDetailKind::from_type_url(&any.type_url),
+                        any.type_url
+                    ),
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use super::super::{ErrorChain, ErrorDetail, ErrorDetails, HttpRequest, HttpResponse};
+
+    #[test]
+    fn display_details() {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("limit".to_string(), "100".to_string());
+        metadata.insert("instanceId".to_string(), "i-1".to_string());
+
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_millis(1500)))
+            .set_debug_info(vec!["trace2".into(), "trace1".into()], "details")
+            .add_quota_failure_violation("clientip:<ip address>", "description")
+            .set_error_info("SOME_INFO", "example.local", metadata)
+            .add_precondition_failure_violation("TOS", "example.local", "description")
+            .add_bad_request_violation("field_a", "description")
+            .add_bad_request_violation("field_b", "description")
+            .set_request_info("request-id", "some-request-data")
+            .set_resource_info("resource-type", "resource-name", "owner", "description")
+            .add_help_link("link to resource", "https://resource.example.local")
+            .set_localized_message("en-US", "message for the user")
+            .set_http_response(HttpResponse::new(404, "Not Found"))
+            .set_error_chain(ErrorChain::new());
+
+        let formatted = err_details.to_string();
+
+        println!("ErrorDetails display ->\n{formatted}");
+
+        let expected = [
+            "retry_info: retry after 1.5s",
+            "debug_info: details (stack: trace2, trace1)",
+            "quota_failure: clientip:<ip address>: description",
+            "error_info: SOME_INFO (example.local) instanceId=i-1, limit=100",
+            "precondition_failure: TOS example.local: description",
+            "bad_request: field_a: description; field_b: description",
+            "request_info: request request-id (some-request-data)",
+            "resource_info: resource-type resource-name: description (owner: owner)",
+            "help: link to resource: https://resource.example.local",
+            "localized_message: en-US: message for the user",
+            "http_response: 404 Not Found",
+            "other: type.googleapis.com/tonic_richer_error.ErrorChain",
+        ]
+        .join("\n");
+
+        assert!(
+            formatted.eq(&expected),
+            "ErrorDetails display differs from expected result"
+        );
+
+        let details: Vec<String> = [
+            ErrorDetail::from(HttpRequest::new("GET", "/books/1")),
+            ErrorDetail::from(HttpResponse::new(404, "Not Found")),
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+        println!("ErrorDetail display -> {:?}", details);
+
+        assert!(
+            details.eq(&["http_request: GET /books/1", "http_response: 404 Not Found"]),
+            "ErrorDetail display differs from expected result"
+        );
+
+        assert!(
+            ErrorDetails::new().to_string().is_empty(),
+            "empty ErrorDetails display is not empty"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::error;
+use std::fmt;
+
+use super::{CustomErrorDetail, ErrorDetails};
+
+/// One cause of an [`ErrorChain`].
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ErrorCause {
+    /// Message describing the cause, usually its `Display` output.
+    #[prost(string, tag = "1")]
+    pub message: String,
+
+    /// Name of the cause type, such as the one returned by
+    /// `std::any::type_name`. Empty if unknown.
+    #[prost(string, tag = "2")]
+    pub type_name: String,
+}
+
+impl ErrorCause {
+    /// Creates a new [`ErrorCause`] struct.
+    pub fn new(message: impl Into<String>, type_name: impl Into<String>) -> Self {
+        ErrorCause {
+            message: message.into(),
+            type_name: type_name.into(),
+        }
+    }
+}
+
+/// Custom error message carrying the chain of causes of an error, from the
+/// outermost to the root cause, similar to the context chain of `anyhow`.
+/// Sent with the `type.googleapis.com/tonic_richer_error.ErrorChain` type
+/// URL, at the `unknown_details` field of [`ErrorDetails`].
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::ErrorChain;
+///
+/// let mut chain = ErrorChain::new();
+///
+/// chain
+///     .add_cause("failed to load user")
+///     .add_typed_cause("connection refused", "std::io::Error");
+///
+/// assert_eq!(chain.to_string(), "failed to load user: connection refused");
+/// ```
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ErrorChain {
+    /// Causes of the error, from the outermost to the root cause.
+    #[prost(message, repeated, tag = "1")]
+    pub causes: Vec<ErrorCause>,
+}
+
+impl ErrorChain {
+    /// Creates an empty [`ErrorChain`].
+    pub fn new() -> Self {
+        ErrorChain::default()
+    }
+
+    /// Creates an [`ErrorChain`] from an error and its chain of sources. The
+    /// type name is only known for the outermost error.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use tonic_richer_error::ErrorChain;
+    ///
+    /// let err = io::Error::other("disk full");
+    ///
+    /// let chain = ErrorChain::from_error(&err);
+    ///
+    /// assert_eq!(chain.causes[0].type_name, "std::io::error::Error");
+    /// ```
+    pub fn from_error<E: error::Error + 'static>(err: &E) -> Self {
+        let mut chain = ErrorChain::new();
+
+        chain.add_typed_cause(err.to_string(), std::any::type_name::<E>());
+
+        let mut source = err.source();
+
+        while let Some(cause) = source {
+            chain.add_cause(cause.to_string());
+            source = cause.source();
+        }
+
+        chain
+    }
+
+    /// Appends a cause with an unknown type name to the chain.
+    pub fn add_cause(&mut self, message: impl Into<String>) -> &mut Self {
+        self.causes.push(ErrorCause::new(message, ""));
+        self
+    }
+
+    /// Appends a cause with a known type name to the chain.
+    pub fn add_typed_cause(
+        &mut self,
+        message: impl Into<String>,
+        type_name: impl Into<String>,
+    ) -> &mut Self {
+        self.causes.push(ErrorCause::new(message, type_name));
+        self
+    }
+
+    /// Returns the innermost cause of the chain, if any.
+    pub fn root_cause(&self) -> Option<&ErrorCause> {
+        self.causes.last()
+    }
+}
+
+impl CustomErrorDetail for ErrorChain {
+    const TYPE_URL: &'static str = "type.googleapis.com/tonic_richer_error.ErrorChain";
+}
+
+impl fmt::Display for ErrorChain {
+    /// Writes the cause messages separated by `": "`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, cause) in self.causes.iter().enumerate() {
+            if index > 0 {
+                f.write_str(": ")?;
+            }
+            f.write_str(&cause.message)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ErrorDetails {
+    /// Set an [`ErrorChain`] custom error message, replacing any previous
+    /// one. Can be chained with other `.set_` and `.add_` [`ErrorDetails`]
+    /// methods.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorChain, ErrorDetails};
+    ///
+    /// let mut chain = ErrorChain::new();
+    /// chain.add_cause("failed to load user");
+    ///
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details.set_error_chain(chain);
+    ///
+    /// assert!(err_details.error_chain().is_some());
+    /// ```
+    pub fn set_error_chain(&mut self, chain: ErrorChain) -> &mut Self {
+        self.unknown_details
+            .retain(|any| any.type_url != ErrorChain::TYPE_URL);
+        self.add_custom(chain)
+    }
+
+    /// Returns the [`ErrorChain`] custom error message, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn error_chain(&self) -> Option<ErrorChain> {
+        self.get_custom::<ErrorChain>()
+    }
+}
+
+#[cfg(all(test, feature = "tonic"))]
+mod tests {
+
+    use std::{error, fmt};
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::ErrorChain;
+
+    #[derive(Debug)]
+    struct LoadError(std::io::Error);
+
+    impl fmt::Display for LoadError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("failed to load user")
+        }
+    }
+
+    impl error::Error for LoadError {
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn error_chain_round_trip() {
+        let err = LoadError(std::io::Error::other("connection refused"));
+
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_error_info("LOAD_FAILED", "example.local", Default::default())
+            .set_error_chain(ErrorChain::new())
+            .set_error_chain(ErrorChain::from_error(&err));
+
+        let status = Status::with_error_details(Code::Internal, "internal", err_details);
+
+        let chain = match status.error_chain() {
+            Some(chain) => chain,
+            None => panic!("status does not contain an error chain"),
+        };
+
+        let formatted = format!("{:?}", chain);
+
+        println!("error chain -> {formatted}");
+
+        let expected = format!(
+            "ErrorChain {{ causes: [ErrorCause {{ message: \"failed to load user\", type_name: {:?} }}, ErrorCause {{ message: \"connection refused\", type_name: \"\" }}] }}",
+            std::any::type_name::<LoadError>()
+        );
+
+        assert!(
+            formatted.eq(&expected),
+            "error chain differs from expected result"
+        );
+
+        assert!(
+            chain.to_string() == "failed to load user: connection refused",
+            "error chain display differs from expected result"
+        );
+
+        assert!(
+            status.error_details().unknown_details.len() == 1,
+            "previous error chain was not replaced"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt;
+
+use super::std_messages::*;
+use super::type_url_prefix::canonical_type_url;
+use super::{ErrorDetail, ErrorDetails};
+
+/// Identifies the kind of an error detail, without holding its data. Used to
+/// cheaply summarize the details carried by a status, for example in logs,
+/// metrics or routing decisions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ErrorDetailKind {
+    /// Identifies [`RetryInfo`] details.
+    RetryInfo,
+
+    /// Identifies [`DebugInfo`] details.
+    DebugInfo,
+
+    /// Identifies [`QuotaFailure`] details.
+    QuotaFailure,
+
+    /// Identifies [`ErrorInfo`] details.
+    ErrorInfo,
+
+    /// Identifies [`PreconditionFailure`] details.
+    PreconditionFailure,
+
+    /// Identifies [`BadRequest`] details.
+    BadRequest,
+
+    /// Identifies [`RequestInfo`] details.
+    RequestInfo,
+
+    /// Identifies [`ResourceInfo`] details.
+    ResourceInfo,
+
+    /// Identifies [`Help`] details.
+    Help,
+
+    /// Identifies [`LocalizedMessage`] details.
+    LocalizedMessage,
+
+    /// Identifies [`HttpRequest`] details.
+    HttpRequest,
+
+    /// Identifies [`HttpResponse`] details.
+    HttpResponse,
+
+    /// Identifies details with unknown type URLs, such as custom messages.
+    Other,
+}
+
+impl ErrorDetailKind {
+    /// Returns the kind of the detail with the given type URL. Standard error
+    /// messages are recognized whatever their type URL prefix.
+    pub fn from_type_url(type_url: &str) -> Self {
+        match canonical_type_url(type_url) {
+            RetryInfo::TYPE_URL => ErrorDetailKind::RetryInfo,
+            DebugInfo::TYPE_URL => ErrorDetailKind::DebugInfo,
+            QuotaFailure::TYPE_URL => ErrorDetailKind::QuotaFailure,
+            ErrorInfo::TYPE_URL => ErrorDetailKind::ErrorInfo,
+            PreconditionFailure::TYPE_URL => ErrorDetailKind::PreconditionFailure,
+            BadRequest::TYPE_URL => ErrorDetailKind::BadRequest,
+            RequestInfo::TYPE_URL => ErrorDetailKind::RequestInfo,
+            ResourceInfo::TYPE_URL => ErrorDetailKind::ResourceInfo,
+            Help::TYPE_URL => ErrorDetailKind::Help,
+            LocalizedMessage::TYPE_URL => ErrorDetailKind::LocalizedMessage,
+            HttpRequest::TYPE_URL => ErrorDetailKind::HttpRequest,
+            HttpResponse::TYPE_URL => ErrorDetailKind::HttpResponse,
+            _ => ErrorDetailKind::Other,
+        }
+    }
+
+    /// Returns the snake_case name of the kind, such as `"bad_request"`,
+    /// suitable for log fields and metric labels.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorDetailKind::RetryInfo => "retry_info",
+            ErrorDetailKind::DebugInfo => "debug_info",
+            ErrorDetailKind::QuotaFailure => "quota_failure",
+            ErrorDetailKind::ErrorInfo => "error_info",
+            ErrorDetailKind::PreconditionFailure => "precondition_failure",
+            ErrorDetailKind::BadRequest => "bad_request",
+            ErrorDetailKind::RequestInfo => "request_info",
+            ErrorDetailKind::ResourceInfo => "resource_info",
+            ErrorDetailKind::Help => "help",
+            ErrorDetailKind::LocalizedMessage => "localized_message",
+            ErrorDetailKind::HttpRequest => "http_request",
+            ErrorDetailKind::HttpResponse => "http_response",
+            ErrorDetailKind::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for ErrorDetailKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ErrorDetails {
+    /// Returns the kinds of the details stored in the [`ErrorDetails`], in
+    /// the order they are declared in [`ErrorDetailKind`]. Each kind is
+    /// listed once, even if several details with unknown type URLs are
+    /// present.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetailKind, ErrorDetails};
+    ///
+    /// let mut err_details = ErrorDetails::with_bad_request_violation("field", "description");
+    ///
+    /// err_details.set_request_info("request-id", "");
+    ///
+    /// assert_eq!(
+    ///     err_details.kinds(),
+    ///     [ErrorDetailKind::BadRequest, ErrorDetailKind::RequestInfo]
+    /// );
+    /// ```
+    pub fn kinds(&self) -> Vec<ErrorDetailKind> {
+        let present = [
+            (ErrorDetailKind::RetryInfo, self.retry_info.is_some()),
+            (ErrorDetailKind::DebugInfo, self.debug_info.is_some()),
+            (ErrorDetailKind::QuotaFailure, self.quota_failure.is_some()),
+            (ErrorDetailKind::ErrorInfo, self.error_info.is_some()),
+            (
+                ErrorDetailKind::PreconditionFailure,
+                self.precondition_failure.is_some(),
+            ),
+            (ErrorDetailKind::BadRequest, self.bad_request.is_some()),
+            (ErrorDetailKind::RequestInfo, self.request_info.is_some()),
+            (ErrorDetailKind::ResourceInfo, self.resource_info.is_some()),
+            (ErrorDetailKind::Help, self.help.is_some()),
+            (
+                ErrorDetailKind::LocalizedMessage,
+                self.localized_message.is_some(),
+            ),
+        ];
+
+        let mut kinds: Vec<ErrorDetailKind> = present
+            .into_iter()
+            .filter_map(|(kind, is_present)| is_present.then_some(kind))
+            .collect();
+
+        kinds.extend(
+            self.unknown_details
+                .iter()
+                .map(|any| ErrorDetailKind::from_type_url(&any.type_url)),
+        );
+
+        kinds.sort();
+        kinds.dedup();
+
+        kinds
+    }
+
+    /// Returns the number of details stored in the [`ErrorDetails`],
+    /// counting each detail with an unknown type URL separately.
+    pub fn len(&self) -> usize {
+        [
+            self.retry_info.is_some(),
+            self.debug_info.is_some(),
+            self.quota_failure.is_some(),
+            self.error_info.is_some(),
+            self.precondition_failure.is_some(),
+            self.bad_request.is_some(),
+            self.request_info.is_some(),
+            self.resource_info.is_some(),
+            self.help.is_some(),
+            self.localized_message.is_some(),
+        ]
+        .into_iter()
+        .filter(|is_present| *is_present)
+        .count()
+            + self.unknown_details.len()
+    }
+
+    /// Returns `true` if the [`ErrorDetails`] stores no details, and `false`
+    /// otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ErrorDetail {
+    /// Returns the kind of the wrapped detail.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{BadRequest, ErrorDetail, ErrorDetailKind};
+    ///
+    /// let detail: ErrorDetail = BadRequest::with_violation("field", "description").into();
+    ///
+    /// assert_eq!(detail.kind(), ErrorDetailKind::BadRequest);
+    /// ```
+    pub fn kind(&self) -> ErrorDetailKind {
+        match self {
+            ErrorDetail::RetryInfo(_) => ErrorDetailKind::RetryInfo,
+            ErrorDetail::DebugInfo(_) => ErrorDetailKind::DebugInfo,
+            ErrorDetail::QuotaFailure(_) => ErrorDetailKind::QuotaFailure,
+            ErrorDetail::ErrorInfo(_) => ErrorDetailKind::ErrorInfo,
+            ErrorDetail::PreconditionFailure(_) => ErrorDetailKind::PreconditionFailure,
+            ErrorDetail::BadRequest(_) => ErrorDetailKind::BadRequest,
+            ErrorDetail::RequestInfo(_) => ErrorDetailKind::RequestInfo,
+            ErrorDetail::ResourceInfo(_) => ErrorDetailKind::ResourceInfo,
+            ErrorDetail::Help(_) => ErrorDetailKind::Help,
+            ErrorDetail::LocalizedMessage(_) => ErrorDetailKind::LocalizedMessage,
+            ErrorDetail::HttpRequest(_) => ErrorDetailKind::HttpRequest,
+            ErrorDetail::HttpResponse(_) => ErrorDetailKind::HttpResponse,
+            ErrorDetail::Other(any) => ErrorDetailKind::from_type_url(&any.type_url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::{ErrorChain, ErrorDetails, HttpRequest};
+    use super::ErrorDetailKind;
+
+    #[test]
+    fn error_details_kinds() {
+        let empty = ErrorDetails::new();
+
+        assert!(
+            empty.is_empty() && empty.kinds().is_empty(),
+            "empty ErrorDetails reports stored details"
+        );
+
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_localized_message("en-US", "message for the user")
+            .add_bad_request_violation("field", "description")
+            .set_http_request(HttpRequest::new("GET", "/books/1"))
+            .set_error_chain(ErrorChain::new())
+            .set_error_info("SOME_INFO", "example.local", Default::default());
+
+        let formatted = format!("{:?}", err_details.kinds());
+
+        println!("ErrorDetails kinds -> {formatted}");
+
+        let expected = "[ErrorInfo, BadRequest, LocalizedMessage, HttpRequest, Other]";
+
+        assert!(
+            formatted.eq(expected),
+            "ErrorDetails kinds differ from expected result"
+        );
+
+        assert!(
+            err_details.len() == 5 && !err_details.is_empty(),
+            "ErrorDetails length differs from expected result"
+        );
+
+        let names: Vec<&str> = err_details
+            .kinds()
+            .iter()
+            .map(ErrorDetailKind::as_str)
+            .collect();
+
+        assert!(
+            names.eq(&[
+                "error_info",
+                "bad_request",
+                "localized_message",
+                "http_request",
+                "other"
+            ]),
+            "ErrorDetailKind names differ from expected result"
+        );
+    }
+}
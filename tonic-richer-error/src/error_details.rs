@@ -1,12 +1,30 @@
-use std::{collections::HashMap, time};
-
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::time;
+
+use prost_types::Any;
+#[cfg(feature = "tonic")]
+use tonic::{Code, Status};
+
+#[cfg(feature = "url")]
+use super::policy;
 use super::std_messages::*;
+#[cfg(feature = "tonic")]
+use super::WithErrorDetails;
 
 /// Groups the standard error messages structs. Provides associated
 /// functions and methods to setup and edit each error message independently.
 /// Used when extracting error details from `tonic::Status`, and when
 /// creating a `tonic::Status` with error details.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ErrorDetails {
     /// This field stores [`RetryInfo`] data, if any.
     pub retry_info: Option<RetryInfo>,
@@ -37,10 +55,22 @@ pub struct ErrorDetails {
 
     /// This field stores [`LocalizedMessage`] data, if any.
     pub localized_message: Option<LocalizedMessage>,
+
+    /// This field stores details with unknown type URLs, such as custom or
+    /// proxied messages, so they are preserved when the details are
+    /// extracted from and added back to a `tonic::Status`. Skipped when
+    /// serializing with the `serde` feature.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(
+        feature = "arbitrary",
+        arbitrary(with = super::arbitrary_details::unknown_details)
+    )]
+    pub unknown_details: Vec<Any>,
 }
 
 impl ErrorDetails {
-    /// Generates an [`ErrorDetails`] struct with all fields set to `None`.
+    /// Generates an [`ErrorDetails`] struct with all fields set to `None`,
+    /// and no unknown details.
     /// # Examples
     ///
     /// ```
@@ -60,9 +90,18 @@ impl ErrorDetails {
             resource_info: None,
             help: None,
             localized_message: None,
+            unknown_details: Vec::new(),
         }
     }
+}
 
+impl Default for ErrorDetails {
+    fn default() -> Self {
+        ErrorDetails::new()
+    }
+}
+
+impl ErrorDetails {
     /// Generates an [`ErrorDetails`] struct with [`RetryInfo`] details and
     /// remaining fields set to `None`.
     /// # Examples
@@ -136,23 +175,56 @@ impl ErrorDetails {
         }
     }
 
+    /// Generates an [`ErrorDetails`] struct with [`QuotaFailure`] details
+    /// describing an exceeded rate limit, as in [`QuotaFailure::rate_limited`],
+    /// and [`RetryInfo`] details if `retry_delay` is set. Remaining fields
+    /// are set to `None`.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tonic_richer_error::{ErrorDetails};
+    ///
+    /// let err_details = ErrorDetails::with_rate_limit(
+    ///     "clientip:127.0.0.1",
+    ///     100,
+    ///     Duration::from_secs(60),
+    ///     Some(Duration::from_secs(15)),
+    /// );
+    /// ```
+    pub fn with_rate_limit(
+        subject: impl Into<String>,
+        limit: u64,
+        window: time::Duration,
+        retry_delay: Option<time::Duration>,
+    ) -> Self {
+        ErrorDetails {
+            retry_info: retry_delay.map(|delay| RetryInfo::new(Some(delay))),
+            quota_failure: Some(QuotaFailure::rate_limited(subject, limit, window)),
+            ..ErrorDetails::new()
+        }
+    }
+
     /// Generates an [`ErrorDetails`] struct with [`ErrorInfo`] details and
     /// remaining fields set to `None`.
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// use std::collections::BTreeMap;
     /// use tonic_richer_error::{ErrorDetails};
     ///
-    /// let mut metadata: HashMap<String, String> = HashMap::new();
+    /// let mut metadata: BTreeMap<String, String> = BTreeMap::new();
     /// metadata.insert("instanceLimitPerRequest".into(), "100".into());
     ///
     /// let err_details = ErrorDetails::with_error_info("reason", "domain", metadata);
+    /// # }
     /// ```
     pub fn with_error_info(
         reason: impl Into<String>,
         domain: impl Into<String>,
-        metadata: HashMap<String, String>,
+        metadata: BTreeMap<String, String>,
     ) -> Self {
         ErrorDetails {
             error_info: Some(ErrorInfo::new(reason, domain, metadata)),
@@ -408,6 +480,34 @@ impl ErrorDetails {
         self
     }
 
+    /// Set [`DebugInfo`] details, with `stack_entries` obtained from a
+    /// `std::backtrace::Backtrace` of the current thread, as described in
+    /// [`DebugInfo::from_backtrace`]. If `max_depth` is set, frames below it
+    /// are redacted. Can be chained with other `.set_` and `.add_`
+    /// [`ErrorDetails`] methods.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetails};
+    ///
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details.set_debug_info_with_backtrace("error details", Some(10));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn set_debug_info_with_backtrace(
+        &mut self,
+        detail: impl Into<String>,
+        max_depth: Option<usize>,
+    ) -> &mut Self {
+        let mut debug_info = DebugInfo::from_backtrace(detail);
+        if let Some(max_depth) = max_depth {
+            debug_info.redact_below(max_depth);
+        }
+        self.debug_info = Some(debug_info);
+        self
+    }
+
     /// Set [`QuotaFailure`] details. Can be chained with other `.set_` and
     /// `.add_` [`ErrorDetails`] methods.
     /// # Examples
@@ -482,21 +582,24 @@ impl ErrorDetails {
     /// # Examples
     ///
     /// ```
-    /// use std::collections::HashMap;
+    /// # #[cfg(feature = "std")]
+    /// # {
+    /// use std::collections::BTreeMap;
     /// use tonic_richer_error::{ErrorDetails};
     ///
     /// let mut err_details = ErrorDetails::new();
     ///
-    /// let mut metadata: HashMap<String, String> = HashMap::new();
+    /// let mut metadata: BTreeMap<String, String> = BTreeMap::new();
     /// metadata.insert("instanceLimitPerRequest".into(), "100".into());
     ///
     /// err_details.set_error_info("reason", "example.local", metadata);
+    /// # }
     /// ```
     pub fn set_error_info(
         &mut self,
         reason: impl Into<String>,
         domain: impl Into<String>,
-        metadata: HashMap<String, String>,
+        metadata: BTreeMap<String, String>,
     ) -> &mut Self {
         self.error_info = Some(ErrorInfo::new(reason, domain, metadata));
         self
@@ -731,8 +834,9 @@ impl ErrorDetails {
     }
 
     /// Adds a [`HelpLink`] to [`Help`] details. Sets [`Help`] details if it is
-    /// not set yet. Can be chained with other `.set_` and `.add_`
-    /// [`ErrorDetails`] methods.
+    /// not set yet. If the installed [`crate::ErrorDetailsPolicy`] requires
+    /// valid help links, links with invalid URLs are discarded. Can be chained
+    /// with other `.set_` and `.add_` [`ErrorDetails`] methods.
     /// # Examples
     ///
     /// ```
@@ -747,12 +851,19 @@ impl ErrorDetails {
         description: impl Into<String>,
         url: impl Into<String>,
     ) -> &mut Self {
+        let link = HelpLink::new(description, url);
+
+        #[cfg(feature = "url")]
+        if !policy::allows_help_link_global(&link) {
+            return self;
+        }
+
         match &mut self.help {
             Some(help) => {
-                help.add_link(description, url);
+                help.links.push(link);
             }
             None => {
-                self.help = Some(Help::with_link(description, url));
+                self.help = Some(Help::new(vec![link]));
             }
         };
         self
@@ -800,3 +911,72 @@ impl ErrorDetails {
         self
     }
 }
+
+impl ErrorDetails {
+    /// Infers the canonical `tonic::Code` for the error details, following
+    /// the code and detail pairings of the Google API error model. Details
+    /// are checked in the following order:
+    /// - [`BadRequest`] details: `Code::InvalidArgument`
+    /// - [`PreconditionFailure`] details: `Code::FailedPrecondition`
+    /// - [`QuotaFailure`] details: `Code::ResourceExhausted`
+    /// - [`RetryInfo`] details: `Code::Unavailable`
+    ///
+    /// If none of them is set, `Code::Unknown` is returned. The inferred code
+    /// is accepted by [`ErrorDetails::check_code_pairing`], unless the
+    /// details cannot share any code, like [`BadRequest`] and [`RetryInfo`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::Code;
+    /// use tonic_richer_error::{ErrorDetails};
+    ///
+    /// let err_details = ErrorDetails::with_quota_failure_violation("subject", "description");
+    ///
+    /// assert_eq!(err_details.inferred_code(), Code::ResourceExhausted);
+    /// ```
+    #[cfg(feature = "tonic")]
+    pub fn inferred_code(&self) -> Code {
+        if self.bad_request.is_some() {
+            return Code::InvalidArgument;
+        }
+        if self.precondition_failure.is_some() {
+            return Code::FailedPrecondition;
+        }
+        if self.quota_failure.is_some() {
+            return Code::ResourceExhausted;
+        }
+        if self.retry_info.is_some() {
+            return Code::Unavailable;
+        }
+        Code::Unknown
+    }
+
+    /// Generates a `tonic::Status` with the error details, and the code
+    /// returned by [`ErrorDetails::inferred_code`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::Code;
+    /// use tonic_richer_error::{ErrorDetails};
+    ///
+    /// let status = ErrorDetails::with_bad_request_violation("field", "description")
+    ///     .into_status("bad request");
+    ///
+    /// assert_eq!(status.code(), Code::InvalidArgument);
+    /// ```
+    #[cfg(feature = "tonic")]
+    pub fn into_status(self, message: impl Into<String>) -> Status {
+        Status::with_error_details(self.inferred_code(), message, self)
+    }
+}
+
+#[cfg(feature = "tonic")]
+impl From<ErrorDetails> for Status {
+    /// Generates a `tonic::Status` with the error details, and the code
+    /// returned by [`ErrorDetails::inferred_code`]. The code description is
+    /// used as message.
+    fn from(details: ErrorDetails) -> Self {
+        let code = details.inferred_code();
+        Status::with_error_details(code, code.description(), details)
+    }
+}
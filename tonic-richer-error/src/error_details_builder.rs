@@ -0,0 +1,229 @@
+use std::{collections::BTreeMap, marker::PhantomData, time};
+
+use super::ErrorDetails;
+
+/// Marks an [`ErrorDetailsBuilder`] that has no error details yet, and thus
+/// cannot be built.
+#[derive(Clone, Copy, Debug)]
+pub struct NoDetails;
+
+/// Marks an [`ErrorDetailsBuilder`] that has at least one error detail, and
+/// thus can be built.
+#[derive(Clone, Copy, Debug)]
+pub struct HasDetails;
+
+/// Builds a validated [`ErrorDetails`] struct. Unlike the [`ErrorDetails`]
+/// setters, every required field of an error message must be provided together
+/// with it, and messages that hold lists (like
+/// [`BadRequest`](crate::BadRequest)) can only be created from their first
+/// entry, so they are never empty. Additionally, [`ErrorDetailsBuilder::build`]
+/// is only available after at least one error detail was added.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::ErrorDetails;
+///
+/// let err_details = ErrorDetails::builder()
+///     .bad_request_violation("field_1", "description 1")
+///     .bad_request_violation("field_2", "description 2")
+///     .localized_message("en-US", "message for the user")
+///     .build();
+///
+/// assert!(err_details.has_bad_request_violations());
+/// ```
+///
+/// A builder without error details does not compile:
+///
+/// ```compile_fail
+/// use tonic_richer_error::ErrorDetails;
+///
+/// let err_details = ErrorDetails::builder().build();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ErrorDetailsBuilder<S = NoDetails> {
+    details: ErrorDetails,
+    state: PhantomData<S>,
+}
+
+impl ErrorDetails {
+    /// Creates an [`ErrorDetailsBuilder`], that enforces the error messages
+    /// invariants at compile time.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let err_details = ErrorDetails::builder()
+    ///     .request_info("request-id", "some-request-data")
+    ///     .build();
+    /// ```
+    pub fn builder() -> ErrorDetailsBuilder<NoDetails> {
+        ErrorDetailsBuilder {
+            details: ErrorDetails::new(),
+            state: PhantomData,
+        }
+    }
+}
+
+impl<S> ErrorDetailsBuilder<S> {
+    fn with_details(self) -> ErrorDetailsBuilder<HasDetails> {
+        ErrorDetailsBuilder {
+            details: self.details,
+            state: PhantomData,
+        }
+    }
+
+    /// Sets [`RetryInfo`](crate::RetryInfo) details.
+    pub fn retry_info(
+        mut self,
+        retry_delay: Option<time::Duration>,
+    ) -> ErrorDetailsBuilder<HasDetails> {
+        self.details.set_retry_info(retry_delay);
+        self.with_details()
+    }
+
+    /// Sets [`DebugInfo`](crate::DebugInfo) details.
+    pub fn debug_info(
+        mut self,
+        stack_entries: Vec<String>,
+        detail: impl Into<String>,
+    ) -> ErrorDetailsBuilder<HasDetails> {
+        self.details.set_debug_info(stack_entries, detail);
+        self.with_details()
+    }
+
+    /// Adds a [`QuotaViolation`](crate::QuotaViolation) to
+    /// [`QuotaFailure`](crate::QuotaFailure) details, setting them if needed.
+    pub fn quota_failure_violation(
+        mut self,
+        subject: impl Into<String>,
+        description: impl Into<String>,
+    ) -> ErrorDetailsBuilder<HasDetails> {
+        self.details
+            .add_quota_failure_violation(subject, description);
+        self.with_details()
+    }
+
+    /// Sets [`ErrorInfo`](crate::ErrorInfo) details. Both `reason` and `domain`
+    /// are required.
+    pub fn error_info(
+        mut self,
+        reason: impl Into<String>,
+        domain: impl Into<String>,
+        metadata: BTreeMap<String, String>,
+    ) -> ErrorDetailsBuilder<HasDetails> {
+        self.details.set_error_info(reason, domain, metadata);
+        self.with_details()
+    }
+
+    /// Adds a [`PreconditionViolation`](crate::PreconditionViolation) to
+    /// [`PreconditionFailure`](crate::PreconditionFailure) details, setting
+    /// them if needed.
+    pub fn precondition_failure_violation(
+        mut self,
+        violation_type: impl Into<String>,
+        subject: impl Into<String>,
+        description: impl Into<String>,
+    ) -> ErrorDetailsBuilder<HasDetails> {
+        self.details
+            .add_precondition_failure_violation(violation_type, subject, description);
+        self.with_details()
+    }
+
+    /// Adds a [`FieldViolation`](crate::FieldViolation) to
+    /// [`BadRequest`](crate::BadRequest) details, setting them if needed.
+    pub fn bad_request_violation(
+        mut self,
+        field: impl Into<String>,
+        description: impl Into<String>,
+    ) -> ErrorDetailsBuilder<HasDetails> {
+        self.details.add_bad_request_violation(field, description);
+        self.with_details()
+    }
+
+    /// Sets [`RequestInfo`](crate::RequestInfo) details.
+    pub fn request_info(
+        mut self,
+        request_id: impl Into<String>,
+        serving_data: impl Into<String>,
+    ) -> ErrorDetailsBuilder<HasDetails> {
+        self.details.set_request_info(request_id, serving_data);
+        self.with_details()
+    }
+
+    /// Sets [`ResourceInfo`](crate::ResourceInfo) details.
+    pub fn resource_info(
+        mut self,
+        resource_type: impl Into<String>,
+        resource_name: impl Into<String>,
+        owner: impl Into<String>,
+        description: impl Into<String>,
+    ) -> ErrorDetailsBuilder<HasDetails> {
+        self.details
+            .set_resource_info(resource_type, resource_name, owner, description);
+        self.with_details()
+    }
+
+    /// Adds a [`HelpLink`](crate::HelpLink) to [`Help`](crate::Help) details,
+    /// setting them if needed.
+    pub fn help_link(
+        mut self,
+        description: impl Into<String>,
+        url: impl Into<String>,
+    ) -> ErrorDetailsBuilder<HasDetails> {
+        self.details.add_help_link(description, url);
+        self.with_details()
+    }
+
+    /// Sets [`LocalizedMessage`](crate::LocalizedMessage) details. Both
+    /// `locale` and `message` are required.
+    pub fn localized_message(
+        mut self,
+        locale: impl Into<String>,
+        message: impl Into<String>,
+    ) -> ErrorDetailsBuilder<HasDetails> {
+        self.details.set_localized_message(locale, message);
+        self.with_details()
+    }
+}
+
+impl ErrorDetailsBuilder<HasDetails> {
+    /// Returns the validated [`ErrorDetails`] struct.
+    pub fn build(self) -> ErrorDetails {
+        self.details
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use super::super::ErrorDetails;
+
+    #[test]
+    fn builder_matches_setters() {
+        let built = ErrorDetails::builder()
+            .retry_info(Some(Duration::from_secs(5)))
+            .error_info("SOME_INFO", "example.local", BTreeMap::new())
+            .bad_request_violation("field", "description")
+            .help_link("link to resource", "resource.example.local")
+            .localized_message("en-US", "message for the user")
+            .build();
+
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .set_error_info("SOME_INFO", "example.local", BTreeMap::new())
+            .add_bad_request_violation("field", "description")
+            .add_help_link("link to resource", "resource.example.local")
+            .set_localized_message("en-US", "message for the user");
+
+        assert!(
+            format!("{:?}", built).eq(&format!("{:?}", err_details)),
+            "built details differ from details created with setters"
+        );
+    }
+}
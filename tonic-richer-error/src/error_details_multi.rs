@@ -0,0 +1,406 @@
+use prost_types::Any;
+
+use super::std_messages::*;
+use super::ErrorDetail;
+
+/// Stores error details in the order they were added or decoded, allowing
+/// multiple details of the same type, such as several [`Help`] messages.
+/// Provides typed accessors for each error message type. Used when the
+/// details of a `tonic::Status` must round trip with their original wire
+/// order, which [`crate::ErrorDetails`] does not preserve.
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "tonic")]
+/// # {
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{ErrorDetailsMulti, Help, WithErrorDetails};
+///
+/// let mut err_details = ErrorDetailsMulti::new();
+///
+/// err_details
+///     .push(Help::with_link("first link", "https://example.local/first"))
+///     .push(Help::with_link("second link", "https://example.local/second"));
+///
+/// let status = Status::with_error_details_multi(Code::NotFound, "not found", err_details);
+///
+/// assert_eq!(status.get_error_details_multi().helps().len(), 2);
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ErrorDetailsMulti {
+    retry_infos: Vec<RetryInfo>,
+    debug_infos: Vec<DebugInfo>,
+    quota_failures: Vec<QuotaFailure>,
+    error_infos: Vec<ErrorInfo>,
+    precondition_failures: Vec<PreconditionFailure>,
+    bad_requests: Vec<BadRequest>,
+    request_infos: Vec<RequestInfo>,
+    resource_infos: Vec<ResourceInfo>,
+    helps: Vec<Help>,
+    localized_messages: Vec<LocalizedMessage>,
+    http_requests: Vec<HttpRequest>,
+    http_responses: Vec<HttpResponse>,
+    other_details: Vec<Any>,
+    order: Vec<Kind>,
+}
+
+/// Identifies the type of each detail stored in [`ErrorDetailsMulti`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    RetryInfo,
+    DebugInfo,
+    QuotaFailure,
+    ErrorInfo,
+    PreconditionFailure,
+    BadRequest,
+    RequestInfo,
+    ResourceInfo,
+    Help,
+    LocalizedMessage,
+    HttpRequest,
+    HttpResponse,
+    Other,
+}
+
+impl ErrorDetailsMulti {
+    /// Generates an empty [`ErrorDetailsMulti`] struct.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorDetailsMulti;
+    ///
+    /// let err_details = ErrorDetailsMulti::new();
+    /// ```
+    pub fn new() -> Self {
+        ErrorDetailsMulti::default()
+    }
+
+    /// Appends a detail, after any previously added details. Details of
+    /// types already present are kept alongside the existing ones.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{BadRequest, ErrorDetailsMulti};
+    ///
+    /// let mut err_details = ErrorDetailsMulti::new();
+    ///
+    /// err_details
+    ///     .push(BadRequest::with_violation("field_a", "description"))
+    ///     .push(BadRequest::with_violation("field_b", "description"));
+    ///
+    /// assert_eq!(err_details.bad_requests().len(), 2);
+    /// ```
+    pub fn push(&mut self, detail: impl Into<ErrorDetail>) -> &mut Self {
+        let kind = match detail.into() {
+            ErrorDetail::RetryInfo(retry_info) => {
+                self.retry_infos.push(retry_info);
+                Kind::RetryInfo
+            }
+            ErrorDetail::DebugInfo(debug_info) => {
+                self.debug_infos.push(debug_info);
+                Kind::DebugInfo
+            }
+            ErrorDetail::QuotaFailure(quota_failure) => {
+                self.quota_failures.push(quota_failure);
+                Kind::QuotaFailure
+            }
+            ErrorDetail::ErrorInfo(error_info) => {
+                self.error_infos.push(error_info);
+                Kind::ErrorInfo
+            }
+            ErrorDetail::PreconditionFailure(precondition_failure) => {
+                self.precondition_failures.push(precondition_failure);
+                Kind::PreconditionFailure
+            }
+            ErrorDetail::BadRequest(bad_request) => {
+                self.bad_requests.push(bad_request);
+                Kind::BadRequest
+            }
+            ErrorDetail::RequestInfo(request_info) => {
+                self.request_infos.push(request_info);
+                Kind::RequestInfo
+            }
+            ErrorDetail::ResourceInfo(resource_info) => {
+                self.resource_infos.push(resource_info);
+                Kind::ResourceInfo
+            }
+            ErrorDetail::Help(help) => {
+                self.helps.push(help);
+                Kind::Help
+            }
+            ErrorDetail::LocalizedMessage(localized_message) => {
+                self.localized_messages.push(localized_message);
+                Kind::LocalizedMessage
+            }
+            ErrorDetail::HttpRequest(http_request) => {
+                self.http_requests.push(http_request);
+                Kind::HttpRequest
+            }
+            ErrorDetail::HttpResponse(http_response) => {
+                self.http_responses.push(http_response);
+                Kind::HttpResponse
+            }
+            ErrorDetail::Other(any) => {
+                self.other_details.push(any);
+                Kind::Other
+            }
+        };
+
+        self.order.push(kind);
+        self
+    }
+
+    /// Returns the number of stored details.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetailsMulti, Help};
+    ///
+    /// let mut err_details = ErrorDetailsMulti::new();
+    ///
+    /// err_details.push(Help::with_link("description", "https://example.local"));
+    ///
+    /// assert_eq!(err_details.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns `true` if no details are stored.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorDetailsMulti;
+    ///
+    /// assert!(ErrorDetailsMulti::new().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Returns the stored [`RetryInfo`] details, in the order they were added.
+    pub fn retry_infos(&self) -> &[RetryInfo] {
+        &self.retry_infos
+    }
+
+    /// Returns the stored [`DebugInfo`] details, in the order they were added.
+    pub fn debug_infos(&self) -> &[DebugInfo] {
+        &self.debug_infos
+    }
+
+    /// Returns the stored [`QuotaFailure`] details, in the order they were added.
+    pub fn quota_failures(&self) -> &[QuotaFailure] {
+        &self.quota_failures
+    }
+
+    /// Returns the stored [`ErrorInfo`] details, in the order they were added.
+    pub fn error_infos(&self) -> &[ErrorInfo] {
+        &self.error_infos
+    }
+
+    /// Returns the stored [`PreconditionFailure`] details, in the order they were added.
+    pub fn precondition_failures(&self) -> &[PreconditionFailure] {
+        &self.precondition_failures
+    }
+
+    /// Returns the stored [`BadRequest`] details, in the order they were added.
+    pub fn bad_requests(&self) -> &[BadRequest] {
+        &self.bad_requests
+    }
+
+    /// Returns the stored [`RequestInfo`] details, in the order they were added.
+    pub fn request_infos(&self) -> &[RequestInfo] {
+        &self.request_infos
+    }
+
+    /// Returns the stored [`ResourceInfo`] details, in the order they were added.
+    pub fn resource_infos(&self) -> &[ResourceInfo] {
+        &self.resource_infos
+    }
+
+    /// Returns the stored [`Help`] details, in the order they were added.
+    pub fn helps(&self) -> &[Help] {
+        &self.helps
+    }
+
+    /// Returns the stored [`LocalizedMessage`] details, in the order they were added.
+    pub fn localized_messages(&self) -> &[LocalizedMessage] {
+        &self.localized_messages
+    }
+
+    /// Returns the stored [`HttpRequest`] details, in the order they were added.
+    pub fn http_requests(&self) -> &[HttpRequest] {
+        &self.http_requests
+    }
+
+    /// Returns the stored [`HttpResponse`] details, in the order they were added.
+    pub fn http_responses(&self) -> &[HttpResponse] {
+        &self.http_responses
+    }
+
+    /// Returns the stored details with unknown type URLs, in the order they
+    /// were added.
+    pub fn other_details(&self) -> &[Any] {
+        &self.other_details
+    }
+
+    /// Returns a vector with copies of all stored details, in the order they
+    /// were added.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetail, ErrorDetailsMulti, Help, RetryInfo};
+    ///
+    /// let mut err_details = ErrorDetailsMulti::new();
+    ///
+    /// err_details
+    ///     .push(Help::with_link("description", "https://example.local"))
+    ///     .push(RetryInfo::new(None));
+    ///
+    /// let details = err_details.to_vec();
+    ///
+    /// assert!(matches!(details[0], ErrorDetail::Help(_)));
+    /// assert!(matches!(details[1], ErrorDetail::RetryInfo(_)));
+    /// ```
+    pub fn to_vec(&self) -> Vec<ErrorDetail> {
+        self.clone().into()
+    }
+}
+
+impl From<Vec<ErrorDetail>> for ErrorDetailsMulti {
+    fn from(details: Vec<ErrorDetail>) -> Self {
+        details.into_iter().collect()
+    }
+}
+
+impl FromIterator<ErrorDetail> for ErrorDetailsMulti {
+    fn from_iter<I: IntoIterator<Item = ErrorDetail>>(iter: I) -> Self {
+        let mut err_details = ErrorDetailsMulti::new();
+
+        for detail in iter {
+            err_details.push(detail);
+        }
+
+        err_details
+    }
+}
+
+impl From<ErrorDetailsMulti> for Vec<ErrorDetail> {
+    fn from(err_details: ErrorDetailsMulti) -> Self {
+        let mut retry_infos = err_details.retry_infos.into_iter();
+        let mut debug_infos = err_details.debug_infos.into_iter();
+        let mut quota_failures = err_details.quota_failures.into_iter();
+        let mut error_infos = err_details.error_infos.into_iter();
+        let mut precondition_failures = err_details.precondition_failures.into_iter();
+        let mut bad_requests = err_details.bad_requests.into_iter();
+        let mut request_infos = err_details.request_infos.into_iter();
+        let mut resource_infos = err_details.resource_infos.into_iter();
+        let mut helps = err_details.helps.into_iter();
+        let mut localized_messages = err_details.localized_messages.into_iter();
+        let mut http_requests = err_details.http_requests.into_iter();
+        let mut http_responses = err_details.http_responses.into_iter();
+        let mut other_details = err_details.other_details.into_iter();
+
+        // Each kind entry in `order` matches exactly one stored detail
+        err_details
+            .order
+            .into_iter()
+            .filter_map(|kind| match kind {
+                Kind::RetryInfo => retry_infos.next().map(ErrorDetail::from),
+                Kind::DebugInfo => debug_infos.next().map(ErrorDetail::from),
+                Kind::QuotaFailure => quota_failures.next().map(ErrorDetail::from),
+                Kind::ErrorInfo => error_infos.next().map(ErrorDetail::from),
+                Kind::PreconditionFailure => precondition_failures.next().map(ErrorDetail::from),
+                Kind::BadRequest => bad_requests.next().map(ErrorDetail::from),
+                Kind::RequestInfo => request_infos.next().map(ErrorDetail::from),
+                Kind::ResourceInfo => resource_infos.next().map(ErrorDetail::from),
+                Kind::Help => helps.next().map(ErrorDetail::from),
+                Kind::LocalizedMessage => localized_messages.next().map(ErrorDetail::from),
+                Kind::HttpRequest => http_requests.next().map(ErrorDetail::from),
+                Kind::HttpResponse => http_responses.next().map(ErrorDetail::from),
+                Kind::Other => other_details.next().map(ErrorDetail::from),
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "tonic"))]
+mod tests {
+
+    use prost_types::Any;
+    use tonic::{Code, Status};
+
+    use super::super::{BadRequest, ErrorDetail, Help, RetryInfo, WithErrorDetails};
+    use super::ErrorDetailsMulti;
+
+    #[test]
+    fn gen_status_with_multi_details() {
+        let mut err_details = ErrorDetailsMulti::new();
+
+        err_details
+            .push(Help::with_link("first link", "https://example.local/first"))
+            .push(BadRequest::with_violation("field", "description"))
+            .push(Any {
+                type_url: "type.example.local/Custom".into(),
+                value: vec![1, 2, 3],
+            })
+            .push(Help::with_link(
+                "second link",
+                "https://example.local/second",
+            ))
+            .push(RetryInfo::new(None));
+
+        let status = Status::with_error_details_multi(Code::NotFound, "not found", err_details);
+
+        let type_urls: Vec<&str> = status
+            .error_details_iter()
+            .map(|detail| detail.type_url)
+            .collect();
+
+        println!("type URLs in wire order -> {:?}", type_urls);
+
+        let expected = [
+            Help::TYPE_URL,
+            BadRequest::TYPE_URL,
+            "type.example.local/Custom",
+            Help::TYPE_URL,
+            RetryInfo::TYPE_URL,
+        ];
+
+        assert!(
+            type_urls.eq(&expected),
+            "type URLs in wire order differ from expected result"
+        );
+
+        let extracted = match status.check_error_details_multi() {
+            Ok(extracted) => extracted,
+            Err(err) => panic!("Error extracting multi details from status: {:?}", err),
+        };
+
+        let formatted = format!("{:?}", extracted.helps());
+
+        println!("extracted Help details -> {formatted}");
+
+        let expected = "[Help { links: [HelpLink { description: \"first link\", url: \"https://example.local/first\" }] }, Help { links: [HelpLink { description: \"second link\", url: \"https://example.local/second\" }] }]";
+
+        assert!(
+            formatted.eq(expected),
+            "extracted Help details differ from expected result"
+        );
+
+        assert!(
+            extracted.len() == 5 && extracted.other_details().len() == 1,
+            "extracted details differ from original"
+        );
+
+        let details: Vec<ErrorDetail> = extracted.into();
+
+        let reencoded = Status::with_error_details_vec(Code::NotFound, "not found", details);
+
+        assert!(
+            reencoded.details() == status.details(),
+            "round trip changed the order of the details"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tonic::codegen::futures_core::Stream;
+use tonic::Status;
+
+use super::{ErrorDetails, WithErrorDetails};
+
+/// Stream generated by [`with_error_details_stream`] and
+/// [`StreamStatusExt::map_error_details`]. Forwards the items of the inner
+/// stream, passing the details of every `Err(Status)` through a closure.
+pub struct ErrorDetailsStream<S, F> {
+    inner: Pin<Box<S>>,
+    enrich: F,
+}
+
+impl<S, F> std::fmt::Debug for ErrorDetailsStream<S, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorDetailsStream").finish_non_exhaustive()
+    }
+}
+
+impl<S, F, T> Stream for ErrorDetailsStream<S, F>
+where
+    S: Stream<Item = Result<T, Status>>,
+    F: FnMut(&mut ErrorDetails) + Unpin,
+{
+    type Item = Result<T, Status>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        match this.inner.as_mut().poll_next(cx) {
+            Poll::Ready(Some(Err(status))) => {
+                let enrich = &mut this.enrich;
+                Poll::Ready(Some(Err(
+                    status.map_error_details(|details| enrich(details))
+                )))
+            }
+            other => other,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Wraps a stream of `Result<T, tonic::Status>`, such as the response stream
+/// of a server-streaming endpoint, so that the error details of every
+/// `Err(Status)` it yields are modified by `enrich`, as in
+/// [`WithErrorDetails::map_error_details`]. `Ok` items are forwarded
+/// unchanged.
+/// # Examples
+///
+/// ```
+/// use tonic::codegen::futures_core::Stream;
+/// use tonic::Status;
+/// use tonic_richer_error::with_error_details_stream;
+///
+/// fn enriched<S, T>(stream: S) -> impl Stream<Item = Result<T, Status>>
+/// where
+///     S: Stream<Item = Result<T, Status>>,
+/// {
+///     with_error_details_stream(stream, |details| {
+///         details.add_help_link("streaming guide", "https://example.local/streaming");
+///     })
+/// }
+/// ```
+pub fn with_error_details_stream<S, F, T>(stream: S, enrich: F) -> ErrorDetailsStream<S, F>
+where
+    S: Stream<Item = Result<T, Status>>,
+    F: FnMut(&mut ErrorDetails) + Unpin,
+{
+    ErrorDetailsStream {
+        inner: Box::pin(stream),
+        enrich,
+    }
+}
+
+/// Extension trait for streams of `Result<T, tonic::Status>`, providing the
+/// error details helpers available for unary results.
+pub trait StreamStatusExt<T>: Stream<Item = Result<T, Status>> + Sized {
+    /// Modifies the error details of every `Err(Status)` yielded by the
+    /// stream, as described in [`with_error_details_stream`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::codegen::futures_core::Stream;
+    /// use tonic::Status;
+    /// use tonic_richer_error::StreamStatusExt;
+    ///
+    /// fn enriched<S, T>(stream: S) -> impl Stream<Item = Result<T, Status>>
+    /// where
+    ///     S: Stream<Item = Result<T, Status>>,
+    /// {
+    ///     stream.map_error_details(|details| {
+    ///         details.set_request_info("request-id", "");
+    ///     })
+    /// }
+    /// ```
+    fn map_error_details<F>(self, enrich: F) -> ErrorDetailsStream<Self, F>
+    where
+        F: FnMut(&mut ErrorDetails) + Unpin,
+    {
+        with_error_details_stream(self, enrich)
+    }
+}
+
+impl<S, T> StreamStatusExt<T> for S where S: Stream<Item = Result<T, Status>> {}
+
+#[cfg(test)]
+mod tests {
+
+    use std::future::poll_fn;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use tonic::codegen::futures_core::Stream;
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::StreamStatusExt;
+
+    /// Yields the items of a vector.
+    struct IterStream(std::vec::IntoIter<Result<u32, Status>>);
+
+    impl Stream for IterStream {
+        type Item = Result<u32, Status>;
+
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Poll::Ready(self.0.next())
+        }
+    }
+
+    #[tokio::test]
+    async fn enrich_stream_errors() {
+        let items = vec![
+            Ok(1),
+            Err(Status::with_error_details(
+                Code::InvalidArgument,
+                "bad request",
+                ErrorDetails::with_bad_request_violation("field", "description"),
+            )),
+            Ok(2),
+            Err(Status::internal("internal")),
+        ];
+
+        let mut stream = IterStream(items.into_iter()).map_error_details(|details| {
+            details.set_request_info("request-id", "");
+        });
+
+        let mut results = Vec::new();
+
+        while let Some(item) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            results.push(match item {
+                Ok(value) => format!("Ok({value})"),
+                Err(status) => format!(
+                    "Err({:?}, {:?}, {:?})",
+                    status.code(),
+                    status.get_details_bad_request().is_some(),
+                    status.get_details_request_info()
+                ),
+            });
+        }
+
+        println!("enriched stream items -> {:?}", results);
+
+        let expected = [
+            "Ok(1)",
+            "Err(InvalidArgument, true, Some(RequestInfo { request_id: \"request-id\", serving_data: \"\" }))",
+            "Ok(2)",
+            "Err(Internal, false, Some(RequestInfo { request_id: \"request-id\", serving_data: \"\" }))",
+        ];
+
+        assert!(
+            results.eq(&expected),
+            "enriched stream items differ from expected result"
+        );
+    }
+}
@@ -1,38 +1,122 @@
+use prost::DecodeError;
+use prost_types::Any;
+
 use super::std_messages::*;
+use super::{AnyRef, FromAny, IntoAny};
 
 /// Wraps the structs corresponding to the standard error messages, allowing
 /// the implementation and handling of vectors containing any of them.
-#[derive(Clone, Debug)]
+/// Details with unknown type URLs are kept at the [`ErrorDetail::Other`]
+/// variant.
+///
+/// When the `serde` feature is enabled, each variant is tagged with the
+/// corresponding type URL at the `@type` field, as in the JSON representation
+/// of `google.protobuf.Any`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "@type")
+)]
 pub enum ErrorDetail {
     /// Wraps the [`RetryInfo`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.RetryInfo")
+    )]
     RetryInfo(RetryInfo),
 
     /// Wraps the [`DebugInfo`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.DebugInfo")
+    )]
     DebugInfo(DebugInfo),
 
     /// Wraps the [`QuotaFailure`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.QuotaFailure")
+    )]
     QuotaFailure(QuotaFailure),
 
     /// Wraps the [`ErrorInfo`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.ErrorInfo")
+    )]
     ErrorInfo(ErrorInfo),
 
     /// Wraps the [`PreconditionFailure`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.PreconditionFailure")
+    )]
     PreconditionFailure(PreconditionFailure),
 
     /// Wraps the [`BadRequest`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.BadRequest")
+    )]
     BadRequest(BadRequest),
 
     /// Wraps the [`RequestInfo`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.RequestInfo")
+    )]
     RequestInfo(RequestInfo),
 
     /// Wraps the [`ResourceInfo`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.ResourceInfo")
+    )]
     ResourceInfo(ResourceInfo),
 
     /// Wraps the [`Help`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.Help")
+    )]
     Help(Help),
 
     /// Wraps the [`LocalizedMessage`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.LocalizedMessage")
+    )]
     LocalizedMessage(LocalizedMessage),
+
+    /// Wraps the [`HttpRequest`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.HttpRequest")
+    )]
+    HttpRequest(HttpRequest),
+
+    /// Wraps the [`HttpResponse`] struct.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "type.googleapis.com/google.rpc.HttpResponse")
+    )]
+    HttpResponse(HttpResponse),
+
+    /// Wraps a detail message with an unknown type URL, such as a custom or
+    /// proxied message, so it is preserved when the details are extracted
+    /// from and added back to a `tonic::Status`. With the `serde` feature,
+    /// serialized as its type URL at the `@type` field, and its encoded
+    /// message as base64 text at the `value` field.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            untagged,
+            serialize_with = "serde_other::serialize",
+            deserialize_with = "serde_other::deserialize"
+        )
+    )]
+    Other(Any),
 }
 
 impl From<RetryInfo> for ErrorDetail {
@@ -94,3 +178,98 @@ impl From<LocalizedMessage> for ErrorDetail {
         ErrorDetail::LocalizedMessage(err_detail)
     }
 }
+
+impl From<HttpRequest> for ErrorDetail {
+    fn from(err_detail: HttpRequest) -> Self {
+        ErrorDetail::HttpRequest(err_detail)
+    }
+}
+
+impl From<HttpResponse> for ErrorDetail {
+    fn from(err_detail: HttpResponse) -> Self {
+        ErrorDetail::HttpResponse(err_detail)
+    }
+}
+
+impl From<Any> for ErrorDetail {
+    fn from(err_detail: Any) -> Self {
+        ErrorDetail::Other(err_detail)
+    }
+}
+
+impl IntoAny for ErrorDetail {
+    fn into_any(self) -> Any {
+        match self {
+            ErrorDetail::RetryInfo(retry_info) => retry_info.into_any(),
+            ErrorDetail::DebugInfo(debug_info) => debug_info.into_any(),
+            ErrorDetail::QuotaFailure(quota_failure) => quota_failure.into_any(),
+            ErrorDetail::ErrorInfo(error_info) => error_info.into_any(),
+            ErrorDetail::PreconditionFailure(prec_failure) => prec_failure.into_any(),
+            ErrorDetail::BadRequest(bad_req) => bad_req.into_any(),
+            ErrorDetail::RequestInfo(req_info) => req_info.into_any(),
+            ErrorDetail::ResourceInfo(res_info) => res_info.into_any(),
+            ErrorDetail::Help(help) => help.into_any(),
+            ErrorDetail::LocalizedMessage(loc_message) => loc_message.into_any(),
+            ErrorDetail::HttpRequest(http_request) => http_request.into_any(),
+            ErrorDetail::HttpResponse(http_response) => http_response.into_any(),
+            ErrorDetail::Other(any) => any,
+        }
+    }
+}
+
+impl FromAny for ErrorDetail {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let detail = match any.type_url {
+            RetryInfo::TYPE_URL => RetryInfo::from_any(any)?.into(),
+            DebugInfo::TYPE_URL => DebugInfo::from_any(any)?.into(),
+            QuotaFailure::TYPE_URL => QuotaFailure::from_any(any)?.into(),
+            ErrorInfo::TYPE_URL => ErrorInfo::from_any(any)?.into(),
+            PreconditionFailure::TYPE_URL => PreconditionFailure::from_any(any)?.into(),
+            BadRequest::TYPE_URL => BadRequest::from_any(any)?.into(),
+            RequestInfo::TYPE_URL => RequestInfo::from_any(any)?.into(),
+            ResourceInfo::TYPE_URL => ResourceInfo::from_any(any)?.into(),
+            Help::TYPE_URL => Help::from_any(any)?.into(),
+            LocalizedMessage::TYPE_URL => LocalizedMessage::from_any(any)?.into(),
+            HttpRequest::TYPE_URL => HttpRequest::from_any(any)?.into(),
+            HttpResponse::TYPE_URL => HttpResponse::from_any(any)?.into(),
+            _ => ErrorDetail::Other(any.to_any()),
+        };
+
+        Ok(detail)
+    }
+}
+
+/// Serializes [`ErrorDetail::Other`] details as `{"@type": .., "value": ..}`
+/// objects, with the encoded message as base64 text.
+#[cfg(feature = "serde")]
+mod serde_other {
+    use prost_types::Any;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct OtherDetail {
+        #[serde(rename = "@type")]
+        type_url: String,
+        value: String,
+    }
+
+    pub(super) fn serialize<S: Serializer>(any: &Any, serializer: S) -> Result<S::Ok, S::Error> {
+        OtherDetail {
+            type_url: any.type_url.clone(),
+            value: base64::encode(&any.value),
+        }
+        .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Any, D::Error> {
+        let detail = OtherDetail::deserialize(deserializer)?;
+
+        let value = base64::decode(&detail.value)
+            .map_err(|_| D::Error::custom("invalid base64 value in detail"))?;
+
+        Ok(Any {
+            type_url: detail.type_url,
+            value,
+        })
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt;
+
+/// Error returned when parsing a reason generated by [`error_reasons!`]
+/// from an unknown string. Holds the unknown reason.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownReason(pub String);
+
+impl fmt::Display for UnknownReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown ErrorInfo reason {:?}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownReason {}
+
+/// Generates an enum with the [`ErrorInfo`](crate::ErrorInfo) reasons of an
+/// API domain, so servers and clients share the same reason constants. Each
+/// reason is validated at compile time, as described in
+/// [`Reason`](crate::Reason). The generated enum provides:
+/// - `DOMAIN` and `ALL` constants, with the domain and every reason
+/// - `as_str` and `reason`, returning the reason as a `&'static str` or a
+///   [`Reason`](crate::Reason)
+/// - `error_info` and `error_info_with`, generating
+///   [`ErrorInfo`](crate::ErrorInfo) details without or with metadata
+/// - `from_error_info`, matching received [`ErrorInfo`](crate::ErrorInfo)
+///   details against the domain and reasons
+/// - `FromStr`, failing with [`UnknownReason`], and `Display`
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::{error_reasons, ErrorInfo};
+///
+/// error_reasons! {
+///     /// Reasons of the shop API.
+///     pub enum ShopReason {
+///         domain = "shop.example.com";
+///         /// The item is out of stock.
+///         OUT_OF_STOCK,
+///         /// The SKU does not exist.
+///         INVALID_SKU,
+///     }
+/// }
+///
+/// // Server side
+/// let error_info = ShopReason::OUT_OF_STOCK.error_info();
+///
+/// assert_eq!(error_info.reason, "OUT_OF_STOCK");
+/// assert_eq!(error_info.domain, ShopReason::DOMAIN);
+///
+/// // Client side
+/// assert_eq!(
+///     ShopReason::from_error_info(&error_info),
+///     Some(ShopReason::OUT_OF_STOCK)
+/// );
+/// assert_eq!("INVALID_SKU".parse(), Ok(ShopReason::INVALID_SKU));
+/// ```
+///
+/// Invalid reasons do not compile:
+///
+/// ```compile_fail
+/// use tonic_richer_error::error_reasons;
+///
+/// error_reasons! {
+///     pub enum ShopReason {
+///         domain = "shop.example.com";
+///         out_of_stock,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! error_reasons {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            domain = $domain:literal;
+            $( $(#[$reason_meta:meta])* $reason:ident ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[allow(non_camel_case_types)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $( $(#[$reason_meta])* $reason, )+
+        }
+
+        const _: () = {
+            $( let _ = $crate::Reason::new(stringify!($reason)); )+
+        };
+
+        #[allow(dead_code, unreachable_pub)]
+        impl $name {
+            /// Domain of the reasons.
+            pub const DOMAIN: &'static str = $domain;
+
+            /// Every reason of the domain.
+            pub const ALL: &'static [$name] = &[ $( $name::$reason, )+ ];
+
+            /// Returns the reason as a string slice.
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $( $name::$reason => stringify!($reason), )+
+                }
+            }
+
+            /// Returns the reason as a validated reason.
+            pub const fn reason(&self) -> $crate::Reason {
+                $crate::Reason::new(self.as_str())
+            }
+
+            /// Generates `ErrorInfo` details with the reason and domain, and
+            /// no metadata.
+            pub fn error_info(&self) -> $crate::ErrorInfo {
+                self.error_info_with(::std::collections::BTreeMap::new())
+            }
+
+            /// Generates `ErrorInfo` details with the reason, domain and
+            /// metadata.
+            pub fn error_info_with(
+                &self,
+                metadata: ::std::collections::BTreeMap<String, String>,
+            ) -> $crate::ErrorInfo {
+                $crate::ErrorInfo::new(self.as_str(), $name::DOMAIN, metadata)
+            }
+
+            /// Returns the reason of the `ErrorInfo` details, if they belong
+            /// to the domain and the reason is known.
+            pub fn from_error_info(error_info: &$crate::ErrorInfo) -> Option<Self> {
+                if error_info.domain != $name::DOMAIN {
+                    return None;
+                }
+
+                error_info.reason.parse().ok()
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::UnknownReason;
+
+            fn from_str(reason: &str) -> Result<Self, Self::Err> {
+                match reason {
+                    $( stringify!($reason) => Ok($name::$reason), )+
+                    _ => Err($crate::UnknownReason(reason.to_string())),
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(reason: $name) -> Self {
+                reason.as_str().to_string()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+
+    use super::super::ErrorInfo;
+    use super::UnknownReason;
+
+    crate::error_reasons! {
+        enum ShopReason {
+            domain = "shop.example.com";
+            OUT_OF_STOCK,
+            INVALID_SKU,
+        }
+    }
+
+    #[test]
+    fn gen_error_reasons() {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("sku".to_string(), "A-1".to_string());
+
+        let error_info = ShopReason::INVALID_SKU.error_info_with(metadata);
+
+        let formatted = format!("{:?} {:?}", ShopReason::ALL, error_info);
+
+        println!("generated reasons -> {formatted}");
+
+        let expected = "[OUT_OF_STOCK, INVALID_SKU] ErrorInfo { reason: \"INVALID_SKU\", domain: \"shop.example.com\", metadata: {\"sku\": \"A-1\"} }";
+
+        assert!(
+            formatted.eq(expected),
+            "generated reasons differ from expected result"
+        );
+
+        assert!(
+            ShopReason::from_error_info(&error_info) == Some(ShopReason::INVALID_SKU),
+            "reason not parsed from ErrorInfo"
+        );
+
+        let other_domain = ErrorInfo::new("INVALID_SKU", "other.example.com", BTreeMap::new());
+
+        assert!(
+            ShopReason::from_error_info(&other_domain).is_none(),
+            "reason parsed from ErrorInfo of another domain"
+        );
+
+        assert!(
+            "UNKNOWN".parse::<ShopReason>() == Err(UnknownReason("UNKNOWN".into())),
+            "unknown reason was parsed"
+        );
+
+        assert!(
+            ShopReason::OUT_OF_STOCK.reason() == "OUT_OF_STOCK"
+                && ShopReason::OUT_OF_STOCK.to_string() == "OUT_OF_STOCK",
+            "reason conversions differ from expected result"
+        );
+    }
+}
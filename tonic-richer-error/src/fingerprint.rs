@@ -0,0 +1,219 @@
+use std::collections::BTreeSet;
+
+use super::{ErrorDetailKind, ErrorDetails};
+
+impl ErrorDetails {
+    /// Summarizes the details into a stable category string, so monitoring
+    /// systems can group errors, for example as a metric label, without
+    /// exploding its cardinality. Details that vary between occurrences of
+    /// the same error, such as request IDs, debug info, [`crate::ErrorInfo`]
+    /// metadata, retry delays and violation descriptions, are excluded.
+    ///
+    /// The fingerprint holds the [`crate::ErrorInfo`] domain and reason, and
+    /// the sorted and deduplicated bad request fields, with list indices
+    /// replaced by `*`, precondition failure types, quota failure metrics or
+    /// IDs, and resource types, followed by the type URLs of unknown
+    /// details. Sections are separated by `|`, and empty ones are omitted.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details
+    ///     .set_error_info("INVALID_ORDER", "shop.example.local", BTreeMap::new())
+    ///     .add_bad_request_violation("items[3].quantity", "must be positive")
+    ///     .add_bad_request_violation("email", "must contain an @")
+    ///     .set_request_info("8f1c2a", "");
+    ///
+    /// assert_eq!(
+    ///     err_details.fingerprint(),
+    ///     "shop.example.local/INVALID_ORDER|bad_request[email,items[*].quantity]"
+    /// );
+    /// ```
+    pub fn fingerprint(&self) -> String {
+        let mut sections = Vec::new();
+
+        if let Some(error_info) = &self.error_info {
+            if !error_info.is_empty() {
+                sections.push(format!("{}/{}", error_info.domain, error_info.reason));
+            }
+        }
+
+        if let Some(bad_request) = &self.bad_request {
+            let fields = bad_request
+                .field_violations
+                .iter()
+                .map(|violation| normalize_field(&violation.field));
+
+            push_section(&mut sections, ErrorDetailKind::BadRequest, fields);
+        }
+
+        if let Some(prec_failure) = &self.precondition_failure {
+            let types = prec_failure
+                .violations
+                .iter()
+                .map(|violation| violation.r#type.clone());
+
+            push_section(&mut sections, ErrorDetailKind::PreconditionFailure, types);
+        }
+
+        if let Some(quota_failure) = &self.quota_failure {
+            // Subjects often hold client addresses or IDs, so metrics and
+            // quota IDs are used instead
+            let quotas = quota_failure.violations.iter().map(|violation| {
+                if violation.quota_metric.is_empty() {
+                    violation.quota_id.clone()
+                } else {
+                    violation.quota_metric.clone()
+                }
+            });
+
+            push_section(&mut sections, ErrorDetailKind::QuotaFailure, quotas);
+        }
+
+        if let Some(res_info) = &self.resource_info {
+            push_section(
+                &mut sections,
+                ErrorDetailKind::ResourceInfo,
+                [res_info.resource_type.clone()].into_iter(),
+            );
+        }
+
+        let others = self.unknown_details.iter().map(|any| {
+            match ErrorDetailKind::from_type_url(&any.type_url) {
+                ErrorDetailKind::Other => any.type_url.clone(),
+                kind => kind.as_str().to_string(),
+            }
+        });
+
+        push_section(&mut sections, ErrorDetailKind::Other, others);
+
+        sections.join("|")
+    }
+}
+
+/// Adds a `kind[value_a,value_b]` section, with the values sorted and
+/// deduplicated. Empty values are ignored, and the section is omitted if
+/// none are left.
+fn push_section(
+    sections: &mut Vec<String>,
+    kind: ErrorDetailKind,
+    values: impl Iterator<Item = String>,
+) {
+    let values: BTreeSet<String> = values.filter(|value| !value.is_empty()).collect();
+
+    if values.is_empty() {
+        return;
+    }
+
+    let values: Vec<String> = values.into_iter().collect();
+
+    sections.push(format!("{kind}[{}]", values.join(",")));
+}
+
+/// Replaces list indices in a field path, as in `items[3].name` or
+/// `items.3.name`, with `*`.
+fn normalize_field(field: &str) -> String {
+    field
+        .split('.')
+        .map(|segment| {
+            if !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+                return "*".to_string();
+            }
+
+            let mut normalized = String::with_capacity(segment.len());
+            let mut rest = segment;
+
+            while let Some(start) = rest.find('[') {
+                let (before, after) = rest.split_at(start + 1);
+                normalized.push_str(before);
+
+                match after.find(']') {
+                    Some(end) if end > 0 && after[..end].bytes().all(|b| b.is_ascii_digit()) => {
+                        normalized.push('*');
+                        rest = &after[end..];
+                    }
+                    _ => rest = after,
+                }
+            }
+
+            normalized.push_str(rest);
+            normalized
+        })
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use prost_types::Any;
+
+    use super::super::{ErrorDetails, QuotaViolation};
+
+    #[test]
+    fn fingerprint_details() {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("limit".to_string(), "100".to_string());
+
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_error_info("QUOTA_EXCEEDED", "example.local", metadata)
+            .set_quota_failure(vec![
+                QuotaViolation::new("clientip:127.0.0.1", "rate exceeded"),
+                QuotaViolation::new("clientip:127.0.0.2", "rate exceeded")
+                    .set_quota_metric("example.local/requests")
+                    .clone(),
+            ])
+            .add_bad_request_violation("items.2.sku", "unknown sku")
+            .add_bad_request_violation("items[0].sku", "unknown sku")
+            .add_bad_request_violation("items[1].sku", "unknown sku")
+            .add_precondition_failure_violation("TOS", "users/1", "terms not accepted")
+            .set_resource_info("book", "shelves/1/books/2", "", "not found")
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .set_request_info("request-id", "serving-data");
+
+        err_details.unknown_details.push(Any {
+            type_url: "type.example.local/custom.Detail".into(),
+            value: vec![1, 2, 3],
+        });
+
+        let fingerprint = err_details.fingerprint();
+
+        println!("fingerprint -> {fingerprint}");
+
+        let expected = "example.local/QUOTA_EXCEEDED\
+            |bad_request[items.*.sku,items[*].sku]\
+            |precondition_failure[TOS]\
+            |quota_failure[example.local/requests]\
+            |resource_info[book]\
+            |other[type.example.local/custom.Detail]";
+
+        assert!(
+            fingerprint.eq(expected),
+            "fingerprint differs from expected result"
+        );
+
+        err_details
+            .set_request_info("other-request-id", "")
+            .set_retry_info(Some(Duration::from_secs(30)))
+            .set_debug_info(vec!["main.rs:1".into()], "detail");
+
+        assert!(
+            err_details.fingerprint().eq(expected),
+            "fingerprint changed with volatile details"
+        );
+
+        assert!(
+            ErrorDetails::new().fingerprint().is_empty(),
+            "fingerprint of empty details is not empty"
+        );
+    }
+}
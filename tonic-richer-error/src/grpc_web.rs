@@ -0,0 +1,224 @@
+//! Helpers for gRPC-Web gateways, such as those built with `tonic-web`, that
+//! surface error details to browser clients. gRPC-Web clients read the
+//! `grpc-status-details-bin` trailer as base64 text, so the helpers encode
+//! the details as padded base64, and accept both padded and unpadded values
+//! when decoding.
+//!
+//! # Examples
+//!
+//! ```
+//! use tonic::codegen::http::HeaderMap;
+//! use tonic::{Code, Status};
+//! use tonic_richer_error::grpc_web::{insert_status, status_from_headers};
+//! use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+//!
+//! let status = Status::with_error_details(
+//!     Code::InvalidArgument,
+//!     "bad request",
+//!     ErrorDetails::with_bad_request_violation("field", "description"),
+//! );
+//!
+//! let mut trailers = HeaderMap::new();
+//!
+//! insert_status(&status, &mut trailers);
+//!
+//! let status = status_from_headers(&trailers).unwrap().unwrap();
+//!
+//! assert!(status.get_details_bad_request().is_some());
+//! ```
+
+use prost::DecodeError;
+use tonic::codegen::http;
+use tonic::{Code, Status};
+
+use super::status_ref::StatusRef;
+use super::{Error, ErrorDetails};
+
+/// Encodes the error details of a status, as carried by its
+/// `tonic::Status::details`, as padded base64 text, suitable for the
+/// `grpc-status-details-bin` trailer of a gRPC-Web response.
+/// # Examples
+///
+/// ```
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::grpc_web::encode_details;
+/// use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+///
+/// let status = Status::with_error_details(
+///     Code::NotFound,
+///     "not found",
+///     ErrorDetails::with_resource_info("book", "shelves/1/books/2", "", "book not found"),
+/// );
+///
+/// let value = encode_details(&status);
+/// ```
+pub fn encode_details(status: &Status) -> String {
+    base64::encode(status.details())
+}
+
+/// Decodes a `grpc-status-details-bin` value, as base64 text, into a
+/// `tonic::Status` with the code, message and error details it carries. If
+/// the value is not valid base64 or the `google.rpc.Status` is malformed,
+/// an [`Error`] is returned.
+/// # Examples
+///
+/// ```
+/// use tonic::Code;
+/// use tonic_richer_error::grpc_web::decode_details;
+/// use tonic_richer_error::WithErrorDetails;
+///
+/// // Where `value` is the `grpc-status-details-bin` trailer received
+/// # let value = "CAUSCW5vdCBmb3VuZA";
+/// if let Ok(status) = decode_details(value) {
+///     assert_eq!(status.code(), Code::NotFound);
+///     assert_eq!(status.message(), "not found");
+/// }
+/// ```
+pub fn decode_details(value: &str) -> Result<Status, Error> {
+    let bytes = base64::decode(value.trim()).map_err(|_| {
+        Error::InvalidStatus(DecodeError::new(
+            "invalid base64 value in grpc-status-details-bin",
+        ))
+    })?;
+
+    let status = StatusRef::decode(&bytes).map_err(Error::InvalidStatus)?;
+
+    let code = Code::from_i32(status.code);
+    let message = status.message.to_string();
+
+    Ok(Status::with_details(code, message, bytes.into()))
+}
+
+/// Inserts the `grpc-status`, `grpc-message` and `grpc-status-details-bin`
+/// entries of a status in a header map, such as the trailers of a gRPC-Web
+/// response, replacing any previous values. The details are encoded as in
+/// [`encode_details`], and are omitted if the status has none.
+pub fn insert_status(status: &Status, headers: &mut http::HeaderMap) {
+    let response = Status::new(status.code(), status.message()).to_http();
+
+    for name in ["grpc-status", "grpc-message"] {
+        match response.headers().get(name) {
+            Some(value) => {
+                headers.insert(name, value.clone());
+            }
+            None => {
+                headers.remove(name);
+            }
+        }
+    }
+
+    if status.details().is_empty() {
+        headers.remove(ErrorDetails::METADATA_KEY);
+        return;
+    }
+
+    // Base64 text is always a valid header value
+    if let Ok(value) = http::HeaderValue::from_str(&encode_details(status)) {
+        headers.insert(ErrorDetails::METADATA_KEY, value);
+    }
+}
+
+/// Reads a status from a header map, such as the trailers of a gRPC-Web
+/// response. If the `grpc-status-details-bin` entry is present, the status
+/// is decoded from it, as in [`decode_details`]. Otherwise, the status is
+/// read from the `grpc-status` and `grpc-message` entries. Returns
+/// `Ok(None)` if no status is found.
+pub fn status_from_headers(headers: &http::HeaderMap) -> Result<Option<Status>, Error> {
+    match headers.get(ErrorDetails::METADATA_KEY) {
+        Some(value) => {
+            let value = value.to_str().map_err(|_| {
+                Error::InvalidStatus(DecodeError::new(
+                    "invalid characters in grpc-status-details-bin",
+                ))
+            })?;
+
+            decode_details(value).map(Some)
+        }
+        None => Ok(Status::from_header_map(headers)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tonic::codegen::http::{HeaderMap, HeaderValue};
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::{decode_details, encode_details, insert_status, status_from_headers};
+
+    #[test]
+    fn grpc_web_details() {
+        let status = Status::with_error_details(
+            Code::InvalidArgument,
+            "invalid name: ç",
+            ErrorDetails::with_bad_request_violation("name", "invalid character"),
+        );
+
+        let value = encode_details(&status);
+
+        println!("encoded details -> {value}");
+
+        assert!(
+            value.ends_with('=') && value.len().is_multiple_of(4),
+            "encoded details are not padded base64"
+        );
+
+        let mut trailers = HeaderMap::new();
+
+        insert_status(&status, &mut trailers);
+
+        let decoded = match status_from_headers(&trailers) {
+            Ok(Some(decoded)) => decoded,
+            other => panic!("Error decoding status from trailers: {:?}", other),
+        };
+
+        let formatted = format!(
+            "{:?} {:?} {:?}",
+            decoded.code(),
+            decoded.message(),
+            decoded.get_details_bad_request()
+        );
+
+        println!("status decoded from trailers -> {formatted}");
+
+        let expected = "InvalidArgument \"invalid name: ç\" Some(BadRequest { field_violations: [FieldViolation { field: \"name\", description: \"invalid character\", reason: \"\", localized_message: None }] })";
+
+        assert!(
+            formatted.eq(expected),
+            "status decoded from trailers differs from expected result"
+        );
+
+        let unpadded = value.trim_end_matches('=');
+
+        assert!(
+            matches!(decode_details(unpadded), Ok(decoded) if decoded.details() == status.details()),
+            "unpadded details were not decoded"
+        );
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert(
+            ErrorDetails::METADATA_KEY,
+            HeaderValue::from_static("not base64!"),
+        );
+
+        assert!(
+            status_from_headers(&trailers).is_err(),
+            "invalid base64 was decoded"
+        );
+
+        let mut trailers = HeaderMap::new();
+
+        insert_status(&Status::not_found("not found"), &mut trailers);
+
+        assert!(
+            !trailers.contains_key(ErrorDetails::METADATA_KEY),
+            "details were inserted for a status without details"
+        );
+
+        assert!(
+            matches!(status_from_headers(&trailers), Ok(Some(status)) if status.code() == Code::NotFound),
+            "status without details was not read from trailers"
+        );
+    }
+}
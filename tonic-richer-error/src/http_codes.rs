@@ -0,0 +1,153 @@
+use tonic::codegen::http::{HeaderValue, StatusCode};
+use tonic::Code;
+
+use super::RetryInfo;
+
+/// Maps a gRPC code to the HTTP status code of the canonical mapping
+/// defined at `google.rpc.Code`, as used by gRPC-JSON transcoders.
+/// # Examples
+///
+/// ```
+/// use tonic::codegen::http::StatusCode;
+/// use tonic::Code;
+/// use tonic_richer_error::code_to_http;
+///
+/// assert_eq!(code_to_http(Code::ResourceExhausted), StatusCode::TOO_MANY_REQUESTS);
+/// assert_eq!(code_to_http(Code::FailedPrecondition), StatusCode::BAD_REQUEST);
+/// ```
+pub fn code_to_http(code: Code) -> StatusCode {
+    match code {
+        Code::Ok => StatusCode::OK,
+        // Non-standard "Client Closed Request" status, always valid
+        Code::Cancelled => StatusCode::from_u16(499).unwrap_or(StatusCode::BAD_REQUEST),
+        Code::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
+        Code::InvalidArgument => StatusCode::BAD_REQUEST,
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists => StatusCode::CONFLICT,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::FailedPrecondition => StatusCode::BAD_REQUEST,
+        Code::Aborted => StatusCode::CONFLICT,
+        Code::OutOfRange => StatusCode::BAD_REQUEST,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+    }
+}
+
+/// Maps an HTTP status code to a gRPC code, following the canonical mapping
+/// defined at `google.rpc.Code`. Codes shared by several gRPC codes map to
+/// the most general one, such as `409` to `Code::Aborted`. Other client
+/// errors map to `Code::InvalidArgument`, and other server errors to
+/// `Code::Internal`.
+/// # Examples
+///
+/// ```
+/// use tonic::codegen::http::StatusCode;
+/// use tonic::Code;
+/// use tonic_richer_error::http_to_code;
+///
+/// assert_eq!(http_to_code(StatusCode::SERVICE_UNAVAILABLE), Code::Unavailable);
+/// assert_eq!(http_to_code(StatusCode::IM_A_TEAPOT), Code::InvalidArgument);
+/// ```
+pub fn http_to_code(status: StatusCode) -> Code {
+    match status {
+        StatusCode::UNAUTHORIZED => Code::Unauthenticated,
+        StatusCode::FORBIDDEN => Code::PermissionDenied,
+        StatusCode::NOT_FOUND => Code::NotFound,
+        StatusCode::CONFLICT => Code::Aborted,
+        StatusCode::TOO_MANY_REQUESTS => Code::ResourceExhausted,
+        StatusCode::NOT_IMPLEMENTED => Code::Unimplemented,
+        StatusCode::SERVICE_UNAVAILABLE => Code::Unavailable,
+        StatusCode::GATEWAY_TIMEOUT => Code::DeadlineExceeded,
+        _ if status.as_u16() == 499 => Code::Cancelled,
+        _ if status.is_success() => Code::Ok,
+        _ if status.is_client_error() => Code::InvalidArgument,
+        _ if status.is_server_error() => Code::Internal,
+        _ => Code::Unknown,
+    }
+}
+
+/// Generates the value of an HTTP `Retry-After` header from the
+/// `retry_delay` of a [`RetryInfo`], in whole seconds, rounded up. Returns
+/// `None` if no delay is set.
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tonic_richer_error::{retry_after_header, RetryInfo};
+///
+/// let retry_info = RetryInfo::new(Some(Duration::from_millis(1500)));
+///
+/// assert_eq!(retry_after_header(&retry_info).unwrap(), "2");
+/// ```
+pub fn retry_after_header(retry_info: &RetryInfo) -> Option<HeaderValue> {
+    let delay = retry_info.retry_delay?;
+
+    let secs = match delay.subsec_nanos() {
+        0 => delay.as_secs(),
+        _ => delay.as_secs().saturating_add(1),
+    };
+
+    Some(HeaderValue::from(secs))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use tonic::codegen::http::StatusCode;
+    use tonic::Code;
+
+    use super::super::RetryInfo;
+    use super::{code_to_http, http_to_code, retry_after_header};
+
+    #[test]
+    fn map_http_codes() {
+        let codes = [
+            Code::Ok,
+            Code::Cancelled,
+            Code::InvalidArgument,
+            Code::DeadlineExceeded,
+            Code::NotFound,
+            Code::PermissionDenied,
+            Code::ResourceExhausted,
+            Code::Unimplemented,
+            Code::Internal,
+            Code::Unavailable,
+            Code::Unauthenticated,
+        ];
+
+        for code in codes {
+            let status = code_to_http(code);
+
+            println!("{:?} -> {status}", code);
+
+            assert!(
+                http_to_code(status) == code,
+                "code does not survive the HTTP status round trip"
+            );
+        }
+
+        assert!(
+            http_to_code(StatusCode::BAD_GATEWAY) == Code::Internal,
+            "unmapped server error has unexpected code"
+        );
+
+        let retry_info = RetryInfo::new(Some(Duration::from_secs(30)));
+
+        assert!(
+            retry_after_header(&retry_info).is_some_and(|value| value == "30"),
+            "Retry-After header differs from expected result"
+        );
+
+        assert!(
+            retry_after_header(&RetryInfo::new(None)).is_none(),
+            "Retry-After header generated without a retry delay"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Conversions between this crate's error details and the ones provided by
+//! `tonic-types`, so projects can migrate between both crates
+//! incrementally. Fields only present in `tonic-types` are set to their
+//! defaults when converting into `tonic-types`, and dropped when converting
+//! from it.
+
+use std::collections::HashMap;
+
+use super::std_messages::*;
+use super::ErrorDetails;
+
+impl From<RetryInfo> for tonic_types::RetryInfo {
+    fn from(retry_info: RetryInfo) -> Self {
+        tonic_types::RetryInfo::new(retry_info.retry_delay)
+    }
+}
+
+impl From<tonic_types::RetryInfo> for RetryInfo {
+    fn from(retry_info: tonic_types::RetryInfo) -> Self {
+        RetryInfo::new(retry_info.retry_delay)
+    }
+}
+
+impl From<DebugInfo> for tonic_types::DebugInfo {
+    fn from(debug_info: DebugInfo) -> Self {
+        tonic_types::DebugInfo::new(debug_info.stack_entries, debug_info.detail)
+    }
+}
+
+impl From<tonic_types::DebugInfo> for DebugInfo {
+    fn from(debug_info: tonic_types::DebugInfo) -> Self {
+        DebugInfo::new(debug_info.stack_entries, debug_info.detail)
+    }
+}
+
+impl From<QuotaViolation> for tonic_types::QuotaViolation {
+    fn from(violation: QuotaViolation) -> Self {
+        let mut conv_violation =
+            tonic_types::QuotaViolation::new(violation.subject, violation.description);
+        conv_violation.api_service = violation.api_service;
+        conv_violation.quota_metric = violation.quota_metric;
+        conv_violation.quota_id = violation.quota_id;
+        conv_violation.quota_dimensions = violation.quota_dimensions.into_iter().collect();
+        conv_violation.quota_value = violation.quota_value;
+        conv_violation.futura_quota_value = violation.future_quota_value;
+        conv_violation
+    }
+}
+
+impl From<tonic_types::QuotaViolation> for QuotaViolation {
+    fn from(violation: tonic_types::QuotaViolation) -> Self {
+        QuotaViolation {
+            subject: violation.subject,
+            description: violation.description,
+            api_service: violation.api_service,
+            quota_metric: violation.quota_metric,
+            quota_id: violation.quota_id,
+            quota_dimensions: violation.quota_dimensions.into_iter().collect(),
+            quota_value: violation.quota_value,
+            future_quota_value: violation.futura_quota_value,
+        }
+    }
+}
+
+impl From<QuotaFailure> for tonic_types::QuotaFailure {
+    fn from(quota_failure: QuotaFailure) -> Self {
+        tonic_types::QuotaFailure::new(convert_vec(quota_failure.violations))
+    }
+}
+
+impl From<tonic_types::QuotaFailure> for QuotaFailure {
+    fn from(quota_failure: tonic_types::QuotaFailure) -> Self {
+        QuotaFailure::new(convert_vec(quota_failure.violations))
+    }
+}
+
+impl From<ErrorInfo> for tonic_types::ErrorInfo {
+    fn from(error_info: ErrorInfo) -> Self {
+        tonic_types::ErrorInfo::new(
+            error_info.reason,
+            error_info.domain,
+            error_info.metadata.into_iter().collect::<HashMap<_, _>>(),
+        )
+    }
+}
+
+impl From<tonic_types::ErrorInfo> for ErrorInfo {
+    fn from(error_info: tonic_types::ErrorInfo) -> Self {
+        ErrorInfo::new(
+            error_info.reason,
+            error_info.domain,
+            error_info.metadata.into_iter().collect(),
+        )
+    }
+}
+
+impl From<PreconditionViolation> for tonic_types::PreconditionViolation {
+    fn from(violation: PreconditionViolation) -> Self {
+        tonic_types::PreconditionViolation::new(
+            violation.r#type,
+            violation.subject,
+            violation.description,
+        )
+    }
+}
+
+impl From<tonic_types::PreconditionViolation> for PreconditionViolation {
+    fn from(violation: tonic_types::PreconditionViolation) -> Self {
+        PreconditionViolation::new(violation.r#type, violation.subject, violation.description)
+    }
+}
+
+impl From<PreconditionFailure> for tonic_types::PreconditionFailure {
+    fn from(prec_failure: PreconditionFailure) -> Self {
+        tonic_types::PreconditionFailure::new(convert_vec(prec_failure.violations))
+    }
+}
+
+impl From<tonic_types::PreconditionFailure> for PreconditionFailure {
+    fn from(prec_failure: tonic_types::PreconditionFailure) -> Self {
+        PreconditionFailure::new(convert_vec(prec_failure.violations))
+    }
+}
+
+impl From<FieldViolation> for tonic_types::FieldViolation {
+    fn from(violation: FieldViolation) -> Self {
+        let mut conv_violation =
+            tonic_types::FieldViolation::new(violation.field, violation.description);
+        conv_violation.reason = violation.reason;
+        conv_violation.localized_message = violation.localized_message.map(Into::into);
+        conv_violation
+    }
+}
+
+impl From<tonic_types::FieldViolation> for FieldViolation {
+    fn from(violation: tonic_types::FieldViolation) -> Self {
+        FieldViolation {
+            field: violation.field,
+            description: violation.description,
+            reason: violation.reason,
+            localized_message: violation.localized_message.map(Into::into),
+        }
+    }
+}
+
+impl From<BadRequest> for tonic_types::BadRequest {
+    fn from(bad_request: BadRequest) -> Self {
+        tonic_types::BadRequest::new(convert_vec(bad_request.field_violations))
+    }
+}
+
+impl From<tonic_types::BadRequest> for BadRequest {
+    fn from(bad_request: tonic_types::BadRequest) -> Self {
+        BadRequest::new(convert_vec(bad_request.field_violations))
+    }
+}
+
+impl From<RequestInfo> for tonic_types::RequestInfo {
+    fn from(req_info: RequestInfo) -> Self {
+        tonic_types::RequestInfo::new(req_info.request_id, req_info.serving_data)
+    }
+}
+
+impl From<tonic_types::RequestInfo> for RequestInfo {
+    fn from(req_info: tonic_types::RequestInfo) -> Self {
+        RequestInfo::new(req_info.request_id, req_info.serving_data)
+    }
+}
+
+impl From<ResourceInfo> for tonic_types::ResourceInfo {
+    fn from(res_info: ResourceInfo) -> Self {
+        tonic_types::ResourceInfo::new(
+            res_info.resource_type,
+            res_info.resource_name,
+            res_info.owner,
+            res_info.description,
+        )
+    }
+}
+
+impl From<tonic_types::ResourceInfo> for ResourceInfo {
+    fn from(res_info: tonic_types::ResourceInfo) -> Self {
+        ResourceInfo::new(
+            res_info.resource_type,
+            res_info.resource_name,
+            res_info.owner,
+            res_info.description,
+        )
+    }
+}
+
+impl From<HelpLink> for tonic_types::HelpLink {
+    fn from(link: HelpLink) -> Self {
+        tonic_types::HelpLink::new(link.description, link.url)
+    }
+}
+
+impl From<tonic_types::HelpLink> for HelpLink {
+    fn from(link: tonic_types::HelpLink) -> Self {
+        HelpLink::new(link.description, link.url)
+    }
+}
+
+impl From<Help> for tonic_types::Help {
+    fn from(help: Help) -> Self {
+        tonic_types::Help::new(convert_vec(help.links))
+    }
+}
+
+impl From<tonic_types::Help> for Help {
+    fn from(help: tonic_types::Help) -> Self {
+        Help::new(convert_vec(help.links))
+    }
+}
+
+impl From<LocalizedMessage> for tonic_types::LocalizedMessage {
+    fn from(loc_message: LocalizedMessage) -> Self {
+        tonic_types::LocalizedMessage::new(loc_message.locale, loc_message.message)
+    }
+}
+
+impl From<tonic_types::LocalizedMessage> for LocalizedMessage {
+    fn from(loc_message: tonic_types::LocalizedMessage) -> Self {
+        LocalizedMessage::new(loc_message.locale, loc_message.message)
+    }
+}
+
+/// Details with unknown type URLs are dropped, since `tonic-types` does not
+/// keep them.
+impl From<ErrorDetails> for tonic_types::ErrorDetails {
+    fn from(details: ErrorDetails) -> Self {
+        let mut conv_details = tonic_types::ErrorDetails::new();
+
+        if let Some(retry_info) = details.retry_info {
+            conv_details.set_retry_info(retry_info.retry_delay);
+        }
+
+        if let Some(debug_info) = details.debug_info {
+            conv_details.set_debug_info(debug_info.stack_entries, debug_info.detail);
+        }
+
+        if let Some(quota_failure) = details.quota_failure {
+            conv_details.set_quota_failure(convert_vec(quota_failure.violations));
+        }
+
+        if let Some(error_info) = details.error_info {
+            conv_details.set_error_info(
+                error_info.reason,
+                error_info.domain,
+                error_info.metadata.into_iter().collect::<HashMap<_, _>>(),
+            );
+        }
+
+        if let Some(prec_failure) = details.precondition_failure {
+            conv_details.set_precondition_failure(convert_vec(prec_failure.violations));
+        }
+
+        if let Some(bad_request) = details.bad_request {
+            conv_details.set_bad_request(convert_vec(bad_request.field_violations));
+        }
+
+        if let Some(req_info) = details.request_info {
+            conv_details.set_request_info(req_info.request_id, req_info.serving_data);
+        }
+
+        if let Some(res_info) = details.resource_info {
+            conv_details.set_resource_info(
+                res_info.resource_type,
+                res_info.resource_name,
+                res_info.owner,
+                res_info.description,
+            );
+        }
+
+        if let Some(help) = details.help {
+            conv_details.set_help(convert_vec(help.links));
+        }
+
+        if let Some(loc_message) = details.localized_message {
+            conv_details.set_localized_message(loc_message.locale, loc_message.message);
+        }
+
+        conv_details
+    }
+}
+
+impl From<tonic_types::ErrorDetails> for ErrorDetails {
+    fn from(details: tonic_types::ErrorDetails) -> Self {
+        ErrorDetails {
+            retry_info: details.retry_info().cloned().map(Into::into),
+            debug_info: details.debug_info().cloned().map(Into::into),
+            quota_failure: details.quota_failure().cloned().map(Into::into),
+            error_info: details.error_info().cloned().map(Into::into),
+            precondition_failure: details.precondition_failure().cloned().map(Into::into),
+            bad_request: details.bad_request().cloned().map(Into::into),
+            request_info: details.request_info().cloned().map(Into::into),
+            resource_info: details.resource_info().cloned().map(Into::into),
+            help: details.help().cloned().map(Into::into),
+            localized_message: details.localized_message().cloned().map(Into::into),
+            unknown_details: Vec::new(),
+        }
+    }
+}
+
+/// Converts every element of a vector.
+fn convert_vec<T: Into<U>, U>(items: Vec<T>) -> Vec<U> {
+    items.into_iter().map(Into::into).collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use super::super::ErrorDetails;
+
+    #[test]
+    fn convert_tonic_types_details() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .set_debug_info(vec!["trace3".into(), "trace2".into()], "details")
+            .add_quota_failure_violation("clientip:<ip address>", "description")
+            .set_error_info("SOME_INFO", "example.local", Default::default())
+            .add_precondition_failure_violation("TOS", "example.local", "description")
+            .add_bad_request_violation("field", "description")
+            .set_request_info("request-id", "some-request-data")
+            .set_resource_info("resource-type", "resource-name", "owner", "description")
+            .add_help_link("link to resource", "resource.example.local")
+            .set_localized_message("en-US", "message for the user");
+
+        let converted = tonic_types::ErrorDetails::from(err_details.clone());
+
+        println!("tonic-types details -> {:?}", converted);
+
+        assert!(
+            converted
+                .bad_request()
+                .map(|br| br.field_violations[0].field.as_str())
+                == Some("field"),
+            "tonic-types details differ from original"
+        );
+
+        let back = ErrorDetails::from(converted);
+
+        let formatted = format!("{:?}", back);
+
+        println!("details converted back -> {formatted}");
+
+        assert!(
+            formatted.eq(&format!("{:?}", err_details)),
+            "details converted back differ from original"
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use prost::bytes::Bytes;
+use prost::DecodeError;
+use prost_types::Any;
+
+use super::std_messages::*;
+use super::{ok_or_warn, AnyRef, CustomErrorDetail, ErrorDetailType, FromAny, StatusRef};
+
+/// Holds the error details of a `tonic::Status`, decoded once into a map of
+/// type URLs to encoded details, that share a single copy of the status
+/// buffer. Each standard error message is only decoded when its getter is
+/// called, so inspecting several detail types of a status does not decode the
+/// whole status multiple times. Returned by
+/// [`crate::WithErrorDetails::error_details_lazy`].
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "tonic")]
+/// # {
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+///
+/// let status = Status::with_error_details(
+///     Code::InvalidArgument,
+///     "bad request",
+///     ErrorDetails::with_bad_request_violation("field", "description"),
+/// );
+///
+/// let err_details = status.error_details_lazy();
+///
+/// if let Some(retry_info) = err_details.retry_info() {
+///     // Handle retry_info details
+/// }
+/// if let Some(bad_request) = err_details.bad_request() {
+///     // Handle bad_request details
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LazyErrorDetails {
+    details: HashMap<String, Bytes>,
+}
+
+impl LazyErrorDetails {
+    /// Creates a new [`LazyErrorDetails`] from an encoded `google.rpc.Status`.
+    /// Only the first detail of each type URL is kept. If the status is
+    /// malformed, a `prost::DecodeError` is returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetails, LazyErrorDetails};
+    ///
+    /// let encoded = ErrorDetails::with_bad_request_violation("field", "description")
+    ///     .encode_status(3, "bad request");
+    ///
+    /// let lazy_details = LazyErrorDetails::decode(&encoded).unwrap();
+    ///
+    /// assert!(lazy_details.bad_request().is_some());
+    /// ```
+    pub fn decode(buf: &[u8]) -> Result<Self, DecodeError> {
+        let buf = Bytes::copy_from_slice(buf);
+
+        let status = StatusRef::decode(&buf)?;
+
+        let mut details = HashMap::with_capacity(status.details.len());
+
+        for any in status.details.into_iter() {
+            if !details.contains_key(any.type_url) {
+                details.insert(any.type_url.to_string(), buf.slice_ref(any.value));
+            }
+        }
+
+        Ok(LazyErrorDetails { details })
+    }
+
+    /// Returns `true` if no error details were found.
+    pub fn is_empty(&self) -> bool {
+        self.details.is_empty()
+    }
+
+    /// Returns `true` if error details of type `T` were found, without
+    /// decoding them.
+    pub fn contains<T: ErrorDetailType>(&self) -> bool {
+        self.details.contains_key(T::TYPE_URL)
+    }
+
+    fn get<T: FromAny>(&self, type_url: &str) -> Option<T> {
+        let value = self.details.get(type_url)?;
+
+        ok_or_warn(T::from_any(AnyRef { type_url, value }), type_url)
+    }
+
+    /// Decodes the [`RetryInfo`] details, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn retry_info(&self) -> Option<RetryInfo> {
+        self.get(RetryInfo::TYPE_URL)
+    }
+
+    /// Decodes the [`DebugInfo`] details, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn debug_info(&self) -> Option<DebugInfo> {
+        self.get(DebugInfo::TYPE_URL)
+    }
+
+    /// Decodes the [`QuotaFailure`] details, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn quota_failure(&self) -> Option<QuotaFailure> {
+        self.get(QuotaFailure::TYPE_URL)
+    }
+
+    /// Decodes the [`ErrorInfo`] details, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn error_info(&self) -> Option<ErrorInfo> {
+        self.get(ErrorInfo::TYPE_URL)
+    }
+
+    /// Decodes the [`PreconditionFailure`] details, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn precondition_failure(&self) -> Option<PreconditionFailure> {
+        self.get(PreconditionFailure::TYPE_URL)
+    }
+
+    /// Decodes the [`BadRequest`] details, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn bad_request(&self) -> Option<BadRequest> {
+        self.get(BadRequest::TYPE_URL)
+    }
+
+    /// Decodes the [`RequestInfo`] details, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn request_info(&self) -> Option<RequestInfo> {
+        self.get(RequestInfo::TYPE_URL)
+    }
+
+    /// Decodes the [`ResourceInfo`] details, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn resource_info(&self) -> Option<ResourceInfo> {
+        self.get(ResourceInfo::TYPE_URL)
+    }
+
+    /// Decodes the [`Help`] details, if any. If some `prost::DecodeError`
+    /// occurs, returns `None`.
+    pub fn help(&self) -> Option<Help> {
+        self.get(Help::TYPE_URL)
+    }
+
+    /// Decodes the [`LocalizedMessage`] details, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn localized_message(&self) -> Option<LocalizedMessage> {
+        self.get(LocalizedMessage::TYPE_URL)
+    }
+
+    /// Decodes the user-defined error message of type `T`, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
+    pub fn custom<T: CustomErrorDetail>(&self) -> Option<T> {
+        let type_url = <T as CustomErrorDetail>::TYPE_URL;
+
+        let any = Any {
+            type_url: type_url.to_string(),
+            value: self.details.get(type_url)?.to_vec(),
+        };
+
+        ok_or_warn(<T as CustomErrorDetail>::from_any(&any), type_url)
+    }
+}
+
+#[cfg(all(test, feature = "tonic"))]
+mod tests {
+
+    use std::time::Duration;
+    use tonic::{Code, Status};
+
+    use super::super::{BadRequest, ErrorDetails, Help, RetryInfo, WithErrorDetails};
+
+    #[test]
+    fn lazy_getters() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .add_bad_request_violation("field", "description");
+
+        let status = Status::with_error_details(Code::InvalidArgument, "bad request", err_details);
+
+        let lazy_details = status.error_details_lazy();
+
+        let formatted = format!(
+            "{:?} {:?} {:?}",
+            lazy_details.retry_info(),
+            lazy_details.bad_request(),
+            lazy_details.help()
+        );
+
+        println!("lazy details -> {formatted}");
+
+        let expected = format!(
+            "{:?} {:?} None",
+            status.get_details_retry_info(),
+            status.get_details_bad_request()
+        );
+
+        assert!(
+            formatted.eq(&expected),
+            "lazy details differ from expected result"
+        );
+
+        assert!(
+            lazy_details.contains::<RetryInfo>()
+                && lazy_details.contains::<BadRequest>()
+                && !lazy_details.contains::<Help>(),
+            "lazy details presence differs from expected result"
+        );
+
+        assert!(
+            Status::internal("internal").error_details_lazy().is_empty(),
+            "status without details resulted in non-empty lazy details"
+        );
+    }
+}
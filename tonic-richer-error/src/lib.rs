@@ -22,12 +22,137 @@ tonic = "0.8"
 tonic-richer-error = "0.3"
 ```
 
+# Optional Features
+- `ansi`: adds `ErrorDetails::render_pretty_ansi`, which renders the same
+  report as [`ErrorDetails::render_pretty`], colored with ANSI escape codes.
+- `arbitrary`: implements `arbitrary::Arbitrary` for [`ErrorDetails`] and the
+  standard error message structs, so services can fuzz their own error
+  handling with realistic details, for example with `cargo fuzz`.
+- `axum`: implements conversions from axum extractor rejections into
+  [`BadRequest`] details and `tonic::Status`, via the `RejectionStatus` trait,
+  and `IntoResponse` for `RichStatus`, which returns the status as an HTTP
+  response, with the HTTP status code mapped from the gRPC code and the
+  details in the canonical JSON body. Enables `json`.
+- `buffer-pool`: adds `PooledStatusExt`, whose `with_error_details_pooled`
+  method encodes error details into a buffer owned by the current thread,
+  reused across statuses, as described in
+  [`WithErrorDetails::with_error_details_into`].
+- `derive`: adds the `IntoStatus` derive macro, which generates
+  `From<T> for tonic::Status` implementations for application error enums,
+  with the error details described by variant attributes.
+- `fluent`: adds `MessageTemplate`, which renders user-facing messages from
+  [Fluent] patterns, with named placeholders, plural variants selected by the
+  rules of the locale and percentages, and
+  `ErrorDetails::set_localized_message_template`, which sets
+  [`LocalizedMessage`] details from such a template.
+- `grpc-web`: adds the `grpc_web` module, which encodes and decodes the
+  `grpc-status-details-bin` trailer as base64 text for gRPC-Web gateways,
+  such as those built with `tonic-web`.
+- `interop`: implements `From` conversions between [`ErrorDetails`], the
+  standard error message structs and their `tonic-types` counterparts, so
+  projects can migrate between both crates incrementally.
+- `overrides`: adds `MessageOverrides`, which loads deployment-specific
+  messages, help links and localized text for `ErrorInfo` reasons from an
+  `overrides.toml` file.
+- `reqwest`: implements `ToRichStatus` for `reqwest::Error`, mapping
+  timeouts, connection failures and upstream error responses to the
+  matching codes.
+- `schema`: adds the `schema` module, which generates JSON Schema
+  definitions and OpenAPI components describing the canonical
+  `google.rpc.Status` JSON representation and the standard error messages,
+  for documentation of REST-facing endpoints.
+- `serde`: derives `Serialize` and `Deserialize` for [`ErrorDetails`],
+  [`ErrorDetail`] and the standard error message structs, following the
+  protobuf JSON mapping (camelCase fields, `"1.5s"` durations, and `@type`
+  tagged [`ErrorDetail`] variants). Details with unknown type URLs keep
+  their encoded message as base64 text at a `value` field.
+- `serde_path_to_error`: adds `BadRequest::from_serde_path_error`, which
+  converts request deserialization errors into [`BadRequest`] details, with
+  the path of the offending field.
+- `sqlx`: implements `ToRichStatus` for `sqlx::Error`, mapping constraint
+  violations to codes and details, such as unique violations to
+  `Code::AlreadyExists` with [`ResourceInfo`] details.
+- `std` (enabled by default): links the standard library. Without it, and
+  without `tonic`, the crate is `no_std` and only requires `alloc`, providing
+  the standard error message structs, [`ErrorDetail`], [`ErrorDetails`] and
+  the `google.rpc.Status` encoding logic, such as [`RpcStatus`], for
+  embedded and WASM targets. In that case, the global prefix and order,
+  installed with [`TypeUrlPrefix`] and [`DetailOrder`], are unavailable.
+  Every other feature enables `std`.
+- `std-io`: implements `ToRichStatus` for `std::io::Error`, mapping each
+  error kind to the matching code.
+- `testing`: adds the `testing` module, with assertion helpers for tests of
+  server handlers that return `tonic::Status` with error details, and the
+  `snapshot` module, which writes statuses in a canonical textual form for
+  golden-file snapshot tests, and parses them back.
+- `test-util`: implements `proptest::arbitrary::Arbitrary` for
+  [`ErrorDetails`] and the standard error message structs, so property
+  tests can generate arbitrary details with `proptest::prelude::any`.
+- `log`: implements `log::kv::Source` for [`ErrorDetails`], so the
+  key-value pairs returned by [`ErrorDetails::log_kv`] can be attached to
+  `log` records.
+- `otel`: adds `record_error_details`, which records a `tonic::Status` and
+  its error details, such as the [`ErrorInfo`] reason and domain, violation
+  counts and retry delay, as attributes of an OpenTelemetry span.
+- `metrics`: increments counters through the `metrics` facade for every
+  error detail added to a generated status, and for every one extracted from
+  a status, as `richer_error_emitted_total` and `richer_error_decoded_total`,
+  labeled with the detail `kind` (such as `bad_request`).
+- `tokio`: adds `RetryInfo::wait`, which sleeps for the `retry_delay`
+  informed by the server before a client retries a call. With the `tonic`
+  feature, also adds `RetryStatusExt`, whose `wait_for_retry` method does
+  the same directly from a `tonic::Status`, with a configurable cap.
+- `json`: enables `serde`, and adds [`ErrorDetails`] methods that convert to
+  and from the canonical `google.rpc.Status` JSON representation used by
+  gRPC-JSON transcoders, and `ErrorDetail::from_json_any`, which decodes
+  `@type` tagged details captured by tools like grpcurl.
+- `retry`: adds `RetryOnRetryInfoLayer`, a tower layer for tonic clients that
+  retries calls failing with [`RetryInfo`] details, honoring the
+  `retry_delay` informed by the server.
+- `layer`: adds `RequestInfoLayer`, a tower layer for tonic servers that
+  appends [`RequestInfo`] details, with the request ID read from metadata or
+  generated, to every error status returned by the service. Also adds
+  `StatusEnricherLayer`, which passes every error status through a stack of
+  `StatusEnricher`s, so details can be added centrally, and
+  `StatusLocalizer`, which sets [`LocalizedMessage`] details translated to
+  the locale requested by the client. `ErrorDetailsPolicy` also implements
+  `StatusEnricher`, so a policy can be applied to every outgoing status,
+  including the ones sent in the trailers of streaming responses.
+- `problem`: adds `ProblemDetails`, which converts `tonic::Status` and its
+  error details to and from [RFC 7807] `application/problem+json` responses,
+  for services that also expose REST endpoints.
+- `redaction`: adds `ErrorDetailsPolicy::deny_metadata_keys_matching`, which
+  removes [`ErrorInfo`] metadata keys matching a regular expression, and
+  `ErrorDetailsPolicy::hash_serving_data`, which replaces [`RequestInfo`]
+  serving data with its SHA-256 hash.
+- `tonic` (enabled by default): adds the [`WithErrorDetails`] trait and its
+  implementation for `tonic::Status`, along with the other helpers that
+  depend on tonic. Without it, the crate only provides the standard error
+  message structs, [`ErrorDetails`] and the `google.rpc.Status` encoding
+  logic, such as [`RpcStatus`] and [`ErrorDetails::encode_status`], for
+  gateways, tools and other non-tonic stacks. Enabled by the `axum`,
+  `buffer-pool`, `derive`, `grpc-web`, `json`, `layer`, `otel`, `overrides`,
+  `problem`, `reqwest`, `retry`, `sqlx`, `std-io` and `testing` features.
+- `tonic-010`, `tonic-011`, `tonic-012`: add the corresponding `compat`
+  submodules, which generate and read error details with the `tonic::Status`
+  and `prost_types::Any` types of newer tonic and prost releases.
+- `tracing`: emits a `tracing` event with the code, message and error details
+  summary whenever a `tonic::Status` is generated with error details, and
+  warnings whenever malformed error details are discarded during extraction.
+- `url`: adds `HelpLink::parse`, which rejects malformed or non-`https`
+  help link URLs, and `ErrorDetailsPolicy::require_valid_help_links`, which
+  makes [`ErrorDetails::add_help_link`] discard such links.
+- `validator`: implements conversions from `validator::ValidationErrors` into
+  [`BadRequest`] details, with one field violation per failed field.
+
 # Examples
 The examples bellow cover a basic use case. More complete server and client
 implementations can be found at the [github examples] directory.
 
 ## Server Side: Generating [`tonic::Status`] with an [`ErrorDetails`] struct
 ```
+# #[cfg(feature = "tonic")]
+# {
 use tonic::{Code, Status};
 use tonic_richer_error::{ErrorDetails, WithErrorDetails};
 
@@ -77,10 +202,13 @@ if err_details.has_bad_request_violations() {
 
 # Ok(tonic::Response::new(()))
 # }
+# }
 ```
 
 ## Client Side: Extracting an [`ErrorDetails`] struct from `tonic::Status`
 ```
+# #[cfg(feature = "tonic")]
+# {
 use tonic::{Response, Status};
 use tonic_richer_error::{WithErrorDetails};
 
@@ -93,7 +221,7 @@ fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
             // Handle successful response
         },
         Err(status) => {
-            let err_details = status.get_error_details();
+            let err_details = status.error_details();
             if let Some(bad_request) = err_details.bad_request {
                 // Handle bad_request details
             }
@@ -106,6 +234,7 @@ fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
         }
     };
 }
+# }
 ```
 
 ## Send different standard error messages
@@ -136,6 +265,8 @@ more direct way of extracting a [`BadRequest`] error message from
 [protoc-install]: https://grpc.io/docs/protoc-installation/
 [github examples]: https://github.com/flemosr/tonic-richer-error/tree/main/examples
 [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
+[RFC 7807]: https://www.rfc-editor.org/rfc/rfc7807
+[Fluent]: https://projectfluent.org/fluent/guide/
 */
 
 #![warn(
@@ -144,41 +275,684 @@ more direct way of extracting a [`BadRequest`] error message from
     rust_2018_idioms,
     unreachable_pub
 )]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
-use prost::{DecodeError, Message};
+use alloc::vec::Vec;
+#[cfg(feature = "tonic")]
+use std::collections::BTreeMap;
+
+#[cfg(feature = "tonic")]
+use prost::bytes::{Bytes, BytesMut};
+use prost::DecodeError;
+#[cfg(feature = "tonic")]
+use prost::Message;
 use prost_types::Any;
-use tonic::{codegen::Bytes, Code, Status};
+#[cfg(feature = "tonic")]
+use tonic::metadata::MetadataMap;
+#[cfg(feature = "tonic")]
+use tonic::{Code, Status};
 
-/// Compiled `google.rpc` protos
+use status_ref::{AnyRef, StatusRef};
+
+/// Compiled `google.rpc` protos. The standard error message structs
+/// implement `From` conversions to and from the corresponding types, which
+/// can be used to interoperate with other code generated from the same
+/// protos, by re-encoding the messages.
+/// # Examples
+///
+/// ```
+/// use prost::Message;
+/// use tonic_richer_error::{pb, BadRequest};
+///
+/// let bad_request = BadRequest::with_violation("field", "description");
+///
+/// // Encoded bytes can be decoded by any `google.rpc.BadRequest` type
+/// let encoded = pb::BadRequest::from(bad_request.clone()).encode_to_vec();
+///
+/// let decoded: BadRequest = pb::BadRequest::decode(encoded.as_slice()).unwrap().into();
+///
+/// assert_eq!(decoded, bad_request);
+/// ```
 pub mod pb {
     include!(concat!(env!("OUT_DIR"), "/google.rpc.rs"));
 }
 
+#[cfg(any(feature = "tonic-010", feature = "tonic-011", feature = "tonic-012"))]
+pub mod compat;
+
+#[cfg(feature = "std")]
+pub mod conformance;
+
+#[cfg(feature = "grpc-web")]
+pub mod grpc_web;
+
+#[cfg(feature = "schema")]
+pub mod schema;
+
+#[cfg(feature = "testing")]
+pub mod snapshot;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+mod custom_detail;
+mod decode_error;
+mod detail_order;
+mod detail_type;
 mod error_details;
 mod error_details_vec;
+mod rpc_status;
+mod status_ref;
 mod std_messages;
+mod type_url_prefix;
+
+#[cfg(feature = "std")]
+mod any_message;
+
+#[cfg(feature = "std")]
+mod backoff;
+
+#[cfg(feature = "std")]
+mod details_diff;
+
+#[cfg(feature = "std")]
+mod display;
+
+#[cfg(feature = "std")]
+mod error_chain;
+
+#[cfg(feature = "std")]
+mod error_detail_kind;
+
+#[cfg(feature = "std")]
+mod error_details_builder;
+
+#[cfg(feature = "std")]
+mod error_details_multi;
+
+#[cfg(feature = "std")]
+mod error_reasons;
+
+#[cfg(feature = "std")]
+mod fingerprint;
+
+#[cfg(feature = "std")]
+mod lazy_details;
+
+#[cfg(feature = "std")]
+mod localized_messages;
+
+#[cfg(feature = "std")]
+mod log_kv;
+
+#[cfg(feature = "std")]
+mod merge;
+
+#[cfg(feature = "std")]
+mod policy;
+
+#[cfg(feature = "std")]
+mod pretty;
+
+#[cfg(feature = "std")]
+mod raw_detail;
+
+#[cfg(feature = "std")]
+mod request_errors;
+
+#[cfg(feature = "std")]
+mod size_budget;
+
+#[cfg(feature = "tonic")]
+mod aggregated_status;
+
+#[cfg(feature = "tonic")]
+mod error_details_stream;
+
+#[cfg(feature = "tonic")]
+mod http_codes;
+
+#[cfg(feature = "tonic")]
+mod pairing;
+
+#[cfg(feature = "tonic")]
+mod precoded_status;
+
+#[cfg(feature = "tonic")]
+mod reason_backoff;
+
+#[cfg(feature = "tonic")]
+mod reason_matcher;
+
+#[cfg(feature = "tonic")]
+mod retry_decision;
+
+#[cfg(feature = "tonic")]
+mod rich_error;
+
+#[cfg(feature = "tonic")]
+mod rich_status;
+
+#[cfg(feature = "tonic")]
+mod status_metadata;
+
+#[cfg(feature = "tonic")]
+mod to_rich_status;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_details;
+
+#[cfg(feature = "axum")]
+mod axum_response;
+
+#[cfg(feature = "axum")]
+mod rejection;
+
+#[cfg(feature = "buffer-pool")]
+mod buffer_pool;
+
+#[cfg(feature = "metrics")]
+mod counters;
+
+#[cfg(feature = "otel")]
+mod otel;
+
+#[cfg(feature = "overrides")]
+mod overrides;
+
+#[cfg(feature = "json")]
+mod status_json;
+
+#[cfg(feature = "retry")]
+mod retry;
+
+#[cfg(all(feature = "tonic", feature = "tokio"))]
+mod retry_wait;
+
+#[cfg(feature = "layer")]
+mod request_info_layer;
+
+#[cfg(feature = "layer")]
+mod status_enricher;
+
+#[cfg(feature = "layer")]
+mod status_localizer;
+
+#[cfg(feature = "fluent")]
+mod message_template;
+
+#[cfg(feature = "interop")]
+mod interop;
+
+#[cfg(feature = "problem")]
+mod problem_details;
+
+#[cfg(any(all(test, feature = "std"), feature = "test-util"))]
+mod proptest_impls;
+
+#[cfg(feature = "tracing")]
+mod trace;
+
+#[cfg(feature = "validator")]
+mod validation;
 
 pub use std_messages::*;
 
+pub use custom_detail::CustomErrorDetail;
+
+pub use decode_error::{DetailDecodeError, Error};
+
+pub use detail_order::{DetailKind, DetailOrder};
+
+pub use detail_type::ErrorDetailType;
+
 pub use error_details::ErrorDetails;
 
 pub use error_details_vec::ErrorDetail;
 
+pub use rpc_status::RpcStatus;
+
+pub use type_url_prefix::TypeUrlPrefix;
+
+#[cfg(feature = "std")]
+pub use any_message::{pack_any, unpack_any, AnyMessage};
+
+#[cfg(feature = "std")]
+pub use backoff::Backoff;
+
+#[cfg(feature = "std")]
+pub use details_diff::{DetailChange, DetailsDiff};
+
+#[cfg(feature = "std")]
+pub use error_chain::{ErrorCause, ErrorChain};
+
+#[cfg(feature = "std")]
+pub use error_detail_kind::ErrorDetailKind;
+
+#[cfg(feature = "std")]
+pub use error_details_builder::{ErrorDetailsBuilder, HasDetails, NoDetails};
+
+#[cfg(feature = "std")]
+pub use error_details_multi::ErrorDetailsMulti;
+
+#[cfg(feature = "std")]
+pub use error_reasons::UnknownReason;
+
+#[cfg(feature = "std")]
+pub use lazy_details::LazyErrorDetails;
+
+#[cfg(feature = "std")]
+pub use localized_messages::LocalizedMessages;
+
+#[cfg(feature = "std")]
+pub use log_kv::LogValue;
+
+#[cfg(feature = "std")]
+pub use merge::MergeStrategy;
+
+#[cfg(feature = "std")]
+pub use policy::ErrorDetailsPolicy;
+
+#[cfg(feature = "std")]
+pub use raw_detail::RawDetail;
+
+#[cfg(feature = "tonic")]
+pub use aggregated_status::AggregatedStatus;
+
+#[cfg(feature = "tonic")]
+pub use error_details_stream::{with_error_details_stream, ErrorDetailsStream, StreamStatusExt};
+
+#[cfg(feature = "tonic")]
+pub use http_codes::{code_to_http, http_to_code, retry_after_header};
+
+#[cfg(feature = "tonic")]
+pub use pairing::PairingError;
+
+#[cfg(feature = "tonic")]
+pub use precoded_status::PrecodedStatus;
+
+#[cfg(feature = "tonic")]
+pub use reason_backoff::ReasonBackoff;
+
+#[cfg(feature = "tonic")]
+pub use reason_matcher::ReasonMatcher;
+
+#[cfg(feature = "tonic")]
+pub use retry_decision::RetryDecision;
+
+#[cfg(feature = "tonic")]
+pub use rich_error::RichError;
+
+#[cfg(feature = "tonic")]
+pub use rich_status::RichStatus;
+
+#[cfg(feature = "tonic")]
+pub use to_rich_status::{IntoRichResult, ToRichStatus};
+
+#[cfg(feature = "axum")]
+pub use rejection::RejectionStatus;
+
+#[cfg(feature = "buffer-pool")]
+pub use buffer_pool::PooledStatusExt;
+
+#[cfg(feature = "retry")]
+pub use retry::{RetryOnRetryInfo, RetryOnRetryInfoLayer};
+
+#[cfg(all(feature = "tonic", feature = "tokio"))]
+pub use retry_wait::RetryStatusExt;
+
+#[cfg(feature = "layer")]
+pub use request_info_layer::{RequestInfoLayer, RequestInfoService};
+
+#[cfg(feature = "layer")]
+pub use status_enricher::{
+    RequestContext, StatusEnricher, StatusEnricherBody, StatusEnricherLayer, StatusEnricherService,
+};
+
+#[cfg(feature = "layer")]
+pub use status_localizer::StatusLocalizer;
+
+#[cfg(feature = "fluent")]
+pub use message_template::{MessageTemplate, TemplateArg, TemplateError};
+
+#[cfg(feature = "problem")]
+pub use problem_details::{InvalidParam, ProblemDetails};
+
+#[cfg(feature = "otel")]
+pub use otel::record_error_details;
+
+#[cfg(feature = "overrides")]
+pub use overrides::{
+    MessageOverrides, OverrideEntry, OverrideLink, OverrideLocalizedMessage, OverrideMode,
+    OverridesError,
+};
+
+/// Derives `From<T> for tonic::Status` for application error types. Variant
+/// attributes describe the status code, message and error details; attributes
+/// on the enum itself apply to every variant. If `#[code(...)]` is absent,
+/// the code is inferred with [`ErrorDetails::inferred_code`], and if
+/// `#[message(...)]` is absent, the `Display` implementation is used.
+/// # Examples
+///
+/// ```
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{IntoStatus, WithErrorDetails};
+///
+/// #[derive(Debug, IntoStatus)]
+/// #[error_info(reason = "SIGNUP_ERROR", domain = "example.local")]
+/// enum SignupError {
+///     #[code(InvalidArgument)]
+///     #[message("invalid email")]
+///     #[bad_request(field = "email", description = "must contain an @")]
+///     InvalidEmail,
+///
+///     #[message("too many signups")]
+///     #[quota_failure(subject = "ip", description = "signup limit reached")]
+///     #[retry_info(delay_secs = 60)]
+///     RateLimited { ip: String },
+/// }
+///
+/// let status = Status::from(SignupError::InvalidEmail);
+///
+/// assert_eq!(status.code(), Code::InvalidArgument);
+/// assert!(status.get_details_bad_request().is_some());
+///
+/// let status = Status::from(SignupError::RateLimited { ip: "::1".into() });
+///
+/// assert_eq!(status.code(), Code::ResourceExhausted);
+/// assert!(status.get_details_error_info().is_some());
+/// ```
+#[cfg(feature = "derive")]
+pub use tonic_richer_error_derive::IntoStatus;
+
 trait IntoAny {
     fn into_any(self) -> Any;
 }
 
 trait FromAny {
-    fn from_any(any: Any) -> Result<Self, DecodeError>
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError>
     where
         Self: Sized;
 }
 
+/// Discards a decoding error, emitting a warning if the `tracing` feature is
+/// enabled.
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn ok_or_warn<T, E: core::fmt::Display>(result: Result<T, E>, target: &str) -> Option<T> {
+    #[cfg(feature = "tracing")]
+    if let Err(err) = &result {
+        trace::decode_failed(target, err);
+    }
+
+    result.ok()
+}
+
+/// Decodes a detail and stores it at the corresponding [`ErrorDetails`]
+/// field. Details with unknown type URLs are kept at `unknown_details`.
+fn insert_detail(details: &mut ErrorDetails, any: AnyRef<'_>) -> Result<(), DecodeError> {
+    match any.type_url {
+        RetryInfo::TYPE_URL => {
+            details.retry_info = Some(RetryInfo::from_any(any)?);
+        }
+        DebugInfo::TYPE_URL => {
+            details.debug_info = Some(DebugInfo::from_any(any)?);
+        }
+        QuotaFailure::TYPE_URL => {
+            details.quota_failure = Some(QuotaFailure::from_any(any)?);
+        }
+        ErrorInfo::TYPE_URL => {
+            details.error_info = Some(ErrorInfo::from_any(any)?);
+        }
+        PreconditionFailure::TYPE_URL => {
+            details.precondition_failure = Some(PreconditionFailure::from_any(any)?);
+        }
+        BadRequest::TYPE_URL => {
+            details.bad_request = Some(BadRequest::from_any(any)?);
+        }
+        RequestInfo::TYPE_URL => {
+            details.request_info = Some(RequestInfo::from_any(any)?);
+        }
+        ResourceInfo::TYPE_URL => {
+            details.resource_info = Some(ResourceInfo::from_any(any)?);
+        }
+        Help::TYPE_URL => {
+            details.help = Some(Help::from_any(any)?);
+        }
+        LocalizedMessage::TYPE_URL => {
+            details.localized_message = Some(LocalizedMessage::from_any(any)?);
+        }
+        _ => {
+            details.unknown_details.push(any.to_any());
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    counters::detail_decoded(any.type_url);
+
+    Ok(())
+}
+
+/// Decodes every detail of a status into [`ErrorDetail`] enums, keeping their
+/// order.
+fn decode_details_vec(status: &StatusRef<'_>) -> Result<Vec<ErrorDetail>, Error> {
+    status
+        .details
+        .iter()
+        .enumerate()
+        .map(|(index, any)| {
+            let detail = ErrorDetail::from_any(*any)
+                .map_err(|err| Error::detail(index, any.type_url, err))?;
+
+            #[cfg(feature = "metrics")]
+            counters::detail_decoded(any.type_url);
+
+            Ok(detail)
+        })
+        .collect()
+}
+
+/// Returns the position of the [`ErrorDetails`] field a detail is stored at.
+/// Details without a dedicated field share the `unknown_details` position.
+#[cfg(feature = "tonic")]
+fn detail_field(detail: &ErrorDetail) -> usize {
+    match detail {
+        ErrorDetail::RetryInfo(_) => 0,
+        ErrorDetail::DebugInfo(_) => 1,
+        ErrorDetail::QuotaFailure(_) => 2,
+        ErrorDetail::ErrorInfo(_) => 3,
+        ErrorDetail::PreconditionFailure(_) => 4,
+        ErrorDetail::BadRequest(_) => 5,
+        ErrorDetail::RequestInfo(_) => 6,
+        ErrorDetail::ResourceInfo(_) => 7,
+        ErrorDetail::Help(_) => 8,
+        ErrorDetail::LocalizedMessage(_) => 9,
+        ErrorDetail::HttpRequest(_) | ErrorDetail::HttpResponse(_) | ErrorDetail::Other(_) => 10,
+    }
+}
+
+/// Applies the changes made from `original` to `details` to the decoded
+/// `wire` details. Details of unchanged fields are kept as they were,
+/// duplicates included, in their original order. A changed field replaces
+/// its first detail and drops the others, while new fields are appended.
+#[cfg(feature = "tonic")]
+fn apply_detail_changes(
+    wire: Vec<ErrorDetail>,
+    original: &ErrorDetails,
+    details: ErrorDetails,
+) -> Vec<ErrorDetail> {
+    let mut changes: [Option<Vec<ErrorDetail>>; 11] = Default::default();
+
+    macro_rules! field_changes {
+        ($($index:literal => $field:ident,)*) => {
+            $(
+                if details.$field != original.$field {
+                    changes[$index] = Some(details.$field.map(ErrorDetail::from).into_iter().collect());
+                }
+            )*
+        };
+    }
+
+    field_changes! {
+        0 => retry_info,
+        1 => debug_info,
+        2 => quota_failure,
+        3 => error_info,
+        4 => precondition_failure,
+        5 => bad_request,
+        6 => request_info,
+        7 => resource_info,
+        8 => help,
+        9 => localized_message,
+    }
+
+    if details.unknown_details != original.unknown_details {
+        changes[10] = Some(
+            details
+                .unknown_details
+                .into_iter()
+                .map(ErrorDetail::Other)
+                .collect(),
+        );
+    }
+
+    let mut result = Vec::with_capacity(wire.len());
+
+    for detail in wire {
+        match &mut changes[detail_field(&detail)] {
+            Some(replacement) => result.append(replacement),
+            None => result.push(detail),
+        }
+    }
+
+    for replacement in changes.iter_mut().flatten() {
+        result.append(replacement);
+    }
+
+    result
+}
+
+/// Generates [`WithErrorDetails`] associated functions that create a
+/// `tonic::Status` with a fixed code, mirroring `tonic::Status`'s own
+/// constructors.
+#[cfg(feature = "tonic")]
+macro_rules! code_constructors {
+    ($($name:ident => $code:ident,)*) => {
+        $(
+            #[doc = concat!(
+                "Generates a `tonic::Status` with the `Code::", stringify!($code),
+                "` code and error details obtained from an [`ErrorDetails`] struct."
+            )]
+            /// # Examples
+            ///
+            /// ```
+            /// use tonic::{Code, Status};
+            /// use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+            ///
+            #[doc = concat!(
+                "let status = Status::", stringify!($name),
+                "(\"message\", ErrorDetails::new());"
+            )]
+            ///
+            #[doc = concat!("assert_eq!(status.code(), Code::", stringify!($code), ");")]
+            /// ```
+            fn $name(message: impl Into<String>, details: ErrorDetails) -> Status {
+                Self::with_error_details(Code::$code, message, details)
+            }
+        )*
+    };
+}
+
+/// Generates a `tonic::Status` with [`ResourceInfo`] details, for the
+/// [`WithErrorDetails`] resource helpers.
+#[cfg(feature = "tonic")]
+fn resource_status(
+    code: Code,
+    resource_type: String,
+    resource_name: String,
+    outcome: &str,
+) -> Status {
+    let message = format!("resource '{resource_name}' {outcome}");
+
+    let details = ErrorDetails::with_resource_info(resource_type, resource_name, "", &message);
+
+    Status::with_error_details(code, message, details)
+}
+
+/// Generates a `tonic::Status` carrying the already converted details.
+#[cfg(feature = "tonic")]
+fn status_with_any_details(code: Code, message: String, conv_details: Vec<Any>) -> Status {
+    status_with_any_details_into(code, message, conv_details, &mut BytesMut::new())
+}
+
+/// Generates a `tonic::Status` carrying the already converted details,
+/// encoded into `buf`, which is cleared first.
+#[cfg(feature = "tonic")]
+fn status_with_any_details_into(
+    code: Code,
+    message: String,
+    conv_details: Vec<Any>,
+    buf: &mut BytesMut,
+) -> Status {
+    let details = encode_any_details(code, &message, conv_details, buf);
+
+    Status::with_details(code, message, details)
+}
+
+/// Generates a `tonic::Status` carrying the already converted details,
+/// along with custom metadata.
+#[cfg(feature = "tonic")]
+fn status_with_any_details_and_metadata(
+    code: Code,
+    message: String,
+    conv_details: Vec<Any>,
+    metadata: MetadataMap,
+) -> Status {
+    let details = encode_any_details(code, &message, conv_details, &mut BytesMut::new());
+
+    Status::with_details_and_metadata(code, message, details, metadata)
+}
+
+/// Encodes a `google.rpc.Status` carrying the already converted details
+/// into `buf`, returning the encoded bytes.
+#[cfg(feature = "tonic")]
+fn encode_any_details(
+    code: Code,
+    message: &str,
+    conv_details: Vec<Any>,
+    buf: &mut BytesMut,
+) -> Bytes {
+    #[cfg(feature = "tracing")]
+    trace::status_built(code, message, &conv_details);
+
+    #[cfg(feature = "metrics")]
+    counters::details_emitted(&conv_details);
+
+    let status = pb::Status {
+        code: code as i32,
+        message: message.to_string(),
+        details: conv_details,
+    };
+
+    buf.clear();
+    buf.reserve(status.encoded_len());
+
+    // `BytesMut` grows as needed, so encoding cannot fail
+    let _ = status.encode(buf);
+
+    buf.split().freeze()
+}
+
 /// Used to implement associated functions and methods on `tonic::Status`, that
 /// allow the addition and extraction of standard error details.
+#[cfg(feature = "tonic")]
 pub trait WithErrorDetails {
     /// Generates a `tonic::Status` with error details obtained from an
-    /// [`ErrorDetails`] struct.
+    /// [`ErrorDetails`] struct. The details are encoded into an in-memory
+    /// buffer, which cannot fail, so the status is returned directly. The
+    /// details follow the globally installed [`DetailOrder`].
     /// # Examples
     ///
     /// ```
@@ -197,6 +971,33 @@ pub trait WithErrorDetails {
         details: ErrorDetails,
     ) -> Status;
 
+    /// Generates a `tonic::Status` with error details obtained from an
+    /// [`ErrorDetails`] struct, encoded in the given [`DetailOrder`] instead
+    /// of the globally installed one.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{DetailOrder, ErrorDetails, WithErrorDetails};
+    ///
+    /// let mut err_details = ErrorDetails::with_bad_request_violation("field", "description");
+    ///
+    /// err_details.set_error_info("INVALID_FIELD", "example.local", Default::default());
+    ///
+    /// let status = Status::with_error_details_ordered(
+    ///     Code::InvalidArgument,
+    ///     "bad request",
+    ///     err_details,
+    ///     &DetailOrder::error_info_first(),
+    /// );
+    /// ```
+    fn with_error_details_ordered(
+        code: tonic::Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+        order: &DetailOrder,
+    ) -> Status;
+
     /// Generates a `tonic::Status` with error details provided in a vector of
     /// [`ErrorDetail`] enums.
     /// # Examples
@@ -219,86 +1020,431 @@ pub trait WithErrorDetails {
         details: Vec<ErrorDetail>,
     ) -> Status;
 
-    /// Can be used to check if the error details contained in `tonic::Status`
-    /// are malformed or not. Tries to get an [`ErrorDetails`] struct from a
-    /// `tonic::Status`. If some `prost::DecodeError` occurs, it will be
-    /// returned. If not debugging, consider using
-    /// [`WithErrorDetails::get_error_details`] or
-    /// [`WithErrorDetails::get_error_details_vec`].
+    /// Generates a `tonic::Status` with error details provided in an
+    /// [`ErrorDetailsMulti`] struct, keeping the order in which the details
+    /// were added and any duplicates.
     /// # Examples
     ///
     /// ```
-    /// use tonic::{Status, Response};
-    /// use tonic_richer_error::{WithErrorDetails};
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{ErrorDetailsMulti, Help, WithErrorDetails};
     ///
-    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
-    ///     match req_result {
-    ///         Ok(_) => {},
-    ///         Err(status) => {
-    ///             let err_details = status.get_error_details();
-    ///             if let Some(bad_request) = err_details.bad_request {
-    ///                 // Handle bad_request details
-    ///             }
-    ///             // ...
-    ///         }
-    ///     };
-    /// }
+    /// let mut err_details = ErrorDetailsMulti::new();
+    ///
+    /// err_details
+    ///     .push(Help::with_link("first link", "https://example.local/first"))
+    ///     .push(Help::with_link("second link", "https://example.local/second"));
+    ///
+    /// let status = Status::with_error_details_multi(Code::NotFound, "not found", err_details);
     /// ```
-    fn check_error_details(&self) -> Result<ErrorDetails, DecodeError>;
+    fn with_error_details_multi(
+        code: tonic::Code,
+        message: impl Into<String>,
+        details: ErrorDetailsMulti,
+    ) -> Status;
 
-    /// Get an [`ErrorDetails`] struct from `tonic::Status`. If some
-    /// `prost::DecodeError` occurs, an empty [`ErrorDetails`] struct will be
-    /// returned.
+    /// Generates a `tonic::Status` with error details obtained from an
+    /// [`ErrorDetails`] struct, if the details can be used with the given
+    /// code, as described in [`ErrorDetails::check_code_pairing`]. Otherwise,
+    /// returns a [`PairingError`].
     /// # Examples
     ///
     /// ```
-    /// use tonic::{Status, Response};
-    /// use tonic_richer_error::{WithErrorDetails};
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{ErrorDetails, WithErrorDetails};
     ///
-    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
-    ///     match req_result {
-    ///         Ok(_) => {},
-    ///         Err(status) => {
-    ///             let err_details = status.get_error_details();
-    ///             if let Some(bad_request) = err_details.bad_request {
-    ///                 // Handle bad_request details
-    ///             }
-    ///             // ...
-    ///         }
-    ///     };
-    /// }
+    /// let result = Status::with_error_details_strict(
+    ///     Code::NotFound,
+    ///     "bad request",
+    ///     ErrorDetails::with_bad_request_violation("field", "description"),
+    /// );
+    ///
+    /// assert!(result.is_err());
     /// ```
-    fn get_error_details(&self) -> ErrorDetails;
+    fn with_error_details_strict(
+        code: tonic::Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+    ) -> Result<Status, PairingError>;
 
-    /// Can be used to check if the error details contained in `tonic::Status`
-    /// are malformed or not. Tries to get a vector of [`ErrorDetail`] enums
-    /// from a `tonic::Status`. If some `prost::DecodeError` occurs, it will be
-    /// returned. If not debugging, consider using
-    /// [`WithErrorDetails::get_error_details_vec`] or
-    /// [`WithErrorDetails::get_error_details`].
+    /// Generates a `tonic::Status` with error details obtained from an
+    /// [`ErrorDetails`] struct, trimming the details, as described in
+    /// [`ErrorDetails::truncate_to_len`], so the encoded `google.rpc.Status`
+    /// sent in the `grpc-status-details-bin` trailer fits within `max_bytes`.
+    /// Useful with long violation lists, which may otherwise be dropped by
+    /// proxies enforcing trailer size limits.
     /// # Examples
     ///
     /// ```
-    /// use tonic::{Status, Response};
-    /// use tonic_richer_error::{ErrorDetail, WithErrorDetails};
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{ErrorDetails, WithErrorDetails};
     ///
-    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
-    ///     match req_result {
-    ///         Ok(_) => {},
-    ///         Err(status) => {
-    ///             match status.check_error_details_vec() {
-    ///                 Ok(err_details) => {
-    ///                     // Handle extracted details
-    ///                 }
-    ///                 Err(decode_error) => {
-    ///                     // Handle decode_error
-    ///                 }
-    ///             }
-    ///         }
-    ///     };
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// for index in 0..1000 {
+    ///     err_details.add_bad_request_violation(format!("items[{index}]"), "is invalid");
     /// }
+    ///
+    /// let status =
+    ///     Status::with_error_details_bounded(Code::InvalidArgument, "bad request", err_details, 4096);
+    ///
+    /// assert!(status.details().len() <= 4096);
     /// ```
-    fn check_error_details_vec(&self) -> Result<Vec<ErrorDetail>, DecodeError>;
+    fn with_error_details_bounded(
+        code: tonic::Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+        max_bytes: usize,
+    ) -> Status;
+
+    /// Generates a `tonic::Status` with error details obtained from an
+    /// [`ErrorDetails`] struct, as [`WithErrorDetails::with_error_details`]
+    /// does, encoding them into `buf`. The buffer is cleared first, so any
+    /// bytes it holds are discarded. The encoded bytes are split off the
+    /// buffer, so its remaining capacity, and the whole allocation once the
+    /// status is dropped, can be reused by the next call. Useful for servers
+    /// returning many errors, avoiding a fresh allocation per status.
+    /// # Examples
+    ///
+    /// ```
+    /// use prost::bytes::BytesMut;
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+    ///
+    /// let mut buf = BytesMut::with_capacity(1024);
+    ///
+    /// let status = Status::with_error_details_into(
+    ///     Code::InvalidArgument,
+    ///     "bad request",
+    ///     ErrorDetails::with_bad_request_violation("field", "description"),
+    ///     &mut buf,
+    /// );
+    ///
+    /// assert!(status.get_details_bad_request().is_some());
+    /// ```
+    fn with_error_details_into(
+        code: tonic::Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+        buf: &mut BytesMut,
+    ) -> Status;
+
+    /// Generates a `tonic::Status` with error details obtained from an
+    /// [`ErrorDetails`] struct, as [`WithErrorDetails::with_error_details`]
+    /// does, along with custom metadata, such as tracing or session
+    /// trailers.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::metadata::MetadataMap;
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+    ///
+    /// let mut metadata = MetadataMap::new();
+    /// metadata.insert("x-trace-id", "4bf92f3577b34da6".parse().unwrap());
+    ///
+    /// let status = Status::with_error_details_and_metadata(
+    ///     Code::InvalidArgument,
+    ///     "bad request",
+    ///     ErrorDetails::with_bad_request_violation("field", "description"),
+    ///     metadata,
+    /// );
+    ///
+    /// assert!(status.metadata().get("x-trace-id").is_some());
+    /// assert!(status.get_details_bad_request().is_some());
+    /// ```
+    fn with_error_details_and_metadata(
+        code: tonic::Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+        metadata: MetadataMap,
+    ) -> Status;
+
+    /// Generates a `tonic::Status` with error details provided in a vector of
+    /// [`ErrorDetail`] enums, as [`WithErrorDetails::with_error_details_vec`]
+    /// does, along with custom metadata.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::metadata::MetadataMap;
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{BadRequest, WithErrorDetails};
+    ///
+    /// let mut metadata = MetadataMap::new();
+    /// metadata.insert("x-session-id", "session-1".parse().unwrap());
+    ///
+    /// let status = Status::with_error_details_vec_and_metadata(
+    ///     Code::InvalidArgument,
+    ///     "bad request",
+    ///     vec![BadRequest::with_violation("field", "description").into()],
+    ///     metadata,
+    /// );
+    ///
+    /// assert!(status.metadata().get("x-session-id").is_some());
+    /// ```
+    fn with_error_details_vec_and_metadata(
+        code: tonic::Code,
+        message: impl Into<String>,
+        details: Vec<ErrorDetail>,
+        metadata: MetadataMap,
+    ) -> Status;
+
+    code_constructors! {
+        cancelled_with => Cancelled,
+        unknown_with => Unknown,
+        invalid_argument_with => InvalidArgument,
+        deadline_exceeded_with => DeadlineExceeded,
+        not_found_with => NotFound,
+        already_exists_with => AlreadyExists,
+        permission_denied_with => PermissionDenied,
+        resource_exhausted_with => ResourceExhausted,
+        failed_precondition_with => FailedPrecondition,
+        aborted_with => Aborted,
+        out_of_range_with => OutOfRange,
+        unimplemented_with => Unimplemented,
+        internal_with => Internal,
+        unavailable_with => Unavailable,
+        data_loss_with => DataLoss,
+        unauthenticated_with => Unauthenticated,
+    }
+
+    /// Generates a `tonic::Status` with the `Code::NotFound` code, a message
+    /// naming the resource, and [`ResourceInfo`] details describing it.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::WithErrorDetails;
+    ///
+    /// let status = Status::resource_not_found("library.example.local/Book", "shelves/s/books/b");
+    ///
+    /// assert_eq!(status.code(), Code::NotFound);
+    /// assert_eq!(status.message(), "resource 'shelves/s/books/b' was not found");
+    /// assert!(status.get_details_resource_info().is_some());
+    /// ```
+    fn resource_not_found(
+        resource_type: impl Into<String>,
+        resource_name: impl Into<String>,
+    ) -> Status {
+        resource_status(
+            Code::NotFound,
+            resource_type.into(),
+            resource_name.into(),
+            "was not found",
+        )
+    }
+
+    /// Generates a `tonic::Status` with the `Code::AlreadyExists` code, a
+    /// message naming the resource, and [`ResourceInfo`] details describing
+    /// it.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::WithErrorDetails;
+    ///
+    /// let status = Status::resource_already_exists("library.example.local/Book", "shelves/s/books/b");
+    ///
+    /// assert_eq!(status.code(), Code::AlreadyExists);
+    /// ```
+    fn resource_already_exists(
+        resource_type: impl Into<String>,
+        resource_name: impl Into<String>,
+    ) -> Status {
+        resource_status(
+            Code::AlreadyExists,
+            resource_type.into(),
+            resource_name.into(),
+            "already exists",
+        )
+    }
+
+    /// Generates a `tonic::Status` with the `Code::PermissionDenied` code, a
+    /// message naming the resource, and [`ResourceInfo`] details describing
+    /// it.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::WithErrorDetails;
+    ///
+    /// let status = Status::resource_permission_denied("library.example.local/Book", "shelves/s/books/b");
+    ///
+    /// assert_eq!(status.code(), Code::PermissionDenied);
+    /// ```
+    fn resource_permission_denied(
+        resource_type: impl Into<String>,
+        resource_name: impl Into<String>,
+    ) -> Status {
+        resource_status(
+            Code::PermissionDenied,
+            resource_type.into(),
+            resource_name.into(),
+            "cannot be accessed",
+        )
+    }
+
+    /// Get an [`ErrorDetails`] struct from `tonic::Status`. If the status
+    /// contains no details, or if some `prost::DecodeError` occurs, an empty
+    /// [`ErrorDetails`] struct will be returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             let err_details = status.error_details();
+    ///             if let Some(bad_request) = err_details.bad_request {
+    ///                 // Handle bad_request details
+    ///             }
+    ///             // ...
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn error_details(&self) -> ErrorDetails;
+
+    /// Tries to get an [`ErrorDetails`] struct from `tonic::Status`,
+    /// distinguishing absent details from malformed ones. Returns `Ok(None)`
+    /// if the status contains no details, and an [`Error`] describing the
+    /// problem if the status or some detail is malformed.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => match status.try_error_details() {
+    ///             Ok(Some(err_details)) => {
+    ///                 // Handle extracted details
+    ///             }
+    ///             Ok(None) => {
+    ///                 // Handle status without details
+    ///             }
+    ///             Err(decode_error) => {
+    ///                 // Handle decode_error
+    ///             }
+    ///         },
+    ///     };
+    /// }
+    /// ```
+    fn try_error_details(&self) -> Result<Option<ErrorDetails>, Error>;
+
+    /// Can be used to check if the error details contained in `tonic::Status`
+    /// are malformed or not. Tries to get an [`ErrorDetails`] struct from a
+    /// `tonic::Status`. If the status or some detail is malformed, an
+    /// [`Error`] describing it will be returned. Deprecated in favor of
+    /// [`WithErrorDetails::try_error_details`], which also tells whether the
+    /// status contains details at all.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             let err_details = status.error_details();
+    ///             if let Some(bad_request) = err_details.bad_request {
+    ///                 // Handle bad_request details
+    ///             }
+    ///             // ...
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    #[deprecated(since = "0.3.3", note = "use `try_error_details` instead")]
+    fn check_error_details(&self) -> Result<ErrorDetails, Error>;
+
+    /// Get an [`ErrorDetails`] struct from `tonic::Status`. If some
+    /// `prost::DecodeError` occurs, an empty [`ErrorDetails`] struct will be
+    /// returned. Deprecated in favor of [`WithErrorDetails::error_details`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             let err_details = status.error_details();
+    ///             if let Some(bad_request) = err_details.bad_request {
+    ///                 // Handle bad_request details
+    ///             }
+    ///             // ...
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    #[deprecated(since = "0.3.3", note = "use `error_details` instead")]
+    fn get_error_details(&self) -> ErrorDetails;
+
+    /// Get an [`ErrorDetails`] struct from `tonic::Status`, keeping every
+    /// well-formed detail. Details that can not be decoded are skipped, and
+    /// reported as [`DetailDecodeError`]s with their type URL and position.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             let (err_details, decode_errors) = status.get_error_details_lenient();
+    ///             for decode_error in decode_errors {
+    ///                 // Report decode_error
+    ///             }
+    ///             if let Some(bad_request) = err_details.bad_request {
+    ///                 // Handle bad_request details
+    ///             }
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn get_error_details_lenient(&self) -> (ErrorDetails, Vec<DetailDecodeError>);
+
+    /// Can be used to check if the error details contained in `tonic::Status`
+    /// are malformed or not. Tries to get a vector of [`ErrorDetail`] enums
+    /// from a `tonic::Status`. If some `prost::DecodeError` occurs, it will be
+    /// returned. If not debugging, consider using
+    /// [`WithErrorDetails::get_error_details_vec`] or
+    /// [`WithErrorDetails::error_details`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{ErrorDetail, WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             match status.check_error_details_vec() {
+    ///                 Ok(err_details) => {
+    ///                     // Handle extracted details
+    ///                 }
+    ///                 Err(decode_error) => {
+    ///                     // Handle decode_error
+    ///                 }
+    ///             }
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn check_error_details_vec(&self) -> Result<Vec<ErrorDetail>, Error>;
 
     /// Get a vector of [`ErrorDetail`] enums from `tonic::Status`. If some
     /// `prost::DecodeError` occurs, an empty vector will be returned.
@@ -328,8 +1474,213 @@ pub trait WithErrorDetails {
     /// ```
     fn get_error_details_vec(&self) -> Vec<ErrorDetail>;
 
-    /// Get first [`RetryInfo`] details found on `tonic::Status`, if any. If
-    /// some `prost::DecodeError` occurs, returns `None`.
+    /// Can be used to check if the error details contained in `tonic::Status`
+    /// are malformed or not. Tries to get an [`ErrorDetailsMulti`] struct
+    /// from a `tonic::Status`, keeping the wire order of the details and any
+    /// duplicates. If some `prost::DecodeError` occurs, it will be returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::WithErrorDetails;
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             match status.check_error_details_multi() {
+    ///                 Ok(err_details) => {
+    ///                     // Handle extracted details
+    ///                 }
+    ///                 Err(decode_error) => {
+    ///                     // Handle decode_error
+    ///                 }
+    ///             }
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn check_error_details_multi(&self) -> Result<ErrorDetailsMulti, Error>;
+
+    /// Get an [`ErrorDetailsMulti`] struct from `tonic::Status`, keeping the
+    /// wire order of the details and any duplicates. If some
+    /// `prost::DecodeError` occurs, an empty struct will be returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::WithErrorDetails;
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             let err_details = status.get_error_details_multi();
+    ///             for help in err_details.helps() {
+    ///                 // Handle help details
+    ///             }
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn get_error_details_multi(&self) -> ErrorDetailsMulti;
+
+    /// Get a [`LazyErrorDetails`] struct from `tonic::Status`, decoding the
+    /// status only once. The standard error messages are decoded on demand,
+    /// by the [`LazyErrorDetails`] getters. If some `prost::DecodeError`
+    /// occurs, an empty [`LazyErrorDetails`] struct will be returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             let err_details = status.error_details_lazy();
+    ///             if let Some(retry_info) = err_details.retry_info() {
+    ///                 // Handle retry_info details
+    ///             }
+    ///             if let Some(quota_failure) = err_details.quota_failure() {
+    ///                 // Handle quota_failure details
+    ///             }
+    ///             // ...
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn error_details_lazy(&self) -> LazyErrorDetails;
+
+    /// Get first [`RetryInfo`] details found on `tonic::Status`, if any. If
+    /// some `prost::DecodeError` occurs, returns `None`.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             if let Some(retry_info) = status.get_details_retry_info() {
+    ///                 // Handle retry_info details
+    ///             }
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn get_details_retry_info(&self) -> Option<RetryInfo>;
+
+    /// Get first [`DebugInfo`] details found on `tonic::Status`, if any. If
+    /// some `prost::DecodeError` occurs, returns `None`.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             if let Some(debug_info) = status.get_details_debug_info() {
+    ///                 // Handle debug_info details
+    ///             }
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn get_details_debug_info(&self) -> Option<DebugInfo>;
+
+    /// Get first [`QuotaFailure`] details found on `tonic::Status`, if any.
+    /// If some `prost::DecodeError` occurs, returns `None`.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             if let Some(quota_failure) = status.get_details_quota_failure() {
+    ///                 // Handle quota_failure details
+    ///             }
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn get_details_quota_failure(&self) -> Option<QuotaFailure>;
+
+    /// Get first [`ErrorInfo`] details found on `tonic::Status`, if any. If
+    /// some `prost::DecodeError` occurs, returns `None`.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             if let Some(error_info) = status.get_details_error_info() {
+    ///                 // Handle error_info details
+    ///             }
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn get_details_error_info(&self) -> Option<ErrorInfo>;
+
+    /// Get first [`PreconditionFailure`] details found on `tonic::Status`,
+    /// if any. If some `prost::DecodeError` occurs, returns `None`.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             if let Some(precondition_failure) = status.get_details_precondition_failure() {
+    ///                 // Handle precondition_failure details
+    ///             }
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn get_details_precondition_failure(&self) -> Option<PreconditionFailure>;
+
+    /// Get first [`BadRequest`] details found on `tonic::Status`, if any. If
+    /// some `prost::DecodeError` occurs, returns `None`.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Status, Response};
+    /// use tonic_richer_error::{WithErrorDetails};
+    ///
+    /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
+    ///     match req_result {
+    ///         Ok(_) => {},
+    ///         Err(status) => {
+    ///             if let Some(bad_request) = status.get_details_bad_request() {
+    ///                 // Handle bad_request details
+    ///             }
+    ///         }
+    ///     };
+    /// }
+    /// ```
+    fn get_details_bad_request(&self) -> Option<BadRequest>;
+
+    /// Get first [`RequestInfo`] details found on `tonic::Status`, if any.
+    /// If some `prost::DecodeError` occurs, returns `None`.
     /// # Examples
     ///
     /// ```
@@ -340,17 +1691,17 @@ pub trait WithErrorDetails {
     ///     match req_result {
     ///         Ok(_) => {},
     ///         Err(status) => {
-    ///             if let Some(retry_info) = status.get_details_retry_info() {
-    ///                 // Handle retry_info details
+    ///             if let Some(request_info) = status.get_details_request_info() {
+    ///                 // Handle request_info details
     ///             }
     ///         }
     ///     };
     /// }
     /// ```
-    fn get_details_retry_info(&self) -> Option<RetryInfo>;
+    fn get_details_request_info(&self) -> Option<RequestInfo>;
 
-    /// Get first [`DebugInfo`] details found on `tonic::Status`, if any. If
-    /// some `prost::DecodeError` occurs, returns `None`.
+    /// Get first [`ResourceInfo`] details found on `tonic::Status`, if any.
+    /// If some `prost::DecodeError` occurs, returns `None`.
     /// # Examples
     ///
     /// ```
@@ -361,17 +1712,17 @@ pub trait WithErrorDetails {
     ///     match req_result {
     ///         Ok(_) => {},
     ///         Err(status) => {
-    ///             if let Some(debug_info) = status.get_details_debug_info() {
-    ///                 // Handle debug_info details
+    ///             if let Some(resource_info) = status.get_details_resource_info() {
+    ///                 // Handle resource_info details
     ///             }
     ///         }
     ///     };
     /// }
     /// ```
-    fn get_details_debug_info(&self) -> Option<DebugInfo>;
+    fn get_details_resource_info(&self) -> Option<ResourceInfo>;
 
-    /// Get first [`QuotaFailure`] details found on `tonic::Status`, if any.
-    /// If some `prost::DecodeError` occurs, returns `None`.
+    /// Get first [`Help`] details found on `tonic::Status`, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`.
     /// # Examples
     ///
     /// ```
@@ -382,17 +1733,17 @@ pub trait WithErrorDetails {
     ///     match req_result {
     ///         Ok(_) => {},
     ///         Err(status) => {
-    ///             if let Some(quota_failure) = status.get_details_quota_failure() {
-    ///                 // Handle quota_failure details
+    ///             if let Some(help) = status.get_details_help() {
+    ///                 // Handle help details
     ///             }
     ///         }
     ///     };
     /// }
     /// ```
-    fn get_details_quota_failure(&self) -> Option<QuotaFailure>;
+    fn get_details_help(&self) -> Option<Help>;
 
-    /// Get first [`ErrorInfo`] details found on `tonic::Status`, if any. If
-    /// some `prost::DecodeError` occurs, returns `None`.
+    /// Get first [`LocalizedMessage`] details found on `tonic::Status`, if
+    /// any. If some `prost::DecodeError` occurs, returns `None`.
     /// # Examples
     ///
     /// ```
@@ -403,80 +1754,101 @@ pub trait WithErrorDetails {
     ///     match req_result {
     ///         Ok(_) => {},
     ///         Err(status) => {
-    ///             if let Some(error_info) = status.get_details_error_info() {
-    ///                 // Handle error_info details
+    ///             if let Some(localized_message) = status.get_details_localized_message() {
+    ///                 // Handle localized_message details
     ///             }
     ///         }
     ///     };
     /// }
     /// ```
-    fn get_details_error_info(&self) -> Option<ErrorInfo>;
+    fn get_details_localized_message(&self) -> Option<LocalizedMessage>;
 
-    /// Get first [`PreconditionFailure`] details found on `tonic::Status`,
-    /// if any. If some `prost::DecodeError` occurs, returns `None`.
+    /// Get first user-defined error message of type `T` found on
+    /// `tonic::Status`, if any. If some `prost::DecodeError` occurs, returns
+    /// `None`.
     /// # Examples
     ///
     /// ```
     /// use tonic::{Status, Response};
-    /// use tonic_richer_error::{WithErrorDetails};
+    /// use tonic_richer_error::{CustomErrorDetail, WithErrorDetails};
     ///
+    /// # #[derive(Clone, PartialEq, prost::Message)]
+    /// # pub struct QuotaPlan {
+    /// #     #[prost(string, tag = "1")]
+    /// #     pub plan: String,
+    /// # }
+    /// # impl CustomErrorDetail for QuotaPlan {
+    /// #     const TYPE_URL: &'static str = "type.example.com/example.QuotaPlan";
+    /// # }
     /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
     ///     match req_result {
     ///         Ok(_) => {},
     ///         Err(status) => {
-    ///             if let Some(precondition_failure) = status.get_details_precondition_failure() {
-    ///                 // Handle precondition_failure details
+    ///             if let Some(quota_plan) = status.get_details_custom::<QuotaPlan>() {
+    ///                 // Handle quota_plan details
     ///             }
     ///         }
     ///     };
     /// }
     /// ```
-    fn get_details_precondition_failure(&self) -> Option<PreconditionFailure>;
+    fn get_details_custom<T: CustomErrorDetail>(&self) -> Option<T>;
 
-    /// Get first [`BadRequest`] details found on `tonic::Status`, if any. If
-    /// some `prost::DecodeError` occurs, returns `None`.
+    /// Get the [`ErrorChain`] found on `tonic::Status`, if any, to walk the
+    /// chain of causes sent by the server. If some `prost::DecodeError`
+    /// occurs, returns `None`.
     /// # Examples
     ///
     /// ```
     /// use tonic::{Status, Response};
-    /// use tonic_richer_error::{WithErrorDetails};
+    /// use tonic_richer_error::WithErrorDetails;
     ///
     /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
     ///     match req_result {
     ///         Ok(_) => {},
     ///         Err(status) => {
-    ///             if let Some(bad_request) = status.get_details_bad_request() {
-    ///                 // Handle bad_request details
+    ///             if let Some(chain) = status.error_chain() {
+    ///                 for cause in chain.causes {
+    ///                     // Handle each cause
+    ///                 }
     ///             }
     ///         }
     ///     };
     /// }
     /// ```
-    fn get_details_bad_request(&self) -> Option<BadRequest>;
+    fn error_chain(&self) -> Option<ErrorChain>;
 
-    /// Get first [`RequestInfo`] details found on `tonic::Status`, if any.
-    /// If some `prost::DecodeError` occurs, returns `None`.
+    /// Returns whether, and when, the call that failed with `tonic::Status`
+    /// should be retried, based on its code and on its [`RetryInfo`] and
+    /// [`ErrorInfo`] details, as described in [`RetryDecision::from_details`].
     /// # Examples
     ///
     /// ```
     /// use tonic::{Status, Response};
-    /// use tonic_richer_error::{WithErrorDetails};
+    /// use tonic_richer_error::{RetryDecision, WithErrorDetails};
     ///
     /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
     ///     match req_result {
     ///         Ok(_) => {},
-    ///         Err(status) => {
-    ///             if let Some(request_info) = status.get_details_request_info() {
-    ///                 // Handle request_info details
+    ///         Err(status) => match status.retry_decision() {
+    ///             RetryDecision::After(delay) => {
+    ///                 // Retry after delay
     ///             }
-    ///         }
+    ///             RetryDecision::Immediately => {
+    ///                 // Retry right away
+    ///             }
+    ///             RetryDecision::DontRetry => {
+    ///                 // Report the error
+    ///             }
+    ///         },
     ///     };
     /// }
     /// ```
-    fn get_details_request_info(&self) -> Option<RequestInfo>;
+    fn retry_decision(&self) -> RetryDecision;
 
-    /// Get first [`ResourceInfo`] details found on `tonic::Status`, if any.
-    /// If some `prost::DecodeError` occurs, returns `None`.
+    /// Get the domain, reason and metadata of the first [`ErrorInfo`]
+    /// details found on `tonic::Status`, if any. If some
+    /// `prost::DecodeError` occurs, returns `None`. To dispatch on the
+    /// reason, consider using [`ReasonMatcher`].
     /// # Examples
     ///
     /// ```
@@ -487,17 +1859,60 @@ pub trait WithErrorDetails {
     ///     match req_result {
     ///         Ok(_) => {},
     ///         Err(status) => {
-    ///             if let Some(resource_info) = status.get_details_resource_info() {
-    ///                 // Handle resource_info details
+    ///             if let Some((domain, reason, metadata)) = status.error_reason() {
+    ///                 // Handle reason
     ///             }
     ///         }
     ///     };
     /// }
     /// ```
-    fn get_details_resource_info(&self) -> Option<ResourceInfo>;
+    fn error_reason(&self) -> Option<(String, String, BTreeMap<String, String>)>;
 
-    /// Get first [`Help`] details found on `tonic::Status`, if any. If some
-    /// `prost::DecodeError` occurs, returns `None`.
+    /// Returns `true` if `tonic::Status` contains any error details. Only the
+    /// type URLs of the details are decoded. If some `prost::DecodeError`
+    /// occurs, returns `false`.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+    ///
+    /// let status = Status::with_error_details(
+    ///     Code::InvalidArgument,
+    ///     "bad request",
+    ///     ErrorDetails::with_bad_request_violation("field", "description"),
+    /// );
+    ///
+    /// assert!(status.has_error_details());
+    /// assert!(!Status::internal("internal").has_error_details());
+    /// ```
+    fn has_error_details(&self) -> bool;
+
+    /// Returns `true` if `tonic::Status` contains error details of type `T`,
+    /// which can be a standard error message struct or a
+    /// [`CustomErrorDetail`]. Only the type URLs of the details are decoded,
+    /// so malformed details of type `T` are also reported. If some
+    /// `prost::DecodeError` occurs while decoding the status, returns
+    /// `false`.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{BadRequest, ErrorDetails, RetryInfo, WithErrorDetails};
+    ///
+    /// let status = Status::with_error_details(
+    ///     Code::InvalidArgument,
+    ///     "bad request",
+    ///     ErrorDetails::with_bad_request_violation("field", "description"),
+    /// );
+    ///
+    /// assert!(status.has_detail::<BadRequest>());
+    /// assert!(!status.has_detail::<RetryInfo>());
+    /// ```
+    fn has_detail<T: ErrorDetailType>(&self) -> bool;
+
+    /// Returns `true` if `tonic::Status` contains [`BadRequest`] details.
+    /// Equivalent to `has_detail::<BadRequest>()`.
     /// # Examples
     ///
     /// ```
@@ -508,251 +1923,345 @@ pub trait WithErrorDetails {
     ///     match req_result {
     ///         Ok(_) => {},
     ///         Err(status) => {
-    ///             if let Some(help) = status.get_details_help() {
-    ///                 // Handle help details
+    ///             if status.has_details_bad_request() {
+    ///                 // Handle bad request
     ///             }
     ///         }
     ///     };
     /// }
     /// ```
-    fn get_details_help(&self) -> Option<Help>;
+    fn has_details_bad_request(&self) -> bool;
 
-    /// Get first [`LocalizedMessage`] details found on `tonic::Status`, if
-    /// any. If some `prost::DecodeError` occurs, returns `None`.
+    /// Returns an iterator over the details of `tonic::Status`, in wire
+    /// order, without decoding them. Each [`RawDetail`] can be decoded on
+    /// demand with [`RawDetail::downcast`]. If some `prost::DecodeError`
+    /// occurs while decoding the status, the iterator is empty.
     /// # Examples
     ///
     /// ```
     /// use tonic::{Status, Response};
-    /// use tonic_richer_error::{WithErrorDetails};
+    /// use tonic_richer_error::{BadRequest, WithErrorDetails};
     ///
     /// fn handle_request_result<T>(req_result: Result<Response<T>, Status>) {
     ///     match req_result {
     ///         Ok(_) => {},
     ///         Err(status) => {
-    ///             if let Some(localized_message) = status.get_details_localized_message() {
-    ///                 // Handle localized_message details
+    ///             for detail in status.error_details_iter() {
+    ///                 if let Some(bad_request) = detail.downcast::<BadRequest>() {
+    ///                     // Handle bad_request details
+    ///                 }
     ///             }
     ///         }
     ///     };
     /// }
     /// ```
-    fn get_details_localized_message(&self) -> Option<LocalizedMessage>;
+    fn error_details_iter(&self) -> std::vec::IntoIter<RawDetail<'_>>;
+
+    /// Generates a new `tonic::Status` with the same code, message and
+    /// metadata, and with error details modified by `f`. Details left
+    /// untouched by `f` are kept as they were, duplicates included, in their
+    /// original order. If the current details are malformed, the status is
+    /// returned unchanged. Can be used to append details to a status
+    /// produced elsewhere, such as by an inner service.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::Status;
+    /// use tonic_richer_error::WithErrorDetails;
+    ///
+    /// let status = Status::not_found("user not found");
+    ///
+    /// let status = status.map_error_details(|details| {
+    ///     details.set_request_info("request-id", "");
+    /// });
+    ///
+    /// assert!(status.get_details_request_info().is_some());
+    /// ```
+    fn map_error_details(self, f: impl FnOnce(&mut ErrorDetails)) -> Status;
+
+    /// Generates a `tonic::Status` with the same code, message and metadata,
+    /// such as tracing trailers set by a downstream service, carrying the
+    /// given error details instead of the original ones.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::Status;
+    /// use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+    ///
+    /// let mut status = Status::not_found("user not found");
+    /// status.metadata_mut().insert("x-trace-id", "trace".parse().unwrap());
+    ///
+    /// let status = status.clone_with_details(ErrorDetails::with_request_info("request-id", ""));
+    ///
+    /// assert!(status.metadata().get("x-trace-id").is_some());
+    /// assert!(status.get_details_request_info().is_some());
+    /// ```
+    fn clone_with_details(&self, details: ErrorDetails) -> Status;
+
+    /// Forwards a status received from an upstream dependency, keeping its
+    /// code, message, metadata and error details, and appending a
+    /// [`DebugInfo`] stack entry naming the current service. As the status
+    /// travels through several services, the stack entries describe the
+    /// path it took, from the service where it originated.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::Status;
+    /// use tonic_richer_error::WithErrorDetails;
+    ///
+    /// let upstream = Status::unavailable("inventory unavailable");
+    ///
+    /// let status = upstream
+    ///     .forward_with_context("orders")
+    ///     .forward_with_context("gateway");
+    ///
+    /// assert_eq!(
+    ///     status.get_details_debug_info().unwrap().stack_entries,
+    ///     vec!["forwarded by orders", "forwarded by gateway"]
+    /// );
+    /// ```
+    fn forward_with_context(self, service_name: impl Into<String>) -> Status;
+
+    /// Forwards a status received from an upstream dependency, as
+    /// [`WithErrorDetails::forward_with_context`] does, applying `policy` to
+    /// the upstream details first, for example to strip their [`DebugInfo`]
+    /// before it leaves the internal network.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{ErrorDetails, ErrorDetailsPolicy, WithErrorDetails};
+    ///
+    /// let upstream = Status::with_error_details(
+    ///     Code::Internal,
+    ///     "query failed",
+    ///     ErrorDetails::with_debug_info(vec!["db.rs:42".into()], "connection reset"),
+    /// );
+    ///
+    /// let policy = ErrorDetailsPolicy::new().redact_debug_info();
+    ///
+    /// let status = upstream.forward_with_policy("gateway", &policy);
+    ///
+    /// assert_eq!(
+    ///     status.get_details_debug_info().unwrap().stack_entries,
+    ///     vec!["forwarded by gateway"]
+    /// );
+    /// ```
+    fn forward_with_policy(
+        self,
+        service_name: impl Into<String>,
+        policy: &ErrorDetailsPolicy,
+    ) -> Status;
 }
 
+#[cfg(feature = "tonic")]
 impl WithErrorDetails for Status {
     fn with_error_details(code: Code, message: impl Into<String>, details: ErrorDetails) -> Self {
+        Status::with_error_details_ordered(code, message, details, &DetailOrder::global())
+    }
+
+    fn with_error_details_ordered(
+        code: Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+        order: &DetailOrder,
+    ) -> Self {
         let message: String = message.into();
 
-        let mut conv_details: Vec<Any> = Vec::with_capacity(10);
+        let mut details = details;
+        policy::apply_global(&mut details);
 
-        if let Some(retry_info) = details.retry_info {
-            conv_details.push(retry_info.into_any());
-        }
+        status_with_any_details(code, message, order.encode(details))
+    }
 
-        if let Some(debug_info) = details.debug_info {
-            conv_details.push(debug_info.into_any());
-        }
+    fn with_error_details_vec(
+        code: Code,
+        message: impl Into<String>,
+        details: Vec<ErrorDetail>,
+    ) -> Self {
+        let message: String = message.into();
 
-        if let Some(quota_failure) = details.quota_failure {
-            conv_details.push(quota_failure.into_any());
-        }
+        let mut details = details;
+        policy::apply_global_vec(&mut details);
 
-        if let Some(error_info) = details.error_info {
-            conv_details.push(error_info.into_any());
-        }
+        let conv_details: Vec<Any> = details.into_iter().map(IntoAny::into_any).collect();
 
-        if let Some(precondition_failure) = details.precondition_failure {
-            conv_details.push(precondition_failure.into_any());
-        }
+        status_with_any_details(code, message, conv_details)
+    }
 
-        if let Some(bad_request) = details.bad_request {
-            conv_details.push(bad_request.into_any());
-        }
+    fn with_error_details_multi(
+        code: Code,
+        message: impl Into<String>,
+        details: ErrorDetailsMulti,
+    ) -> Self {
+        Status::with_error_details_vec(code, message, details.into())
+    }
 
-        if let Some(request_info) = details.request_info {
-            conv_details.push(request_info.into_any());
-        }
+    fn with_error_details_strict(
+        code: Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+    ) -> Result<Self, PairingError> {
+        details.check_code_pairing(code)?;
 
-        if let Some(resource_info) = details.resource_info {
-            conv_details.push(resource_info.into_any());
-        }
+        Ok(Status::with_error_details(code, message, details))
+    }
 
-        if let Some(help) = details.help {
-            conv_details.push(help.into_any());
-        }
+    fn with_error_details_bounded(
+        code: Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+        max_bytes: usize,
+    ) -> Self {
+        let message: String = message.into();
 
-        if let Some(localized_message) = details.localized_message {
-            conv_details.push(localized_message.into_any());
-        }
+        let mut details = details;
+        policy::apply_global(&mut details);
 
-        let status = pb::Status {
+        let overhead = pb::Status {
             code: code as i32,
             message: message.clone(),
-            details: conv_details,
-        };
+            details: Vec::new(),
+        }
+        .encoded_len();
+
+        details.truncate_to_len(max_bytes.saturating_sub(overhead));
 
-        Status::with_details(code, message, Bytes::from(status.encode_to_vec()))
+        status_with_any_details(code, message, DetailOrder::global().encode(details))
     }
 
-    fn with_error_details_vec(
+    fn with_error_details_into(
+        code: Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+        buf: &mut BytesMut,
+    ) -> Self {
+        let message: String = message.into();
+
+        let mut details = details;
+        policy::apply_global(&mut details);
+
+        status_with_any_details_into(code, message, DetailOrder::global().encode(details), buf)
+    }
+
+    fn with_error_details_and_metadata(
+        code: Code,
+        message: impl Into<String>,
+        details: ErrorDetails,
+        metadata: MetadataMap,
+    ) -> Self {
+        let message: String = message.into();
+
+        let mut details = details;
+        policy::apply_global(&mut details);
+
+        let conv_details = DetailOrder::global().encode(details);
+
+        status_with_any_details_and_metadata(code, message, conv_details, metadata)
+    }
+
+    fn with_error_details_vec_and_metadata(
         code: Code,
         message: impl Into<String>,
         details: Vec<ErrorDetail>,
+        metadata: MetadataMap,
     ) -> Self {
         let message: String = message.into();
 
-        let mut conv_details: Vec<Any> = Vec::with_capacity(details.len());
+        let mut details = details;
+        policy::apply_global_vec(&mut details);
 
-        for error_detail in details.into_iter() {
-            match error_detail {
-                ErrorDetail::RetryInfo(retry_info) => {
-                    conv_details.push(retry_info.into_any());
-                }
-                ErrorDetail::DebugInfo(debug_info) => {
-                    conv_details.push(debug_info.into_any());
-                }
-                ErrorDetail::QuotaFailure(quota_failure) => {
-                    conv_details.push(quota_failure.into_any());
-                }
-                ErrorDetail::ErrorInfo(error_info) => {
-                    conv_details.push(error_info.into_any());
-                }
-                ErrorDetail::PreconditionFailure(prec_failure) => {
-                    conv_details.push(prec_failure.into_any());
-                }
-                ErrorDetail::BadRequest(bad_req) => {
-                    conv_details.push(bad_req.into_any());
-                }
-                ErrorDetail::RequestInfo(req_info) => {
-                    conv_details.push(req_info.into_any());
-                }
-                ErrorDetail::ResourceInfo(res_info) => {
-                    conv_details.push(res_info.into_any());
-                }
-                ErrorDetail::Help(help) => {
-                    conv_details.push(help.into_any());
-                }
-                ErrorDetail::LocalizedMessage(loc_message) => {
-                    conv_details.push(loc_message.into_any());
-                }
-            }
-        }
+        let conv_details: Vec<Any> = details.into_iter().map(IntoAny::into_any).collect();
 
-        let status = pb::Status {
-            code: code as i32,
-            message: message.clone(),
-            details: conv_details,
-        };
+        status_with_any_details_and_metadata(code, message, conv_details, metadata)
+    }
 
-        Status::with_details(code, message, Bytes::from(status.encode_to_vec()))
+    fn error_details(&self) -> ErrorDetails {
+        ok_or_warn(ErrorDetails::decode_status(self.details()), "error details").unwrap_or_default()
     }
 
-    fn check_error_details(&self) -> Result<ErrorDetails, DecodeError> {
-        let status = pb::Status::decode(self.details())?;
+    fn try_error_details(&self) -> Result<Option<ErrorDetails>, Error> {
+        let status = StatusRef::decode(self.details()).map_err(Error::InvalidStatus)?;
+
+        if status.details.is_empty() {
+            return Ok(None);
+        }
 
         let mut details = ErrorDetails::new();
 
-        for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                RetryInfo::TYPE_URL => {
-                    details.retry_info = Some(RetryInfo::from_any(any)?);
-                }
-                DebugInfo::TYPE_URL => {
-                    details.debug_info = Some(DebugInfo::from_any(any)?);
-                }
-                QuotaFailure::TYPE_URL => {
-                    details.quota_failure = Some(QuotaFailure::from_any(any)?);
-                }
-                ErrorInfo::TYPE_URL => {
-                    details.error_info = Some(ErrorInfo::from_any(any)?);
-                }
-                PreconditionFailure::TYPE_URL => {
-                    details.precondition_failure = Some(PreconditionFailure::from_any(any)?);
-                }
-                BadRequest::TYPE_URL => {
-                    details.bad_request = Some(BadRequest::from_any(any)?);
-                }
-                RequestInfo::TYPE_URL => {
-                    details.request_info = Some(RequestInfo::from_any(any)?);
-                }
-                ResourceInfo::TYPE_URL => {
-                    details.resource_info = Some(ResourceInfo::from_any(any)?);
-                }
-                Help::TYPE_URL => {
-                    details.help = Some(Help::from_any(any)?);
-                }
-                LocalizedMessage::TYPE_URL => {
-                    details.localized_message = Some(LocalizedMessage::from_any(any)?);
-                }
-                _ => {}
-            }
+        for (index, any) in status.details.into_iter().enumerate() {
+            insert_detail(&mut details, any)
+                .map_err(|err| Error::detail(index, any.type_url, err))?;
         }
 
-        Ok(details)
+        Ok(Some(details))
     }
 
-    fn get_error_details(&self) -> ErrorDetails {
-        self.check_error_details().unwrap_or(ErrorDetails::new())
+    fn check_error_details(&self) -> Result<ErrorDetails, Error> {
+        ErrorDetails::decode_status(self.details())
     }
 
-    fn check_error_details_vec(&self) -> Result<Vec<ErrorDetail>, DecodeError> {
-        let status = pb::Status::decode(self.details())?;
+    fn get_error_details(&self) -> ErrorDetails {
+        self.error_details()
+    }
 
-        let mut details: Vec<ErrorDetail> = Vec::with_capacity(status.details.len());
+    fn get_error_details_lenient(&self) -> (ErrorDetails, Vec<DetailDecodeError>) {
+        let (status, status_err) = StatusRef::decode_partial(self.details());
 
-        for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                RetryInfo::TYPE_URL => {
-                    details.push(RetryInfo::from_any(any)?.into());
-                }
-                DebugInfo::TYPE_URL => {
-                    details.push(DebugInfo::from_any(any)?.into());
-                }
-                QuotaFailure::TYPE_URL => {
-                    details.push(QuotaFailure::from_any(any)?.into());
-                }
-                ErrorInfo::TYPE_URL => {
-                    details.push(ErrorInfo::from_any(any)?.into());
-                }
-                PreconditionFailure::TYPE_URL => {
-                    details.push(PreconditionFailure::from_any(any)?.into());
-                }
-                BadRequest::TYPE_URL => {
-                    details.push(BadRequest::from_any(any)?.into());
-                }
-                RequestInfo::TYPE_URL => {
-                    details.push(RequestInfo::from_any(any)?.into());
-                }
-                ResourceInfo::TYPE_URL => {
-                    details.push(ResourceInfo::from_any(any)?.into());
-                }
-                Help::TYPE_URL => {
-                    details.push(Help::from_any(any)?.into());
-                }
-                LocalizedMessage::TYPE_URL => {
-                    details.push(LocalizedMessage::from_any(any)?.into());
-                }
-                _ => {}
+        let mut details = ErrorDetails::new();
+        let mut errors = Vec::new();
+
+        for (index, any) in status.details.iter().enumerate() {
+            if let Err(source) = insert_detail(&mut details, *any) {
+                errors.push(DetailDecodeError {
+                    index,
+                    type_url: any.type_url.to_string(),
+                    source,
+                });
             }
         }
 
-        Ok(details)
+        if let Some(source) = status_err {
+            errors.push(DetailDecodeError {
+                index: status.details.len(),
+                type_url: String::new(),
+                source,
+            });
+        }
+
+        (details, errors)
+    }
+
+    fn check_error_details_vec(&self) -> Result<Vec<ErrorDetail>, Error> {
+        let status = StatusRef::decode(self.details()).map_err(Error::InvalidStatus)?;
+
+        decode_details_vec(&status)
     }
 
     fn get_error_details_vec(&self) -> Vec<ErrorDetail> {
-        self.check_error_details_vec().unwrap_or(Vec::new())
+        ok_or_warn(self.check_error_details_vec(), "error details").unwrap_or_default()
+    }
+
+    fn check_error_details_multi(&self) -> Result<ErrorDetailsMulti, Error> {
+        self.check_error_details_vec().map(ErrorDetailsMulti::from)
+    }
+
+    fn get_error_details_multi(&self) -> ErrorDetailsMulti {
+        self.get_error_details_vec().into()
+    }
+
+    fn error_details_lazy(&self) -> LazyErrorDetails {
+        ok_or_warn(LazyErrorDetails::decode(self.details()), "status").unwrap_or_default()
     }
 
     fn get_details_retry_info(&self) -> Option<RetryInfo> {
-        let status = pb::Status::decode(self.details()).ok()?;
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
 
         for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                RetryInfo::TYPE_URL => match RetryInfo::from_any(any) {
-                    Ok(detail) => return Some(detail),
-                    Err(_) => {}
-                },
-                _ => {}
+            if any.type_url == RetryInfo::TYPE_URL {
+                if let Some(detail) = ok_or_warn(RetryInfo::from_any(any), RetryInfo::TYPE_URL) {
+                    return Some(detail);
+                }
             }
         }
 
@@ -760,15 +2269,13 @@ impl WithErrorDetails for Status {
     }
 
     fn get_details_debug_info(&self) -> Option<DebugInfo> {
-        let status = pb::Status::decode(self.details()).ok()?;
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
 
         for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                DebugInfo::TYPE_URL => match DebugInfo::from_any(any) {
-                    Ok(detail) => return Some(detail),
-                    Err(_) => {}
-                },
-                _ => {}
+            if any.type_url == DebugInfo::TYPE_URL {
+                if let Some(detail) = ok_or_warn(DebugInfo::from_any(any), DebugInfo::TYPE_URL) {
+                    return Some(detail);
+                }
             }
         }
 
@@ -776,15 +2283,15 @@ impl WithErrorDetails for Status {
     }
 
     fn get_details_quota_failure(&self) -> Option<QuotaFailure> {
-        let status = pb::Status::decode(self.details()).ok()?;
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
 
         for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                QuotaFailure::TYPE_URL => match QuotaFailure::from_any(any) {
-                    Ok(detail) => return Some(detail),
-                    Err(_) => {}
-                },
-                _ => {}
+            if any.type_url == QuotaFailure::TYPE_URL {
+                if let Some(detail) =
+                    ok_or_warn(QuotaFailure::from_any(any), QuotaFailure::TYPE_URL)
+                {
+                    return Some(detail);
+                }
             }
         }
 
@@ -792,15 +2299,13 @@ impl WithErrorDetails for Status {
     }
 
     fn get_details_error_info(&self) -> Option<ErrorInfo> {
-        let status = pb::Status::decode(self.details()).ok()?;
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
 
         for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                ErrorInfo::TYPE_URL => match ErrorInfo::from_any(any) {
-                    Ok(detail) => return Some(detail),
-                    Err(_) => {}
-                },
-                _ => {}
+            if any.type_url == ErrorInfo::TYPE_URL {
+                if let Some(detail) = ok_or_warn(ErrorInfo::from_any(any), ErrorInfo::TYPE_URL) {
+                    return Some(detail);
+                }
             }
         }
 
@@ -808,15 +2313,16 @@ impl WithErrorDetails for Status {
     }
 
     fn get_details_precondition_failure(&self) -> Option<PreconditionFailure> {
-        let status = pb::Status::decode(self.details()).ok()?;
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
 
         for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                PreconditionFailure::TYPE_URL => match PreconditionFailure::from_any(any) {
-                    Ok(detail) => return Some(detail),
-                    Err(_) => {}
-                },
-                _ => {}
+            if any.type_url == PreconditionFailure::TYPE_URL {
+                if let Some(detail) = ok_or_warn(
+                    PreconditionFailure::from_any(any),
+                    PreconditionFailure::TYPE_URL,
+                ) {
+                    return Some(detail);
+                }
             }
         }
 
@@ -824,15 +2330,13 @@ impl WithErrorDetails for Status {
     }
 
     fn get_details_bad_request(&self) -> Option<BadRequest> {
-        let status = pb::Status::decode(self.details()).ok()?;
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
 
         for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                BadRequest::TYPE_URL => match BadRequest::from_any(any) {
-                    Ok(detail) => return Some(detail),
-                    Err(_) => {}
-                },
-                _ => {}
+            if any.type_url == BadRequest::TYPE_URL {
+                if let Some(detail) = ok_or_warn(BadRequest::from_any(any), BadRequest::TYPE_URL) {
+                    return Some(detail);
+                }
             }
         }
 
@@ -840,15 +2344,14 @@ impl WithErrorDetails for Status {
     }
 
     fn get_details_request_info(&self) -> Option<RequestInfo> {
-        let status = pb::Status::decode(self.details()).ok()?;
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
 
         for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                RequestInfo::TYPE_URL => match RequestInfo::from_any(any) {
-                    Ok(detail) => return Some(detail),
-                    Err(_) => {}
-                },
-                _ => {}
+            if any.type_url == RequestInfo::TYPE_URL {
+                if let Some(detail) = ok_or_warn(RequestInfo::from_any(any), RequestInfo::TYPE_URL)
+                {
+                    return Some(detail);
+                }
             }
         }
 
@@ -856,15 +2359,15 @@ impl WithErrorDetails for Status {
     }
 
     fn get_details_resource_info(&self) -> Option<ResourceInfo> {
-        let status = pb::Status::decode(self.details()).ok()?;
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
 
         for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                ResourceInfo::TYPE_URL => match ResourceInfo::from_any(any) {
-                    Ok(detail) => return Some(detail),
-                    Err(_) => {}
-                },
-                _ => {}
+            if any.type_url == ResourceInfo::TYPE_URL {
+                if let Some(detail) =
+                    ok_or_warn(ResourceInfo::from_any(any), ResourceInfo::TYPE_URL)
+                {
+                    return Some(detail);
+                }
             }
         }
 
@@ -872,15 +2375,13 @@ impl WithErrorDetails for Status {
     }
 
     fn get_details_help(&self) -> Option<Help> {
-        let status = pb::Status::decode(self.details()).ok()?;
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
 
         for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                Help::TYPE_URL => match Help::from_any(any) {
-                    Ok(detail) => return Some(detail),
-                    Err(_) => {}
-                },
-                _ => {}
+            if any.type_url == Help::TYPE_URL {
+                if let Some(detail) = ok_or_warn(Help::from_any(any), Help::TYPE_URL) {
+                    return Some(detail);
+                }
             }
         }
 
@@ -888,36 +2389,154 @@ impl WithErrorDetails for Status {
     }
 
     fn get_details_localized_message(&self) -> Option<LocalizedMessage> {
-        let status = pb::Status::decode(self.details()).ok()?;
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
 
         for any in status.details.into_iter() {
-            match any.type_url.as_str() {
-                LocalizedMessage::TYPE_URL => match LocalizedMessage::from_any(any) {
-                    Ok(detail) => return Some(detail),
-                    Err(_) => {}
-                },
-                _ => {}
+            if any.type_url == LocalizedMessage::TYPE_URL {
+                if let Some(detail) =
+                    ok_or_warn(LocalizedMessage::from_any(any), LocalizedMessage::TYPE_URL)
+                {
+                    return Some(detail);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn get_details_custom<T: CustomErrorDetail>(&self) -> Option<T> {
+        let status = ok_or_warn(StatusRef::decode(self.details()), "status")?;
+
+        for any in status.details.into_iter() {
+            if any.type_url == T::TYPE_URL {
+                if let Some(detail) = ok_or_warn(T::from_any(&any.to_any()), T::TYPE_URL) {
+                    return Some(detail);
+                }
             }
         }
 
         None
     }
+
+    fn error_chain(&self) -> Option<ErrorChain> {
+        self.get_details_custom::<ErrorChain>()
+    }
+
+    fn retry_decision(&self) -> RetryDecision {
+        RetryDecision::from_details(self.code(), &self.error_details())
+    }
+
+    fn error_reason(&self) -> Option<(String, String, BTreeMap<String, String>)> {
+        let error_info = self.get_details_error_info()?;
+
+        Some((error_info.domain, error_info.reason, error_info.metadata))
+    }
+
+    fn has_error_details(&self) -> bool {
+        match ok_or_warn(StatusRef::decode(self.details()), "status") {
+            Some(status) => !status.details.is_empty(),
+            None => false,
+        }
+    }
+
+    fn has_detail<T: ErrorDetailType>(&self) -> bool {
+        match ok_or_warn(StatusRef::decode(self.details()), "status") {
+            Some(status) => status.details.iter().any(|any| any.type_url == T::TYPE_URL),
+            None => false,
+        }
+    }
+
+    fn has_details_bad_request(&self) -> bool {
+        self.has_detail::<BadRequest>()
+    }
+
+    fn error_details_iter(&self) -> std::vec::IntoIter<RawDetail<'_>> {
+        let details = match ok_or_warn(StatusRef::decode(self.details()), "status") {
+            Some(status) => status
+                .details
+                .iter()
+                .map(|any| RawDetail {
+                    type_url: any.type_url,
+                    bytes: any.value,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        details.into_iter()
+    }
+
+    fn map_error_details(self, f: impl FnOnce(&mut ErrorDetails)) -> Status {
+        let (wire, original) = match (self.check_error_details_vec(), self.try_error_details()) {
+            (Ok(wire), Ok(original)) => (wire, original.unwrap_or_default()),
+            _ => return self,
+        };
+
+        let mut details = original.clone();
+
+        f(&mut details);
+
+        if details == original {
+            return self;
+        }
+
+        Status::with_error_details_vec_and_metadata(
+            self.code(),
+            self.message(),
+            apply_detail_changes(wire, &original, details),
+            self.metadata().clone(),
+        )
+    }
+
+    fn clone_with_details(&self, details: ErrorDetails) -> Status {
+        Status::with_error_details_and_metadata(
+            self.code(),
+            self.message(),
+            details,
+            self.metadata().clone(),
+        )
+    }
+
+    fn forward_with_context(self, service_name: impl Into<String>) -> Status {
+        self.forward_with_policy(service_name, &ErrorDetailsPolicy::new())
+    }
+
+    fn forward_with_policy(
+        self,
+        service_name: impl Into<String>,
+        policy: &ErrorDetailsPolicy,
+    ) -> Status {
+        let entry = format!("forwarded by {}", service_name.into());
+
+        self.map_error_details(|details| {
+            policy.apply(details);
+
+            details
+                .debug_info
+                .get_or_insert_with(|| DebugInfo::new(Vec::new(), ""))
+                .stack_entries
+                .push(entry);
+        })
+    }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "tonic"))]
 mod tests {
-    use std::collections::HashMap;
+    use prost::Message;
+    use std::collections::BTreeMap;
     use std::time::Duration;
+    use tonic::metadata::MetadataMap;
     use tonic::{Code, Status};
 
     use super::{
-        BadRequest, DebugInfo, ErrorDetails, ErrorInfo, Help, LocalizedMessage,
-        PreconditionFailure, QuotaFailure, RequestInfo, ResourceInfo, RetryInfo, WithErrorDetails,
+        BadRequest, DebugInfo, ErrorDetails, ErrorDetailsPolicy, ErrorInfo, Help, IntoAny,
+        LocalizedMessage, PreconditionFailure, QuotaFailure, RequestInfo, ResourceInfo, RetryInfo,
+        WithErrorDetails,
     };
 
     #[test]
     fn gen_status_with_details() {
-        let mut metadata = HashMap::new();
+        let mut metadata = BTreeMap::new();
         metadata.insert("limitPerRequest".to_string(), "100".into());
 
         let mut err_details = ErrorDetails::new();
@@ -990,8 +2609,9 @@ mod tests {
 
         println!("{:?}\n", fmt_status_with_details_vec);
 
-        let ext_details = match status_from_vec.check_error_details() {
-            Ok(ext_details) => ext_details,
+        let ext_details = match status_from_vec.try_error_details() {
+            Ok(Some(ext_details)) => ext_details,
+            Ok(None) => panic!("No details extracted from status_from_vec"),
             Err(err) => panic!(
                 "Error extracting details struct from status_from_vec: {:?}",
                 err
@@ -1025,4 +2645,423 @@ mod tests {
             "Extracted details vec differs from original details vec"
         );
     }
+
+    #[test]
+    fn preserve_unknown_details() {
+        let custom = prost_types::Any {
+            type_url: "type.example.com/custom.Detail".into(),
+            value: vec![8, 1],
+        };
+
+        let mut err_details = ErrorDetails::with_bad_request_violation("field", "description");
+        err_details.unknown_details.push(custom.clone());
+
+        let status = Status::with_error_details(Code::InvalidArgument, "bad request", err_details);
+
+        let ext_details = status.error_details();
+
+        let fmt_unknown = format!("{:?}", ext_details.unknown_details);
+
+        println!("unknown details -> {fmt_unknown}");
+
+        assert!(
+            fmt_unknown.eq(&format!("{:?}", vec![custom.clone()])),
+            "unknown details were not preserved in details struct"
+        );
+
+        let status = Status::with_error_details(Code::InvalidArgument, "bad request", ext_details);
+
+        let fmt_details_vec = format!("{:?}", status.get_error_details_vec());
+
+        let expected = format!(
+            "{:?}",
+            vec![
+                BadRequest::with_violation("field", "description").into(),
+                super::ErrorDetail::Other(custom),
+            ]
+        );
+
+        assert!(
+            fmt_details_vec.eq(&expected),
+            "unknown details were not preserved in details vec"
+        );
+    }
+
+    #[test]
+    fn compare_extracted_details() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .set_error_info("SOME_INFO", "example.local", BTreeMap::new())
+            .add_bad_request_violation("field", "description")
+            .set_localized_message("en-US", "message for the user");
+
+        let status =
+            Status::with_error_details(Code::Unavailable, "unavailable", err_details.clone());
+
+        assert_eq!(status.error_details(), err_details);
+
+        assert_eq!(
+            status.get_details_bad_request(),
+            Some(BadRequest::with_violation("field", "description"))
+        );
+
+        assert_ne!(
+            status.get_details_retry_info(),
+            Some(RetryInfo::new(Some(Duration::from_secs(1))))
+        );
+    }
+
+    #[test]
+    fn lenient_details_extraction() {
+        let status = super::pb::Status {
+            code: Code::InvalidArgument as i32,
+            message: "bad request".into(),
+            details: vec![
+                super::Any {
+                    type_url: RetryInfo::TYPE_URL.into(),
+                    value: vec![10, 5],
+                },
+                BadRequest::with_violation("field", "description").into_any(),
+            ],
+        };
+
+        let encoded_details = status.encode_to_vec();
+
+        // Truncated detail
+        let mut encoded = encoded_details.clone();
+        encoded.extend([26, 10, 10]);
+
+        let status = Status::with_details(Code::InvalidArgument, "bad request", encoded.into());
+
+        let (details, errors) = status.get_error_details_lenient();
+
+        let formatted = format!("{:?}", errors);
+
+        println!("lenient decode errors -> {formatted}");
+
+        let expected = "[DetailDecodeError { index: 0, type_url: \"type.googleapis.com/google.rpc.RetryInfo\", source: DecodeError { description: \"buffer underflow\", stack: [(\"RetryInfo\", \"retry_delay\")] } }, DetailDecodeError { index: 2, type_url: \"\", source: DecodeError { description: \"buffer underflow\", stack: [] } }]";
+
+        assert!(
+            formatted.eq(expected),
+            "lenient decode errors differ from expected result"
+        );
+
+        assert!(
+            details.bad_request == Some(BadRequest::with_violation("field", "description"))
+                && details.retry_info.is_none(),
+            "well-formed details were not kept"
+        );
+
+        let formatted = match status.try_error_details() {
+            Ok(details) => panic!("malformed details were decoded: {:?}", details),
+            Err(err) => err.to_string(),
+        };
+
+        println!("strict decode error -> {formatted}");
+
+        assert!(
+            matches!(Status::internal("internal").try_error_details(), Ok(None)),
+            "absent details were not distinguished from malformed ones"
+        );
+
+        let expected = "malformed status: failed to decode Protobuf message: buffer underflow";
+
+        assert!(
+            formatted.eq(expected),
+            "strict decode error differs from expected result"
+        );
+
+        let formatted = match Status::with_details(Code::Unknown, "", encoded_details.into())
+            .check_error_details_vec()
+        {
+            Ok(details) => panic!("malformed details were decoded: {:?}", details),
+            Err(err) => format!("{:?}", err),
+        };
+
+        let expected = "Decode { type_url: \"type.googleapis.com/google.rpc.RetryInfo\", index: 0, source: DecodeError { description: \"buffer underflow\", stack: [(\"RetryInfo\", \"retry_delay\")] } }";
+
+        assert!(
+            formatted.eq(expected),
+            "strict decode error differs from expected result"
+        );
+    }
+
+    #[test]
+    fn map_status_details() {
+        let mut status = Status::with_error_details(
+            Code::InvalidArgument,
+            "bad request",
+            ErrorDetails::with_bad_request_violation("field_a", "description"),
+        );
+
+        status
+            .metadata_mut()
+            .insert("x-trace-id", "trace".parse().unwrap());
+
+        let status = status.map_error_details(|details| {
+            details
+                .add_bad_request_violation("field_b", "description")
+                .set_request_info("request-id", "");
+        });
+
+        let formatted = format!(
+            "{:?} {:?} {:?} {:?}",
+            status.code(),
+            status.message(),
+            status.metadata().get("x-trace-id"),
+            status.error_details()
+        );
+
+        println!("mapped status -> {formatted}");
+
+        let expected = "InvalidArgument \"bad request\" Some(\"trace\") ErrorDetails { retry_info: None, debug_info: None, quota_failure: None, error_info: None, precondition_failure: None, bad_request: Some(BadRequest { field_violations: [FieldViolation { field: \"field_a\", description: \"description\", reason: \"\", localized_message: None }, FieldViolation { field: \"field_b\", description: \"description\", reason: \"\", localized_message: None }] }), request_info: Some(RequestInfo { request_id: \"request-id\", serving_data: \"\" }), resource_info: None, help: None, localized_message: None, unknown_details: [] }";
+
+        assert!(
+            formatted.eq(expected),
+            "mapped status differs from expected result"
+        );
+
+        let status = Status::with_error_details_vec(
+            Code::InvalidArgument,
+            "bad request",
+            vec![
+                Help::with_link("first", "https://example.com/first").into(),
+                BadRequest::with_violation("field_a", "description").into(),
+                Help::with_link("second", "https://example.com/second").into(),
+            ],
+        );
+
+        let status = status.map_error_details(|details| {
+            details.add_bad_request_violation("field_b", "description");
+        });
+
+        let formatted = format!("{:?}", status.get_error_details_vec());
+
+        println!("mapped details vec -> {formatted}");
+
+        let expected = "[Help(Help { links: [HelpLink { description: \"first\", url: \"https://example.com/first\" }] }), BadRequest(BadRequest { field_violations: [FieldViolation { field: \"field_a\", description: \"description\", reason: \"\", localized_message: None }, FieldViolation { field: \"field_b\", description: \"description\", reason: \"\", localized_message: None }] }), Help(Help { links: [HelpLink { description: \"second\", url: \"https://example.com/second\" }] })]";
+
+        assert!(
+            formatted.eq(expected),
+            "mapped details vec differs from expected result"
+        );
+
+        let malformed = Status::with_details(Code::Internal, "internal", vec![0xff].into());
+
+        let status = malformed.clone().map_error_details(|details| {
+            details.set_request_info("request-id", "");
+        });
+
+        assert!(
+            status.details() == malformed.details(),
+            "malformed details were not kept"
+        );
+    }
+
+    #[test]
+    fn clone_status_with_details() {
+        let mut status = Status::not_found("user not found");
+
+        status
+            .metadata_mut()
+            .insert("x-trace-id", "trace".parse().unwrap());
+
+        let cloned = status.clone_with_details(ErrorDetails::with_request_info("request-id", ""));
+
+        let formatted = format!(
+            "{:?} {:?} {:?} {:?}",
+            cloned.code(),
+            cloned.message(),
+            cloned.metadata().get("x-trace-id"),
+            cloned.get_details_request_info()
+        );
+
+        println!("cloned status -> {formatted}");
+
+        let expected = "NotFound \"user not found\" Some(\"trace\") Some(RequestInfo { request_id: \"request-id\", serving_data: \"\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "cloned status differs from expected result"
+        );
+    }
+
+    #[test]
+    fn forward_statuses() {
+        let mut upstream = Status::with_error_details(
+            Code::Internal,
+            "query failed",
+            ErrorDetails::with_debug_info(vec!["db.rs:42".into()], "connection reset"),
+        );
+
+        upstream
+            .metadata_mut()
+            .insert("x-trace-id", "trace".parse().unwrap());
+
+        let forwarded = upstream.clone().forward_with_context("orders");
+
+        let formatted = format!(
+            "{:?} {:?} {:?} {:?}",
+            forwarded.code(),
+            forwarded.message(),
+            forwarded.metadata().get("x-trace-id"),
+            forwarded.get_details_debug_info()
+        );
+
+        println!("forwarded status -> {formatted}");
+
+        let expected = "Internal \"query failed\" Some(\"trace\") Some(DebugInfo { stack_entries: [\"db.rs:42\", \"forwarded by orders\"], detail: \"connection reset\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "forwarded status differs from expected result"
+        );
+
+        let policy = ErrorDetailsPolicy::new().redact_debug_info();
+
+        let forwarded = upstream.forward_with_policy("gateway", &policy);
+
+        let formatted = format!("{:?}", forwarded.get_details_debug_info());
+
+        println!("forwarded status with policy -> {formatted}");
+
+        let expected =
+            "Some(DebugInfo { stack_entries: [\"forwarded by gateway\"], detail: \"\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "forwarded status with policy differs from expected result"
+        );
+    }
+
+    #[test]
+    fn gen_status_with_metadata() {
+        let mut metadata = MetadataMap::new();
+        metadata.insert("x-trace-id", "trace".parse().unwrap());
+
+        let err_details = ErrorDetails::with_bad_request_violation("field", "description");
+
+        let status = Status::with_error_details_and_metadata(
+            Code::InvalidArgument,
+            "bad request",
+            err_details.clone(),
+            metadata.clone(),
+        );
+
+        let expected =
+            Status::with_error_details(Code::InvalidArgument, "bad request", err_details);
+
+        println!("status with metadata -> {:?}", status);
+
+        assert!(
+            status.details() == expected.details()
+                && status.metadata().get("x-trace-id") == metadata.get("x-trace-id"),
+            "status with metadata differs from expected result"
+        );
+
+        let status = Status::with_error_details_vec_and_metadata(
+            Code::InvalidArgument,
+            "bad request",
+            vec![BadRequest::with_violation("field", "description").into()],
+            metadata,
+        );
+
+        assert!(
+            status.details() == expected.details() && status.metadata().get("x-trace-id").is_some(),
+            "status with details vec and metadata differs from expected result"
+        );
+    }
+
+    #[test]
+    fn gen_resource_statuses() {
+        let statuses = [
+            Status::resource_not_found("library.example.local/Book", "shelves/s/books/b"),
+            Status::resource_already_exists("library.example.local/Book", "shelves/s/books/b"),
+            Status::resource_permission_denied("library.example.local/Book", "shelves/s/books/b"),
+        ];
+
+        let formatted: Vec<String> = statuses
+            .iter()
+            .map(|status| {
+                format!(
+                    "{:?} {:?} {:?}",
+                    status.code(),
+                    status.message(),
+                    status.get_details_resource_info()
+                )
+            })
+            .collect();
+
+        println!("resource statuses -> {:?}", formatted);
+
+        let expected = [
+            "NotFound \"resource 'shelves/s/books/b' was not found\" Some(ResourceInfo { resource_type: \"library.example.local/Book\", resource_name: \"shelves/s/books/b\", owner: \"\", description: \"resource 'shelves/s/books/b' was not found\" })",
+            "AlreadyExists \"resource 'shelves/s/books/b' already exists\" Some(ResourceInfo { resource_type: \"library.example.local/Book\", resource_name: \"shelves/s/books/b\", owner: \"\", description: \"resource 'shelves/s/books/b' already exists\" })",
+            "PermissionDenied \"resource 'shelves/s/books/b' cannot be accessed\" Some(ResourceInfo { resource_type: \"library.example.local/Book\", resource_name: \"shelves/s/books/b\", owner: \"\", description: \"resource 'shelves/s/books/b' cannot be accessed\" })",
+        ];
+
+        assert!(
+            formatted.eq(&expected),
+            "resource statuses differ from expected result"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_details() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_millis(1500)))
+            .add_precondition_failure_violation("TOS", "example.local", "description")
+            .add_bad_request_violation("field", "description");
+
+        let err_details_vec: Vec<super::ErrorDetail> = vec![
+            RetryInfo::new(Some(Duration::from_millis(1500))).into(),
+            BadRequest::with_violation("field", "description").into(),
+            prost_types::Any {
+                type_url: "type.googleapis.com/example.Custom".to_string(),
+                value: vec![1, 2, 3],
+            }
+            .into(),
+        ];
+
+        let json = serde_json::to_string(&err_details_vec).unwrap();
+
+        println!("ErrorDetail vec serialized -> {json}");
+
+        let expected = r#"[{"@type":"type.googleapis.com/google.rpc.RetryInfo","retryDelay":"1.500s"},{"@type":"type.googleapis.com/google.rpc.BadRequest","fieldViolations":[{"field":"field","description":"description"}]},{"@type":"type.googleapis.com/example.Custom","value":"AQID"}]"#;
+
+        assert!(
+            json.eq(expected),
+            "serialized ErrorDetail vec differs from expected result"
+        );
+
+        let de_details_vec: Vec<super::ErrorDetail> = serde_json::from_str(&json).unwrap();
+
+        assert!(
+            format!("{:?}", de_details_vec).eq(&format!("{:?}", err_details_vec)),
+            "deserialized ErrorDetail vec differs from original"
+        );
+
+        let json = serde_json::to_string(&err_details).unwrap();
+
+        println!("ErrorDetails serialized -> {json}");
+
+        let de_details: ErrorDetails = serde_json::from_str(&json).unwrap();
+
+        assert!(
+            format!("{:?}", de_details).eq(&format!("{:?}", err_details)),
+            "deserialized ErrorDetails differs from original"
+        );
+
+        let de_retry_info: RetryInfo = serde_json::from_str(r#"{"retryDelay":"-3s"}"#).unwrap();
+
+        assert!(
+            de_retry_info.retry_delay == Some(Duration::ZERO),
+            "negative retryDelay was not deserialized as zero"
+        );
+    }
 }
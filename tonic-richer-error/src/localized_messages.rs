@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+#[cfg(feature = "tonic")]
+use tonic::metadata::MetadataMap;
+
+use super::{ErrorDetails, LocalizedMessage};
+
+/// Metadata key read by [`LocalizedMessage::for_request`] and
+/// `StatusLocalizer`.
+#[cfg(feature = "tonic")]
+pub(crate) const ACCEPT_LANGUAGE: &str = "accept-language";
+
+static GLOBAL_CATALOG: RwLock<Option<LocalizedMessages>> = RwLock::new(None);
+
+/// Catalog of translated messages, keyed by locale and message key. Messages
+/// can contain `{name}` placeholders, replaced by the arguments given to
+/// [`LocalizedMessages::localize`]. The best locale is selected with the
+/// [RFC 4647] lookup scheme. Once installed with
+/// [`LocalizedMessages::install`], the catalog is used by
+/// [`ErrorDetails::set_localized_message_for`].
+///
+/// [RFC 4647]: https://www.rfc-editor.org/rfc/rfc4647#section-3.4
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::LocalizedMessages;
+///
+/// let catalog = LocalizedMessages::new()
+///     .default_locale("en-US")
+///     .message("en-US", "QUOTA_EXCEEDED", "You have used all {limit} requests")
+///     .message("pt-BR", "QUOTA_EXCEEDED", "Você usou todas as {limit} requisições");
+///
+/// let loc_message = catalog
+///     .localize(&["pt-BR", "en"], "QUOTA_EXCEEDED", &[("limit", "100")])
+///     .unwrap();
+///
+/// assert_eq!(loc_message.locale, "pt-BR");
+/// assert_eq!(loc_message.message, "Você usou todas as 100 requisições");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LocalizedMessages {
+    default_locale: Option<String>,
+    messages: HashMap<String, HashMap<String, String>>,
+}
+
+impl LocalizedMessages {
+    /// Creates a new empty [`LocalizedMessages`] catalog.
+    pub fn new() -> Self {
+        LocalizedMessages::default()
+    }
+
+    /// Sets the locale used when none of the preferred locales match.
+    pub fn default_locale(mut self, locale: impl Into<String>) -> Self {
+        self.default_locale = Some(locale.into());
+        self
+    }
+
+    /// Adds a message to the catalog, replacing any previous message with the
+    /// same locale and key.
+    pub fn message(
+        mut self,
+        locale: impl Into<String>,
+        key: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.messages
+            .entry(locale.into())
+            .or_default()
+            .insert(key.into(), message.into());
+        self
+    }
+
+    /// Installs the catalog globally, replacing the previously installed one.
+    pub fn install(self) {
+        match GLOBAL_CATALOG.write() {
+            Ok(mut catalog) => *catalog = Some(self),
+            Err(poisoned) => *poisoned.into_inner() = Some(self),
+        }
+    }
+
+    /// Returns a copy of the globally installed catalog, if any.
+    pub fn global() -> Option<Self> {
+        match GLOBAL_CATALOG.read() {
+            Ok(catalog) => catalog.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Returns the catalog locale that best matches the preferred locales,
+    /// among the locales that contain the given key. Each preferred locale is
+    /// progressively truncated, as described in [RFC 4647], until a match is
+    /// found. If none of the preferred locales match, the default locale is
+    /// returned, if it contains the key.
+    ///
+    /// [RFC 4647]: https://www.rfc-editor.org/rfc/rfc4647#section-3.4
+    pub fn lookup(&self, locale_preferences: &[&str], key: &str) -> Option<&str> {
+        let available = |tag: &str| {
+            self.messages
+                .iter()
+                .find(|(locale, messages)| {
+                    locale.eq_ignore_ascii_case(tag) && messages.contains_key(key)
+                })
+                .map(|(locale, _)| locale.as_str())
+        };
+
+        for range in locale_preferences.iter() {
+            let mut tag = range.trim();
+
+            while !tag.is_empty() && tag != "*" {
+                if let Some(locale) = available(tag) {
+                    return Some(locale);
+                }
+
+                tag = match tag.rfind('-') {
+                    Some(index) => &tag[..index],
+                    None => "",
+                };
+
+                // single-character subtags are removed with the following one
+                if tag.len() >= 2 && tag.as_bytes()[tag.len() - 2] == b'-' {
+                    tag = &tag[..tag.len() - 2];
+                }
+            }
+        }
+
+        self.default_locale.as_deref().and_then(available)
+    }
+
+    /// Generates a [`LocalizedMessage`] for the message key, in the locale
+    /// that best matches the preferred locales, as in
+    /// [`LocalizedMessages::lookup`]. The `{name}` placeholders are replaced
+    /// by the corresponding arguments in a single pass, so placeholders
+    /// inside argument values are kept as is. Returns `None` if no locale
+    /// matches.
+    pub fn localize(
+        &self,
+        locale_preferences: &[&str],
+        key: &str,
+        args: &[(&str, &str)],
+    ) -> Option<LocalizedMessage> {
+        let locale = self.lookup(locale_preferences, key)?;
+
+        let template = self.messages.get(locale)?.get(key)?;
+
+        Some(LocalizedMessage::new(
+            locale,
+            fill_placeholders(template, args),
+        ))
+    }
+}
+
+/// Replaces the `{name}` placeholders of a template by the corresponding
+/// arguments, scanning the template once. Placeholders without a matching
+/// argument are kept unchanged.
+fn fill_placeholders(template: &str, args: &[(&str, &str)]) -> String {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let value = rest.find('}').and_then(|end| {
+            let name = &rest[1..end];
+
+            args.iter()
+                .find(|(arg_name, _)| *arg_name == name)
+                .map(|(_, value)| (*value, end))
+        });
+
+        match value {
+            Some((value, end)) => {
+                message.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    message.push_str(rest);
+    message
+}
+
+impl From<HashMap<String, HashMap<String, String>>> for LocalizedMessages {
+    /// Creates a catalog from a map of locales to maps of message keys to
+    /// messages.
+    fn from(messages: HashMap<String, HashMap<String, String>>) -> Self {
+        LocalizedMessages {
+            default_locale: None,
+            messages,
+        }
+    }
+}
+
+#[cfg(feature = "tonic")]
+impl LocalizedMessage {
+    /// Generates a [`LocalizedMessage`] for the message key, in the catalog
+    /// locale that best matches the `accept-language` metadata of a request.
+    /// Language ranges are tried in decreasing order of their `q` values, and
+    /// ranges with `q=0` are ignored. If the metadata is absent or no range
+    /// matches, the default locale of the catalog is used. Returns `None` if
+    /// no locale matches.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::metadata::MetadataMap;
+    /// use tonic_richer_error::{LocalizedMessage, LocalizedMessages};
+    ///
+    /// let catalog = LocalizedMessages::new()
+    ///     .default_locale("en")
+    ///     .message("en", "NOT_FOUND", "{name} was not found")
+    ///     .message("fr", "NOT_FOUND", "{name} est introuvable");
+    ///
+    /// let mut metadata = MetadataMap::new();
+    /// metadata.insert("accept-language", "de;q=0.5, fr-CA, en;q=0.8".parse().unwrap());
+    ///
+    /// let loc_message =
+    ///     LocalizedMessage::for_request(&metadata, &catalog, "NOT_FOUND", &[("name", "file")])
+    ///         .unwrap();
+    ///
+    /// assert_eq!(loc_message.message, "file est introuvable");
+    /// ```
+    pub fn for_request(
+        metadata: &MetadataMap,
+        catalog: &LocalizedMessages,
+        key: &str,
+        args: &[(&str, &str)],
+    ) -> Option<LocalizedMessage> {
+        let ranges = metadata
+            .get_all(ACCEPT_LANGUAGE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<&str>>()
+            .join(",");
+
+        let ranges = parse_accept_language(&ranges);
+
+        catalog.localize(&ranges, key, args)
+    }
+}
+
+/// Parses an `accept-language` value into language ranges, sorted by
+/// decreasing `q` value. Ranges with the same `q` value keep their order,
+/// and ranges with `q=0` or an invalid `q` value are discarded.
+#[cfg(feature = "tonic")]
+pub(crate) fn parse_accept_language(value: &str) -> Vec<&str> {
+    let mut ranges: Vec<(&str, f32)> = value
+        .split(',')
+        .filter_map(|item| {
+            let mut params = item.split(';');
+
+            let range = params.next()?.trim();
+
+            if range.is_empty() {
+                return None;
+            }
+
+            let mut weight = 1.0;
+
+            for param in params {
+                if let Some(q) = param.trim().strip_prefix("q=") {
+                    weight = q.trim().parse::<f32>().ok()?;
+                }
+            }
+
+            if weight > 0.0 && weight <= 1.0 {
+                Some((range, weight))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    ranges.into_iter().map(|(range, _)| range).collect()
+}
+
+impl ErrorDetails {
+    /// Set [`LocalizedMessage`] details, with the message key translated to
+    /// the locale that best matches the preferred locales, using the globally
+    /// installed [`LocalizedMessages`] catalog. If no catalog is installed or
+    /// no locale matches, the details are left unchanged. Can be chained with
+    /// other `.set_` and `.add_` [`ErrorDetails`] methods.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetails, LocalizedMessages};
+    ///
+    /// LocalizedMessages::new()
+    ///     .message("en", "INVALID_EMAIL", "{email} is not a valid email")
+    ///     .install();
+    ///
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details.set_localized_message_for(
+    ///     &["en-GB", "fr"],
+    ///     "INVALID_EMAIL",
+    ///     &[("email", "user@")],
+    /// );
+    ///
+    /// assert_eq!(err_details.localized_message.unwrap().locale, "en");
+    /// ```
+    pub fn set_localized_message_for(
+        &mut self,
+        locale_preferences: &[&str],
+        key: &str,
+        args: &[(&str, &str)],
+    ) -> &mut Self {
+        let localized_message = match GLOBAL_CATALOG.read() {
+            Ok(catalog) => catalog
+                .as_ref()
+                .and_then(|catalog| catalog.localize(locale_preferences, key, args)),
+            Err(_) => None,
+        };
+
+        if let Some(localized_message) = localized_message {
+            self.localized_message = Some(localized_message);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[cfg(feature = "tonic")]
+    use tonic::metadata::MetadataMap;
+
+    #[cfg(feature = "tonic")]
+    use super::super::LocalizedMessage;
+    #[cfg(feature = "tonic")]
+    use super::parse_accept_language;
+    use super::LocalizedMessages;
+
+    #[test]
+    fn lookup_locales() {
+        let catalog = LocalizedMessages::new()
+            .default_locale("en")
+            .message("en", "GREETING", "Hello, {name}")
+            .message("de-CH", "GREETING", "Grüezi, {name}")
+            .message("zh-Hant", "GREETING", "你好, {name}")
+            .message("fr", "OTHER", "Autre");
+
+        let lookups = [
+            catalog.lookup(&["de-CH-1996"], "GREETING"),
+            catalog.lookup(&["DE-ch"], "GREETING"),
+            catalog.lookup(&["zh-Hant-CN-x-private1"], "GREETING"),
+            catalog.lookup(&["fr-FR", "*"], "GREETING"),
+            catalog.lookup(&["fr-FR"], "OTHER"),
+            catalog.lookup(&["es"], "OTHER"),
+        ];
+
+        println!("looked up locales -> {:?}", lookups);
+
+        let expected = [
+            Some("de-CH"),
+            Some("de-CH"),
+            Some("zh-Hant"),
+            Some("en"),
+            Some("fr"),
+            None,
+        ];
+
+        assert!(
+            lookups.eq(&expected),
+            "looked up locales differ from expected result"
+        );
+
+        let formatted = format!(
+            "{:?}",
+            catalog.localize(&["de"], "GREETING", &[("name", "Anna")])
+        );
+
+        let expected = "Some(LocalizedMessage { locale: \"en\", message: \"Hello, Anna\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "localized message differs from expected result"
+        );
+
+        let catalog = LocalizedMessages::new().message(
+            "en",
+            "RENAMED",
+            "{old} was renamed to {new}, {unknown} {{new}",
+        );
+
+        let formatted = format!(
+            "{:?}",
+            catalog.localize(&["en"], "RENAMED", &[("old", "{new}"), ("new", "{old}")])
+        );
+
+        let expected = "Some(LocalizedMessage { locale: \"en\", message: \"{new} was renamed to {old}, {unknown} {{old}\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "placeholders inside arguments were replaced"
+        );
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn localize_for_request() {
+        let catalog = LocalizedMessages::new()
+            .default_locale("en")
+            .message("en", "GREETING", "Hello, {name}")
+            .message("de-CH", "GREETING", "Grüezi, {name}")
+            .message("fr", "OTHER", "Autre");
+
+        let ranges = parse_accept_language("fr;q=0.5, de-CH , en;q=0.9, es;q=0, it;q=x, *;q=0.1");
+
+        println!("parsed language ranges -> {:?}", ranges);
+
+        assert!(
+            ranges.eq(&["de-CH", "en", "fr", "*"]),
+            "parsed language ranges differ from expected result"
+        );
+
+        let mut metadata = MetadataMap::new();
+        metadata.append("accept-language", "fr;q=0.5".parse().unwrap());
+        metadata.append("accept-language", "de-CH-1996".parse().unwrap());
+
+        let formatted = format!(
+            "{:?} {:?}",
+            LocalizedMessage::for_request(&metadata, &catalog, "GREETING", &[("name", "Anna")]),
+            LocalizedMessage::for_request(&MetadataMap::new(), &catalog, "OTHER", &[])
+        );
+
+        let expected =
+            "Some(LocalizedMessage { locale: \"de-CH\", message: \"Grüezi, Anna\" }) None";
+
+        assert!(
+            formatted.eq(expected),
+            "localized messages for request differ from expected result"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::ErrorDetails;
+
+/// Value of the key-value pairs returned by [`ErrorDetails::log_kv`].
+/// Borrows from the error details, so no serialization or allocation is
+/// needed to log them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogValue<'a> {
+    /// A string field.
+    Str(&'a str),
+
+    /// A number, such as a delay in milliseconds or a number of violations.
+    U64(u64),
+
+    /// A string map, such as the [`crate::ErrorInfo`] metadata.
+    Map(&'a BTreeMap<String, String>),
+}
+
+impl fmt::Display for LogValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogValue::Str(value) => f.write_str(value),
+            LogValue::U64(value) => write!(f, "{value}"),
+            LogValue::Map(map) => write!(f, "{:?}", map),
+        }
+    }
+}
+
+impl ErrorDetails {
+    /// Returns the error details as key-value pairs, so logging pipelines can
+    /// emit rich error context without JSON serialization. Keys are prefixed
+    /// by the snake_case name of the detail, as in `error_info.reason`.
+    /// Lists, such as [`crate::BadRequest`] violations, are summarized by
+    /// their lengths.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetails, LogValue};
+    ///
+    /// let mut err_details = ErrorDetails::with_bad_request_violation("field", "description");
+    ///
+    /// err_details.set_error_info("INVALID_FIELD", "example.local", Default::default());
+    ///
+    /// for (key, value) in err_details.log_kv() {
+    ///     println!("{key}={value}");
+    /// }
+    ///
+    /// assert!(err_details
+    ///     .log_kv()
+    ///     .any(|pair| pair == ("bad_request.violations", LogValue::U64(1))));
+    /// ```
+    pub fn log_kv(&self) -> std::vec::IntoIter<(&'static str, LogValue<'_>)> {
+        let mut pairs: Vec<(&'static str, LogValue<'_>)> = Vec::new();
+
+        if let Some(retry_info) = &self.retry_info {
+            if let Some(delay) = retry_info.retry_delay {
+                let millis = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX);
+                pairs.push(("retry_info.retry_delay_ms", LogValue::U64(millis)));
+            }
+        }
+
+        if let Some(debug_info) = &self.debug_info {
+            pairs.push(("debug_info.detail", LogValue::Str(&debug_info.detail)));
+            pairs.push((
+                "debug_info.stack_entries",
+                LogValue::U64(debug_info.stack_entries.len() as u64),
+            ));
+        }
+
+        if let Some(quota_failure) = &self.quota_failure {
+            pairs.push((
+                "quota_failure.violations",
+                LogValue::U64(quota_failure.violations.len() as u64),
+            ));
+        }
+
+        if let Some(error_info) = &self.error_info {
+            pairs.push(("error_info.reason", LogValue::Str(&error_info.reason)));
+            pairs.push(("error_info.domain", LogValue::Str(&error_info.domain)));
+            pairs.push(("error_info.metadata", LogValue::Map(&error_info.metadata)));
+        }
+
+        if let Some(prec_failure) = &self.precondition_failure {
+            pairs.push((
+                "precondition_failure.violations",
+                LogValue::U64(prec_failure.violations.len() as u64),
+            ));
+        }
+
+        if let Some(bad_request) = &self.bad_request {
+            pairs.push((
+                "bad_request.violations",
+                LogValue::U64(bad_request.field_violations.len() as u64),
+            ));
+        }
+
+        if let Some(req_info) = &self.request_info {
+            pairs.push((
+                "request_info.request_id",
+                LogValue::Str(&req_info.request_id),
+            ));
+            pairs.push((
+                "request_info.serving_data",
+                LogValue::Str(&req_info.serving_data),
+            ));
+        }
+
+        if let Some(res_info) = &self.resource_info {
+            pairs.push((
+                "resource_info.resource_type",
+                LogValue::Str(&res_info.resource_type),
+            ));
+            pairs.push((
+                "resource_info.resource_name",
+                LogValue::Str(&res_info.resource_name),
+            ));
+            pairs.push(("resource_info.owner", LogValue::Str(&res_info.owner)));
+            pairs.push((
+                "resource_info.description",
+                LogValue::Str(&res_info.description),
+            ));
+        }
+
+        if let Some(help) = &self.help {
+            pairs.push(("help.links", LogValue::U64(help.links.len() as u64)));
+        }
+
+        if let Some(loc_message) = &self.localized_message {
+            pairs.push((
+                "localized_message.locale",
+                LogValue::Str(&loc_message.locale),
+            ));
+            pairs.push((
+                "localized_message.message",
+                LogValue::Str(&loc_message.message),
+            ));
+        }
+
+        if !self.unknown_details.is_empty() {
+            pairs.push((
+                "unknown_details",
+                LogValue::U64(self.unknown_details.len() as u64),
+            ));
+        }
+
+        pairs.into_iter()
+    }
+}
+
+/// Allows [`ErrorDetails`] to be attached to `log` records, with the pairs
+/// returned by [`ErrorDetails::log_kv`].
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::ErrorDetails;
+///
+/// let err_details = ErrorDetails::with_bad_request_violation("field", "description");
+///
+/// log::logger().log(
+///     &log::Record::builder()
+///         .level(log::Level::Warn)
+///         .args(format_args!("request rejected"))
+///         .key_values(&err_details)
+///         .build(),
+/// );
+/// ```
+#[cfg(feature = "log")]
+impl log::kv::Source for ErrorDetails {
+    fn visit<'kvs>(
+        &'kvs self,
+        visitor: &mut dyn log::kv::VisitSource<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        for (key, value) in self.log_kv() {
+            let value = match value {
+                LogValue::Str(value) => log::kv::Value::from(value),
+                LogValue::U64(value) => log::kv::Value::from(value),
+                LogValue::Map(map) => log::kv::Value::from_debug(map),
+            };
+
+            visitor.visit_pair(log::kv::Key::from_str(key), value)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use super::super::ErrorDetails;
+
+    fn details() -> ErrorDetails {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("limit".to_string(), "100".to_string());
+
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_millis(1500)))
+            .set_error_info("QUOTA_EXCEEDED", "example.local", metadata)
+            .add_bad_request_violation("field_a", "description_a")
+            .add_bad_request_violation("field_b", "description_b")
+            .set_request_info("request-id", "");
+
+        err_details
+    }
+
+    #[test]
+    fn details_as_key_values() {
+        let err_details = details();
+
+        let formatted: Vec<String> = err_details
+            .log_kv()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect();
+
+        println!("details key-values -> {:?}", formatted);
+
+        let expected = [
+            "retry_info.retry_delay_ms=1500",
+            "error_info.reason=QUOTA_EXCEEDED",
+            "error_info.domain=example.local",
+            "error_info.metadata={\"limit\": \"100\"}",
+            "bad_request.violations=2",
+            "request_info.request_id=request-id",
+            "request_info.serving_data=",
+        ];
+
+        assert!(
+            formatted.eq(&expected),
+            "details key-values differ from expected result"
+        );
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn details_as_log_source() {
+        use log::kv::{Error, Key, Source, Value, VisitSource};
+
+        struct Collect(Vec<String>);
+
+        impl<'kvs> VisitSource<'kvs> for Collect {
+            fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), Error> {
+                self.0.push(format!("{key}={value}"));
+                Ok(())
+            }
+        }
+
+        let err_details = details();
+
+        let mut collect = Collect(Vec::new());
+
+        if let Err(err) = err_details.visit(&mut collect) {
+            panic!("Error visiting details: {:?}", err);
+        }
+
+        println!("details log source -> {:?}", collect.0);
+
+        let expected: Vec<String> = err_details
+            .log_kv()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect();
+
+        assert!(
+            collect.0.eq(&expected),
+            "details log source differs from key-values"
+        );
+
+        assert!(
+            err_details
+                .get(Key::from_str("error_info.reason"))
+                .is_some(),
+            "error_info.reason not found in details log source"
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use super::std_messages::*;
+use super::ErrorDetails;
+
+/// Describes how [`ErrorDetails::merge_with`] resolves conflicts between
+/// single-valued details, such as [`ErrorInfo`] or [`RetryInfo`], that are
+/// set on both sides.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keeps the details already set on the receiving [`ErrorDetails`].
+    #[default]
+    KeepExisting,
+
+    /// Replaces the details set on the receiving [`ErrorDetails`] with the
+    /// merged ones.
+    Overwrite,
+}
+
+impl ErrorDetails {
+    /// Merges another [`ErrorDetails`] struct into this one, keeping the
+    /// existing single-valued details on conflicts. Equivalent to
+    /// [`ErrorDetails::merge_with`] with [`MergeStrategy::KeepExisting`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let mut err_details = ErrorDetails::with_bad_request_violation("field_a", "description");
+    ///
+    /// err_details
+    ///     .merge(ErrorDetails::with_bad_request_violation("field_b", "description"))
+    ///     .merge(ErrorDetails::with_request_info("request-id", ""));
+    ///
+    /// assert_eq!(err_details.bad_request.unwrap().field_violations.len(), 2);
+    /// assert!(err_details.request_info.is_some());
+    /// ```
+    pub fn merge(&mut self, other: ErrorDetails) -> &mut Self {
+        self.merge_with(other, MergeStrategy::KeepExisting)
+    }
+
+    /// Merges another [`ErrorDetails`] struct into this one. Violations, help
+    /// links, stack entries and unknown details are concatenated. For
+    /// single-valued details, non-empty details are preferred over empty or
+    /// missing ones, and conflicts between non-empty details are resolved by
+    /// the given [`MergeStrategy`]. Can be chained with other `.set_` and
+    /// `.add_` [`ErrorDetails`] methods.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tonic_richer_error::{ErrorDetails, MergeStrategy};
+    ///
+    /// let mut err_details = ErrorDetails::with_retry_info(Some(Duration::from_secs(1)));
+    ///
+    /// err_details.merge_with(
+    ///     ErrorDetails::with_retry_info(Some(Duration::from_secs(5))),
+    ///     MergeStrategy::Overwrite,
+    /// );
+    ///
+    /// assert_eq!(
+    ///     err_details.retry_info.unwrap().retry_delay,
+    ///     Some(Duration::from_secs(5))
+    /// );
+    /// ```
+    pub fn merge_with(&mut self, other: ErrorDetails, strategy: MergeStrategy) -> &mut Self {
+        merge_single(
+            &mut self.retry_info,
+            other.retry_info,
+            strategy,
+            RetryInfo::is_empty,
+        );
+
+        merge_list(&mut self.debug_info, other.debug_info, |current, other| {
+            current.stack_entries.extend(other.stack_entries);
+
+            let replace = current.detail.is_empty() || strategy == MergeStrategy::Overwrite;
+
+            if replace && !other.detail.is_empty() {
+                current.detail = other.detail;
+            }
+        });
+
+        merge_list(
+            &mut self.quota_failure,
+            other.quota_failure,
+            |current, other| {
+                current.violations.extend(other.violations);
+            },
+        );
+
+        merge_single(
+            &mut self.error_info,
+            other.error_info,
+            strategy,
+            ErrorInfo::is_empty,
+        );
+
+        merge_list(
+            &mut self.precondition_failure,
+            other.precondition_failure,
+            |current, other| current.violations.extend(other.violations),
+        );
+
+        merge_list(
+            &mut self.bad_request,
+            other.bad_request,
+            |current, other| {
+                current.field_violations.extend(other.field_violations);
+            },
+        );
+
+        merge_single(
+            &mut self.request_info,
+            other.request_info,
+            strategy,
+            RequestInfo::is_empty,
+        );
+
+        merge_single(
+            &mut self.resource_info,
+            other.resource_info,
+            strategy,
+            ResourceInfo::is_empty,
+        );
+
+        merge_list(&mut self.help, other.help, |current, other| {
+            current.links.extend(other.links);
+        });
+
+        merge_single(
+            &mut self.localized_message,
+            other.localized_message,
+            strategy,
+            LocalizedMessage::is_empty,
+        );
+
+        self.unknown_details.extend(other.unknown_details);
+
+        self
+    }
+}
+
+/// Merges single-valued details, preferring non-empty ones.
+fn merge_single<T>(
+    current: &mut Option<T>,
+    other: Option<T>,
+    strategy: MergeStrategy,
+    is_empty: impl Fn(&T) -> bool,
+) {
+    let other = match other {
+        Some(other) if !is_empty(&other) => other,
+        _ => return,
+    };
+
+    let replace = match current {
+        Some(current) if !is_empty(current) => strategy == MergeStrategy::Overwrite,
+        _ => true,
+    };
+
+    if replace {
+        *current = Some(other);
+    }
+}
+
+/// Merges details that hold lists, by extending the current details.
+fn merge_list<T>(current: &mut Option<T>, other: Option<T>, extend: impl FnOnce(&mut T, T)) {
+    let other = match other {
+        Some(other) => other,
+        None => return,
+    };
+
+    match current {
+        Some(current) => extend(current, other),
+        None => *current = Some(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use super::super::ErrorDetails;
+    use super::MergeStrategy;
+
+    #[test]
+    fn merge_details() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_debug_info(vec!["trace2".into()], "")
+            .add_bad_request_violation("field_a", "description a")
+            .set_error_info("SOME_INFO", "example.local", BTreeMap::new())
+            .set_request_info("", "");
+
+        let mut other = ErrorDetails::new();
+
+        other
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .set_debug_info(vec!["trace1".into()], "details")
+            .add_bad_request_violation("field_b", "description b")
+            .set_error_info("OTHER_INFO", "example.local", BTreeMap::new())
+            .set_request_info("request-id", "");
+
+        let mut kept = err_details.clone();
+        kept.merge(other.clone());
+
+        let formatted = format!(
+            "{:?} {:?} {:?} {:?} {:?}",
+            kept.retry_info,
+            kept.debug_info,
+            kept.bad_request,
+            kept.error_info.map(|info| info.reason),
+            kept.request_info
+        );
+
+        println!("merged details -> {formatted}");
+
+        let expected = "Some(RetryInfo { retry_delay: Some(5s) }) Some(DebugInfo { stack_entries: [\"trace2\", \"trace1\"], detail: \"details\" }) Some(BadRequest { field_violations: [FieldViolation { field: \"field_a\", description: \"description a\", reason: \"\", localized_message: None }, FieldViolation { field: \"field_b\", description: \"description b\", reason: \"\", localized_message: None }] }) Some(\"SOME_INFO\") Some(RequestInfo { request_id: \"request-id\", serving_data: \"\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "merged details differ from expected result"
+        );
+
+        err_details.merge_with(other, MergeStrategy::Overwrite);
+
+        let formatted = format!("{:?}", err_details.error_info.map(|info| info.reason));
+
+        assert!(
+            formatted.eq("Some(\"OTHER_INFO\")"),
+            "overwritten ErrorInfo differs from expected result"
+        );
+    }
+}
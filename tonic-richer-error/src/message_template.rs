@@ -0,0 +1,300 @@
+use std::fmt;
+use std::sync::Arc;
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+use super::ErrorDetails;
+
+/// Id of the single message wrapped by a [`MessageTemplate`].
+const MESSAGE_ID: &str = "message";
+
+/// Error returned when a [`MessageTemplate`] cannot be parsed or rendered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TemplateError {
+    /// The template is not a valid [Fluent] pattern.
+    ///
+    /// [Fluent]: https://projectfluent.org/fluent/guide/
+    Parse(String),
+
+    /// The locale is not a valid BCP 47 language identifier.
+    InvalidLocale(String),
+
+    /// The template references missing arguments, or could not be rendered
+    /// for another reason.
+    Render(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Parse(err) => write!(f, "invalid message template: {err}"),
+            TemplateError::InvalidLocale(locale) => write!(f, "invalid locale {locale:?}"),
+            TemplateError::Render(err) => write!(f, "failed to render message template: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Argument of a [`MessageTemplate`]. Numbers can be used to select plural
+/// variants, and percentages are rendered as `45%` or `12.5%`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TemplateArg {
+    /// A string, rendered unchanged.
+    Str(String),
+
+    /// A number, rendered and matched against the plural rules of the locale.
+    Number(f64),
+
+    /// A ratio, such as `0.45`, rendered as a percentage.
+    Percent(f64),
+}
+
+impl TemplateArg {
+    fn to_fluent_value(&self) -> FluentValue<'_> {
+        match self {
+            TemplateArg::Str(value) => FluentValue::from(value.as_str()),
+            TemplateArg::Number(value) => FluentValue::from(*value),
+            TemplateArg::Percent(ratio) => {
+                let percent = format!("{:.2}", ratio * 100.0);
+                let percent = percent.trim_end_matches('0').trim_end_matches('.');
+                FluentValue::from(format!("{percent}%"))
+            }
+        }
+    }
+}
+
+impl From<&str> for TemplateArg {
+    fn from(value: &str) -> Self {
+        TemplateArg::Str(value.into())
+    }
+}
+
+impl From<String> for TemplateArg {
+    fn from(value: String) -> Self {
+        TemplateArg::Str(value)
+    }
+}
+
+macro_rules! number_arg_from {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for TemplateArg {
+                fn from(value: $ty) -> Self {
+                    TemplateArg::Number(value as f64)
+                }
+            }
+        )*
+    };
+}
+
+number_arg_from!(i32, i64, u32, u64, usize, f32, f64);
+
+/// A user-facing message template, with named placeholders and plural
+/// variants, following the [Fluent] pattern syntax, where each variant of a
+/// selector goes in its own line. Rendered with
+/// [`MessageTemplate::render`], or directly into [`crate::LocalizedMessage`]
+/// details with [`ErrorDetails::set_localized_message_template`].
+///
+/// [Fluent]: https://projectfluent.org/fluent/guide/
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::MessageTemplate;
+///
+/// let template = MessageTemplate::new(
+///     "{ $count ->
+///         [one] One item is
+///        *[other] { $count } items are
+///     } out of stock",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     template.render("en-US", [("count", 1)]).unwrap(),
+///     "One item is out of stock"
+/// );
+/// assert_eq!(
+///     template.render("en-US", [("count", 3)]).unwrap(),
+///     "3 items are out of stock"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct MessageTemplate {
+    resource: Arc<FluentResource>,
+}
+
+impl MessageTemplate {
+    /// Parses a new [`MessageTemplate`]. Returns a [`TemplateError`] if the
+    /// template is not a valid Fluent pattern.
+    pub fn new(template: impl Into<String>) -> Result<Self, TemplateError> {
+        let template: String = template.into();
+
+        let source = format!("{MESSAGE_ID} = {}", template.replace('\n', "\n    "));
+
+        let resource = FluentResource::try_new(source).map_err(|(_, errors)| {
+            TemplateError::Parse(format!("{:?}", errors.first().map(|err| &err.kind)))
+        })?;
+
+        Ok(MessageTemplate {
+            resource: Arc::new(resource),
+        })
+    }
+
+    /// Renders the template for a locale, such as `"en-US"`, with the given
+    /// arguments. Plural variants are selected following the plural rules of
+    /// the locale.
+    pub fn render<K, V>(
+        &self,
+        locale: &str,
+        args: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<String, TemplateError>
+    where
+        K: Into<String>,
+        V: Into<TemplateArg>,
+    {
+        let lang_id: LanguageIdentifier = locale
+            .parse()
+            .map_err(|_| TemplateError::InvalidLocale(locale.into()))?;
+
+        let mut bundle = FluentBundle::new(vec![lang_id]);
+        bundle.set_use_isolating(false);
+
+        bundle
+            .add_resource(Arc::clone(&self.resource))
+            .map_err(|errors| TemplateError::Render(format!("{:?}", errors.first())))?;
+
+        let pattern = bundle
+            .get_message(MESSAGE_ID)
+            .and_then(|message| message.value())
+            .ok_or_else(|| TemplateError::Parse("empty template".into()))?;
+
+        let args: Vec<(String, TemplateArg)> = args
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .collect();
+
+        let mut fluent_args = FluentArgs::new();
+        for (key, value) in args.iter() {
+            fluent_args.set(key.as_str(), value.to_fluent_value());
+        }
+
+        let mut errors = Vec::new();
+
+        let rendered = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+
+        if let Some(err) = errors.first() {
+            return Err(TemplateError::Render(err.to_string()));
+        }
+
+        Ok(rendered.into_owned())
+    }
+}
+
+impl ErrorDetails {
+    /// Set [`crate::LocalizedMessage`] details, with the message rendered
+    /// from a [`MessageTemplate`] for the given locale and arguments.
+    /// Returns a [`TemplateError`], leaving the details unchanged, if the
+    /// template could not be rendered.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetails, MessageTemplate, TemplateArg};
+    ///
+    /// let template = MessageTemplate::new("{ $used } of your quota was used").unwrap();
+    ///
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details
+    ///     .set_localized_message_template("en-US", &template, [("used", TemplateArg::Percent(0.95))])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     err_details.localized_message.unwrap().message,
+    ///     "95% of your quota was used"
+    /// );
+    /// ```
+    pub fn set_localized_message_template<K, V>(
+        &mut self,
+        locale: impl Into<String>,
+        template: &MessageTemplate,
+        args: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<&mut Self, TemplateError>
+    where
+        K: Into<String>,
+        V: Into<TemplateArg>,
+    {
+        let locale: String = locale.into();
+
+        let message = template.render(&locale, args)?;
+
+        Ok(self.set_localized_message(locale, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::ErrorDetails;
+    use super::{MessageTemplate, TemplateArg, TemplateError};
+
+    #[test]
+    fn render_message_templates() {
+        let template = match MessageTemplate::new(
+            "{ $count ->\n    [one] { $name } tem um item\n   *[other] { $name } tem { $count } itens\n} ({ $share } do estoque)",
+        ) {
+            Ok(template) => template,
+            Err(err) => panic!("Error parsing template: {:?}", err),
+        };
+
+        let mut err_details = ErrorDetails::new();
+
+        let result = err_details.set_localized_message_template(
+            "pt-BR",
+            &template,
+            [
+                ("count", TemplateArg::from(2)),
+                ("name", TemplateArg::from("Ana")),
+                ("share", TemplateArg::Percent(0.125)),
+            ],
+        );
+
+        if let Err(err) = result {
+            panic!("Error rendering template: {:?}", err);
+        }
+
+        let formatted = format!("{:?}", err_details.localized_message);
+
+        println!("rendered template -> {formatted}");
+
+        let expected = "Some(LocalizedMessage { locale: \"pt-BR\", message: \"Ana tem 2 itens (12.5% do estoque)\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "rendered template differs from expected result"
+        );
+
+        let missing_arg = template.render("pt-BR", [("count", 1)]);
+
+        assert!(
+            matches!(missing_arg, Err(TemplateError::Render(_))),
+            "missing argument was not reported"
+        );
+
+        let invalid_locale = template.render("not a locale", [("count", 1)]);
+
+        assert!(
+            invalid_locale == Err(TemplateError::InvalidLocale("not a locale".into())),
+            "invalid locale was not reported"
+        );
+
+        assert!(
+            matches!(
+                MessageTemplate::new("{ $count -> }"),
+                Err(TemplateError::Parse(_))
+            ),
+            "invalid template was not rejected"
+        );
+    }
+}
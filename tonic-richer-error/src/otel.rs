@@ -0,0 +1,150 @@
+use opentelemetry::trace::Span;
+use opentelemetry::KeyValue;
+use tonic::Status;
+
+use super::{ErrorDetails, WithErrorDetails};
+
+impl ErrorDetails {
+    /// Returns the OpenTelemetry attributes describing the error details,
+    /// for cross-service error analytics:
+    /// - `error.reason` and `error.domain`, from [`crate::ErrorInfo`]
+    /// - `error.request_id`, from [`crate::RequestInfo`]
+    /// - `error.retry_delay_ms`, from [`crate::RetryInfo`]
+    /// - `error.bad_request.violation_count`,
+    ///   `error.quota_failure.violation_count` and
+    ///   `error.precondition_failure.violation_count`
+    ///
+    /// Attributes of absent details are omitted.
+    /// # Examples
+    ///
+    /// ```
+    /// use opentelemetry::KeyValue;
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let err_details = ErrorDetails::with_bad_request_violation("field", "description");
+    ///
+    /// assert_eq!(
+    ///     err_details.otel_attributes(),
+    ///     vec![KeyValue::new("error.bad_request.violation_count", 1)],
+    /// );
+    /// ```
+    pub fn otel_attributes(&self) -> Vec<KeyValue> {
+        let mut attributes = Vec::new();
+
+        if let Some(error_info) = &self.error_info {
+            attributes.push(KeyValue::new("error.reason", error_info.reason.clone()));
+            attributes.push(KeyValue::new("error.domain", error_info.domain.clone()));
+        }
+
+        if let Some(req_info) = &self.request_info {
+            attributes.push(KeyValue::new(
+                "error.request_id",
+                req_info.request_id.clone(),
+            ));
+        }
+
+        if let Some(delay) = self.retry_info.as_ref().and_then(|r| r.retry_delay) {
+            let millis = i64::try_from(delay.as_millis()).unwrap_or(i64::MAX);
+            attributes.push(KeyValue::new("error.retry_delay_ms", millis));
+        }
+
+        let counts = [
+            (
+                "error.bad_request.violation_count",
+                self.bad_request.as_ref().map(|d| d.field_violations.len()),
+            ),
+            (
+                "error.quota_failure.violation_count",
+                self.quota_failure.as_ref().map(|d| d.violations.len()),
+            ),
+            (
+                "error.precondition_failure.violation_count",
+                self.precondition_failure
+                    .as_ref()
+                    .map(|d| d.violations.len()),
+            ),
+        ];
+
+        for (key, count) in counts {
+            if let Some(count) = count {
+                attributes.push(KeyValue::new(key, count as i64));
+            }
+        }
+
+        attributes
+    }
+}
+
+/// Records a `tonic::Status` on an OpenTelemetry span. Sets the
+/// `rpc.grpc.status_code` attribute, the attributes returned by
+/// [`ErrorDetails::otel_attributes`] and, for codes other than `Code::Ok`,
+/// an error span status with the status message.
+/// # Examples
+///
+/// ```
+/// use opentelemetry::global;
+/// use opentelemetry::trace::Tracer;
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{record_error_details, ErrorDetails, WithErrorDetails};
+///
+/// let mut span = global::tracer("example").start("call");
+///
+/// let status = Status::with_error_details(
+///     Code::InvalidArgument,
+///     "bad request",
+///     ErrorDetails::with_bad_request_violation("field", "description"),
+/// );
+///
+/// record_error_details(&mut span, &status);
+/// ```
+pub fn record_error_details<S: Span>(span: &mut S, status: &Status) {
+    span.set_attribute(KeyValue::new("rpc.grpc.status_code", status.code() as i64));
+
+    span.set_attributes(status.error_details().otel_attributes());
+
+    if status.code() != tonic::Code::Ok {
+        span.set_status(opentelemetry::trace::Status::error(
+            status.message().to_string(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use super::super::ErrorDetails;
+
+    #[test]
+    fn details_as_otel_attributes() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_millis(1500)))
+            .set_error_info("QUOTA_EXCEEDED", "example.local", Default::default())
+            .add_quota_failure_violation("clientip:127.0.0.1", "rate exceeded")
+            .set_request_info("request-id", "");
+
+        let formatted: Vec<String> = err_details
+            .otel_attributes()
+            .iter()
+            .map(|kv| format!("{}={}", kv.key, kv.value))
+            .collect();
+
+        println!("otel attributes -> {:?}", formatted);
+
+        let expected = [
+            "error.reason=QUOTA_EXCEEDED",
+            "error.domain=example.local",
+            "error.request_id=request-id",
+            "error.retry_delay_ms=1500",
+            "error.quota_failure.violation_count=1",
+        ];
+
+        assert!(
+            formatted.eq(&expected),
+            "otel attributes differ from expected result"
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::{fmt, fs, io, path::Path};
+
+use serde::Deserialize;
+use tonic::Status;
+
+use super::std_messages::LocalizedMessage;
+use super::{ErrorDetails, WithErrorDetails};
+
+/// Describes how the values of an [`OverrideEntry`] are combined with the
+/// details produced by a service.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OverrideMode {
+    /// Configured help links are appended to the existing ones, and the
+    /// configured localized message is only used if none is set.
+    #[default]
+    Augment,
+
+    /// Configured help links and localized message replace the existing ones.
+    Replace,
+}
+
+/// Used at the `links` field of the [`OverrideEntry`] struct.
+#[derive(Clone, Debug, Deserialize)]
+pub struct OverrideLink {
+    /// Description of what the link offers.
+    pub description: String,
+
+    /// URL of the link.
+    pub url: String,
+}
+
+/// Used at the `localized_message` field of the [`OverrideEntry`] struct.
+#[derive(Clone, Debug, Deserialize)]
+pub struct OverrideLocalizedMessage {
+    /// Locale used following the specification defined in [BCP 47].
+    ///
+    /// [BCP 47]: https://www.rfc-editor.org/rfc/bcp/bcp47.txt
+    pub locale: String,
+
+    /// Message for the user, in the given locale.
+    pub message: String,
+}
+
+/// Overrides configured for a single `ErrorInfo` reason.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct OverrideEntry {
+    /// If set, the entry only applies to [`crate::ErrorInfo`] details of this
+    /// domain.
+    pub domain: Option<String>,
+
+    /// How help links and localized message are combined with the existing
+    /// details.
+    pub mode: OverrideMode,
+
+    /// Replaces the `tonic::Status` message, if set.
+    pub message: Option<String>,
+
+    /// Help links to attach to the details.
+    pub links: Vec<OverrideLink>,
+
+    /// Localized message to attach to the details.
+    pub localized_message: Option<OverrideLocalizedMessage>,
+}
+
+/// Error returned when loading [`MessageOverrides`] fails.
+#[derive(Debug)]
+pub enum OverridesError {
+    /// The configuration file could not be read.
+    Io(io::Error),
+
+    /// The configuration is not valid TOML, or does not match the expected
+    /// format.
+    Parse(toml::de::Error),
+}
+
+impl fmt::Display for OverridesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverridesError::Io(err) => write!(f, "failed to read overrides: {err}"),
+            OverridesError::Parse(err) => write!(f, "failed to parse overrides: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for OverridesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OverridesError::Io(err) => Some(err),
+            OverridesError::Parse(err) => Some(err),
+        }
+    }
+}
+
+/// Deployment-specific overrides for the user-facing content of error
+/// details, keyed by `ErrorInfo` reason. Allows operators to tune messages,
+/// help links and localized text at startup, without recompiling services.
+///
+/// The expected configuration format (usually an `overrides.toml` file) is:
+///
+/// ```toml
+/// [reasons.OUT_OF_STOCK]
+/// domain = "shop.example.com"
+/// mode = "replace"
+/// message = "item is currently unavailable"
+/// links = [
+///     { description = "stock policy", url = "https://example.com/stock" },
+/// ]
+/// localized_message = { locale = "en-US", message = "Sorry, sold out!" }
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct MessageOverrides {
+    /// Overrides entries, keyed by `ErrorInfo` reason.
+    #[serde(default)]
+    pub reasons: HashMap<String, OverrideEntry>,
+}
+
+impl MessageOverrides {
+    /// Parses [`MessageOverrides`] from a TOML string.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::MessageOverrides;
+    ///
+    /// let overrides = MessageOverrides::from_toml_str(r#"
+    ///     [reasons.OUT_OF_STOCK]
+    ///     message = "item is currently unavailable"
+    /// "#).unwrap();
+    /// ```
+    pub fn from_toml_str(config: &str) -> Result<Self, OverridesError> {
+        toml::from_str(config).map_err(OverridesError::Parse)
+    }
+
+    /// Reads and parses [`MessageOverrides`] from a TOML file.
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tonic_richer_error::MessageOverrides;
+    ///
+    /// let overrides = MessageOverrides::from_file("overrides.toml").unwrap();
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, OverridesError> {
+        let config = fs::read_to_string(path).map_err(OverridesError::Io)?;
+        MessageOverrides::from_toml_str(&config)
+    }
+
+    /// Returns the [`OverrideEntry`] matching the `ErrorInfo` details, if any.
+    pub fn entry_for(&self, details: &ErrorDetails) -> Option<&OverrideEntry> {
+        let error_info = details.error_info.as_ref()?;
+        let entry = self.reasons.get(&error_info.reason)?;
+
+        match &entry.domain {
+            Some(domain) if domain != &error_info.domain => None,
+            _ => Some(entry),
+        }
+    }
+
+    /// Applies the matching [`OverrideEntry`] help links and localized
+    /// message to an [`ErrorDetails`] struct. Returns `true` if an entry
+    /// matched.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use tonic_richer_error::{ErrorDetails, MessageOverrides};
+    ///
+    /// let overrides = MessageOverrides::from_toml_str(r#"
+    ///     [reasons.OUT_OF_STOCK]
+    ///     localized_message = { locale = "en-US", message = "Sold out!" }
+    /// "#).unwrap();
+    ///
+    /// let mut err_details =
+    ///     ErrorDetails::with_error_info("OUT_OF_STOCK", "example.com", BTreeMap::new());
+    ///
+    /// assert!(overrides.apply(&mut err_details));
+    /// assert!(err_details.localized_message.is_some());
+    /// ```
+    pub fn apply(&self, details: &mut ErrorDetails) -> bool {
+        let entry = match self.entry_for(details) {
+            Some(entry) => entry,
+            None => return false,
+        };
+
+        if entry.mode == OverrideMode::Replace && !entry.links.is_empty() {
+            details.help = None;
+        }
+
+        for link in &entry.links {
+            details.add_help_link(&link.description, &link.url);
+        }
+
+        if let Some(loc_message) = &entry.localized_message {
+            if entry.mode == OverrideMode::Replace || details.localized_message.is_none() {
+                details.localized_message = Some(LocalizedMessage::new(
+                    &loc_message.locale,
+                    &loc_message.message,
+                ));
+            }
+        }
+
+        true
+    }
+
+    /// Applies the matching [`OverrideEntry`] to a `tonic::Status`, replacing
+    /// its message if configured, and its details as described in
+    /// [`MessageOverrides::apply`]. The status metadata is kept. Statuses
+    /// without matching `ErrorInfo` details are returned unchanged.
+    pub fn apply_to_status(&self, status: Status) -> Status {
+        let mut details = match status.try_error_details() {
+            Ok(Some(details)) => details,
+            _ => return status,
+        };
+
+        let message = match self.entry_for(&details) {
+            Some(entry) => entry
+                .message
+                .clone()
+                .unwrap_or_else(|| status.message().to_string()),
+            None => return status,
+        };
+
+        self.apply(&mut details);
+
+        Status::with_error_details_and_metadata(
+            status.code(),
+            message,
+            details,
+            status.metadata().clone(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use tonic::{metadata::MetadataMap, Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::MessageOverrides;
+
+    const CONFIG: &str = r#"
+        [reasons.OUT_OF_STOCK]
+        domain = "shop.example.com"
+        mode = "replace"
+        message = "item is currently unavailable"
+        links = [{ description = "stock policy", url = "https://example.com/stock" }]
+        localized_message = { locale = "en-US", message = "Sorry, sold out!" }
+
+        [reasons.QUOTA]
+        links = [{ description = "quotas", url = "https://example.com/quotas" }]
+        localized_message = { locale = "en-US", message = "Slow down" }
+    "#;
+
+    #[test]
+    fn apply_overrides() {
+        let overrides = match MessageOverrides::from_toml_str(CONFIG) {
+            Ok(overrides) => overrides,
+            Err(err) => panic!("Error parsing overrides: {:?}", err),
+        };
+
+        let mut err_details = ErrorDetails::new();
+        err_details
+            .set_error_info("QUOTA", "shop.example.com", BTreeMap::new())
+            .add_help_link("original", "https://example.com/original")
+            .set_localized_message("en-US", "original message");
+
+        assert!(
+            overrides.apply(&mut err_details),
+            "QUOTA entry did not match"
+        );
+
+        let formatted = format!("{:?}", (err_details.help, err_details.localized_message));
+
+        println!("augmented details -> {formatted}");
+
+        let expected = "(Some(Help { links: [HelpLink { description: \"original\", url: \"https://example.com/original\" }, HelpLink { description: \"quotas\", url: \"https://example.com/quotas\" }] }), Some(LocalizedMessage { locale: \"en-US\", message: \"original message\" }))";
+
+        assert!(
+            formatted.eq(expected),
+            "augmented details differ from expected result"
+        );
+
+        let mut err_details = ErrorDetails::new();
+        err_details
+            .set_error_info("OUT_OF_STOCK", "shop.example.com", BTreeMap::new())
+            .add_help_link("original", "https://example.com/original");
+
+        let mut metadata = MetadataMap::new();
+        metadata.insert("x-request-id", "req-1".parse().unwrap());
+
+        let status = Status::with_error_details_and_metadata(
+            Code::NotFound,
+            "not found",
+            err_details,
+            metadata,
+        );
+        let status = overrides.apply_to_status(status);
+
+        assert!(
+            status.message() == "item is currently unavailable",
+            "status message was not replaced"
+        );
+
+        assert!(
+            status
+                .metadata()
+                .get("x-request-id")
+                .is_some_and(|v| v == "req-1"),
+            "status metadata was not kept"
+        );
+
+        let formatted = format!("{:?}", status.get_details_help());
+
+        println!("replaced help -> {formatted}");
+
+        let expected = "Some(Help { links: [HelpLink { description: \"stock policy\", url: \"https://example.com/stock\" }] })";
+
+        assert!(
+            formatted.eq(expected),
+            "replaced help differs from expected result"
+        );
+
+        let mut err_details =
+            ErrorDetails::with_error_info("OUT_OF_STOCK", "other.example.com", BTreeMap::new());
+
+        assert!(
+            !overrides.apply(&mut err_details),
+            "entry matched ErrorInfo from a different domain"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+
+use tonic::Code;
+
+use super::ErrorDetails;
+
+/// Error returned when error details are paired with a `tonic::Code` they
+/// are not meant to be used with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairingError {
+    /// The code the details were paired with.
+    pub code: Code,
+
+    /// Name of the offending error message type, like `"BadRequest"`. Empty
+    /// if the code is `Code::Ok`, which cannot carry any error details.
+    pub detail: &'static str,
+
+    /// Codes the offending error message can be used with.
+    pub allowed: &'static [Code],
+}
+
+impl fmt::Display for PairingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.detail.is_empty() {
+            return write!(f, "error details cannot be used with code {:?}", self.code);
+        }
+
+        write!(
+            f,
+            "{} details cannot be used with code {:?}, expected one of {:?}",
+            self.detail, self.code, self.allowed
+        )
+    }
+}
+
+impl std::error::Error for PairingError {}
+
+impl ErrorDetails {
+    /// Checks if the error details can be used with the given `tonic::Code`,
+    /// following the code and detail pairings of [AIP-193]:
+    /// - [`BadRequest`](crate::BadRequest) details: `Code::InvalidArgument`
+    ///   or `Code::OutOfRange`
+    /// - [`PreconditionFailure`](crate::PreconditionFailure) details:
+    ///   `Code::FailedPrecondition`
+    /// - [`QuotaFailure`](crate::QuotaFailure) details:
+    ///   `Code::ResourceExhausted`
+    /// - [`RetryInfo`](crate::RetryInfo) details: `Code::Unavailable`,
+    ///   `Code::ResourceExhausted` or `Code::Aborted`
+    ///
+    /// Every present detail is checked, in the order above, so
+    /// [`RetryInfo`](crate::RetryInfo) and [`QuotaFailure`](crate::QuotaFailure)
+    /// details can be paired with `Code::ResourceExhausted`, while
+    /// [`RetryInfo`](crate::RetryInfo) and [`BadRequest`](crate::BadRequest)
+    /// details cannot share any code. Remaining error messages can be used
+    /// with any code, and no details can be used with `Code::Ok`. The first
+    /// invalid pairing is returned as a [`PairingError`].
+    ///
+    /// [AIP-193]: https://google.aip.dev/193
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::Code;
+    /// use tonic_richer_error::{ErrorDetails};
+    ///
+    /// let err_details = ErrorDetails::with_bad_request_violation("field", "description");
+    ///
+    /// assert!(err_details.check_code_pairing(Code::InvalidArgument).is_ok());
+    /// assert!(err_details.check_code_pairing(Code::NotFound).is_err());
+    /// ```
+    pub fn check_code_pairing(&self, code: Code) -> Result<(), PairingError> {
+        if code == Code::Ok && has_details(self) {
+            return Err(PairingError {
+                code,
+                detail: "",
+                allowed: &[],
+            });
+        }
+
+        let pairings: [(&'static str, bool, &'static [Code]); 4] = [
+            (
+                "BadRequest",
+                self.bad_request.is_some(),
+                &[Code::InvalidArgument, Code::OutOfRange],
+            ),
+            (
+                "PreconditionFailure",
+                self.precondition_failure.is_some(),
+                &[Code::FailedPrecondition],
+            ),
+            (
+                "QuotaFailure",
+                self.quota_failure.is_some(),
+                &[Code::ResourceExhausted],
+            ),
+            (
+                "RetryInfo",
+                self.retry_info.is_some(),
+                &[Code::Unavailable, Code::ResourceExhausted, Code::Aborted],
+            ),
+        ];
+
+        let invalid = pairings
+            .into_iter()
+            .find(|(_, is_present, allowed)| *is_present && !allowed.contains(&code));
+
+        match invalid {
+            Some((detail, _, allowed)) => Err(PairingError {
+                code,
+                detail,
+                allowed,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+fn has_details(details: &ErrorDetails) -> bool {
+    details.retry_info.is_some()
+        || details.debug_info.is_some()
+        || details.quota_failure.is_some()
+        || details.error_info.is_some()
+        || details.precondition_failure.is_some()
+        || details.bad_request.is_some()
+        || details.request_info.is_some()
+        || details.resource_info.is_some()
+        || details.help.is_some()
+        || details.localized_message.is_some()
+        || !details.unknown_details.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+
+    #[test]
+    fn strict_pairings() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .add_quota_failure_violation("subject", "description")
+            .set_localized_message("en-US", "message for the user");
+
+        let result = Status::with_error_details_strict(
+            Code::ResourceExhausted,
+            "quota exceeded",
+            err_details.clone(),
+        );
+
+        assert!(result.is_ok(), "valid pairing was rejected");
+
+        let err = match Status::with_error_details_strict(
+            Code::Unavailable,
+            "unavailable",
+            err_details,
+        ) {
+            Ok(_) => panic!("QuotaFailure paired with Unavailable was accepted"),
+            Err(err) => err,
+        };
+
+        println!("pairing error -> {err}");
+
+        let expected =
+            "QuotaFailure details cannot be used with code Unavailable, expected one of [ResourceExhausted]";
+
+        assert!(
+            err.to_string().eq(expected),
+            "pairing error differs from expected result"
+        );
+
+        let mut mixed_details = vec![
+            ErrorDetails::with_bad_request_violation("field", "description"),
+            ErrorDetails::with_precondition_failure_violation("TOS", "subject", "description"),
+            ErrorDetails::with_quota_failure_violation("subject", "description"),
+            ErrorDetails::with_retry_info(Some(Duration::from_secs(5))),
+        ];
+
+        mixed_details[0].set_localized_message("en-US", "message for the user");
+        mixed_details[1].add_help_link("terms", "https://example.local/terms");
+        mixed_details[2].set_retry_info(Some(Duration::from_secs(5)));
+
+        for err_details in mixed_details {
+            let code = err_details.inferred_code();
+
+            assert!(
+                err_details.check_code_pairing(code).is_ok(),
+                "inferred code {code:?} was rejected for mixed details"
+            );
+        }
+
+        let mut err_details = ErrorDetails::with_bad_request_violation("field", "description");
+        err_details.set_retry_info(Some(Duration::from_secs(5)));
+
+        let err = match err_details.check_code_pairing(Code::InvalidArgument) {
+            Ok(()) => panic!("RetryInfo with BadRequest paired with InvalidArgument was accepted"),
+            Err(err) => err,
+        };
+
+        println!("mixed pairing error -> {err}");
+
+        let expected = "RetryInfo details cannot be used with code InvalidArgument, expected one of [Unavailable, ResourceExhausted, Aborted]";
+
+        assert!(
+            err.to_string().eq(expected),
+            "mixed pairing error differs from expected result"
+        );
+
+        assert!(
+            err_details.check_code_pairing(Code::Unavailable).is_err(),
+            "BadRequest with RetryInfo paired with Unavailable was accepted"
+        );
+
+        let err_details = ErrorDetails::with_request_info("request-id", "some-request-data");
+
+        assert!(
+            err_details.check_code_pairing(Code::Ok).is_err(),
+            "details paired with Ok were accepted"
+        );
+    }
+}
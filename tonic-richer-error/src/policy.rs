@@ -0,0 +1,432 @@
+use std::sync::RwLock;
+
+use super::{ErrorDetail, ErrorDetails, ErrorInfo};
+
+#[cfg(feature = "redaction")]
+use super::RequestInfo;
+
+#[cfg(feature = "url")]
+use super::HelpLink;
+
+static GLOBAL_POLICY: RwLock<ErrorDetailsPolicy> = RwLock::new(ErrorDetailsPolicy::new());
+
+/// Describes error details that must never be sent to clients, like stack
+/// traces in production builds. Once installed with
+/// [`ErrorDetailsPolicy::install`], the policy is applied by
+/// [`crate::WithErrorDetails::with_error_details`] and
+/// [`crate::WithErrorDetails::with_error_details_vec`] before encoding the
+/// details.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::ErrorDetailsPolicy;
+///
+/// if !cfg!(debug_assertions) {
+///     ErrorDetailsPolicy::new()
+///         .redact_debug_info()
+///         .deny_metadata_key("internalHost")
+///         .install();
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ErrorDetailsPolicy {
+    redact_debug_info: bool,
+    denied_metadata_keys: Vec<String>,
+    #[cfg(feature = "redaction")]
+    denied_metadata_patterns: Vec<regex::Regex>,
+    #[cfg(feature = "redaction")]
+    hash_serving_data: bool,
+    #[cfg(feature = "url")]
+    require_valid_help_links: bool,
+}
+
+impl ErrorDetailsPolicy {
+    /// Creates a new [`ErrorDetailsPolicy`], that does not change any
+    /// details.
+    pub const fn new() -> Self {
+        ErrorDetailsPolicy {
+            redact_debug_info: false,
+            denied_metadata_keys: Vec::new(),
+            #[cfg(feature = "redaction")]
+            denied_metadata_patterns: Vec::new(),
+            #[cfg(feature = "redaction")]
+            hash_serving_data: false,
+            #[cfg(feature = "url")]
+            require_valid_help_links: false,
+        }
+    }
+
+    /// Removes [`crate::DebugInfo`] details.
+    pub fn redact_debug_info(mut self) -> Self {
+        self.redact_debug_info = true;
+        self
+    }
+
+    /// Removes the given key from [`crate::ErrorInfo`] metadata.
+    pub fn deny_metadata_key(mut self, key: impl Into<String>) -> Self {
+        self.denied_metadata_keys.push(key.into());
+        self
+    }
+
+    /// Removes the keys matching the given regular expression from
+    /// [`crate::ErrorInfo`] metadata.
+    /// # Examples
+    ///
+    /// ```
+    /// use regex::Regex;
+    /// use tonic_richer_error::{ErrorDetails, ErrorDetailsPolicy};
+    ///
+    /// let policy = ErrorDetailsPolicy::new()
+    ///     .deny_metadata_keys_matching(Regex::new("^internal").unwrap());
+    ///
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details.set_error_info(
+    ///     "SOME_INFO",
+    ///     "example.local",
+    ///     [("internalHost".to_string(), "db-1".to_string())].into(),
+    /// );
+    ///
+    /// policy.apply(&mut err_details);
+    ///
+    /// assert!(err_details.error_info.unwrap().metadata.is_empty());
+    /// ```
+    #[cfg(feature = "redaction")]
+    pub fn deny_metadata_keys_matching(mut self, pattern: regex::Regex) -> Self {
+        self.denied_metadata_patterns.push(pattern);
+        self
+    }
+
+    /// Replaces [`crate::RequestInfo`] `serving_data` with its SHA-256 hash,
+    /// hex encoded, so it can still be correlated with server logs without
+    /// being disclosed.
+    #[cfg(feature = "redaction")]
+    pub fn hash_serving_data(mut self) -> Self {
+        self.hash_serving_data = true;
+        self
+    }
+
+    /// Makes [`ErrorDetails::add_help_link`] discard links whose URL is not a
+    /// well-formed `https` URL, as described in [`HelpLink::parse`]. A
+    /// warning is emitted for each discarded link if the `tracing` feature is
+    /// enabled.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorDetailsPolicy;
+    ///
+    /// let policy = ErrorDetailsPolicy::new().require_valid_help_links();
+    ///
+    /// assert!(!policy.is_noop());
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn require_valid_help_links(mut self) -> Self {
+        self.require_valid_help_links = true;
+        self
+    }
+
+    /// Installs the policy globally, replacing the previously installed one.
+    pub fn install(self) {
+        match GLOBAL_POLICY.write() {
+            Ok(mut policy) => *policy = self,
+            Err(poisoned) => *poisoned.into_inner() = self,
+        }
+    }
+
+    /// Returns a copy of the globally installed policy.
+    pub fn global() -> Self {
+        match GLOBAL_POLICY.read() {
+            Ok(policy) => policy.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Returns `true` if the policy does not change any details.
+    pub fn is_noop(&self) -> bool {
+        #[cfg(feature = "url")]
+        if self.require_valid_help_links {
+            return false;
+        }
+
+        #[cfg(feature = "redaction")]
+        if self.hash_serving_data || !self.denied_metadata_patterns.is_empty() {
+            return false;
+        }
+
+        !self.redact_debug_info && self.denied_metadata_keys.is_empty()
+    }
+
+    /// Returns `true` if the policy allows adding the given link through
+    /// [`ErrorDetails::add_help_link`].
+    #[cfg(feature = "url")]
+    pub(crate) fn allows_help_link(&self, link: &HelpLink) -> bool {
+        !self.require_valid_help_links || super::ok_or_warn(link.validate(), "help link").is_some()
+    }
+
+    /// Applies the policy to an [`ErrorDetails`] struct.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetails, ErrorDetailsPolicy};
+    ///
+    /// let policy = ErrorDetailsPolicy::new().redact_debug_info();
+    ///
+    /// let mut err_details = ErrorDetails::with_debug_info(vec![], "details");
+    ///
+    /// policy.apply(&mut err_details);
+    ///
+    /// assert!(err_details.debug_info.is_none());
+    /// ```
+    pub fn apply(&self, details: &mut ErrorDetails) {
+        if self.redact_debug_info {
+            details.debug_info = None;
+        }
+
+        if let Some(error_info) = &mut details.error_info {
+            self.apply_error_info(error_info);
+        }
+
+        #[cfg(feature = "redaction")]
+        if let Some(req_info) = &mut details.request_info {
+            self.apply_request_info(req_info);
+        }
+    }
+
+    /// Applies the policy to a vector of [`ErrorDetail`] enums.
+    pub fn apply_vec(&self, details: &mut Vec<ErrorDetail>) {
+        if self.redact_debug_info {
+            details.retain(|detail| !matches!(detail, ErrorDetail::DebugInfo(_)));
+        }
+
+        for detail in details.iter_mut() {
+            match detail {
+                ErrorDetail::ErrorInfo(error_info) => self.apply_error_info(error_info),
+                #[cfg(feature = "redaction")]
+                ErrorDetail::RequestInfo(req_info) => self.apply_request_info(req_info),
+                _ => {}
+            }
+        }
+    }
+
+    /// Removes the denied keys from [`ErrorInfo`] metadata.
+    fn apply_error_info(&self, error_info: &mut ErrorInfo) {
+        for key in self.denied_metadata_keys.iter() {
+            error_info.metadata.remove(key);
+        }
+
+        #[cfg(feature = "redaction")]
+        if !self.denied_metadata_patterns.is_empty() {
+            error_info.metadata.retain(|key, _| {
+                !self
+                    .denied_metadata_patterns
+                    .iter()
+                    .any(|pattern| pattern.is_match(key))
+            });
+        }
+    }
+
+    /// Hashes [`RequestInfo`] `serving_data`, if required.
+    #[cfg(feature = "redaction")]
+    fn apply_request_info(&self, req_info: &mut RequestInfo) {
+        use sha2::{Digest, Sha256};
+
+        if !self.hash_serving_data || req_info.serving_data.is_empty() {
+            return;
+        }
+
+        let digest = Sha256::digest(req_info.serving_data.as_bytes());
+
+        req_info.serving_data = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    }
+}
+
+/// Applies the globally installed policy to an [`ErrorDetails`] struct.
+pub(crate) fn apply_global(details: &mut ErrorDetails) {
+    let policy = match GLOBAL_POLICY.read() {
+        Ok(policy) => policy,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    if !policy.is_noop() {
+        policy.apply(details);
+    }
+}
+
+/// Applies the globally installed policy to a vector of [`ErrorDetail`]
+/// enums.
+pub(crate) fn apply_global_vec(details: &mut Vec<ErrorDetail>) {
+    let policy = match GLOBAL_POLICY.read() {
+        Ok(policy) => policy,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    if !policy.is_noop() {
+        policy.apply_vec(details);
+    }
+}
+
+/// Returns `true` if the globally installed policy allows adding the given
+/// link through [`ErrorDetails::add_help_link`].
+#[cfg(feature = "url")]
+pub(crate) fn allows_help_link_global(link: &HelpLink) -> bool {
+    match GLOBAL_POLICY.read() {
+        Ok(policy) => policy.allows_help_link(link),
+        Err(poisoned) => poisoned.into_inner().allows_help_link(link),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+
+    use super::super::{DebugInfo, ErrorDetail, ErrorDetails, ErrorInfo};
+
+    #[cfg(feature = "url")]
+    use super::super::HelpLink;
+
+    #[cfg(feature = "redaction")]
+    use super::super::RequestInfo;
+    use super::ErrorDetailsPolicy;
+
+    #[test]
+    fn apply_policy() {
+        let policy = ErrorDetailsPolicy::new()
+            .redact_debug_info()
+            .deny_metadata_key("internalHost");
+
+        let mut metadata = BTreeMap::new();
+        metadata.insert("internalHost".to_string(), "db-1.internal".to_string());
+        metadata.insert("limit".to_string(), "100".to_string());
+
+        let mut err_details = ErrorDetails::new();
+        err_details
+            .set_debug_info(vec!["trace 1".into()], "details")
+            .set_error_info("SOME_INFO", "example.local", metadata.clone());
+
+        policy.apply(&mut err_details);
+
+        let formatted = format!("{:?} {:?}", err_details.debug_info, err_details.error_info);
+
+        println!("details after policy -> {formatted}");
+
+        let expected = "None Some(ErrorInfo { reason: \"SOME_INFO\", domain: \"example.local\", metadata: {\"limit\": \"100\"} })";
+
+        assert!(
+            formatted.eq(expected),
+            "details after policy differ from expected result"
+        );
+
+        let mut err_details_vec: Vec<ErrorDetail> = vec![
+            DebugInfo::new(vec!["trace 1".into()], "details").into(),
+            ErrorInfo::new("SOME_INFO", "example.local", metadata).into(),
+        ];
+
+        policy.apply_vec(&mut err_details_vec);
+
+        let formatted = format!("{:?}", err_details_vec);
+
+        let expected = "[ErrorInfo(ErrorInfo { reason: \"SOME_INFO\", domain: \"example.local\", metadata: {\"limit\": \"100\"} })]";
+
+        assert!(
+            formatted.eq(expected),
+            "details vec after policy differs from expected result"
+        );
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn require_valid_help_links() {
+        let links = [
+            HelpLink::new("docs", "https://docs.example.local/errors"),
+            HelpLink::new("docs", "http://docs.example.local/errors"),
+            HelpLink::new("docs", "docs.example.local/errors"),
+        ];
+
+        let results: Vec<(bool, bool)> = links
+            .iter()
+            .map(|link| {
+                (
+                    ErrorDetailsPolicy::new().allows_help_link(link),
+                    ErrorDetailsPolicy::new()
+                        .require_valid_help_links()
+                        .allows_help_link(link),
+                )
+            })
+            .collect();
+
+        println!("help link policy results -> {:?}", results);
+
+        assert!(
+            results.eq(&[(true, true), (true, false), (true, false)]),
+            "help link policy results differ from expected result"
+        );
+
+        let errors: Vec<String> = links[1..]
+            .iter()
+            .filter_map(|link| link.validate().err())
+            .map(|err| err.to_string())
+            .collect();
+
+        println!("help link errors -> {:?}", errors);
+
+        assert!(
+            errors.eq(&[
+                "help link URL `http://docs.example.local/errors` does not use https",
+                "malformed help link URL `docs.example.local/errors`: relative URL without a base",
+            ]),
+            "help link errors differ from expected result"
+        );
+    }
+
+    #[cfg(feature = "redaction")]
+    #[test]
+    fn apply_redaction_rules() {
+        let policy = ErrorDetailsPolicy::new()
+            .deny_metadata_keys_matching(regex::Regex::new("(?i)token|^internal").unwrap())
+            .hash_serving_data();
+
+        let mut metadata = BTreeMap::new();
+        metadata.insert("internalHost".to_string(), "db-1.internal".to_string());
+        metadata.insert("authToken".to_string(), "secret".to_string());
+        metadata.insert("limit".to_string(), "100".to_string());
+
+        let mut err_details = ErrorDetails::new();
+        err_details
+            .set_error_info("SOME_INFO", "example.local", metadata.clone())
+            .set_request_info("request-id", "user=alice");
+
+        policy.apply(&mut err_details);
+
+        let formatted = format!(
+            "{:?} {:?}",
+            err_details.error_info, err_details.request_info
+        );
+
+        println!("details after redaction -> {formatted}");
+
+        let expected = "Some(ErrorInfo { reason: \"SOME_INFO\", domain: \"example.local\", metadata: {\"limit\": \"100\"} }) Some(RequestInfo { request_id: \"request-id\", serving_data: \"b8b02f6f2a7be6b63ead9aeac3d0fdfaad41f589de3c7189584703b426406eba\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "details after redaction differ from expected result"
+        );
+
+        let mut err_details_vec: Vec<ErrorDetail> = vec![
+            ErrorInfo::new("SOME_INFO", "example.local", metadata).into(),
+            RequestInfo::new("request-id", "user=alice").into(),
+        ];
+
+        policy.apply_vec(&mut err_details_vec);
+
+        assert!(
+            format!("{:?} {:?}", err_details_vec[0], err_details_vec[1])
+                == format!(
+                    "ErrorInfo({:?}) RequestInfo({:?})",
+                    err_details.error_info.unwrap(),
+                    err_details.request_info.unwrap()
+                ),
+            "details vec after redaction differs from details struct"
+        );
+    }
+}
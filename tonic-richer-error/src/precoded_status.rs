@@ -0,0 +1,126 @@
+use tonic::codegen::Bytes;
+use tonic::{Code, Status};
+
+use super::{ErrorDetails, WithErrorDetails};
+
+/// A `tonic::Status` whose error details were encoded once, obtained with
+/// [`ErrorDetails::freeze`]. Statuses generated from it share the encoded
+/// buffer, so servers that return the same status repeatedly, such as when
+/// rate limiting, avoid re-encoding the details for every response.
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tonic::Code;
+/// use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+///
+/// let mut err_details = ErrorDetails::new();
+///
+/// err_details
+///     .set_retry_info(Some(Duration::from_secs(1)))
+///     .add_quota_failure_violation("clientip:127.0.0.1", "rate exceeded");
+///
+/// let rate_limited = err_details.freeze(Code::ResourceExhausted, "rate exceeded");
+///
+/// let status = rate_limited.to_status();
+///
+/// assert_eq!(status.code(), Code::ResourceExhausted);
+/// assert!(status.get_details_quota_failure().is_some());
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrecodedStatus {
+    code: Code,
+    message: String,
+    details: Bytes,
+}
+
+impl PrecodedStatus {
+    /// Returns the status code.
+    pub fn code(&self) -> Code {
+        self.code
+    }
+
+    /// Returns the status message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the encoded `google.rpc.Status`, shared by every generated
+    /// `tonic::Status`.
+    pub fn details(&self) -> &Bytes {
+        &self.details
+    }
+
+    /// Generates a `tonic::Status`, without re-encoding the details.
+    pub fn to_status(&self) -> Status {
+        Status::with_details(self.code, self.message.clone(), self.details.clone())
+    }
+}
+
+impl From<&PrecodedStatus> for Status {
+    fn from(precoded: &PrecodedStatus) -> Self {
+        precoded.to_status()
+    }
+}
+
+impl ErrorDetails {
+    /// Encodes the error details once, along with a code and message, as in
+    /// [`WithErrorDetails::with_error_details`], returning a
+    /// [`PrecodedStatus`] that can cheaply generate identical
+    /// `tonic::Status`es.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::Code;
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let precoded = ErrorDetails::with_bad_request_violation("field", "description")
+    ///     .freeze(Code::InvalidArgument, "bad request");
+    ///
+    /// assert_eq!(precoded.message(), "bad request");
+    /// ```
+    pub fn freeze(self, code: Code, message: impl Into<String>) -> PrecodedStatus {
+        let status = Status::with_error_details(code, message, self);
+
+        PrecodedStatus {
+            code,
+            message: status.message().to_string(),
+            details: Bytes::copy_from_slice(status.details()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+
+    #[test]
+    fn precoded_statuses() {
+        let err_details = ErrorDetails::with_bad_request_violation("field", "description");
+
+        let expected =
+            Status::with_error_details(Code::InvalidArgument, "bad request", err_details.clone());
+
+        let precoded = err_details.freeze(Code::InvalidArgument, "bad request");
+
+        let first = precoded.to_status();
+        let second = Status::from(&precoded);
+
+        println!("precoded status -> {:?}", first);
+
+        assert!(
+            first.code() == expected.code()
+                && first.message() == expected.message()
+                && first.details() == expected.details(),
+            "precoded status differs from status with error details"
+        );
+
+        assert!(
+            first.details().as_ptr() == second.details().as_ptr(),
+            "precoded statuses do not share the encoded details"
+        );
+    }
+}
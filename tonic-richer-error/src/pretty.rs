@@ -0,0 +1,314 @@
+use std::fmt::Write;
+
+use super::{ErrorDetail, ErrorDetails, FromAny};
+
+/// Canonical names of the gRPC status codes, indexed by code.
+const CODE_NAMES: [&str; 17] = [
+    "OK",
+    "CANCELLED",
+    "UNKNOWN",
+    "INVALID_ARGUMENT",
+    "DEADLINE_EXCEEDED",
+    "NOT_FOUND",
+    "ALREADY_EXISTS",
+    "PERMISSION_DENIED",
+    "RESOURCE_EXHAUSTED",
+    "FAILED_PRECONDITION",
+    "ABORTED",
+    "OUT_OF_RANGE",
+    "UNIMPLEMENTED",
+    "INTERNAL",
+    "UNAVAILABLE",
+    "DATA_LOSS",
+    "UNAUTHENTICATED",
+];
+
+const BOLD_RED: &str = "1;31";
+const BOLD: &str = "1";
+const YELLOW: &str = "33";
+const GREEN: &str = "32";
+const CYAN: &str = "36";
+
+/// Applies ANSI styles to the rendered text, if enabled.
+struct Painter {
+    ansi: bool,
+}
+
+impl Painter {
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.ansi {
+            format!("\x1b[{style}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+impl ErrorDetails {
+    /// Renders a human-readable, indented report of a status with the given
+    /// code and message, carrying the error details, for CLI clients and
+    /// other tools that show errors to people. Violations are laid out as
+    /// aligned tables, followed by the retry hint and help links.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details
+    ///     .add_bad_request_violation("email", "must contain an @")
+    ///     .add_bad_request_violation("password", "is too short")
+    ///     .set_retry_info(Some(Duration::from_secs(5)));
+    ///
+    /// assert_eq!(
+    ///     err_details.render_pretty(3, "invalid signup"),
+    ///     "INVALID_ARGUMENT (3): invalid signup\n\
+    ///     \x20 field violations:\n\
+    ///     \x20   email     must contain an @\n\
+    ///     \x20   password  is too short\n\
+    ///     \x20 retry after 5s\n"
+    /// );
+    /// ```
+    pub fn render_pretty(&self, code: i32, message: &str) -> String {
+        self.render(code, message, &Painter { ansi: false })
+    }
+
+    /// Renders the same report as [`ErrorDetails::render_pretty`], colored
+    /// with ANSI escape codes, for output to terminals.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let err_details = ErrorDetails::with_bad_request_violation("email", "must contain an @");
+    ///
+    /// let report = err_details.render_pretty_ansi(3, "invalid signup");
+    ///
+    /// assert!(report.starts_with("\x1b[1;31mINVALID_ARGUMENT (3)\x1b[0m"));
+    /// ```
+    #[cfg(feature = "ansi")]
+    pub fn render_pretty_ansi(&self, code: i32, message: &str) -> String {
+        self.render(code, message, &Painter { ansi: true })
+    }
+
+    fn render(&self, code: i32, message: &str, painter: &Painter) -> String {
+        let code_name = usize::try_from(code)
+            .ok()
+            .and_then(|index| CODE_NAMES.get(index))
+            .unwrap_or(&"UNKNOWN");
+
+        let mut out = painter.paint(BOLD_RED, &format!("{code_name} ({code})"));
+
+        if !message.is_empty() {
+            out.push_str(": ");
+            out.push_str(message);
+        }
+        out.push('\n');
+
+        if let Some(error_info) = &self.error_info {
+            line(&mut out, 1, &format!("reason: {error_info}"));
+        }
+
+        if let Some(loc_message) = &self.localized_message {
+            let text = format!(
+                "localized message ({}): {}",
+                loc_message.locale, loc_message.message
+            );
+
+            line(&mut out, 1, &text);
+        }
+
+        if let Some(bad_request) = &self.bad_request {
+            let rows = bad_request
+                .field_violations
+                .iter()
+                .map(|v| vec![v.field.as_str(), v.description.as_str()]);
+
+            table(&mut out, painter, "field violations", rows);
+        }
+
+        if let Some(prec_failure) = &self.precondition_failure {
+            let rows = prec_failure.violations.iter().map(|v| {
+                vec![
+                    v.r#type.as_str(),
+                    v.subject.as_str(),
+                    v.description.as_str(),
+                ]
+            });
+
+            table(&mut out, painter, "precondition failures", rows);
+        }
+
+        if let Some(quota_failure) = &self.quota_failure {
+            let rows = quota_failure
+                .violations
+                .iter()
+                .map(|v| vec![v.subject.as_str(), v.description.as_str()]);
+
+            table(&mut out, painter, "quota failures", rows);
+        }
+
+        if let Some(res_info) = &self.resource_info {
+            line(&mut out, 1, &format!("resource: {res_info}"));
+        }
+
+        if let Some(req_info) = &self.request_info {
+            line(&mut out, 1, &req_info.to_string());
+        }
+
+        if let Some(retry_info) = &self.retry_info {
+            line(&mut out, 1, &painter.paint(GREEN, &retry_info.to_string()));
+        }
+
+        if let Some(help) = &self.help {
+            line(&mut out, 1, &painter.paint(BOLD, "help:"));
+
+            for link in &help.links {
+                let url = painter.paint(CYAN, &link.url);
+                line(&mut out, 2, &format!("{}: {url}", link.description));
+            }
+        }
+
+        if let Some(debug_info) = &self.debug_info {
+            line(&mut out, 1, &format!("debug: {}", debug_info.detail));
+
+            for entry in &debug_info.stack_entries {
+                line(&mut out, 2, &format!("at {entry}"));
+            }
+        }
+
+        for any in &self.unknown_details {
+            match ErrorDetail::from_any(any.into()) {
+                Ok(detail) => line(&mut out, 1, &detail.to_string()),
+                Err(_) => line(&mut out, 1, &format!("other: {}", any.type_url)),
+            }
+        }
+
+        out
+    }
+}
+
+/// Appends a line, indented by two spaces per level.
+fn line(out: &mut String, level: usize, text: &str) {
+    // Writing to a `String` cannot fail
+    let _ = writeln!(out, "{:indent$}{text}", "", indent = level * 2);
+}
+
+/// Appends a titled table, with its columns aligned. The first column is
+/// highlighted.
+fn table<'a>(
+    out: &mut String,
+    painter: &Painter,
+    title: &str,
+    rows: impl Iterator<Item = Vec<&'a str>>,
+) {
+    let rows: Vec<Vec<&str>> = rows.collect();
+
+    let mut widths: Vec<usize> = Vec::new();
+
+    for row in &rows {
+        for (index, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+
+            match widths.get_mut(index) {
+                Some(current) => *current = (*current).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+
+    line(out, 1, &painter.paint(BOLD, &format!("{title}:")));
+
+    for row in rows {
+        let mut text = String::new();
+
+        for (index, cell) in row.iter().enumerate() {
+            if index > 0 {
+                text.push_str("  ");
+            }
+
+            // The last column is not padded, to avoid trailing spaces
+            let padding = if index + 1 < row.len() {
+                widths[index] - cell.chars().count()
+            } else {
+                0
+            };
+
+            match index {
+                0 => text.push_str(&painter.paint(YELLOW, cell)),
+                _ => text.push_str(cell),
+            }
+
+            text.push_str(&" ".repeat(padding));
+        }
+
+        line(out, 2, &text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+
+    use super::super::ErrorDetails;
+
+    #[test]
+    fn render_pretty_report() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_error_info("TOS_REQUIRED", "example.local", BTreeMap::new())
+            .add_precondition_failure_violation("TOS", "users/1", "terms not accepted")
+            .add_precondition_failure_violation("AGE", "users/1", "age not verified")
+            .add_help_link("terms of service", "https://example.local/tos")
+            .set_debug_info(vec!["signup.rs:12".into()], "missing acceptance");
+
+        let report = err_details.render_pretty(9, "cannot sign up");
+
+        println!("pretty report ->\n{report}");
+
+        let expected = "FAILED_PRECONDITION (9): cannot sign up\n\
+            \x20 reason: TOS_REQUIRED (example.local)\n\
+            \x20 precondition failures:\n\
+            \x20   TOS  users/1  terms not accepted\n\
+            \x20   AGE  users/1  age not verified\n\
+            \x20 help:\n\
+            \x20   terms of service: https://example.local/tos\n\
+            \x20 debug: missing acceptance\n\
+            \x20   at signup.rs:12\n";
+
+        assert!(
+            report.eq(expected),
+            "pretty report differs from expected result"
+        );
+
+        let report = ErrorDetails::new().render_pretty(42, "");
+
+        assert!(
+            report.eq("UNKNOWN (42)\n"),
+            "pretty report with unknown code differs from expected result"
+        );
+    }
+
+    #[cfg(feature = "ansi")]
+    #[test]
+    fn render_pretty_ansi_report() {
+        let err_details = ErrorDetails::with_bad_request_violation("email", "must contain an @");
+
+        let report = err_details.render_pretty_ansi(3, "invalid signup");
+
+        println!("ansi report -> {:?}", report);
+
+        let expected = "\x1b[1;31mINVALID_ARGUMENT (3)\x1b[0m: invalid signup\n\
+            \x20 \x1b[1mfield violations:\x1b[0m\n\
+            \x20   \x1b[33memail\x1b[0m  must contain an @\n";
+
+        assert!(
+            report.eq(expected),
+            "ANSI report differs from expected result"
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tonic::codegen::http::StatusCode;
+use tonic::{Code, Status};
+
+use super::http_codes::{code_to_http, http_to_code};
+use super::{ErrorDetails, WithErrorDetails};
+
+/// Used at the `invalid_params` field of the [`ProblemDetails`] struct.
+/// Corresponds to a [`crate::FieldViolation`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InvalidParam {
+    /// Path to the invalid parameter.
+    pub name: String,
+
+    /// Reason why the parameter is invalid.
+    pub reason: String,
+}
+
+/// HTTP problem details, as described in [RFC 7807]. Allows services that
+/// expose both gRPC and REST endpoints to return the same errors on both
+/// interfaces.
+///
+/// Besides the standard members, the following extension members are used:
+/// - `code`: gRPC status code name, like `INVALID_ARGUMENT`.
+/// - `reason` and `domain`: from [`crate::ErrorInfo`] details.
+/// - `invalid-params`: from [`crate::BadRequest`] violations.
+/// - `retry-after`: seconds, from [`crate::RetryInfo`] details. Servers
+///   should also send it as the `Retry-After` HTTP header.
+///
+/// [RFC 7807]: https://www.rfc-editor.org/rfc/rfc7807
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProblemDetails {
+    /// URI reference that identifies the problem type.
+    #[serde(rename = "type")]
+    pub problem_type: String,
+
+    /// Short, human-readable summary of the problem type.
+    pub title: String,
+
+    /// HTTP status code.
+    pub status: u16,
+
+    /// Human-readable explanation specific to this occurrence of the problem.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub detail: String,
+
+    /// URI reference that identifies the specific occurrence of the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+
+    /// gRPC status code name, like `INVALID_ARGUMENT`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
+    /// Reason of the error, from [`crate::ErrorInfo`] details.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Logical grouping of the error reason, from [`crate::ErrorInfo`]
+    /// details.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+
+    /// Invalid request parameters, from [`crate::BadRequest`] violations.
+    #[serde(rename = "invalid-params", skip_serializing_if = "Vec::is_empty")]
+    pub invalid_params: Vec<InvalidParam>,
+
+    /// Seconds to wait before retrying, from [`crate::RetryInfo`] details.
+    #[serde(rename = "retry-after", skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+}
+
+impl Default for ProblemDetails {
+    fn default() -> Self {
+        ProblemDetails {
+            problem_type: ProblemDetails::DEFAULT_TYPE.to_string(),
+            title: String::new(),
+            status: 500,
+            detail: String::new(),
+            instance: None,
+            code: None,
+            reason: None,
+            domain: None,
+            invalid_params: Vec::new(),
+            retry_after: None,
+        }
+    }
+}
+
+impl ProblemDetails {
+    /// Media type of the JSON representation of [`ProblemDetails`].
+    pub const CONTENT_TYPE: &'static str = "application/problem+json";
+
+    /// Problem type used when no additional semantics are available.
+    pub const DEFAULT_TYPE: &'static str = "about:blank";
+
+    /// Generates a [`ProblemDetails`] struct from a `tonic::Status` and its
+    /// error details. The HTTP status follows the canonical mapping of gRPC
+    /// status codes.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{ErrorDetails, ProblemDetails, WithErrorDetails};
+    ///
+    /// let status = Status::with_error_details(
+    ///     Code::InvalidArgument,
+    ///     "bad request",
+    ///     ErrorDetails::with_bad_request_violation("field", "description"),
+    /// );
+    ///
+    /// let problem = ProblemDetails::from_status(&status);
+    ///
+    /// assert_eq!(problem.status, 400);
+    /// assert_eq!(problem.invalid_params[0].name, "field");
+    /// ```
+    pub fn from_status(status: &Status) -> Self {
+        let details = status.error_details();
+
+        let mut problem = ProblemDetails {
+            title: status.code().description().to_string(),
+            status: code_to_http(status.code()).as_u16(),
+            detail: status.message().to_string(),
+            code: Some(code_name(status.code()).to_string()),
+            ..ProblemDetails::default()
+        };
+
+        if let Some(error_info) = details.error_info {
+            problem.reason = Some(error_info.reason);
+            problem.domain = Some(error_info.domain);
+        }
+
+        if let Some(bad_request) = details.bad_request {
+            problem.invalid_params = bad_request
+                .field_violations
+                .into_iter()
+                .map(|violation| InvalidParam {
+                    name: violation.field,
+                    reason: violation.description,
+                })
+                .collect();
+        }
+
+        if let Some(retry_info) = details.retry_info {
+            let delay = retry_info.retry_delay.unwrap_or(Duration::ZERO);
+            let secs = delay.as_secs() + u64::from(delay.subsec_nanos() > 0);
+            problem.retry_after = Some(secs);
+        }
+
+        problem
+    }
+
+    /// Generates a `tonic::Status` with error details from a
+    /// [`ProblemDetails`] struct. The code is read from the `code` member,
+    /// or inferred from the HTTP status if it is absent or not a valid code
+    /// name.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::Code;
+    /// use tonic_richer_error::{ProblemDetails, WithErrorDetails};
+    ///
+    /// let problem = ProblemDetails::from_json(r#"{
+    ///     "type": "about:blank",
+    ///     "title": "Too Many Requests",
+    ///     "status": 429,
+    ///     "retry-after": 5
+    /// }"#).unwrap();
+    ///
+    /// let status = problem.into_status();
+    ///
+    /// assert_eq!(status.code(), Code::ResourceExhausted);
+    /// assert!(status.get_details_retry_info().is_some());
+    /// ```
+    pub fn into_status(self) -> Status {
+        let code = self
+            .code
+            .as_deref()
+            .and_then(code_from_name)
+            .unwrap_or_else(|| match StatusCode::from_u16(self.status) {
+                Ok(status) => http_to_code(status),
+                Err(_) => Code::Unknown,
+            });
+
+        let mut details = ErrorDetails::new();
+
+        if self.reason.is_some() || self.domain.is_some() {
+            details.set_error_info(
+                self.reason.unwrap_or_default(),
+                self.domain.unwrap_or_default(),
+                Default::default(),
+            );
+        }
+
+        for param in self.invalid_params {
+            details.add_bad_request_violation(param.name, param.reason);
+        }
+
+        if let Some(secs) = self.retry_after {
+            details.set_retry_info(Some(Duration::from_secs(secs)));
+        }
+
+        let message = match self.detail.is_empty() {
+            true => self.title,
+            false => self.detail,
+        };
+
+        Status::with_error_details(code, message, details)
+    }
+
+    /// Generates the `application/problem+json` representation of the
+    /// [`ProblemDetails`] struct.
+    pub fn to_json(&self) -> String {
+        // Serializing plain structs cannot fail
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Parses a [`ProblemDetails`] struct from its `application/problem+json`
+    /// representation. Missing members are set to their default values.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl From<&Status> for ProblemDetails {
+    fn from(status: &Status) -> Self {
+        ProblemDetails::from_status(status)
+    }
+}
+
+impl From<ProblemDetails> for Status {
+    fn from(problem: ProblemDetails) -> Self {
+        problem.into_status()
+    }
+}
+
+const CODE_NAMES: [(Code, &str); 17] = [
+    (Code::Ok, "OK"),
+    (Code::Cancelled, "CANCELLED"),
+    (Code::Unknown, "UNKNOWN"),
+    (Code::InvalidArgument, "INVALID_ARGUMENT"),
+    (Code::DeadlineExceeded, "DEADLINE_EXCEEDED"),
+    (Code::NotFound, "NOT_FOUND"),
+    (Code::AlreadyExists, "ALREADY_EXISTS"),
+    (Code::PermissionDenied, "PERMISSION_DENIED"),
+    (Code::ResourceExhausted, "RESOURCE_EXHAUSTED"),
+    (Code::FailedPrecondition, "FAILED_PRECONDITION"),
+    (Code::Aborted, "ABORTED"),
+    (Code::OutOfRange, "OUT_OF_RANGE"),
+    (Code::Unimplemented, "UNIMPLEMENTED"),
+    (Code::Internal, "INTERNAL"),
+    (Code::Unavailable, "UNAVAILABLE"),
+    (Code::DataLoss, "DATA_LOSS"),
+    (Code::Unauthenticated, "UNAUTHENTICATED"),
+];
+
+fn code_name(code: Code) -> &'static str {
+    CODE_NAMES
+        .iter()
+        .find(|(known, _)| *known == code)
+        .map(|(_, name)| *name)
+        .unwrap_or("UNKNOWN")
+}
+
+fn code_from_name(name: &str) -> Option<Code> {
+    CODE_NAMES
+        .iter()
+        .find(|(_, known)| *known == name)
+        .map(|(code, _)| *code)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::ProblemDetails;
+
+    #[test]
+    fn problem_details_round_trip() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_millis(1500)))
+            .set_error_info("RATE_LIMITED", "example.local", BTreeMap::new())
+            .add_bad_request_violation("field", "description");
+
+        let status = Status::with_error_details(Code::ResourceExhausted, "slow down", err_details);
+
+        let json = ProblemDetails::from_status(&status).to_json();
+
+        println!("problem+json -> {json}");
+
+        let expected = r#"{"type":"about:blank","title":"Some resource has been exhausted","status":429,"detail":"slow down","code":"RESOURCE_EXHAUSTED","reason":"RATE_LIMITED","domain":"example.local","invalid-params":[{"name":"field","reason":"description"}],"retry-after":2}"#;
+
+        assert!(
+            json.eq(expected),
+            "problem+json differs from expected result"
+        );
+
+        let problem = match ProblemDetails::from_json(&json) {
+            Ok(problem) => problem,
+            Err(err) => panic!("Error parsing problem+json: {:?}", err),
+        };
+
+        let status = problem.into_status();
+
+        let formatted = format!(
+            "{:?} {:?} {:?} {:?}",
+            status.code(),
+            status.message(),
+            status.get_details_retry_info(),
+            status.get_details_bad_request()
+        );
+
+        let expected = "ResourceExhausted \"slow down\" Some(RetryInfo { retry_delay: Some(2s) }) Some(BadRequest { field_violations: [FieldViolation { field: \"field\", description: \"description\", reason: \"\", localized_message: None }] })";
+
+        assert!(
+            formatted.eq(expected),
+            "status from problem details differs from expected result"
+        );
+    }
+}
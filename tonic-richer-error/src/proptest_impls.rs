@@ -0,0 +1,410 @@
+use std::time::Duration;
+
+use proptest::collection::{btree_map, vec};
+use proptest::option;
+use proptest::prelude::{any, Arbitrary, BoxedStrategy, Strategy};
+use prost_types::Any;
+
+use super::{
+    BadRequest, DebugInfo, ErrorDetails, ErrorInfo, FieldViolation, Help, HelpLink, HttpHeader,
+    HttpRequest, HttpResponse, LocalizedMessage, PreconditionFailure, PreconditionViolation,
+    QuotaFailure, QuotaViolation, RequestInfo, ResourceInfo, RetryInfo,
+};
+
+/// Maximum number of elements in generated lists and maps.
+const MAX_LEN: usize = 4;
+
+/// Generates unknown details, with type URLs that never match the standard
+/// error messages.
+fn unknown_details() -> impl Strategy<Value = Vec<Any>> {
+    vec((0..64u32, vec(any::<u8>(), 0..16)), 0..MAX_LEN).prop_map(|details| {
+        details
+            .into_iter()
+            .map(|(id, value)| Any {
+                type_url: format!("type.example.local/custom.Detail{id}"),
+                value,
+            })
+            .collect()
+    })
+}
+
+impl Arbitrary for RetryInfo {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let max_secs = RetryInfo::MAX_RETRY_DELAY.as_secs();
+
+        option::of((0..=max_secs, 0..1_000_000_000u32))
+            .prop_map(|delay| RetryInfo {
+                retry_delay: delay.map(|(secs, nanos)| Duration::new(secs, nanos)),
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for DebugInfo {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (vec(any::<String>(), 0..MAX_LEN), any::<String>())
+            .prop_map(|(stack_entries, detail)| DebugInfo {
+                stack_entries,
+                detail,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for QuotaViolation {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<[String; 5]>(),
+            btree_map(any::<String>(), any::<String>(), 0..MAX_LEN),
+            any::<i64>(),
+            any::<Option<i64>>(),
+        )
+            .prop_map(
+                |(
+                    [subject, description, api_service, quota_metric, quota_id],
+                    quota_dimensions,
+                    quota_value,
+                    future_quota_value,
+                )| {
+                    QuotaViolation {
+                        subject,
+                        description,
+                        api_service,
+                        quota_metric,
+                        quota_id,
+                        quota_dimensions,
+                        quota_value,
+                        future_quota_value,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for QuotaFailure {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        vec(any::<QuotaViolation>(), 0..MAX_LEN)
+            .prop_map(|violations| QuotaFailure { violations })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ErrorInfo {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+            btree_map(any::<String>(), any::<String>(), 0..MAX_LEN),
+        )
+            .prop_map(|(reason, domain, metadata)| ErrorInfo {
+                reason,
+                domain,
+                metadata,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for PreconditionViolation {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<[String; 3]>()
+            .prop_map(|[r#type, subject, description]| PreconditionViolation {
+                r#type,
+                subject,
+                description,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for PreconditionFailure {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        vec(any::<PreconditionViolation>(), 0..MAX_LEN)
+            .prop_map(|violations| PreconditionFailure { violations })
+            .boxed()
+    }
+}
+
+impl Arbitrary for FieldViolation {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<[String; 3]>(), any::<Option<LocalizedMessage>>())
+            .prop_map(
+                |([field, description, reason], localized_message)| FieldViolation {
+                    field,
+                    description,
+                    reason,
+                    localized_message,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for BadRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        vec(any::<FieldViolation>(), 0..MAX_LEN)
+            .prop_map(|field_violations| BadRequest { field_violations })
+            .boxed()
+    }
+}
+
+impl Arbitrary for RequestInfo {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<[String; 2]>()
+            .prop_map(|[request_id, serving_data]| RequestInfo {
+                request_id,
+                serving_data,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ResourceInfo {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<[String; 4]>()
+            .prop_map(
+                |[resource_type, resource_name, owner, description]| ResourceInfo {
+                    resource_type,
+                    resource_name,
+                    owner,
+                    description,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for HelpLink {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<[String; 2]>()
+            .prop_map(|[description, url]| HelpLink { description, url })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Help {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        vec(any::<HelpLink>(), 0..MAX_LEN)
+            .prop_map(|links| Help { links })
+            .boxed()
+    }
+}
+
+impl Arbitrary for LocalizedMessage {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<[String; 2]>()
+            .prop_map(|[locale, message]| LocalizedMessage { locale, message })
+            .boxed()
+    }
+}
+
+impl Arbitrary for HttpHeader {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<[String; 2]>()
+            .prop_map(|[key, value]| HttpHeader { key, value })
+            .boxed()
+    }
+}
+
+impl Arbitrary for HttpRequest {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<[String; 2]>(), vec(any::<HttpHeader>(), 0..MAX_LEN))
+            .prop_map(|([method, uri], headers)| HttpRequest {
+                method,
+                uri,
+                headers,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for HttpResponse {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<i32>(),
+            any::<String>(),
+            vec(any::<HttpHeader>(), 0..MAX_LEN),
+        )
+            .prop_map(|(status, reason, headers)| HttpResponse {
+                status,
+                reason,
+                headers,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ErrorDetails {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            (
+                any::<Option<RetryInfo>>(),
+                any::<Option<DebugInfo>>(),
+                any::<Option<QuotaFailure>>(),
+                any::<Option<ErrorInfo>>(),
+                any::<Option<PreconditionFailure>>(),
+            ),
+            (
+                any::<Option<BadRequest>>(),
+                any::<Option<RequestInfo>>(),
+                any::<Option<ResourceInfo>>(),
+                any::<Option<Help>>(),
+                any::<Option<LocalizedMessage>>(),
+            ),
+            unknown_details(),
+        )
+            .prop_map(
+                |(
+                    (retry_info, debug_info, quota_failure, error_info, precondition_failure),
+                    (bad_request, request_info, resource_info, help, localized_message),
+                    unknown_details,
+                )| ErrorDetails {
+                    retry_info,
+                    debug_info,
+                    quota_failure,
+                    error_info,
+                    precondition_failure,
+                    bad_request,
+                    request_info,
+                    resource_info,
+                    help,
+                    localized_message,
+                    unknown_details,
+                },
+            )
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use proptest::prelude::{any, prop_assert_eq, proptest};
+
+    use super::super::{
+        pack_any, unpack_any, AnyMessage, BadRequest, DebugInfo, ErrorDetails, ErrorInfo, Help,
+        HttpRequest, HttpResponse, LocalizedMessage, PreconditionFailure, QuotaFailure,
+        RequestInfo, ResourceInfo, RetryInfo,
+    };
+    use super::unknown_details;
+
+    fn round_trip<T: AnyMessage>(detail: &T) -> T {
+        match unpack_any::<T>(&pack_any(detail)) {
+            Ok(decoded) => decoded,
+            Err(err) => panic!("Error unpacking detail from Any: {:?}", err),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn detail_round_trips(
+            retry_info in any::<RetryInfo>(),
+            debug_info in any::<DebugInfo>(),
+            quota_failure in any::<QuotaFailure>(),
+            error_info in any::<ErrorInfo>(),
+            precondition_failure in any::<PreconditionFailure>(),
+            bad_request in any::<BadRequest>(),
+            request_info in any::<RequestInfo>(),
+            resource_info in any::<ResourceInfo>(),
+            help in any::<Help>(),
+            localized_message in any::<LocalizedMessage>(),
+            http_request in any::<HttpRequest>(),
+            http_response in any::<HttpResponse>(),
+        ) {
+            prop_assert_eq!(round_trip(&retry_info), retry_info);
+            prop_assert_eq!(round_trip(&debug_info), debug_info);
+            prop_assert_eq!(round_trip(&quota_failure), quota_failure);
+            prop_assert_eq!(round_trip(&error_info), error_info);
+            prop_assert_eq!(round_trip(&precondition_failure), precondition_failure);
+            prop_assert_eq!(round_trip(&bad_request), bad_request);
+            prop_assert_eq!(round_trip(&request_info), request_info);
+            prop_assert_eq!(round_trip(&resource_info), resource_info);
+            prop_assert_eq!(round_trip(&help), help);
+            prop_assert_eq!(round_trip(&localized_message), localized_message);
+            prop_assert_eq!(round_trip(&http_request), http_request);
+            prop_assert_eq!(round_trip(&http_response), http_response);
+        }
+
+        #[test]
+        fn error_details_round_trip(err_details in any::<ErrorDetails>()) {
+            let encoded = err_details.clone().encode_status(3, "invalid argument");
+
+            let decoded = ErrorDetails::decode_status(&encoded);
+
+            prop_assert_eq!(decoded, Ok(err_details));
+        }
+
+        #[test]
+        fn preserve_unknown_details(
+            err_details in any::<ErrorDetails>(),
+            unknown in unknown_details(),
+        ) {
+            let mut err_details = err_details;
+            err_details.unknown_details = unknown.clone();
+
+            let encoded = err_details.encode_status(3, "invalid argument");
+
+            let decoded = match ErrorDetails::decode_status(&encoded) {
+                Ok(decoded) => decoded,
+                Err(err) => panic!("Error decoding details: {:?}", err),
+            };
+
+            prop_assert_eq!(decoded.unknown_details, unknown);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use prost_types::Any;
+
+use super::type_url_prefix::canonical_type_url;
+use super::{ok_or_warn, ErrorDetailType};
+
+/// A detail of a `google.rpc.Status` that was not decoded yet, borrowing its
+/// type URL and encoded value from the status. Yielded by
+/// [`crate::WithErrorDetails::error_details_iter`], and decoded on demand
+/// with [`RawDetail::downcast`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RawDetail<'a> {
+    /// Type URL of the detail.
+    pub type_url: &'a str,
+
+    /// Encoded value of the detail.
+    pub bytes: &'a [u8],
+}
+
+impl<'a> RawDetail<'a> {
+    /// Returns `true` if the detail has the type URL of `T`, which can be a
+    /// standard error message struct or a [`crate::CustomErrorDetail`].
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "tonic")]
+    /// # {
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{BadRequest, ErrorDetails, RetryInfo, WithErrorDetails};
+    ///
+    /// let status = Status::with_error_details(
+    ///     Code::InvalidArgument,
+    ///     "bad request",
+    ///     ErrorDetails::with_bad_request_violation("field", "description"),
+    /// );
+    ///
+    /// let detail = status.error_details_iter().next().unwrap();
+    ///
+    /// assert!(detail.is::<BadRequest>());
+    /// assert!(!detail.is::<RetryInfo>());
+    /// # }
+    /// ```
+    pub fn is<T: ErrorDetailType>(&self) -> bool {
+        canonical_type_url(self.type_url) == T::TYPE_URL
+    }
+
+    /// Decodes the detail as `T`, which can be a standard error message
+    /// struct or a [`crate::CustomErrorDetail`]. Returns `None` if the type
+    /// URL does not match or if some `prost::DecodeError` occurs.
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "tonic")]
+    /// # {
+    /// use tonic::{Code, Status};
+    /// use tonic_richer_error::{BadRequest, ErrorDetails, WithErrorDetails};
+    ///
+    /// let status = Status::with_error_details(
+    ///     Code::InvalidArgument,
+    ///     "bad request",
+    ///     ErrorDetails::with_bad_request_violation("field", "description"),
+    /// );
+    ///
+    /// for detail in status.error_details_iter() {
+    ///     if let Some(bad_request) = detail.downcast::<BadRequest>() {
+    ///         assert_eq!(bad_request.field_violations[0].field, "field");
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn downcast<T: ErrorDetailType>(&self) -> Option<T> {
+        if !self.is::<T>() {
+            return None;
+        }
+
+        ok_or_warn(T::decode_detail(self.bytes), self.type_url)
+    }
+
+    /// Copies the detail into an owned `prost_types::Any`.
+    pub fn to_any(&self) -> Any {
+        Any {
+            type_url: self.type_url.to_string(),
+            value: self.bytes.to_vec(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tonic"))]
+mod tests {
+
+    use std::time::Duration;
+    use tonic::{Code, Status};
+
+    use super::super::{BadRequest, ErrorDetails, ErrorInfo, RetryInfo, WithErrorDetails};
+
+    #[test]
+    fn iter_raw_details() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .add_bad_request_violation("field", "description");
+
+        let status = Status::with_error_details(Code::InvalidArgument, "bad request", err_details);
+
+        let type_urls: Vec<&str> = status
+            .error_details_iter()
+            .map(|detail| detail.type_url)
+            .collect();
+
+        println!("raw detail type URLs -> {:?}", type_urls);
+
+        assert!(
+            type_urls.eq(&[RetryInfo::TYPE_URL, BadRequest::TYPE_URL]),
+            "raw detail type URLs differ from expected result"
+        );
+
+        let bad_requests: Vec<BadRequest> = status
+            .error_details_iter()
+            .filter_map(|detail| detail.downcast::<BadRequest>())
+            .collect();
+
+        let formatted = format!("{:?}", bad_requests);
+
+        println!("downcast BadRequest details -> {formatted}");
+
+        let expected = "[BadRequest { field_violations: [FieldViolation { field: \"field\", description: \"description\", reason: \"\", localized_message: None }] }]";
+
+        assert!(
+            formatted.eq(expected),
+            "downcast BadRequest details differ from expected result"
+        );
+
+        let detail = status.error_details_iter().next().unwrap();
+
+        assert!(
+            detail.downcast::<ErrorInfo>().is_none(),
+            "detail was downcast to the wrong type"
+        );
+
+        let any = detail.to_any();
+
+        assert!(
+            any.type_url == detail.type_url && any.value == detail.bytes,
+            "detail was not copied into Any"
+        );
+
+        let malformed = Status::with_details(Code::Unknown, "", vec![10, 2].into());
+
+        assert!(
+            malformed.error_details_iter().next().is_none(),
+            "details were yielded from a malformed status"
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use tonic::Status;
+
+use super::{Backoff, RetryDecision, WithErrorDetails};
+
+/// Tracks consecutive failures of a client per [`crate::ErrorInfo`] `reason`
+/// and `domain`, deciding if and when each failed call should be retried.
+/// Statuses that should not be retried, as described in
+/// [`RetryDecision::from_details`], are never retried. Otherwise, the delay
+/// advertised in [`crate::RetryInfo`] details is used when present, and the
+/// [`Backoff`] delay for the number of previous failures with the same
+/// reason otherwise. Failures without [`crate::ErrorInfo`] details share a
+/// single counter.
+///
+/// Can be shared between tasks, such as by client loops or tower
+/// middleware.
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{Backoff, ReasonBackoff};
+///
+/// let backoff = ReasonBackoff::new(
+///     Backoff::new(Duration::from_millis(100), Duration::from_secs(10)).jitter(0.0),
+/// )
+/// .max_attempts(2);
+///
+/// let status = Status::new(Code::Unavailable, "unavailable");
+///
+/// assert_eq!(backoff.should_retry(&status), Some(Duration::from_millis(100)));
+/// assert_eq!(backoff.should_retry(&status), Some(Duration::from_millis(200)));
+/// assert_eq!(backoff.should_retry(&status), None);
+/// ```
+#[derive(Debug)]
+pub struct ReasonBackoff {
+    backoff: Backoff,
+    max_attempts: u32,
+    failures: Mutex<HashMap<(String, String), u32>>,
+}
+
+impl ReasonBackoff {
+    /// Default maximum number of retries per reason.
+    pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+    /// Creates a new [`ReasonBackoff`], using the given [`Backoff`] when no
+    /// delay is advertised, and [`ReasonBackoff::DEFAULT_MAX_ATTEMPTS`].
+    pub fn new(backoff: Backoff) -> Self {
+        ReasonBackoff {
+            backoff,
+            max_attempts: ReasonBackoff::DEFAULT_MAX_ATTEMPTS,
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the maximum number of consecutive retries per reason.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Records a failure with the given status, returning the delay to wait
+    /// before retrying, or `None` if the call should not be retried, or the
+    /// maximum number of retries for the status reason was reached.
+    pub fn should_retry(&self, status: &Status) -> Option<Duration> {
+        let details = status.error_details();
+
+        let decision = RetryDecision::from_details(status.code(), &details);
+
+        if !decision.should_retry() {
+            return None;
+        }
+
+        let key = match details.error_info {
+            Some(error_info) => (error_info.reason, error_info.domain),
+            None => (String::new(), String::new()),
+        };
+
+        let attempt = {
+            let mut failures = self.lock_failures();
+
+            let attempt = failures.entry(key).or_insert(0);
+
+            if *attempt >= self.max_attempts {
+                return None;
+            }
+
+            *attempt += 1;
+            *attempt - 1
+        };
+
+        match decision {
+            RetryDecision::After(delay) => Some(delay),
+            _ => Some(self.backoff.delay(attempt)),
+        }
+    }
+
+    /// Returns the number of consecutive failures recorded for the given
+    /// reason and domain. Use empty strings for failures without
+    /// [`crate::ErrorInfo`] details.
+    pub fn failures(&self, reason: &str, domain: &str) -> u32 {
+        self.lock_failures()
+            .get(&(reason.to_string(), domain.to_string()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Clears the failures recorded for the given reason and domain, usually
+    /// after a successful call.
+    pub fn reset(&self, reason: &str, domain: &str) {
+        self.lock_failures()
+            .remove(&(reason.to_string(), domain.to_string()));
+    }
+
+    /// Clears all recorded failures.
+    pub fn reset_all(&self) {
+        self.lock_failures().clear();
+    }
+
+    fn lock_failures(&self) -> MutexGuard<'_, HashMap<(String, String), u32>> {
+        match self.failures.lock() {
+            Ok(failures) => failures,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+    use tonic::{Code, Status};
+
+    use super::super::{Backoff, ErrorDetails, WithErrorDetails};
+    use super::ReasonBackoff;
+
+    fn rate_limited(retry_delay: Option<Duration>) -> Status {
+        let mut err_details = ErrorDetails::new();
+
+        err_details.set_error_info("RATE_LIMITED", "example.local", Default::default());
+
+        if retry_delay.is_some() {
+            err_details.set_retry_info(retry_delay);
+        }
+
+        Status::with_error_details(Code::Unavailable, "rate limited", err_details)
+    }
+
+    #[test]
+    fn backoff_per_reason() {
+        let backoff = ReasonBackoff::new(
+            Backoff::new(Duration::from_millis(100), Duration::from_millis(300)).jitter(0.0),
+        )
+        .max_attempts(3);
+
+        let delays: Vec<Option<Duration>> = [
+            rate_limited(None),
+            rate_limited(None),
+            Status::new(Code::Unavailable, "unavailable"),
+            rate_limited(Some(Duration::from_secs(2))),
+            rate_limited(None),
+            Status::new(Code::InvalidArgument, "bad request"),
+        ]
+        .iter()
+        .map(|status| backoff.should_retry(status))
+        .collect();
+
+        println!("retry delays -> {:?}", delays);
+
+        let expected = [
+            Some(Duration::from_millis(100)),
+            Some(Duration::from_millis(200)),
+            Some(Duration::from_millis(100)),
+            Some(Duration::from_secs(2)),
+            None,
+            None,
+        ];
+
+        assert!(
+            delays.eq(&expected),
+            "retry delays differ from expected result"
+        );
+
+        assert!(
+            backoff.failures("RATE_LIMITED", "example.local") == 3 && backoff.failures("", "") == 1,
+            "recorded failures differ from expected result"
+        );
+
+        backoff.reset("RATE_LIMITED", "example.local");
+
+        assert!(
+            backoff.should_retry(&rate_limited(None)) == Some(Duration::from_millis(100)),
+            "failures were not reset"
+        );
+
+        backoff.reset_all();
+
+        assert!(backoff.failures("", "") == 0, "failures were not cleared");
+    }
+}
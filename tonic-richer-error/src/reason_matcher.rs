@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use tonic::Status;
+
+use super::WithErrorDetails;
+
+/// Dispatches a `tonic::Status` to the handler registered for its
+/// [`crate::ErrorInfo`] reason. Only the first matching handler runs, and
+/// the [`crate::ErrorInfo`] details are decoded only once.
+/// # Examples
+///
+/// ```
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{ErrorDetails, ReasonMatcher, WithErrorDetails};
+///
+/// let mut err_details = ErrorDetails::new();
+///
+/// err_details.set_error_info("OUT_OF_STOCK", "shop.example.com", Default::default());
+///
+/// let status = Status::with_error_details(Code::FailedPrecondition, "out of stock", err_details);
+///
+/// let action = ReasonMatcher::new(&status)
+///     .on("INVALID_SKU", |_| "fix the SKU")
+///     .on_domain("shop.example.com", "OUT_OF_STOCK", |_| "wait for restock")
+///     .otherwise(|_| "report the error");
+///
+/// assert_eq!(action, "wait for restock");
+/// ```
+#[derive(Debug)]
+pub struct ReasonMatcher<'a, T> {
+    status: &'a Status,
+    error_reason: Option<(String, String, BTreeMap<String, String>)>,
+    outcome: Option<T>,
+}
+
+impl<'a, T> ReasonMatcher<'a, T> {
+    /// Creates a new [`ReasonMatcher`], with the [`crate::ErrorInfo`]
+    /// details of the `tonic::Status`, if any.
+    pub fn new(status: &'a Status) -> Self {
+        ReasonMatcher {
+            status,
+            error_reason: status.error_reason(),
+            outcome: None,
+        }
+    }
+
+    /// Registers a handler for a reason, from any domain. The handler
+    /// receives the [`crate::ErrorInfo`] metadata.
+    pub fn on(mut self, reason: &str, f: impl FnOnce(&BTreeMap<String, String>) -> T) -> Self {
+        if self.outcome.is_none() {
+            if let Some((_, r, metadata)) = &self.error_reason {
+                if r == reason {
+                    self.outcome = Some(f(metadata));
+                }
+            }
+        }
+        self
+    }
+
+    /// Registers a handler for a reason of a specific domain. The handler
+    /// receives the [`crate::ErrorInfo`] metadata.
+    pub fn on_domain(
+        mut self,
+        domain: &str,
+        reason: &str,
+        f: impl FnOnce(&BTreeMap<String, String>) -> T,
+    ) -> Self {
+        if self.outcome.is_none() {
+            if let Some((d, r, metadata)) = &self.error_reason {
+                if d == domain && r == reason {
+                    self.outcome = Some(f(metadata));
+                }
+            }
+        }
+        self
+    }
+
+    /// Returns the outcome of the matching handler, if any.
+    pub fn finish(self) -> Option<T> {
+        self.outcome
+    }
+
+    /// Returns the outcome of the matching handler or, if no handler
+    /// matched, calls `f` with the `tonic::Status`.
+    pub fn otherwise(self, f: impl FnOnce(&Status) -> T) -> T {
+        match self.outcome {
+            Some(outcome) => outcome,
+            None => f(self.status),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::ReasonMatcher;
+
+    #[test]
+    fn dispatch_on_reason() {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("sku".to_string(), "A-1".to_string());
+
+        let mut err_details = ErrorDetails::new();
+        err_details.set_error_info("INVALID_SKU", "shop.example.local", metadata);
+
+        let status = Status::with_error_details(Code::InvalidArgument, "invalid", err_details);
+
+        let formatted = format!("{:?}", status.error_reason());
+
+        println!("error reason -> {formatted}");
+
+        let expected = "Some((\"shop.example.local\", \"INVALID_SKU\", {\"sku\": \"A-1\"}))";
+
+        assert!(
+            formatted.eq(expected),
+            "error reason differs from expected result"
+        );
+
+        let outcome = ReasonMatcher::new(&status)
+            .on_domain("other.example.local", "INVALID_SKU", |_| {
+                "other".to_string()
+            })
+            .on("INVALID_SKU", |metadata| {
+                format!("invalid {}", metadata["sku"])
+            })
+            .on("INVALID_SKU", |_| "second".to_string())
+            .finish();
+
+        assert!(
+            outcome.as_deref() == Some("invalid A-1"),
+            "dispatch outcome differs from expected result"
+        );
+
+        let outcome = ReasonMatcher::new(&Status::internal("internal"))
+            .on("INVALID_SKU", |_| Code::InvalidArgument)
+            .otherwise(|status| status.code());
+
+        assert!(outcome == Code::Internal, "fallback handler was not called");
+    }
+}
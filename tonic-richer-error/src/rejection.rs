@@ -0,0 +1,254 @@
+use std::error::Error;
+
+use axum::extract::path::ErrorKind;
+use axum::extract::rejection::{JsonRejection, PathRejection, QueryRejection};
+use axum::http::StatusCode;
+use tonic::{Code, Status};
+
+use super::request_errors::{serde_path_violation, violation_from_message};
+use super::std_messages::{BadRequest, FieldViolation};
+use super::{ErrorDetails, WithErrorDetails};
+
+/// Used to convert axum extractor rejections into a `tonic::Status`. Data
+/// related rejections generate a status with [`BadRequest`] details, so
+/// hybrid REST + gRPC services can report invalid input the same way,
+/// regardless of which edge rejected it.
+pub trait RejectionStatus {
+    /// Converts the rejection into a `tonic::Status`. Rejections caused by
+    /// the request content use `Code::InvalidArgument`, and contain a
+    /// [`BadRequest`] detail whenever the offending field can be described.
+    /// Rejections caused by server misconfiguration use `Code::Internal`.
+    /// # Examples
+    ///
+    /// ```
+    /// use axum::extract::rejection::JsonRejection;
+    /// use tonic::Status;
+    /// use tonic_richer_error::RejectionStatus;
+    ///
+    /// fn handle_rejection(rejection: JsonRejection) -> Status {
+    ///     rejection.into_status()
+    /// }
+    /// ```
+    fn into_status(self) -> Status;
+}
+
+impl From<&JsonRejection> for BadRequest {
+    fn from(rejection: &JsonRejection) -> Self {
+        let violations = match rejection {
+            JsonRejection::JsonDataError(err) => serde_violation(err).into_iter().collect(),
+            JsonRejection::JsonSyntaxError(err) => serde_violation(err).into_iter().collect(),
+            _ => Vec::new(),
+        };
+        BadRequest::new(violations)
+    }
+}
+
+impl From<JsonRejection> for BadRequest {
+    fn from(rejection: JsonRejection) -> Self {
+        BadRequest::from(&rejection)
+    }
+}
+
+impl From<&QueryRejection> for BadRequest {
+    fn from(rejection: &QueryRejection) -> Self {
+        let violations = match rejection {
+            QueryRejection::FailedToDeserializeQueryString(err) => {
+                serde_violation(err).into_iter().collect()
+            }
+            _ => Vec::new(),
+        };
+        BadRequest::new(violations)
+    }
+}
+
+impl From<QueryRejection> for BadRequest {
+    fn from(rejection: QueryRejection) -> Self {
+        BadRequest::from(&rejection)
+    }
+}
+
+impl From<&PathRejection> for BadRequest {
+    fn from(rejection: &PathRejection) -> Self {
+        let err = match rejection {
+            PathRejection::FailedToDeserializePathParams(err) => err,
+            _ => return BadRequest::new(Vec::new()),
+        };
+
+        let violation = match err.kind() {
+            ErrorKind::ParseErrorAtKey {
+                key,
+                value,
+                expected_type,
+            } => FieldViolation::new(
+                key.as_str(),
+                format!("cannot parse `{value}` to a `{expected_type}`"),
+            ),
+            ErrorKind::ParseErrorAtIndex {
+                index,
+                value,
+                expected_type,
+            } => FieldViolation::new(
+                index.to_string(),
+                format!("cannot parse `{value}` to a `{expected_type}`"),
+            ),
+            ErrorKind::InvalidUtf8InPathParam { key } => {
+                FieldViolation::new(key.as_str(), "invalid UTF-8")
+            }
+            ErrorKind::Message(message) => violation_from_message(None, message),
+            _ => FieldViolation::new("", err.body_text()),
+        };
+
+        BadRequest::new(vec![violation])
+    }
+}
+
+impl From<PathRejection> for BadRequest {
+    fn from(rejection: PathRejection) -> Self {
+        BadRequest::from(&rejection)
+    }
+}
+
+impl RejectionStatus for JsonRejection {
+    fn into_status(self) -> Status {
+        let bad_request = BadRequest::from(&self);
+        rejection_status(self.status(), self.to_string(), bad_request)
+    }
+}
+
+impl RejectionStatus for QueryRejection {
+    fn into_status(self) -> Status {
+        let bad_request = BadRequest::from(&self);
+        rejection_status(self.status(), self.to_string(), bad_request)
+    }
+}
+
+impl RejectionStatus for PathRejection {
+    fn into_status(self) -> Status {
+        let bad_request = BadRequest::from(&self);
+        rejection_status(self.status(), self.to_string(), bad_request)
+    }
+}
+
+fn rejection_status(http_status: StatusCode, message: String, bad_request: BadRequest) -> Status {
+    let code = if http_status.is_server_error() {
+        Code::Internal
+    } else {
+        Code::InvalidArgument
+    };
+
+    if bad_request.is_empty() {
+        return Status::new(code, message);
+    }
+
+    let details = ErrorDetails {
+        bad_request: Some(bad_request),
+        ..ErrorDetails::new()
+    };
+
+    Status::with_error_details(code, message, details)
+}
+
+/// Walks the source chain of an axum rejection looking for the underlying
+/// serde error, which carries the path of the offending field.
+fn serde_violation(rejection: &(dyn Error + 'static)) -> Option<FieldViolation> {
+    let mut source = rejection.source();
+
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>() {
+            return Some(serde_path_violation(err));
+        }
+
+        if err.source().is_none() {
+            return Some(violation_from_message(None, &err.to_string()));
+        }
+
+        source = err.source();
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use axum::extract::{FromRequest, FromRequestParts, Json, Query};
+    use axum::http::Request;
+    use serde::Deserialize;
+    use tonic::Code;
+
+    use super::super::WithErrorDetails;
+    use super::{BadRequest, RejectionStatus};
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    struct Payload {
+        name: String,
+        items: Vec<Item>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    struct Item {
+        sku: u32,
+    }
+
+    #[tokio::test]
+    async fn gen_status_from_rejections() {
+        let req = Request::builder()
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(
+                r#"{"name": "a", "items": [{"sku": 1}, {"sku": "x"}]}"#,
+            ))
+            .unwrap();
+
+        let rejection = match Json::<Payload>::from_request(req, &()).await {
+            Err(rejection) => rejection,
+            Ok(_) => panic!("invalid JSON body was accepted"),
+        };
+
+        let formatted = format!("{:?}", BadRequest::from(&rejection));
+
+        println!("BadRequest generated from JsonRejection -> {formatted}");
+
+        let expected = "BadRequest { field_violations: [FieldViolation { field: \"items[1].sku\", description: \"invalid type: string \\\"x\\\", expected u32\", reason: \"\", localized_message: None }] }";
+
+        assert!(
+            formatted.eq(expected),
+            "BadRequest from JsonRejection differs from expected result"
+        );
+
+        let status = rejection.into_status();
+
+        assert!(
+            status.code() == Code::InvalidArgument,
+            "status from JsonRejection has unexpected code"
+        );
+
+        assert!(
+            status.get_details_bad_request().is_some(),
+            "status from JsonRejection is missing BadRequest details"
+        );
+
+        let req = Request::builder()
+            .uri("http://example.local/?name=a")
+            .body(())
+            .unwrap();
+        let (mut parts, _) = req.into_parts();
+
+        let rejection = match Query::<Payload>::from_request_parts(&mut parts, &()).await {
+            Err(rejection) => rejection,
+            Ok(_) => panic!("invalid query string was accepted"),
+        };
+
+        let formatted = format!("{:?}", BadRequest::from(&rejection));
+
+        println!("BadRequest generated from QueryRejection -> {formatted}");
+
+        let expected = "BadRequest { field_violations: [FieldViolation { field: \"items\", description: \"missing field `items`\", reason: \"\", localized_message: None }] }";
+
+        assert!(
+            formatted.eq(expected),
+            "BadRequest from QueryRejection differs from expected result"
+        );
+    }
+}
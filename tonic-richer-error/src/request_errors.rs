@@ -0,0 +1,179 @@
+use prost::DecodeError;
+
+use super::std_messages::BadRequest;
+
+#[cfg(feature = "serde_path_to_error")]
+use super::std_messages::FieldViolation;
+
+impl BadRequest {
+    /// Creates a new [`BadRequest`] struct from a `prost::DecodeError`, with
+    /// a single [`FieldViolation`](crate::FieldViolation). The field path is
+    /// recovered from the message and field names included in the error,
+    /// joined with dots. If the error does not carry field context, the field
+    /// is left empty.
+    /// # Examples
+    ///
+    /// ```
+    /// use prost::Message;
+    /// use tonic_richer_error::BadRequest;
+    ///
+    /// # #[derive(Clone, PartialEq, prost::Message)]
+    /// # pub struct CreateUser {
+    /// #     #[prost(string, tag = "1")]
+    /// #     pub email: String,
+    /// # }
+    /// let err = CreateUser::decode(&[10, 2, 0xff, 0xfe][..]).unwrap_err();
+    ///
+    /// let bad_request = BadRequest::from_decode_error(&err);
+    ///
+    /// assert_eq!(bad_request.field_violations[0].field, "email");
+    /// ```
+    pub fn from_decode_error(err: &DecodeError) -> Self {
+        let message = err.to_string();
+
+        let message = message
+            .strip_prefix("failed to decode Protobuf message: ")
+            .unwrap_or(&message);
+
+        // prost lists the `Message.field` pairs from the innermost field
+        let mut fields = Vec::new();
+        let mut description = message;
+
+        while let Some((segment, rest)) = description.split_once(": ") {
+            match segment.split_once('.') {
+                Some((msg_name, field)) if is_ident(msg_name) && is_ident(field) => {
+                    fields.push(field);
+                    description = rest;
+                }
+                _ => break,
+            }
+        }
+
+        fields.reverse();
+
+        BadRequest::with_violation(fields.join("."), description)
+    }
+
+    /// Creates a new [`BadRequest`] struct from a `serde_path_to_error`
+    /// error, with a single [`FieldViolation`](crate::FieldViolation). The
+    /// field is set to the path of the offending value, as in `items[1].sku`.
+    /// If the path is not known, tries to recover the field name from the
+    /// standard serde `missing field`, `unknown field` and `duplicate field`
+    /// messages.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::BadRequest;
+    ///
+    /// #[derive(Debug, serde::Deserialize)]
+    /// struct CreateUser {
+    ///     age: u8,
+    /// }
+    ///
+    /// let de = &mut serde_json::Deserializer::from_str(r#"{"age": 300}"#);
+    /// let err = serde_path_to_error::deserialize::<_, CreateUser>(de).unwrap_err();
+    ///
+    /// let bad_request = BadRequest::from_serde_path_error(&err);
+    ///
+    /// assert_eq!(bad_request.field_violations[0].field, "age");
+    /// ```
+    #[cfg(feature = "serde_path_to_error")]
+    pub fn from_serde_path_error<E: std::fmt::Display>(
+        err: &serde_path_to_error::Error<E>,
+    ) -> Self {
+        BadRequest::new(vec![serde_path_violation(err)])
+    }
+}
+
+/// Builds a [`FieldViolation`] from a `serde_path_to_error` error.
+#[cfg(feature = "serde_path_to_error")]
+pub(crate) fn serde_path_violation<E: std::fmt::Display>(
+    err: &serde_path_to_error::Error<E>,
+) -> FieldViolation {
+    let path = match err.path().to_string() {
+        path if path == "." => None,
+        path => Some(path),
+    };
+
+    violation_from_message(path, &err.inner().to_string())
+}
+
+/// Builds a [`FieldViolation`] from a serde error message. If no field path
+/// is known, tries to recover the field name from the standard serde
+/// `missing field`, `unknown field` and `duplicate field` messages.
+#[cfg(feature = "serde_path_to_error")]
+pub(crate) fn violation_from_message(path: Option<String>, message: &str) -> FieldViolation {
+    // serde_json appends the error position, which is not relevant here
+    let description = match message.rfind(" at line ") {
+        Some(index) => &message[..index],
+        None => message,
+    };
+
+    let field = path.or_else(|| {
+        ["missing field `", "unknown field `", "duplicate field `"]
+            .iter()
+            .find_map(|prefix| description.strip_prefix(prefix))
+            .and_then(|rest| rest.split('`').next())
+            .map(String::from)
+    });
+
+    FieldViolation::new(field.unwrap_or_default(), description)
+}
+
+fn is_ident(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+
+    use prost::Message;
+
+    use super::super::BadRequest;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Address {
+        #[prost(string, tag = "1")]
+        city: String,
+    }
+
+    #[derive(Clone, PartialEq, Message)]
+    struct CreateUser {
+        #[prost(message, optional, tag = "1")]
+        address: Option<Address>,
+    }
+
+    #[test]
+    fn gen_bad_request_from_decode_error() {
+        // `address.city` is set to invalid UTF-8
+        let err = match CreateUser::decode(&[10, 4, 10, 2, 0xff, 0xfe][..]) {
+            Err(err) => err,
+            Ok(_) => panic!("invalid message was decoded"),
+        };
+
+        let formatted = format!("{:?}", BadRequest::from_decode_error(&err));
+
+        println!("BadRequest generated from DecodeError -> {formatted}");
+
+        let expected = "BadRequest { field_violations: [FieldViolation { field: \"address.city\", description: \"invalid string value: data is not UTF-8 encoded\", reason: \"\", localized_message: None }] }";
+
+        assert!(
+            formatted.eq(expected),
+            "BadRequest from DecodeError differs from expected result"
+        );
+
+        let err = match CreateUser::decode(&[10][..]) {
+            Err(err) => err,
+            Ok(_) => panic!("truncated message was decoded"),
+        };
+
+        let bad_request = BadRequest::from_decode_error(&err);
+
+        println!("BadRequest generated from DecodeError -> {:?}", bad_request);
+
+        assert!(
+            bad_request.field_violations[0].field.eq("address"),
+            "field differs from expected result"
+        );
+    }
+}
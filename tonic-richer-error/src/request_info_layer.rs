@@ -0,0 +1,286 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tonic::codegen::{http, BoxFuture, Service};
+use tower_layer::Layer;
+
+use super::status_enricher::replace_status;
+use super::WithErrorDetails;
+
+type IdGenerator = Arc<dyn Fn() -> String + Send + Sync>;
+
+/// A tower `Layer` that can be installed on a tonic server, to append a
+/// [`crate::RequestInfo`] detail to every error status returned by the
+/// service. The request ID is read from the request metadata (the
+/// `x-request-id` key, by default) or generated if absent. Generated IDs are
+/// also inserted in the request metadata, so handlers can access them.
+///
+/// Statuses that already carry [`crate::RequestInfo`] details are left
+/// untouched. Only statuses sent as trailers-only responses (the usual case
+/// for errors returned by unary endpoints) are enriched.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::{RequestInfoLayer, RequestInfoService};
+/// use tower::ServiceBuilder;
+///
+/// // Where `service` is a tonic generated server, like `GreeterServer`
+/// fn with_request_info<S>(service: S) -> RequestInfoService<S> {
+///     ServiceBuilder::new()
+///         .layer(RequestInfoLayer::new().serving_data("greeter-1"))
+///         .service(service)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RequestInfoLayer {
+    header_name: http::HeaderName,
+    serving_data: String,
+    id_generator: IdGenerator,
+}
+
+impl RequestInfoLayer {
+    /// Default metadata key used to read the request ID.
+    pub const DEFAULT_HEADER_NAME: &'static str = "x-request-id";
+
+    /// Creates a new [`RequestInfoLayer`], reading request IDs from
+    /// [`RequestInfoLayer::DEFAULT_HEADER_NAME`], and generating process
+    /// unique IDs for requests that do not contain one.
+    pub fn new() -> Self {
+        RequestInfoLayer {
+            header_name: http::HeaderName::from_static(RequestInfoLayer::DEFAULT_HEADER_NAME),
+            serving_data: String::new(),
+            id_generator: Arc::new(generate_request_id),
+        }
+    }
+
+    /// Sets the metadata key used to read the request ID.
+    pub fn header_name(mut self, header_name: http::HeaderName) -> Self {
+        self.header_name = header_name;
+        self
+    }
+
+    /// Sets the `serving_data` used in the generated [`crate::RequestInfo`]
+    /// details.
+    pub fn serving_data(mut self, serving_data: impl Into<String>) -> Self {
+        self.serving_data = serving_data.into();
+        self
+    }
+
+    /// Sets the function used to generate request IDs, for requests that do
+    /// not contain one.
+    pub fn id_generator(
+        mut self,
+        id_generator: impl Fn() -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.id_generator = Arc::new(id_generator);
+        self
+    }
+}
+
+impl Default for RequestInfoLayer {
+    fn default() -> Self {
+        RequestInfoLayer::new()
+    }
+}
+
+impl fmt::Debug for RequestInfoLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestInfoLayer")
+            .field("header_name", &self.header_name)
+            .field("serving_data", &self.serving_data)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for RequestInfoLayer {
+    type Service = RequestInfoService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestInfoService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service generated by [`RequestInfoLayer`].
+#[derive(Clone, Debug)]
+pub struct RequestInfoService<S> {
+    inner: S,
+    layer: RequestInfoLayer,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for RequestInfoService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<ResBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<ReqBody>) -> Self::Future {
+        let header_name = &self.layer.header_name;
+
+        let request_id = match req
+            .headers()
+            .get(header_name)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(request_id) => request_id.to_string(),
+            None => {
+                let request_id = (self.layer.id_generator)();
+                if let Ok(value) = http::HeaderValue::from_str(&request_id) {
+                    req.headers_mut().insert(header_name.clone(), value);
+                }
+                request_id
+            }
+        };
+
+        let serving_data = self.layer.serving_data.clone();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let mut response = future.await?;
+            add_request_info(response.headers_mut(), request_id, serving_data);
+            Ok(response)
+        })
+    }
+}
+
+/// Appends [`crate::RequestInfo`] details to the status contained in the
+/// headers of a trailers-only response, if any.
+fn add_request_info(headers: &mut http::HeaderMap, request_id: String, serving_data: String) {
+    replace_status(headers, |status| {
+        let mut details = match status.try_error_details() {
+            Ok(details) => details.unwrap_or_default(),
+            Err(_) => return status,
+        };
+
+        if details.request_info.is_some() {
+            return status;
+        }
+
+        details.set_request_info(request_id, serving_data);
+
+        status.clone_with_details(details)
+    });
+}
+
+/// Generates a process unique request ID, combining the process start time
+/// and a counter.
+fn generate_request_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    static START: std::sync::OnceLock<u128> = std::sync::OnceLock::new();
+
+    let start = START.get_or_init(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or_default()
+    });
+
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    format!("{start:x}-{count:x}")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::convert::Infallible;
+    use std::task::{Context, Poll};
+
+    use tonic::body::BoxBody;
+    use tonic::codegen::{http, BoxFuture, Service};
+    use tonic::{Code, Status};
+    use tower_layer::Layer;
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::RequestInfoLayer;
+
+    /// Fails every call with a status carrying `BadRequest` details.
+    #[derive(Clone)]
+    struct FailingService;
+
+    impl Service<http::Request<()>> for FailingService {
+        type Response = http::Response<BoxBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: http::Request<()>) -> Self::Future {
+            assert!(
+                req.headers().contains_key("x-request-id"),
+                "request ID was not inserted in the request metadata"
+            );
+
+            Box::pin(async move {
+                let status = Status::with_error_details(
+                    Code::InvalidArgument,
+                    "bad request",
+                    ErrorDetails::with_bad_request_violation("field", "description"),
+                );
+                Ok(status.to_http())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn add_request_info() {
+        let mut svc = RequestInfoLayer::new()
+            .serving_data("server-1")
+            .layer(FailingService);
+
+        let req = http::Request::builder()
+            .header("x-request-id", "some-request-id")
+            .body(())
+            .unwrap();
+
+        let response = svc.call(req).await.unwrap();
+
+        let status = match Status::from_header_map(response.headers()) {
+            Some(status) => status,
+            None => panic!("response does not contain a status"),
+        };
+
+        let formatted = format!(
+            "{:?} {:?}",
+            status.get_details_request_info(),
+            status.get_details_bad_request()
+        );
+
+        println!("enriched status details -> {formatted}");
+
+        let expected = "Some(RequestInfo { request_id: \"some-request-id\", serving_data: \"server-1\" }) Some(BadRequest { field_violations: [FieldViolation { field: \"field\", description: \"description\", reason: \"\", localized_message: None }] })";
+
+        assert!(
+            formatted.eq(expected),
+            "enriched status details differ from expected result"
+        );
+
+        let mut svc = RequestInfoLayer::new()
+            .id_generator(|| "generated-id".into())
+            .layer(FailingService);
+
+        let response = svc.call(http::Request::new(())).await.unwrap();
+
+        let request_info = Status::from_header_map(response.headers())
+            .and_then(|status| status.get_details_request_info());
+
+        assert!(
+            matches!(request_info, Some(info) if info.request_id == "generated-id"),
+            "generated request ID was not used"
+        );
+    }
+}
@@ -0,0 +1,521 @@
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use http_body::{Body, SizeHint};
+use tonic::body::BoxBody;
+use tonic::codegen::{http, BoxFuture, Bytes, Service, StdError};
+use tonic::{Code, Status};
+use tower_layer::Layer;
+
+use super::WithErrorDetails;
+
+/// A tower `Layer` that can be installed on a tonic client channel, to
+/// automatically retry calls that fail with a status carrying
+/// [`crate::RetryInfo`] details. The `retry_delay` informed by the server is
+/// honored, limited by a configurable cap.
+///
+/// The request is sent right away, while a copy of its body is kept, up to
+/// a configurable size. Only statuses sent as trailers-only responses (the
+/// usual case for errors returned by unary endpoints) are retried, and only
+/// if the whole request body was sent by the time the response arrived, so
+/// streaming calls are never delayed. Request extensions are only passed to
+/// the first attempt, since they cannot be cloned.
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tonic_richer_error::{RetryOnRetryInfo, RetryOnRetryInfoLayer};
+/// use tower::ServiceBuilder;
+///
+/// // Where `channel` is a `tonic::transport::Channel`
+/// fn with_retries<C>(channel: C) -> RetryOnRetryInfo<C> {
+///     ServiceBuilder::new()
+///         .layer(
+///             RetryOnRetryInfoLayer::new()
+///                 .max_attempts(5)
+///                 .max_delay(Duration::from_secs(10)),
+///         )
+///         .service(channel)
+/// }
+///
+/// // The returned service can then be used to build the generated client
+/// ```
+#[derive(Clone, Debug)]
+pub struct RetryOnRetryInfoLayer {
+    max_attempts: usize,
+    max_delay: Duration,
+    max_body_size: usize,
+}
+
+impl RetryOnRetryInfoLayer {
+    /// Default maximum number of attempts, including the first one.
+    pub const DEFAULT_MAX_ATTEMPTS: usize = 3;
+
+    /// Default cap applied to the `retry_delay` informed by the server.
+    pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+    /// Default maximum size, in bytes, of the request bodies kept to be
+    /// resent.
+    pub const DEFAULT_MAX_BODY_SIZE: usize = 64 * 1024;
+
+    /// Creates a new [`RetryOnRetryInfoLayer`], using
+    /// [`RetryOnRetryInfoLayer::DEFAULT_MAX_ATTEMPTS`],
+    /// [`RetryOnRetryInfoLayer::DEFAULT_MAX_DELAY`] and
+    /// [`RetryOnRetryInfoLayer::DEFAULT_MAX_BODY_SIZE`].
+    pub fn new() -> Self {
+        RetryOnRetryInfoLayer {
+            max_attempts: RetryOnRetryInfoLayer::DEFAULT_MAX_ATTEMPTS,
+            max_delay: RetryOnRetryInfoLayer::DEFAULT_MAX_DELAY,
+            max_body_size: RetryOnRetryInfoLayer::DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+
+    /// Sets the maximum number of attempts, including the first one. Values
+    /// lower than 1 are treated as 1.
+    pub fn max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the maximum delay between attempts. Larger `retry_delay`s
+    /// informed by the server are capped to this value.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of the request bodies kept to be
+    /// resent. Calls with larger bodies are not retried.
+    pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+}
+
+impl Default for RetryOnRetryInfoLayer {
+    fn default() -> Self {
+        RetryOnRetryInfoLayer::new()
+    }
+}
+
+impl<S> Layer<S> for RetryOnRetryInfoLayer {
+    type Service = RetryOnRetryInfo<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RetryOnRetryInfo {
+            inner,
+            max_attempts: self.max_attempts,
+            max_delay: self.max_delay,
+            max_body_size: self.max_body_size,
+        }
+    }
+}
+
+/// Service generated by [`RetryOnRetryInfoLayer`].
+#[derive(Clone, Debug)]
+pub struct RetryOnRetryInfo<S> {
+    inner: S,
+    max_attempts: usize,
+    max_delay: Duration,
+    max_body_size: usize,
+}
+
+impl<S, ResBody> Service<http::Request<BoxBody>> for RetryOnRetryInfo<S>
+where
+    S: Service<http::Request<BoxBody>, Response = http::Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Into<StdError>,
+    ResBody: Send + 'static,
+{
+    type Response = http::Response<ResBody>;
+    type Error = StdError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: http::Request<BoxBody>) -> Self::Future {
+        // The ready service must be used for the first attempt
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let max_attempts = self.max_attempts;
+        let max_delay = self.max_delay;
+
+        let mut head = http::Request::new(());
+        *head.method_mut() = req.method().clone();
+        *head.uri_mut() = req.uri().clone();
+        *head.version_mut() = req.version();
+        *head.headers_mut() = req.headers().clone();
+
+        let recording = Arc::new(Mutex::new(Recording::new(self.max_body_size)));
+
+        let req = req.map(|body| {
+            RecordingBody {
+                inner: body,
+                recording: recording.clone(),
+            }
+            .boxed_unsync()
+        });
+
+        Box::pin(async move {
+            let mut response = inner.call(req).await.map_err(Into::into)?;
+
+            let mut attempt = 1;
+
+            loop {
+                if attempt >= max_attempts {
+                    return Ok(response);
+                }
+
+                let delay = match retry_delay(response.headers()) {
+                    Some(delay) => delay.min(max_delay),
+                    None => return Ok(response),
+                };
+
+                let body = match lock(&recording).replayable_body() {
+                    Some(body) => body,
+                    None => return Ok(response),
+                };
+
+                tokio::time::sleep(delay).await;
+
+                std::future::poll_fn(|cx| inner.poll_ready(cx))
+                    .await
+                    .map_err(Into::into)?;
+
+                let mut req = http::Request::new(full_body(body));
+                *req.method_mut() = head.method().clone();
+                *req.uri_mut() = head.uri().clone();
+                *req.version_mut() = head.version();
+                *req.headers_mut() = head.headers().clone();
+
+                response = inner.call(req).await.map_err(Into::into)?;
+
+                attempt += 1;
+            }
+        })
+    }
+}
+
+/// Returns the `retry_delay` of the status contained in a trailers-only
+/// response, if any. A [`crate::RetryInfo`] without `retry_delay` means the
+/// call can be retried immediately.
+fn retry_delay(headers: &http::HeaderMap) -> Option<Duration> {
+    let status = Status::from_header_map(headers)?;
+
+    if status.code() == Code::Ok {
+        return None;
+    }
+
+    let retry_info = status.get_details_retry_info()?;
+
+    Some(retry_info.retry_delay.unwrap_or(Duration::ZERO))
+}
+
+fn full_body(body: Bytes) -> BoxBody {
+    http_body::Full::new(body)
+        .map_err(|err| match err {})
+        .boxed_unsync()
+}
+
+/// Copy of the request body sent in the first attempt.
+struct Recording {
+    data: Vec<u8>,
+    max_size: usize,
+    complete: bool,
+    discarded: bool,
+}
+
+impl Recording {
+    fn new(max_size: usize) -> Self {
+        Recording {
+            data: Vec::new(),
+            max_size,
+            complete: false,
+            discarded: false,
+        }
+    }
+
+    fn push(&mut self, chunk: &Bytes) {
+        if self.data.len() + chunk.len() > self.max_size {
+            self.discarded = true;
+            self.data = Vec::new();
+        }
+
+        if !self.discarded {
+            self.data.extend_from_slice(chunk);
+        }
+    }
+
+    /// Returns the body to be resent, if the whole body was sent without
+    /// exceeding the maximum size.
+    fn replayable_body(&self) -> Option<Bytes> {
+        if !self.complete || self.discarded {
+            return None;
+        }
+
+        Some(Bytes::copy_from_slice(&self.data))
+    }
+}
+
+/// Locks the recording, ignoring poisoning, since it is never left in an
+/// inconsistent state.
+fn lock(recording: &Mutex<Recording>) -> MutexGuard<'_, Recording> {
+    match recording.lock() {
+        Ok(recording) => recording,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Request body that forwards the data of the inner body as it becomes
+/// available, keeping a copy of it.
+struct RecordingBody {
+    inner: BoxBody,
+    recording: Arc<Mutex<Recording>>,
+}
+
+impl Body for RecordingBody {
+    type Data = Bytes;
+    type Error = Status;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.inner).poll_data(cx);
+
+        if let Poll::Ready(data) = &poll {
+            let mut recording = lock(&this.recording);
+
+            match data {
+                Some(Ok(chunk)) => recording.push(chunk),
+                Some(Err(_)) => recording.discarded = true,
+                None => recording.complete = true,
+            }
+        }
+
+        poll
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.inner).poll_trailers(cx);
+
+        // Request trailers are not part of the copy, so the body cannot be
+        // resent as is
+        if let Poll::Ready(Ok(Some(_)) | Err(_)) = &poll {
+            lock(&this.recording).discarded = true;
+        }
+
+        poll
+    }
+
+    fn is_end_stream(&self) -> bool {
+        // The data of ended bodies might not be polled to the end
+        let end_stream = self.inner.is_end_stream();
+
+        if end_stream {
+            lock(&self.recording).complete = true;
+        }
+
+        end_stream
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::convert::Infallible;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    use http_body::Body;
+    use tonic::body::BoxBody;
+    use tonic::codegen::{empty_body, http, BoxFuture, Bytes, Service};
+    use tonic::{Code, Status};
+    use tower_layer::Layer;
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::{full_body, RetryOnRetryInfoLayer};
+
+    /// Extension set on the requests sent by the tests.
+    #[derive(Clone)]
+    struct RequestTag;
+
+    /// Reads the request body, and fails with a status carrying `RetryInfo`
+    /// details until `failures` calls were made. If `read_body` is `false`,
+    /// fails without waiting for the request body.
+    #[derive(Clone)]
+    struct FlakyService {
+        calls: Arc<AtomicUsize>,
+        tagged_calls: Arc<AtomicUsize>,
+        bodies: Arc<Mutex<Vec<Bytes>>>,
+        failures: usize,
+        read_body: bool,
+    }
+
+    impl Service<http::Request<BoxBody>> for FlakyService {
+        type Response = http::Response<BoxBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: http::Request<BoxBody>) -> Self::Future {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+
+            if req.extensions().get::<RequestTag>().is_some() {
+                self.tagged_calls.fetch_add(1, Ordering::SeqCst);
+            }
+
+            let bodies = self.bodies.clone();
+            let failures = self.failures;
+            let read_body = self.read_body;
+
+            Box::pin(async move {
+                let mut body = req.into_body();
+
+                if read_body {
+                    let mut data = Vec::new();
+
+                    while let Some(Ok(chunk)) = body.data().await {
+                        data.extend_from_slice(&chunk);
+                    }
+
+                    bodies.lock().unwrap().push(data.into());
+                }
+
+                if calls <= failures {
+                    let status = Status::with_error_details(
+                        Code::Unavailable,
+                        "try again later",
+                        ErrorDetails::with_retry_info(Some(Duration::from_millis(5))),
+                    );
+                    return Ok(status.to_http());
+                }
+                Ok(http::Response::new(empty_body()))
+            })
+        }
+    }
+
+    async fn call_with_retries(
+        max_attempts: usize,
+        failures: usize,
+        body: BoxBody,
+        read_body: bool,
+    ) -> (usize, usize, Code, Vec<Bytes>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let tagged_calls = Arc::new(AtomicUsize::new(0));
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+
+        let mut svc = RetryOnRetryInfoLayer::new()
+            .max_attempts(max_attempts)
+            .layer(FlakyService {
+                calls: calls.clone(),
+                tagged_calls: tagged_calls.clone(),
+                bodies: bodies.clone(),
+                failures,
+                read_body,
+            });
+
+        std::future::poll_fn(|cx| svc.poll_ready(cx)).await.unwrap();
+
+        let mut req = http::Request::new(body);
+        req.extensions_mut().insert(RequestTag);
+
+        let response = svc.call(req).await.unwrap();
+
+        let code = Status::from_header_map(response.headers())
+            .map(|status| status.code())
+            .unwrap_or(Code::Ok);
+
+        let bodies = bodies.lock().unwrap().clone();
+
+        (
+            calls.load(Ordering::SeqCst),
+            tagged_calls.load(Ordering::SeqCst),
+            code,
+            bodies,
+        )
+    }
+
+    #[tokio::test]
+    async fn retry_on_retry_info() {
+        let body = full_body(Bytes::from_static(b"request"));
+
+        let (calls, tagged_calls, code, bodies) = call_with_retries(3, 2, body, true).await;
+
+        assert!(
+            calls == 3 && code == Code::Ok,
+            "call was not retried until success: {calls} calls, {code:?}"
+        );
+
+        assert!(
+            bodies.iter().all(|body| body == "request"),
+            "request body was not resent: {bodies:?}"
+        );
+
+        assert!(
+            tagged_calls == 1,
+            "request extensions were not passed to the first attempt"
+        );
+
+        let (calls, _, code, _) = call_with_retries(2, 5, empty_body(), true).await;
+
+        assert!(
+            calls == 2 && code == Code::Unavailable,
+            "max attempts were not respected: {calls} calls, {code:?}"
+        );
+    }
+
+    /// Client stream that is still open, without data to send.
+    struct OpenStream;
+
+    impl Body for OpenStream {
+        type Data = Bytes;
+        type Error = Status;
+
+        fn poll_data(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            Poll::Pending
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+        ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+            Poll::Pending
+        }
+    }
+
+    #[tokio::test]
+    async fn pass_streaming_calls_through() {
+        let body = OpenStream.boxed_unsync();
+
+        let (calls, _, code, _) = call_with_retries(3, 1, body, false).await;
+
+        assert!(
+            calls == 1 && code == Code::Unavailable,
+            "streaming call was retried: {calls} calls, {code:?}"
+        );
+    }
+}
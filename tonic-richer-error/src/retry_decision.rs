@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use tonic::Code;
+
+use super::ErrorDetails;
+
+/// Minimum delay before retrying a `Code::ResourceExhausted` status that
+/// carries no [`crate::RetryInfo`] details.
+const RESOURCE_EXHAUSTED_DELAY: Duration = Duration::from_secs(30);
+
+/// Well known [`crate::ErrorInfo`] reasons, from `google.api.ErrorReason`,
+/// describing failures that cannot be solved by retrying.
+const NON_RETRYABLE_REASONS: [&str; 14] = [
+    "SERVICE_DISABLED",
+    "BILLING_DISABLED",
+    "API_KEY_INVALID",
+    "API_KEY_SERVICE_BLOCKED",
+    "API_KEY_HTTP_REFERRER_BLOCKED",
+    "API_KEY_IP_ADDRESS_BLOCKED",
+    "API_KEY_ANDROID_APP_BLOCKED",
+    "API_KEY_IOS_APP_BLOCKED",
+    "CONSUMER_INVALID",
+    "RESOURCE_PROJECT_INVALID",
+    "ACCESS_TOKEN_TYPE_UNSUPPORTED",
+    "ACCESS_TOKEN_SCOPE_INSUFFICIENT",
+    "ORG_RESTRICTION_VIOLATION",
+    "LOCATION_POLICY_VIOLATION",
+];
+
+/// Whether, and when, a failed call should be retried, following the
+/// [retrying errors] guidance of the Google API design guide. Obtained with
+/// [`crate::WithErrorDetails::retry_decision`].
+///
+/// [retrying errors]: https://cloud.google.com/apis/design/errors#retrying_errors
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{ErrorDetails, RetryDecision, WithErrorDetails};
+///
+/// let status = Status::with_error_details(
+///     Code::Unavailable,
+///     "service unavailable",
+///     ErrorDetails::with_retry_info(Some(Duration::from_secs(5))),
+/// );
+///
+/// match status.retry_decision() {
+///     RetryDecision::After(delay) => assert_eq!(delay, Duration::from_secs(5)),
+///     decision => panic!("unexpected decision {:?}", decision),
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetryDecision {
+    /// The call should be retried after the given delay.
+    After(Duration),
+
+    /// The call can be retried right away, usually with the client's own
+    /// backoff.
+    Immediately,
+
+    /// The call should not be retried.
+    DontRetry,
+}
+
+impl RetryDecision {
+    /// Computes the decision for a status with the given code and error
+    /// details:
+    /// - `Code::Ok` is never retried.
+    /// - If [`crate::RetryInfo`] details are present, the call is retried
+    ///   after the advertised delay, or immediately if no delay is set.
+    /// - If the [`crate::ErrorInfo`] reason is a well known non-retryable
+    ///   `google.api.ErrorReason`, such as `SERVICE_DISABLED` or
+    ///   `API_KEY_INVALID`, the call is not retried.
+    /// - `Code::Unavailable` is retried immediately.
+    /// - `Code::ResourceExhausted` is retried after 30 seconds.
+    /// - Other codes are not retried.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tonic::Code;
+    /// use tonic_richer_error::{ErrorDetails, RetryDecision};
+    ///
+    /// let err_details = ErrorDetails::new();
+    ///
+    /// assert_eq!(
+    ///     RetryDecision::from_details(Code::Unavailable, &err_details),
+    ///     RetryDecision::Immediately,
+    /// );
+    /// assert_eq!(
+    ///     RetryDecision::from_details(Code::InvalidArgument, &err_details),
+    ///     RetryDecision::DontRetry,
+    /// );
+    /// ```
+    pub fn from_details(code: Code, details: &ErrorDetails) -> Self {
+        if code == Code::Ok {
+            return RetryDecision::DontRetry;
+        }
+
+        if let Some(retry_info) = &details.retry_info {
+            return match retry_info.retry_delay {
+                Some(delay) if !delay.is_zero() => RetryDecision::After(delay),
+                _ => RetryDecision::Immediately,
+            };
+        }
+
+        if let Some(error_info) = &details.error_info {
+            if NON_RETRYABLE_REASONS.contains(&error_info.reason.as_str()) {
+                return RetryDecision::DontRetry;
+            }
+        }
+
+        match code {
+            Code::Unavailable => RetryDecision::Immediately,
+            Code::ResourceExhausted => RetryDecision::After(RESOURCE_EXHAUSTED_DELAY),
+            _ => RetryDecision::DontRetry,
+        }
+    }
+
+    /// Returns `true` if the call should be retried.
+    pub fn should_retry(&self) -> bool {
+        !matches!(self, RetryDecision::DontRetry)
+    }
+
+    /// Returns the delay to wait before retrying, if any. Returns
+    /// `Some(Duration::ZERO)` for [`RetryDecision::Immediately`].
+    pub fn delay(&self) -> Option<Duration> {
+        match self {
+            RetryDecision::After(delay) => Some(*delay),
+            RetryDecision::Immediately => Some(Duration::ZERO),
+            RetryDecision::DontRetry => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::RetryDecision;
+
+    #[test]
+    fn retry_decisions() {
+        let mut disabled = ErrorDetails::new();
+        disabled.set_error_info("SERVICE_DISABLED", "googleapis.com", Default::default());
+
+        let mut retry_now = ErrorDetails::new();
+        retry_now.set_retry_info(None);
+
+        let statuses = [
+            Status::with_error_details(
+                Code::Unavailable,
+                "unavailable",
+                ErrorDetails::with_retry_info(Some(Duration::from_secs(5))),
+            ),
+            Status::with_error_details(Code::Aborted, "aborted", retry_now),
+            Status::new(Code::Unavailable, "unavailable"),
+            Status::with_error_details(Code::Unavailable, "unavailable", disabled),
+            Status::new(Code::ResourceExhausted, "quota"),
+            Status::new(Code::InvalidArgument, "bad request"),
+            Status::new(Code::Ok, ""),
+        ];
+
+        let decisions: Vec<RetryDecision> = statuses
+            .iter()
+            .map(|status| status.retry_decision())
+            .collect();
+
+        println!("retry decisions -> {:?}", decisions);
+
+        let expected = [
+            RetryDecision::After(Duration::from_secs(5)),
+            RetryDecision::Immediately,
+            RetryDecision::Immediately,
+            RetryDecision::DontRetry,
+            RetryDecision::After(Duration::from_secs(30)),
+            RetryDecision::DontRetry,
+            RetryDecision::DontRetry,
+        ];
+
+        assert!(
+            decisions.eq(&expected),
+            "retry decisions differ from expected result"
+        );
+
+        let delays: Vec<Option<Duration>> = decisions.iter().map(RetryDecision::delay).collect();
+
+        assert!(
+            delays[1] == Some(Duration::ZERO) && delays[3].is_none(),
+            "retry delays differ from expected result"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use tonic::Status;
+
+use super::WithErrorDetails;
+
+/// Extension trait for `tonic::Status`, streamlining hand-written client
+/// retry loops that honor the [`crate::RetryInfo`] details sent by servers.
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{ErrorDetails, RetryStatusExt, WithErrorDetails};
+///
+/// # async fn call() -> Result<(), Status> {
+/// #     Err(Status::with_error_details(
+/// #         Code::Unavailable,
+/// #         "unavailable",
+/// #         ErrorDetails::with_retry_info(Some(Duration::from_millis(10))),
+/// #     ))
+/// # }
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// for _ in 0..3 {
+///     match call().await {
+///         Ok(response) => break,
+///         Err(status) => {
+///             if !status.wait_for_retry().await {
+///                 break;
+///             }
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub trait RetryStatusExt {
+    /// Default cap applied to the `retry_delay` informed by the server.
+    const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+    /// Sleeps for the `retry_delay` of the [`crate::RetryInfo`] details found
+    /// on `tonic::Status`, capped by [`RetryStatusExt::DEFAULT_MAX_DELAY`].
+    /// Returns `true` if a delay was advertised, and `false`, without
+    /// sleeping, otherwise.
+    fn wait_for_retry(&self) -> Pin<Box<dyn Future<Output = bool> + Send + 'static>> {
+        self.wait_for_retry_max(Self::DEFAULT_MAX_DELAY)
+    }
+
+    /// Sleeps for the `retry_delay` of the [`crate::RetryInfo`] details found
+    /// on `tonic::Status`, capped by `max_delay`. Returns `true` if a delay
+    /// was advertised, and `false`, without sleeping, otherwise.
+    fn wait_for_retry_max(
+        &self,
+        max_delay: Duration,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'static>>;
+}
+
+impl RetryStatusExt for Status {
+    fn wait_for_retry_max(
+        &self,
+        max_delay: Duration,
+    ) -> Pin<Box<dyn Future<Output = bool> + Send + 'static>> {
+        let retry_delay = self
+            .get_details_retry_info()
+            .and_then(|retry_info| retry_info.retry_delay);
+
+        Box::pin(async move {
+            match retry_delay {
+                Some(delay) => {
+                    tokio::time::sleep(delay.min(max_delay)).await;
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::{Duration, Instant};
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::RetryStatusExt;
+
+    #[tokio::test]
+    async fn wait_for_retry() {
+        let status = Status::with_error_details(
+            Code::Unavailable,
+            "unavailable",
+            ErrorDetails::with_retry_info(Some(Duration::from_secs(60))),
+        );
+
+        let start = Instant::now();
+
+        let waited = status.wait_for_retry_max(Duration::from_millis(5)).await;
+
+        let elapsed = start.elapsed();
+
+        println!("waited for retry -> {waited} ({elapsed:?})");
+
+        assert!(
+            waited && elapsed >= Duration::from_millis(5) && elapsed < Duration::from_secs(30),
+            "advertised delay was not capped"
+        );
+
+        let status = Status::new(Code::Unavailable, "unavailable");
+
+        assert!(
+            !status.wait_for_retry().await,
+            "status without RetryInfo reported an advertised delay"
+        );
+    }
+}
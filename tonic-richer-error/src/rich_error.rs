@@ -0,0 +1,152 @@
+use std::fmt;
+
+use tonic::{Code, Status};
+
+use super::{ErrorDetails, WithErrorDetails};
+
+/// Wraps a `tonic::Status` together with its parsed [`ErrorDetails`].
+/// Implements `std::error::Error`, with the `tonic::Status` as source, and
+/// `Display`, with a human-readable summary of the violations, help links
+/// and localized message. Can be used with `?` and error handling crates
+/// like `anyhow` in client applications.
+/// # Examples
+///
+/// ```
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{ErrorDetails, RichError, WithErrorDetails};
+///
+/// let status = Status::with_error_details(
+///     Code::InvalidArgument,
+///     "bad request",
+///     ErrorDetails::with_bad_request_violation("field", "description"),
+/// );
+///
+/// let err = RichError::from(status);
+///
+/// assert_eq!(err.to_string(), "InvalidArgument: bad request; field: description");
+/// ```
+#[derive(Debug)]
+pub struct RichError {
+    status: Status,
+    details: ErrorDetails,
+}
+
+impl RichError {
+    /// Creates a new [`RichError`], parsing the error details of the
+    /// `tonic::Status`. Malformed details are ignored.
+    pub fn new(status: Status) -> Self {
+        let details = status.error_details();
+        RichError { status, details }
+    }
+
+    /// Returns the status code.
+    pub fn code(&self) -> Code {
+        self.status.code()
+    }
+
+    /// Returns the status message.
+    pub fn message(&self) -> &str {
+        self.status.message()
+    }
+
+    /// Returns the wrapped `tonic::Status`.
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    /// Returns the parsed [`ErrorDetails`].
+    pub fn details(&self) -> &ErrorDetails {
+        &self.details
+    }
+
+    /// Consumes the [`RichError`], returning the wrapped `tonic::Status` and
+    /// the parsed [`ErrorDetails`].
+    pub fn into_parts(self) -> (Status, ErrorDetails) {
+        (self.status, self.details)
+    }
+}
+
+impl From<Status> for RichError {
+    fn from(status: Status) -> Self {
+        RichError::new(status)
+    }
+}
+
+impl From<RichError> for Status {
+    fn from(err: RichError) -> Self {
+        err.status
+    }
+}
+
+/// Formats as `Code: message`, followed by the violations, help links and
+/// localized message, each formatted by its own `Display` implementation and
+/// separated by `"; "`.
+impl fmt::Display for RichError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.status.code(), self.status.message())?;
+
+        let details: [Option<&dyn fmt::Display>; 5] = [
+            self.details.quota_failure.as_ref().map(|d| d as _),
+            self.details.precondition_failure.as_ref().map(|d| d as _),
+            self.details.bad_request.as_ref().map(|d| d as _),
+            self.details.help.as_ref().map(|d| d as _),
+            self.details.localized_message.as_ref().map(|d| d as _),
+        ];
+
+        for detail in details.into_iter().flatten() {
+            write!(f, "; {detail}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for RichError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::error::Error;
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::RichError;
+
+    #[test]
+    fn display_rich_error() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .add_precondition_failure_violation("TOS", "example.local", "terms not accepted")
+            .add_help_link("terms of service", "https://example.local/tos")
+            .set_localized_message("en-US", "Please accept the terms of service");
+
+        let status = Status::with_error_details(
+            Code::FailedPrecondition,
+            "precondition failed",
+            err_details,
+        );
+
+        let err = RichError::from(status);
+
+        let formatted = err.to_string();
+
+        println!("RichError -> {formatted}");
+
+        let expected = "FailedPrecondition: precondition failed; TOS example.local: terms not accepted; terms of service: https://example.local/tos; en-US: Please accept the terms of service";
+
+        assert!(
+            formatted.eq(expected),
+            "RichError display differs from expected result"
+        );
+
+        assert!(
+            err.source().is_some(),
+            "RichError does not expose the status as source"
+        );
+    }
+}
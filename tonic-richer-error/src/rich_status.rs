@@ -0,0 +1,497 @@
+use std::collections::BTreeMap;
+
+use tonic::codegen::http;
+use tonic::metadata::{MetadataMap, MetadataValue};
+use tonic::{Code, Status};
+
+use super::{Error, ErrorDetails, PairingError, WithErrorDetails};
+
+/// Builds a `tonic::Status` around an [`crate::ErrorInfo`], the primary
+/// machine-readable detail recommended by [AIP-193]. Starts from a reason
+/// and domain, and optionally takes metadata, a message template, a
+/// localized message and supplementary details.
+///
+/// Message templates can reference metadata values as `{key}`. Unknown
+/// placeholders are kept unchanged. If no code is set, it is inferred from
+/// the supplementary details: `Code::InvalidArgument` for
+/// [`crate::BadRequest`], `Code::FailedPrecondition` for
+/// [`crate::PreconditionFailure`], `Code::ResourceExhausted` for
+/// [`crate::QuotaFailure`], `Code::Unavailable` for [`crate::RetryInfo`], and
+/// `Code::Unknown` otherwise.
+///
+/// [AIP-193]: https://google.aip.dev/193
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use tonic::Code;
+/// use tonic_richer_error::{RichStatus, WithErrorDetails};
+///
+/// let status = RichStatus::for_reason("QUOTA_EXCEEDED", "example.local")
+///     .metadata("limit", "100")
+///     .message("quota of {limit} requests exceeded")
+///     .localized_message("pt-BR", "cota de {limit} requisições excedida")
+///     .details(|details| {
+///         details
+///             .add_quota_failure_violation("clientip:127.0.0.1", "rate exceeded")
+///             .set_retry_info(Some(Duration::from_secs(30)));
+///     })
+///     .build();
+///
+/// assert_eq!(status.code(), Code::ResourceExhausted);
+/// assert_eq!(status.message(), "quota of 100 requests exceeded");
+/// assert_eq!(status.get_details_error_info().unwrap().reason, "QUOTA_EXCEEDED");
+/// ```
+#[derive(Clone, Debug)]
+pub struct RichStatus {
+    code: Option<Code>,
+    reason: String,
+    domain: String,
+    metadata: BTreeMap<String, String>,
+    message: Option<String>,
+    localized_message: Option<(String, String)>,
+    details: ErrorDetails,
+}
+
+impl RichStatus {
+    /// Creates a new [`RichStatus`], with the [`crate::ErrorInfo`] reason and
+    /// domain.
+    pub fn for_reason(reason: impl Into<String>, domain: impl Into<String>) -> Self {
+        RichStatus {
+            code: None,
+            reason: reason.into(),
+            domain: domain.into(),
+            metadata: BTreeMap::new(),
+            message: None,
+            localized_message: None,
+            details: ErrorDetails::new(),
+        }
+    }
+
+    /// Sets the status code, instead of inferring it from the details.
+    pub fn code(mut self, code: Code) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Adds an entry to the [`crate::ErrorInfo`] metadata.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the template of the status message. Defaults to the reason.
+    pub fn message(mut self, template: impl Into<String>) -> Self {
+        self.message = Some(template.into());
+        self
+    }
+
+    /// Sets [`crate::LocalizedMessage`] details, with the message rendered
+    /// from the given template.
+    pub fn localized_message(
+        mut self,
+        locale: impl Into<String>,
+        template: impl Into<String>,
+    ) -> Self {
+        self.localized_message = Some((locale.into(), template.into()));
+        self
+    }
+
+    /// Adds supplementary details, such as [`crate::BadRequest`] or
+    /// [`crate::Help`]. The [`crate::ErrorInfo`] and
+    /// [`crate::LocalizedMessage`] details are set by the [`RichStatus`]
+    /// itself, replacing the ones set here.
+    pub fn details(mut self, f: impl FnOnce(&mut ErrorDetails)) -> Self {
+        f(&mut self.details);
+        self
+    }
+
+    /// Generates the `tonic::Status`.
+    pub fn build(self) -> Status {
+        let (code, message, details) = self.into_parts();
+
+        Status::with_error_details(code, message, details)
+    }
+
+    /// Generates the `tonic::Status`, if the details can be used with its
+    /// code, as described in [`ErrorDetails::check_code_pairing`]. Otherwise,
+    /// returns a [`PairingError`].
+    pub fn build_strict(self) -> Result<Status, PairingError> {
+        let (code, message, details) = self.into_parts();
+
+        Status::with_error_details_strict(code, message, details)
+    }
+
+    /// Reads the code, message and error details of a status from the
+    /// headers or trailers of a gRPC response, for raw hyper or h2 clients
+    /// and proxies that do not use tonic's client machinery. The code and
+    /// the percent-encoded message are read from the `grpc-status` and
+    /// `grpc-message` entries, and the error details from the
+    /// `grpc-status-details-bin` entry, if present. Returns `Ok(None)` if
+    /// there is no `grpc-status` entry. If the details are malformed, an
+    /// [`Error`] is returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::codegen::http::{HeaderMap, HeaderValue};
+    /// use tonic::Code;
+    /// use tonic_richer_error::{ErrorDetails, RichStatus};
+    ///
+    /// let mut trailers = HeaderMap::new();
+    /// trailers.insert("grpc-status", HeaderValue::from_static("5"));
+    /// trailers.insert("grpc-message", HeaderValue::from_static("book%20not%20found"));
+    ///
+    /// let (code, message, err_details) = RichStatus::from_headers(&trailers).unwrap().unwrap();
+    ///
+    /// assert_eq!(code, Code::NotFound);
+    /// assert_eq!(message, "book not found");
+    /// assert_eq!(err_details, ErrorDetails::new());
+    /// ```
+    pub fn from_headers(
+        headers: &http::HeaderMap,
+    ) -> Result<Option<(Code, String, ErrorDetails)>, Error> {
+        let code = match headers.get("grpc-status") {
+            Some(value) => Code::from_bytes(value.as_bytes()),
+            None => return Ok(None),
+        };
+
+        let message = headers
+            .get("grpc-message")
+            .map(|value| percent_decode(value.as_bytes()))
+            .unwrap_or_default();
+
+        let mut details_headers = http::HeaderMap::new();
+        if let Some(value) = headers.get(ErrorDetails::METADATA_KEY) {
+            details_headers.insert(ErrorDetails::METADATA_KEY, value.clone());
+        }
+
+        let details =
+            match ErrorDetails::from_metadata(&MetadataMap::from_headers(details_headers))? {
+                Some((_, _, details)) => details,
+                None => ErrorDetails::new(),
+            };
+
+        Ok(Some((code, message, details)))
+    }
+
+    /// Generates the `tonic::Status`, and writes it to the headers or
+    /// trailers of a gRPC response, for gateways and proxies that do not use
+    /// tonic's server machinery. Sets the `grpc-status` entry, the
+    /// percent-encoded `grpc-message` entry and the base64 encoded
+    /// `grpc-status-details-bin` entry, replacing previous values. Can be read
+    /// back with [`RichStatus::from_headers`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::codegen::http::HeaderMap;
+    /// use tonic_richer_error::RichStatus;
+    ///
+    /// let mut trailers = HeaderMap::new();
+    ///
+    /// RichStatus::for_reason("OUT_OF_STOCK", "shop.example.local")
+    ///     .message("item is out of stock (100%)")
+    ///     .write_headers(&mut trailers);
+    ///
+    /// assert_eq!(trailers["grpc-status"], "2");
+    /// assert_eq!(trailers["grpc-message"], "item is out of stock (100%25)");
+    /// assert!(trailers.contains_key("grpc-status-details-bin"));
+    /// ```
+    pub fn write_headers(self, headers: &mut http::HeaderMap) {
+        let status = self.build();
+
+        headers.insert("grpc-status", http::HeaderValue::from(status.code() as i32));
+
+        // Percent-encoded text is always a valid header value
+        match http::HeaderValue::from_str(&percent_encode(status.message())) {
+            Ok(value) if !status.message().is_empty() => {
+                headers.insert("grpc-message", value);
+            }
+            _ => {
+                headers.remove("grpc-message");
+            }
+        }
+
+        headers.remove(ErrorDetails::METADATA_KEY);
+
+        if !status.details().is_empty() {
+            let mut metadata = MetadataMap::new();
+            metadata.insert_bin(
+                ErrorDetails::METADATA_KEY,
+                MetadataValue::from_bytes(status.details()),
+            );
+            headers.extend(metadata.into_headers());
+        }
+    }
+
+    pub(crate) fn into_parts(self) -> (Code, String, ErrorDetails) {
+        let mut details = self.details;
+
+        let code = self.code.unwrap_or_else(|| infer_code(&details));
+
+        let message = match &self.message {
+            Some(template) => render(template, &self.metadata),
+            None => self.reason.clone(),
+        };
+
+        if let Some((locale, template)) = &self.localized_message {
+            details.set_localized_message(locale.clone(), render(template, &self.metadata));
+        }
+
+        details.set_error_info(self.reason, self.domain, self.metadata);
+
+        (code, message, details)
+    }
+}
+
+impl From<RichStatus> for Status {
+    fn from(rich_status: RichStatus) -> Self {
+        rich_status.build()
+    }
+}
+
+/// Infers the status code from the details paired with a single code.
+fn infer_code(details: &ErrorDetails) -> Code {
+    if details.bad_request.is_some() {
+        Code::InvalidArgument
+    } else if details.precondition_failure.is_some() {
+        Code::FailedPrecondition
+    } else if details.quota_failure.is_some() {
+        Code::ResourceExhausted
+    } else if details.retry_info.is_some() {
+        Code::Unavailable
+    } else {
+        Code::Unknown
+    }
+}
+
+/// Encodes the bytes outside the printable ASCII range, and `%`, as `%XX`
+/// sequences, as required for the `grpc-message` header.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        match byte {
+            b' '..=b'~' if byte != b'%' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    encoded
+}
+
+/// Decodes `%XX` sequences, as used by the `grpc-message` header. Invalid
+/// UTF-8 is replaced with `U+FFFD`.
+fn percent_decode(value: &[u8]) -> String {
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut i = 0;
+
+    while i < value.len() {
+        let escaped = value
+            .get(i + 1..i + 3)
+            .filter(|_| value[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(value[i]);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Replaces `{key}` placeholders with the matching metadata values.
+fn render(template: &str, metadata: &BTreeMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+
+        let placeholder = &rest[start..];
+
+        match placeholder.find('}') {
+            Some(end) => match metadata.get(&placeholder[1..end]) {
+                Some(value) => {
+                    rendered.push_str(value);
+                    rest = &placeholder[end + 1..];
+                }
+                None => {
+                    rendered.push('{');
+                    rest = &placeholder[1..];
+                }
+            },
+            None => {
+                rendered.push_str(placeholder);
+                rest = "";
+            }
+        }
+    }
+
+    rendered.push_str(rest);
+
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use tonic::codegen::http::HeaderValue;
+    use tonic::metadata::MetadataMap;
+    use tonic::Code;
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::{render, RichStatus};
+
+    #[test]
+    fn gen_rich_statuses() {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("sku".to_string(), "A-1".to_string());
+
+        let rendered = render("{sku} is {state} {", &metadata);
+
+        println!("rendered template -> {rendered}");
+
+        assert!(
+            rendered == "A-1 is {state} {",
+            "rendered template differs from expected result"
+        );
+
+        let status = RichStatus::for_reason("OUT_OF_STOCK", "shop.example.local")
+            .metadata("sku", "A-1")
+            .message("item {sku} is out of stock")
+            .localized_message("pt-BR", "item {sku} esgotado")
+            .details(|details| {
+                details.add_precondition_failure_violation("STOCK", "A-1", "out of stock");
+            })
+            .build();
+
+        let formatted = format!(
+            "{:?} {:?} {:?} {:?}",
+            status.code(),
+            status.message(),
+            status.get_details_error_info(),
+            status.get_details_localized_message()
+        );
+
+        println!("rich status -> {formatted}");
+
+        let expected = "FailedPrecondition \"item A-1 is out of stock\" Some(ErrorInfo { reason: \"OUT_OF_STOCK\", domain: \"shop.example.local\", metadata: {\"sku\": \"A-1\"} }) Some(LocalizedMessage { locale: \"pt-BR\", message: \"item A-1 esgotado\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "rich status differs from expected result"
+        );
+
+        let status = RichStatus::for_reason("ACCOUNT_LOCKED", "example.local").build();
+
+        assert!(
+            status.code() == Code::Unknown && status.message() == "ACCOUNT_LOCKED",
+            "default code and message differ from expected result"
+        );
+
+        let result = RichStatus::for_reason("INVALID_SKU", "shop.example.local")
+            .code(Code::NotFound)
+            .details(|details| {
+                details.add_bad_request_violation("sku", "is invalid");
+            })
+            .build_strict();
+
+        assert!(result.is_err(), "invalid pairing was not rejected");
+    }
+
+    #[test]
+    fn rich_status_from_headers() {
+        let mut metadata = MetadataMap::new();
+
+        ErrorDetails::with_bad_request_violation("field", "description").insert_into_metadata(
+            Code::InvalidArgument,
+            "ignored",
+            &mut metadata,
+        );
+
+        let mut headers = metadata.into_headers();
+        headers.insert("grpc-status", HeaderValue::from_static("3"));
+        headers.insert(
+            "grpc-message",
+            HeaderValue::from_static("campo%20inv%C3%A1lido%2"),
+        );
+
+        let formatted = format!("{:?}", RichStatus::from_headers(&headers));
+
+        println!("status from headers -> {formatted}");
+
+        let expected = "Ok(Some((InvalidArgument, \"campo inválido%2\", ErrorDetails { retry_info: None, debug_info: None, quota_failure: None, error_info: None, precondition_failure: None, bad_request: Some(BadRequest { field_violations: [FieldViolation { field: \"field\", description: \"description\", reason: \"\", localized_message: None }] }), request_info: None, resource_info: None, help: None, localized_message: None, unknown_details: [] })))";
+
+        assert!(
+            formatted.eq(expected),
+            "status from headers differs from expected result"
+        );
+
+        headers.remove("grpc-status");
+
+        assert!(
+            matches!(RichStatus::from_headers(&headers), Ok(None)),
+            "status read from headers without grpc-status"
+        );
+
+        headers.insert("grpc-status", HeaderValue::from_static("3"));
+        headers.insert(
+            ErrorDetails::METADATA_KEY,
+            HeaderValue::from_static("not base64!"),
+        );
+
+        assert!(
+            RichStatus::from_headers(&headers).is_err(),
+            "malformed details were not reported"
+        );
+    }
+
+    #[test]
+    fn rich_status_to_headers() {
+        let mut headers = MetadataMap::new().into_headers();
+        headers.insert("grpc-message", HeaderValue::from_static("stale"));
+
+        RichStatus::for_reason("INVALID_NAME", "example.local")
+            .message("nome inválido: 100%")
+            .details(|details| {
+                details.add_bad_request_violation("name", "is invalid");
+            })
+            .write_headers(&mut headers);
+
+        println!("status headers -> {:?}", headers);
+
+        assert!(
+            headers["grpc-message"] == "nome inv%C3%A1lido: 100%25",
+            "grpc-message differs from expected result"
+        );
+
+        let formatted = match RichStatus::from_headers(&headers) {
+            Ok(Some((code, message, details))) => format!(
+                "{:?} {:?} {:?} {:?}",
+                code,
+                message,
+                details.error_info.map(|info| info.reason),
+                details.bad_request
+            ),
+            other => panic!("Error reading status from headers: {:?}", other),
+        };
+
+        println!("status read back -> {formatted}");
+
+        let expected = "InvalidArgument \"nome inválido: 100%\" Some(\"INVALID_NAME\") Some(BadRequest { field_violations: [FieldViolation { field: \"name\", description: \"is invalid\", reason: \"\", localized_message: None }] })";
+
+        assert!(
+            formatted.eq(expected),
+            "status read back differs from expected result"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use prost::Message;
+
+use super::pb;
+#[cfg(feature = "std")]
+use super::policy;
+use super::{
+    decode_details_vec, insert_detail, DetailOrder, Error, ErrorDetail, ErrorDetails, IntoAny,
+    StatusRef,
+};
+
+/// Used to encode/decode the `google.rpc.Status` message without depending
+/// on `tonic::Status`. Can be used to store statuses, for example in message
+/// queues or databases, or to implement custom transports, while reusing the
+/// standard error message structs.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::{BadRequest, RpcStatus};
+///
+/// let status = RpcStatus::new(
+///     3,
+///     "bad request",
+///     vec![BadRequest::with_violation("field", "description").into()],
+/// );
+///
+/// let encoded = status.encode_to_vec();
+///
+/// let decoded = RpcStatus::decode(&encoded[..]).unwrap();
+///
+/// assert_eq!(decoded.message, "bad request");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RpcStatus {
+    /// Status code, which should be a value of `google.rpc.Code`.
+    pub code: i32,
+
+    /// Developer-facing error message.
+    pub message: String,
+
+    /// Error details. Details with unknown type URLs are kept at the
+    /// [`ErrorDetail::Other`] variant.
+    pub details: Vec<ErrorDetail>,
+}
+
+impl RpcStatus {
+    /// Creates a new [`RpcStatus`] struct.
+    pub fn new(code: i32, message: impl Into<String>, details: Vec<ErrorDetail>) -> Self {
+        RpcStatus {
+            code,
+            message: message.into(),
+            details,
+        }
+    }
+
+    /// Encodes the [`RpcStatus`] as a `google.rpc.Status` protobuf message.
+    /// As in [`crate::WithErrorDetails::with_error_details_vec`], the global
+    /// [`crate::ErrorDetailsPolicy`] is applied to the details, which were
+    /// encoded as they were by earlier releases.
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        #[cfg_attr(not(feature = "std"), allow(unused_mut))]
+        let mut details = self.details.clone();
+
+        #[cfg(feature = "std")]
+        policy::apply_global_vec(&mut details);
+
+        let status = pb::Status {
+            code: self.code,
+            message: self.message.clone(),
+            details: details.into_iter().map(IntoAny::into_any).collect(),
+        };
+
+        status.encode_to_vec()
+    }
+
+    /// Decodes an [`RpcStatus`] from a `google.rpc.Status` protobuf message.
+    /// If the status or some of the standard error messages are malformed, an
+    /// [`Error`] is returned.
+    pub fn decode(buf: &[u8]) -> Result<Self, Error> {
+        let status = StatusRef::decode(buf).map_err(Error::InvalidStatus)?;
+
+        let details = decode_details_vec(&status)?;
+
+        Ok(RpcStatus {
+            code: status.code,
+            message: status.message.to_string(),
+            details,
+        })
+    }
+}
+
+impl ErrorDetails {
+    /// Encodes a `google.rpc.Status` with the given code and message, carrying
+    /// the error details, without depending on `tonic::Status`. As in
+    /// [`crate::WithErrorDetails::with_error_details`], the global
+    /// [`crate::ErrorDetailsPolicy`] is applied and the details are encoded
+    /// in the global [`DetailOrder`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let encoded = ErrorDetails::with_bad_request_violation("field", "description")
+    ///     .encode_status(3, "bad request");
+    ///
+    /// let err_details = ErrorDetails::decode_status(&encoded).unwrap();
+    ///
+    /// assert!(err_details.bad_request.is_some());
+    /// ```
+    pub fn encode_status(self, code: i32, message: impl Into<String>) -> Vec<u8> {
+        #[cfg_attr(not(feature = "std"), allow(unused_mut))]
+        let mut details = self;
+
+        #[cfg(feature = "std")]
+        policy::apply_global(&mut details);
+
+        #[cfg(feature = "std")]
+        let order = DetailOrder::global();
+
+        #[cfg(not(feature = "std"))]
+        let order = DetailOrder::standard();
+
+        let conv_details = order.encode(details);
+
+        #[cfg(feature = "metrics")]
+        super::counters::details_emitted(&conv_details);
+
+        let status = pb::Status {
+            code,
+            message: message.into(),
+            details: conv_details,
+        };
+
+        status.encode_to_vec()
+    }
+
+    /// Decodes the error details of an encoded `google.rpc.Status`, without
+    /// depending on `tonic::Status`. If the status or some of the standard
+    /// error messages are malformed, an [`Error`] is returned.
+    pub fn decode_status(buf: &[u8]) -> Result<Self, Error> {
+        let status = StatusRef::decode(buf).map_err(Error::InvalidStatus)?;
+
+        let mut details = ErrorDetails::new();
+
+        for (index, any) in status.details.into_iter().enumerate() {
+            insert_detail(&mut details, any)
+                .map_err(|err| Error::detail(index, any.type_url, err))?;
+        }
+
+        Ok(details)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    #[cfg(feature = "tonic")]
+    use super::super::WithErrorDetails;
+    use super::super::{BadRequest, ErrorDetail, RetryInfo};
+    use super::RpcStatus;
+
+    #[test]
+    fn rpc_status_round_trip() {
+        let custom = prost_types::Any {
+            type_url: "type.example.com/custom.Detail".into(),
+            value: vec![8, 1],
+        };
+
+        let status = RpcStatus::new(
+            14,
+            "unavailable",
+            vec![
+                RetryInfo::new(Some(Duration::from_secs(5))).into(),
+                BadRequest::with_violation("field", "description").into(),
+                ErrorDetail::Other(custom),
+            ],
+        );
+
+        let encoded = status.encode_to_vec();
+
+        let decoded = match RpcStatus::decode(&encoded) {
+            Ok(decoded) => decoded,
+            Err(err) => panic!("Error decoding RpcStatus: {:?}", err),
+        };
+
+        assert!(
+            format!("{:?}", decoded).eq(&format!("{:?}", status)),
+            "decoded RpcStatus differs from original"
+        );
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn rpc_status_in_tonic_status() {
+        let status = RpcStatus::new(
+            14,
+            "unavailable",
+            vec![
+                RetryInfo::new(Some(Duration::from_secs(5))).into(),
+                BadRequest::with_violation("field", "description").into(),
+            ],
+        );
+
+        let encoded = status.encode_to_vec();
+
+        let tonic_status =
+            tonic::Status::with_details(tonic::Code::Unavailable, "unavailable", encoded.into());
+
+        assert!(
+            format!("{:?}", tonic_status.get_error_details_vec())
+                .eq(&format!("{:?}", status.details)),
+            "details from tonic::Status differ from RpcStatus details"
+        );
+    }
+}
@@ -0,0 +1,387 @@
+//! JSON Schema definitions for the canonical JSON representation of
+//! `google.rpc.Status` and the standard error messages, as produced by
+//! [`crate::ErrorDetails::to_status_json`] and gRPC-JSON transcoders. Used to
+//! document REST-facing error responses from the same source as the Rust
+//! types, either as a standalone JSON Schema (draft 2020-12) document, or as
+//! OpenAPI 3.1 components.
+//!
+//! Each detail of the `details` array is described by the schema of its
+//! message, with an `@type` field fixed to its type URL. Details with other
+//! type URLs are described by the generic `Any` schema.
+//!
+//! # Examples
+//!
+//! ```
+//! use tonic_richer_error::schema::{json_schema, openapi_components};
+//!
+//! let schema = json_schema();
+//!
+//! assert_eq!(schema["$defs"]["BadRequest"]["type"], "object");
+//!
+//! let components = openapi_components();
+//!
+//! assert_eq!(
+//!     components["schemas"]["Status"]["properties"]["details"]["items"]["anyOf"][0]["$ref"],
+//!     "#/components/schemas/RetryInfo"
+//! );
+//! ```
+
+use serde_json::{json, Map, Value};
+
+use super::std_messages::*;
+
+/// Names of the message schemas that can be sent as details, along with
+/// their type URLs.
+const DETAIL_SCHEMAS: [(&str, &str); 12] = [
+    ("RetryInfo", RetryInfo::TYPE_URL),
+    ("DebugInfo", DebugInfo::TYPE_URL),
+    ("QuotaFailure", QuotaFailure::TYPE_URL),
+    ("ErrorInfo", ErrorInfo::TYPE_URL),
+    ("PreconditionFailure", PreconditionFailure::TYPE_URL),
+    ("BadRequest", BadRequest::TYPE_URL),
+    ("RequestInfo", RequestInfo::TYPE_URL),
+    ("ResourceInfo", ResourceInfo::TYPE_URL),
+    ("Help", Help::TYPE_URL),
+    ("LocalizedMessage", LocalizedMessage::TYPE_URL),
+    ("HttpRequest", HttpRequest::TYPE_URL),
+    ("HttpResponse", HttpResponse::TYPE_URL),
+];
+
+/// Generates a JSON Schema (draft 2020-12) document describing the
+/// canonical JSON representation of `google.rpc.Status`. The schemas of the
+/// standard error messages are included at `$defs`.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::schema::json_schema;
+///
+/// let schema = json_schema();
+///
+/// assert_eq!(schema["title"], "Status");
+/// assert_eq!(schema["$defs"]["RetryInfo"]["properties"]["retryDelay"]["type"], "string");
+/// ```
+pub fn json_schema() -> Value {
+    let mut defs = definitions("#/$defs/");
+
+    let mut schema = match defs.remove("Status") {
+        Some(Value::Object(schema)) => schema,
+        _ => Map::new(),
+    };
+
+    schema.insert(
+        "$schema".into(),
+        "https://json-schema.org/draft/2020-12/schema".into(),
+    );
+    schema.insert("title".into(), "Status".into());
+    schema.insert("$defs".into(), Value::Object(defs));
+
+    Value::Object(schema)
+}
+
+/// Generates OpenAPI 3.1 components describing the canonical JSON
+/// representation of `google.rpc.Status`, as the `Status` schema, and the
+/// standard error messages. Can be merged into the `components` object of
+/// an OpenAPI document.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::schema::openapi_components;
+///
+/// let components = openapi_components();
+///
+/// assert_eq!(
+///     components["schemas"]["BadRequest"]["properties"]["fieldViolations"]["items"]["$ref"],
+///     "#/components/schemas/FieldViolation"
+/// );
+/// ```
+pub fn openapi_components() -> Value {
+    json!({ "schemas": definitions("#/components/schemas/") })
+}
+
+/// Generates the schemas of `google.rpc.Status` and the standard error
+/// messages, referencing each other with the given prefix.
+fn definitions(ref_prefix: &str) -> Map<String, Value> {
+    let schema_ref = |name: &str| json!({ "$ref": format!("{ref_prefix}{name}") });
+
+    let string = json!({ "type": "string" });
+    let string_list = json!({ "type": "array", "items": string });
+    let string_map = json!({ "type": "object", "additionalProperties": string });
+
+    let detail_items: Vec<Value> = DETAIL_SCHEMAS
+        .iter()
+        .map(|(name, type_url)| {
+            json!({
+                "$ref": format!("{ref_prefix}{name}"),
+                "properties": { "@type": { "const": type_url } },
+                "required": ["@type"],
+            })
+        })
+        .chain([schema_ref("Any")])
+        .collect();
+
+    let schemas = json!({
+        "Status": {
+            "type": "object",
+            "description": "Canonical JSON representation of google.rpc.Status.",
+            "properties": {
+                "code": { "type": "integer", "minimum": 0, "maximum": 16 },
+                "message": string,
+                "details": { "type": "array", "items": { "anyOf": detail_items } },
+            },
+        },
+        "Any": {
+            "type": "object",
+            "description": "Error detail of a type not described by the other schemas.",
+            "properties": { "@type": string },
+            "required": ["@type"],
+        },
+        "RetryInfo": {
+            "type": "object",
+            "properties": {
+                "retryDelay": {
+                    "type": "string",
+                    "description": "Duration in seconds, such as \"1.5s\".",
+                    "pattern": "^-?[0-9]+(\\.[0-9]{1,9})?s$",
+                },
+            },
+        },
+        "DebugInfo": {
+            "type": "object",
+            "properties": {
+                "stackEntries": string_list,
+                "detail": string,
+            },
+        },
+        "QuotaFailure": {
+            "type": "object",
+            "properties": {
+                "violations": { "type": "array", "items": schema_ref("QuotaViolation") },
+            },
+        },
+        "QuotaViolation": {
+            "type": "object",
+            "properties": {
+                "subject": string,
+                "description": string,
+                "apiService": string,
+                "quotaMetric": string,
+                "quotaId": string,
+                "quotaDimensions": string_map,
+                "quotaValue": { "type": "integer" },
+                "futureQuotaValue": { "type": "integer" },
+            },
+        },
+        "ErrorInfo": {
+            "type": "object",
+            "properties": {
+                "reason": string,
+                "domain": string,
+                "metadata": string_map,
+            },
+        },
+        "PreconditionFailure": {
+            "type": "object",
+            "properties": {
+                "violations": {
+                    "type": "array",
+                    "items": schema_ref("PreconditionViolation"),
+                },
+            },
+        },
+        "PreconditionViolation": {
+            "type": "object",
+            "properties": {
+                "type": string,
+                "subject": string,
+                "description": string,
+            },
+        },
+        "BadRequest": {
+            "type": "object",
+            "properties": {
+                "fieldViolations": { "type": "array", "items": schema_ref("FieldViolation") },
+            },
+        },
+        "FieldViolation": {
+            "type": "object",
+            "properties": {
+                "field": string,
+                "description": string,
+                "reason": string,
+                "localizedMessage": schema_ref("LocalizedMessage"),
+            },
+        },
+        "RequestInfo": {
+            "type": "object",
+            "properties": {
+                "requestId": string,
+                "servingData": string,
+            },
+        },
+        "ResourceInfo": {
+            "type": "object",
+            "properties": {
+                "resourceType": string,
+                "resourceName": string,
+                "owner": string,
+                "description": string,
+            },
+        },
+        "Help": {
+            "type": "object",
+            "properties": {
+                "links": { "type": "array", "items": schema_ref("HelpLink") },
+            },
+        },
+        "HelpLink": {
+            "type": "object",
+            "properties": {
+                "description": string,
+                "url": string,
+            },
+        },
+        "LocalizedMessage": {
+            "type": "object",
+            "properties": {
+                "locale": string,
+                "message": string,
+            },
+        },
+        "HttpRequest": {
+            "type": "object",
+            "properties": {
+                "method": string,
+                "uri": string,
+                "headers": { "type": "array", "items": schema_ref("HttpHeader") },
+            },
+        },
+        "HttpResponse": {
+            "type": "object",
+            "properties": {
+                "status": { "type": "integer" },
+                "reason": string,
+                "headers": { "type": "array", "items": schema_ref("HttpHeader") },
+            },
+        },
+        "HttpHeader": {
+            "type": "object",
+            "properties": {
+                "key": string,
+                "value": string,
+            },
+        },
+    });
+
+    match schemas {
+        Value::Object(schemas) => schemas,
+        _ => Map::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde_json::Value;
+
+    use super::{definitions, json_schema, openapi_components, DETAIL_SCHEMAS};
+
+    fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(schema_ref)) = map.get("$ref") {
+                    refs.push(schema_ref.clone());
+                }
+                map.values().for_each(|value| collect_refs(value, refs));
+            }
+            Value::Array(values) => values.iter().for_each(|value| collect_refs(value, refs)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn schema_refs_resolve() {
+        let schema = json_schema();
+
+        let mut refs = Vec::new();
+        collect_refs(&schema, &mut refs);
+
+        println!("JSON Schema refs -> {:?}", refs);
+
+        for schema_ref in &refs {
+            let name = schema_ref.trim_start_matches("#/$defs/");
+
+            assert!(
+                schema["$defs"].get(name).is_some(),
+                "JSON Schema ref {schema_ref} does not resolve"
+            );
+        }
+
+        let components = openapi_components();
+
+        let mut refs = Vec::new();
+        collect_refs(&components, &mut refs);
+
+        for schema_ref in &refs {
+            let name = schema_ref.trim_start_matches("#/components/schemas/");
+
+            assert!(
+                components["schemas"].get(name).is_some(),
+                "OpenAPI ref {schema_ref} does not resolve"
+            );
+        }
+
+        let defs = definitions("");
+
+        for (name, type_url) in DETAIL_SCHEMAS {
+            let detail_item = defs["Status"]["properties"]["details"]["items"]["anyOf"]
+                .as_array()
+                .and_then(|items| items.iter().find(|item| item["$ref"] == name));
+
+            assert!(
+                detail_item.is_some_and(|item| item["properties"]["@type"]["const"] == type_url),
+                "detail {name} is missing from the Status schema"
+            );
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn schema_describes_status_json() {
+        use tonic::Code;
+
+        use super::super::ErrorDetails;
+
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .add_bad_request_violation("field", "description")
+            .set_error_info(
+                "REASON",
+                "example.local",
+                [("key".into(), "value".into())].into(),
+            );
+
+        let json = err_details.to_status_json(Code::InvalidArgument, "bad request");
+
+        let status: Value = serde_json::from_str(&json).unwrap();
+
+        let defs = definitions("");
+
+        for detail in status["details"].as_array().into_iter().flatten() {
+            let (name, _) = match DETAIL_SCHEMAS
+                .iter()
+                .find(|(_, type_url)| detail["@type"] == *type_url)
+            {
+                Some(detail_schema) => detail_schema,
+                None => panic!("unexpected detail type {}", detail["@type"]),
+            };
+
+            for field in detail.as_object().into_iter().flatten().map(|(key, _)| key) {
+                assert!(
+                    field == "@type" || defs[*name]["properties"].get(field).is_some(),
+                    "field {field} of {name} is not described by its schema"
+                );
+            }
+        }
+    }
+}
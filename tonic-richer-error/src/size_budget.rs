@@ -0,0 +1,258 @@
+use prost::Message;
+
+use super::pb;
+use super::std_messages::*;
+use super::{DetailOrder, ErrorDetails};
+
+/// Reason, or type, set on the marker violations appended to truncated
+/// violation lists.
+const TRUNCATED: &str = "TRUNCATED";
+
+impl ErrorDetails {
+    /// Returns the length, in bytes, of the error details once encoded in a
+    /// `google.rpc.Status` message, not counting its code and message.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let err_details = ErrorDetails::with_bad_request_violation("field", "description");
+    ///
+    /// assert!(err_details.encoded_len() > 0);
+    /// assert_eq!(ErrorDetails::new().encoded_len(), 0);
+    /// ```
+    pub fn encoded_len(&self) -> usize {
+        let status = pb::Status {
+            code: 0,
+            message: String::new(),
+            details: DetailOrder::global().encode(self.clone()),
+        };
+
+        status.encoded_len()
+    }
+
+    /// Trims the [`BadRequest`], [`QuotaFailure`] and [`PreconditionFailure`]
+    /// violations, and the [`DebugInfo`] stack entries, so the details fit
+    /// within `max_bytes`, as measured by [`ErrorDetails::encoded_len`]. The
+    /// same number of leading entries is kept in every trimmed list, followed
+    /// by a marker entry informing how many were omitted. Other details are
+    /// never changed, so the result can still exceed `max_bytes`. Returns
+    /// `true` if any list was trimmed.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// for index in 0..100 {
+    ///     err_details.add_bad_request_violation(format!("field_{index}"), "is invalid");
+    /// }
+    ///
+    /// assert!(err_details.truncate_to_len(512));
+    /// assert!(err_details.encoded_len() <= 512);
+    ///
+    /// let violations = err_details.bad_request.unwrap().field_violations;
+    ///
+    /// assert_eq!(violations[0].field, "field_0");
+    /// assert_eq!(violations.last().unwrap().reason, "TRUNCATED");
+    /// ```
+    pub fn truncate_to_len(&mut self, max_bytes: usize) -> bool {
+        if self.encoded_len() <= max_bytes {
+            return false;
+        }
+
+        let longest = [
+            self.bad_request.as_ref().map(|d| d.field_violations.len()),
+            self.quota_failure.as_ref().map(|d| d.violations.len()),
+            self.precondition_failure
+                .as_ref()
+                .map(|d| d.violations.len()),
+            self.debug_info.as_ref().map(|d| d.stack_entries.len()),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(0);
+
+        if longest == 0 {
+            return false;
+        }
+
+        // Largest number of kept entries that fits the budget, or 0 if none
+        // does. Keeping `longest` entries would leave the details unchanged.
+        let mut best = 0;
+        let (mut low, mut high) = (1, longest - 1);
+
+        while low <= high {
+            let keep = low + (high - low) / 2;
+
+            if self.truncated(keep).encoded_len() <= max_bytes {
+                best = keep;
+                low = keep + 1;
+            } else {
+                high = keep - 1;
+            }
+        }
+
+        *self = self.truncated(best);
+
+        true
+    }
+
+    /// Returns a copy of the details, with every trimmable list cut to `keep`
+    /// entries, plus a marker entry.
+    fn truncated(&self, keep: usize) -> ErrorDetails {
+        let mut details = self.clone();
+
+        if let Some(bad_request) = &mut details.bad_request {
+            truncate_entries(&mut bad_request.field_violations, keep, |omitted| {
+                let mut violation = FieldViolation::new("", omitted_message(omitted));
+                violation.reason = TRUNCATED.into();
+                violation
+            });
+        }
+
+        if let Some(quota_failure) = &mut details.quota_failure {
+            truncate_entries(&mut quota_failure.violations, keep, |omitted| {
+                QuotaViolation::new("", omitted_message(omitted))
+            });
+        }
+
+        if let Some(prec_failure) = &mut details.precondition_failure {
+            truncate_entries(&mut prec_failure.violations, keep, |omitted| {
+                PreconditionViolation::new(TRUNCATED, "", omitted_message(omitted))
+            });
+        }
+
+        if let Some(debug_info) = &mut details.debug_info {
+            truncate_entries(&mut debug_info.stack_entries, keep, |omitted| {
+                format!("... {omitted} more entries truncated")
+            });
+        }
+
+        details
+    }
+}
+
+/// Keeps the first `keep` entries, appending the marker returned by `marker`,
+/// called with the number of omitted entries. Lists that are not longer than
+/// `keep` are left unchanged.
+fn truncate_entries<T>(entries: &mut Vec<T>, keep: usize, marker: impl FnOnce(usize) -> T) {
+    if entries.len() <= keep {
+        return;
+    }
+
+    let omitted = entries.len() - keep;
+
+    entries.truncate(keep);
+    entries.push(marker(omitted));
+}
+
+fn omitted_message(omitted: usize) -> String {
+    format!("{omitted} more violations truncated")
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[cfg(feature = "tonic")]
+    use tonic::{Code, Status};
+
+    use super::super::ErrorDetails;
+    #[cfg(feature = "tonic")]
+    use super::super::WithErrorDetails;
+
+    fn large_details() -> ErrorDetails {
+        let mut err_details = ErrorDetails::new();
+
+        for index in 0..200 {
+            err_details.add_bad_request_violation(format!("field_{index}"), "is invalid");
+        }
+
+        let stack_entries = (0..50).map(|index| format!("frame {index}")).collect();
+
+        err_details
+            .set_debug_info(stack_entries, "details")
+            .set_request_info("request-id", "");
+
+        err_details
+    }
+
+    #[test]
+    fn truncate_details_to_budget() {
+        let mut err_details = large_details();
+
+        assert!(
+            err_details.truncate_to_len(1024),
+            "details were not truncated"
+        );
+
+        let len = err_details.encoded_len();
+
+        assert!(len <= 1024, "truncated details exceed the budget");
+
+        let violations = &err_details.bad_request.as_ref().unwrap().field_violations;
+        let stack_entries = &err_details.debug_info.as_ref().unwrap().stack_entries;
+
+        let formatted = format!(
+            "{:?} {:?} {:?}",
+            violations.first(),
+            violations.last(),
+            stack_entries.last()
+        );
+
+        let kept = violations.len() - 1;
+
+        let expected = format!(
+            "Some(FieldViolation {{ field: \"field_0\", description: \"is invalid\", reason: \"\", localized_message: None }}) Some(FieldViolation {{ field: \"\", description: \"{} more violations truncated\", reason: \"TRUNCATED\", localized_message: None }}) Some(\"... {} more entries truncated\")",
+            200 - kept,
+            50 - kept,
+        );
+
+        assert!(
+            formatted.eq(&expected),
+            "truncated details differ from expected result"
+        );
+
+        assert!(
+            err_details.request_info.is_some(),
+            "request info was removed by truncation"
+        );
+
+        let mut untouched = large_details();
+
+        assert!(
+            !untouched.truncate_to_len(usize::MAX) && untouched == large_details(),
+            "details within budget were changed"
+        );
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn gen_status_with_bounded_details() {
+        let status = Status::with_error_details_bounded(
+            Code::InvalidArgument,
+            "bad request",
+            large_details(),
+            2048,
+        );
+
+        println!("encoded status length -> {}", status.details().len());
+
+        assert!(
+            status.details().len() <= 2048,
+            "encoded status exceeds the budget"
+        );
+
+        let bad_request = match status.get_details_bad_request() {
+            Some(bad_request) => bad_request,
+            None => panic!("bad request details were not found"),
+        };
+
+        assert!(
+            bad_request.field_violations.last().unwrap().reason == "TRUNCATED",
+            "truncation marker was not found"
+        );
+    }
+}
@@ -0,0 +1,907 @@
+//! Canonical textual form of `tonic::Status` and its error details, for
+//! golden-file snapshot tests of error contracts, such as the ones written
+//! with `insta`. The form is stable across versions of this crate: details
+//! are written in the order of the [`ErrorDetails`] fields, every field is
+//! written once, map entries are sorted by key, and strings are always
+//! quoted. Snapshots can be parsed back with [`parse_snapshot`].
+//!
+//! ```text
+//! code = InvalidArgument
+//! message = "bad request"
+//!
+//! [bad_request]
+//! field_violations[0].field = "name"
+//! field_violations[0].description = "is required"
+//! field_violations[0].reason = ""
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use tonic::{Code, Status};
+//! use tonic_richer_error::snapshot::{from_snapshot, to_snapshot};
+//! use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+//!
+//! let status = Status::with_error_details(
+//!     Code::InvalidArgument,
+//!     "bad request",
+//!     ErrorDetails::with_bad_request_violation("name", "is required"),
+//! );
+//!
+//! let snapshot = to_snapshot(&status);
+//!
+//! assert_eq!(
+//!     snapshot,
+//!     "code = InvalidArgument\n\
+//!      message = \"bad request\"\n\
+//!      \n\
+//!      [bad_request]\n\
+//!      field_violations[0].field = \"name\"\n\
+//!      field_violations[0].description = \"is required\"\n\
+//!      field_violations[0].reason = \"\"\n"
+//! );
+//!
+//! assert_eq!(to_snapshot(&from_snapshot(&snapshot).unwrap()), snapshot);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use prost_types::Any;
+use tonic::{Code, Status};
+
+use super::{ErrorDetails, LocalizedMessage, WithErrorDetails};
+
+/// Error returned when a snapshot cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotError {
+    /// Line of the snapshot where the error was found, starting at 1.
+    pub line: usize,
+
+    /// Description of the error.
+    pub message: String,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid snapshot at line {}: {}",
+            self.line, self.message
+        )
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// Writes the code, message and error details of a `tonic::Status` in the
+/// canonical textual form. Malformed details are discarded, as in
+/// [`WithErrorDetails::error_details`].
+pub fn to_snapshot(status: &Status) -> String {
+    details_to_snapshot(status.code(), status.message(), &status.error_details())
+}
+
+/// Writes a code, message and error details in the canonical textual form.
+pub fn details_to_snapshot(code: Code, message: &str, details: &ErrorDetails) -> String {
+    let mut out = String::new();
+
+    entry(&mut out, "code", format!("{code:?}"));
+    entry(&mut out, "message", quote(message));
+
+    if let Some(retry_info) = &details.retry_info {
+        section(&mut out, "retry_info");
+        if let Some(delay) = retry_info.retry_delay {
+            entry(&mut out, "retry_delay", format_duration(delay));
+        }
+    }
+
+    if let Some(debug_info) = &details.debug_info {
+        section(&mut out, "debug_info");
+        for (i, stack_entry) in debug_info.stack_entries.iter().enumerate() {
+            entry(&mut out, format!("stack_entries[{i}]"), quote(stack_entry));
+        }
+        entry(&mut out, "detail", quote(&debug_info.detail));
+    }
+
+    if let Some(quota_failure) = &details.quota_failure {
+        section(&mut out, "quota_failure");
+        for (i, violation) in quota_failure.violations.iter().enumerate() {
+            let prefix = format!("violations[{i}]");
+            entry(
+                &mut out,
+                format!("{prefix}.subject"),
+                quote(&violation.subject),
+            );
+            entry(
+                &mut out,
+                format!("{prefix}.description"),
+                quote(&violation.description),
+            );
+            entry(
+                &mut out,
+                format!("{prefix}.api_service"),
+                quote(&violation.api_service),
+            );
+            entry(
+                &mut out,
+                format!("{prefix}.quota_metric"),
+                quote(&violation.quota_metric),
+            );
+            entry(
+                &mut out,
+                format!("{prefix}.quota_id"),
+                quote(&violation.quota_id),
+            );
+            for (key, value) in violation.quota_dimensions.iter() {
+                entry(
+                    &mut out,
+                    format!("{prefix}.quota_dimensions[{}]", quote(key)),
+                    quote(value),
+                );
+            }
+            entry(
+                &mut out,
+                format!("{prefix}.quota_value"),
+                violation.quota_value.to_string(),
+            );
+            if let Some(future_quota_value) = violation.future_quota_value {
+                entry(
+                    &mut out,
+                    format!("{prefix}.future_quota_value"),
+                    future_quota_value.to_string(),
+                );
+            }
+        }
+    }
+
+    if let Some(error_info) = &details.error_info {
+        section(&mut out, "error_info");
+        entry(&mut out, "reason", quote(&error_info.reason));
+        entry(&mut out, "domain", quote(&error_info.domain));
+        let metadata: BTreeMap<_, _> = error_info.metadata.iter().collect();
+        for (key, value) in metadata {
+            entry(&mut out, format!("metadata[{}]", quote(key)), quote(value));
+        }
+    }
+
+    if let Some(prec_failure) = &details.precondition_failure {
+        section(&mut out, "precondition_failure");
+        for (i, violation) in prec_failure.violations.iter().enumerate() {
+            let prefix = format!("violations[{i}]");
+            entry(&mut out, format!("{prefix}.type"), quote(&violation.r#type));
+            entry(
+                &mut out,
+                format!("{prefix}.subject"),
+                quote(&violation.subject),
+            );
+            entry(
+                &mut out,
+                format!("{prefix}.description"),
+                quote(&violation.description),
+            );
+        }
+    }
+
+    if let Some(bad_request) = &details.bad_request {
+        section(&mut out, "bad_request");
+        for (i, violation) in bad_request.field_violations.iter().enumerate() {
+            let prefix = format!("field_violations[{i}]");
+            entry(&mut out, format!("{prefix}.field"), quote(&violation.field));
+            entry(
+                &mut out,
+                format!("{prefix}.description"),
+                quote(&violation.description),
+            );
+            entry(
+                &mut out,
+                format!("{prefix}.reason"),
+                quote(&violation.reason),
+            );
+            if let Some(loc_message) = &violation.localized_message {
+                entry(
+                    &mut out,
+                    format!("{prefix}.localized_message.locale"),
+                    quote(&loc_message.locale),
+                );
+                entry(
+                    &mut out,
+                    format!("{prefix}.localized_message.message"),
+                    quote(&loc_message.message),
+                );
+            }
+        }
+    }
+
+    if let Some(req_info) = &details.request_info {
+        section(&mut out, "request_info");
+        entry(&mut out, "request_id", quote(&req_info.request_id));
+        entry(&mut out, "serving_data", quote(&req_info.serving_data));
+    }
+
+    if let Some(res_info) = &details.resource_info {
+        section(&mut out, "resource_info");
+        entry(&mut out, "resource_type", quote(&res_info.resource_type));
+        entry(&mut out, "resource_name", quote(&res_info.resource_name));
+        entry(&mut out, "owner", quote(&res_info.owner));
+        entry(&mut out, "description", quote(&res_info.description));
+    }
+
+    if let Some(help) = &details.help {
+        section(&mut out, "help");
+        for (i, link) in help.links.iter().enumerate() {
+            entry(
+                &mut out,
+                format!("links[{i}].description"),
+                quote(&link.description),
+            );
+            entry(&mut out, format!("links[{i}].url"), quote(&link.url));
+        }
+    }
+
+    if let Some(loc_message) = &details.localized_message {
+        section(&mut out, "localized_message");
+        entry(&mut out, "locale", quote(&loc_message.locale));
+        entry(&mut out, "message", quote(&loc_message.message));
+    }
+
+    if !details.unknown_details.is_empty() {
+        section(&mut out, "unknown_details");
+        for (i, any) in details.unknown_details.iter().enumerate() {
+            entry(&mut out, format!("[{i}].type_url"), quote(&any.type_url));
+            entry(&mut out, format!("[{i}].value"), quote(&hex(&any.value)));
+        }
+    }
+
+    out
+}
+
+/// Parses a snapshot written by [`to_snapshot`], returning its code,
+/// message and error details. Returns a [`SnapshotError`] describing the
+/// first invalid line, if any.
+pub fn parse_snapshot(snapshot: &str) -> Result<(Code, String, ErrorDetails), SnapshotError> {
+    let mut code = None;
+    let mut message = None;
+    let mut details = ErrorDetails::new();
+    let mut section: Option<&str> = None;
+
+    for (index, line) in snapshot.lines().enumerate() {
+        let err = |message: String| SnapshotError {
+            line: index + 1,
+            message,
+        };
+
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            if !name.starts_with(|c: char| c.is_ascii_lowercase()) {
+                return Err(err(format!("invalid section [{name}]")));
+            }
+            open_section(&mut details, name).map_err(err)?;
+            section = Some(name);
+            continue;
+        }
+
+        let (path, value) = parse_entry(line).map_err(err)?;
+
+        match section {
+            None => match path.as_slice() {
+                [Segment::Field("code")] => code = Some(parse_code(value).map_err(err)?),
+                [Segment::Field("message")] => {
+                    message = Some(parse_string(value).map_err(err)?);
+                }
+                _ => return Err(err(format!("unknown status field `{line}`"))),
+            },
+            Some(name) => set_field(&mut details, name, &path, value).map_err(err)?,
+        }
+    }
+
+    let missing = |field: &str| SnapshotError {
+        line: snapshot.lines().count(),
+        message: format!("missing `{field}`"),
+    };
+
+    Ok((
+        code.ok_or_else(|| missing("code"))?,
+        message.ok_or_else(|| missing("message"))?,
+        details,
+    ))
+}
+
+/// Parses a snapshot written by [`to_snapshot`] into a `tonic::Status`,
+/// as described in [`parse_snapshot`].
+pub fn from_snapshot(snapshot: &str) -> Result<Status, SnapshotError> {
+    let (code, message, details) = parse_snapshot(snapshot)?;
+
+    Ok(Status::with_error_details(code, message, details))
+}
+
+/// Segment of an entry key, such as `violations`, `[0]` or `["key"]`.
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Field(&'a str),
+    Index(usize),
+    Key(String),
+}
+
+fn entry(out: &mut String, key: impl fmt::Display, value: impl fmt::Display) {
+    out.push_str(&format!("{key} = {value}\n"));
+}
+
+fn section(out: &mut String, name: &str) {
+    out.push_str(&format!("\n[{name}]\n"));
+}
+
+/// Quotes a string, escaping backslashes, quotes and control characters.
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// Parses a quoted string at the start of `input`, returning it along with
+/// the rest of the input.
+fn parse_quoted(input: &str) -> Result<(String, &str), String> {
+    let mut chars = input
+        .strip_prefix('"')
+        .ok_or_else(|| format!("expected quoted string, found `{input}`"))?
+        .char_indices();
+
+    let mut value = String::new();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &input[i + 2..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('"') => value.push('"'),
+                Some('\\') => value.push('\\'),
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some('u') => {
+                    let rest = &input[i + 3..];
+                    let end = rest
+                        .strip_prefix('{')
+                        .and_then(|r| r.find('}'))
+                        .ok_or("invalid unicode escape")?;
+                    let c = u32::from_str_radix(&rest[1..end + 1], 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or("invalid unicode escape")?;
+                    value.push(c);
+                    for _ in 0..end + 2 {
+                        chars.next();
+                    }
+                }
+                _ => return Err("invalid escape sequence".into()),
+            },
+            c => value.push(c),
+        }
+    }
+
+    Err("unterminated string".into())
+}
+
+fn parse_string(value: &str) -> Result<String, String> {
+    match parse_quoted(value)? {
+        (value, "") => Ok(value),
+        (_, rest) => Err(format!("unexpected `{rest}` after string")),
+    }
+}
+
+/// Splits a `key = value` line into the key segments and the raw value.
+fn parse_entry(line: &str) -> Result<(Vec<Segment<'_>>, &str), String> {
+    let mut segments = Vec::new();
+    let mut rest = line;
+
+    loop {
+        if let Some(value) = rest.strip_prefix(" = ") {
+            if segments.is_empty() {
+                return Err("missing key".into());
+            }
+            return Ok((segments, value));
+        } else if let Some(index) = rest.strip_prefix('[') {
+            if index.starts_with('"') {
+                let (key, after) = parse_quoted(index)?;
+                segments.push(Segment::Key(key));
+                rest = after;
+            } else {
+                let end = index.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+                let value = index[..end]
+                    .parse()
+                    .map_err(|_| format!("invalid index in `{line}`"))?;
+                segments.push(Segment::Index(value));
+                rest = &index[end..];
+            }
+            rest = rest
+                .strip_prefix(']')
+                .ok_or_else(|| format!("expected `]` in `{line}`"))?;
+        } else {
+            let field = rest.strip_prefix('.').unwrap_or(rest);
+            let end = field
+                .find(|c: char| !(c.is_ascii_lowercase() || c == '_'))
+                .unwrap_or(field.len());
+            if end == 0 || (rest.starts_with('.') == segments.is_empty()) {
+                return Err(format!("expected `key = value`, found `{line}`"));
+            }
+            segments.push(Segment::Field(&field[..end]));
+            rest = &field[end..];
+        }
+    }
+}
+
+fn parse_code(value: &str) -> Result<Code, String> {
+    (0..=16)
+        .map(Code::from_i32)
+        .find(|code| format!("{code:?}") == value)
+        .ok_or_else(|| format!("unknown code `{value}`"))
+}
+
+fn format_duration(duration: Duration) -> String {
+    let nanos = format!("{:09}", duration.subsec_nanos());
+    let nanos = nanos.trim_end_matches('0');
+
+    if nanos.is_empty() {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}.{}s", duration.as_secs(), nanos)
+    }
+}
+
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration `{value}`");
+
+    let value_secs = value.strip_suffix('s').ok_or_else(invalid)?;
+    let (secs, nanos) = value_secs.split_once('.').unwrap_or((value_secs, ""));
+
+    if nanos.len() > 9 || !nanos.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let secs = secs.parse().map_err(|_| invalid())?;
+    let nanos = format!("{nanos:0<9}").parse().map_err(|_| invalid())?;
+
+    Ok(Duration::new(secs, nanos))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn unhex(value: &str) -> Result<Vec<u8>, String> {
+    if !value.len().is_multiple_of(2) || !value.is_ascii() {
+        return Err(format!("invalid hex value `{value}`"));
+    }
+
+    (0..value.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&value[i..i + 2], 16)
+                .map_err(|_| format!("invalid hex value `{value}`"))
+        })
+        .collect()
+}
+
+/// Returns the element at `index`, appending it if `index` is the length of
+/// the list. Indices must be consecutive.
+fn slot<T: Default>(list: &mut Vec<T>, index: usize) -> Result<&mut T, String> {
+    if index == list.len() {
+        list.push(T::default());
+    }
+
+    list.get_mut(index)
+        .ok_or_else(|| format!("index {index} is out of order"))
+}
+
+fn open<T: Default>(detail: &mut Option<T>, name: &str) -> Result<(), String> {
+    if detail.is_some() {
+        return Err(format!("duplicated section [{name}]"));
+    }
+
+    *detail = Some(T::default());
+
+    Ok(())
+}
+
+fn open_section(details: &mut ErrorDetails, name: &str) -> Result<(), String> {
+    match name {
+        "retry_info" => open(&mut details.retry_info, name),
+        "debug_info" => open(&mut details.debug_info, name),
+        "quota_failure" => open(&mut details.quota_failure, name),
+        "error_info" => open(&mut details.error_info, name),
+        "precondition_failure" => open(&mut details.precondition_failure, name),
+        "bad_request" => open(&mut details.bad_request, name),
+        "request_info" => open(&mut details.request_info, name),
+        "resource_info" => open(&mut details.resource_info, name),
+        "help" => open(&mut details.help, name),
+        "localized_message" => open(&mut details.localized_message, name),
+        "unknown_details" if details.unknown_details.is_empty() => Ok(()),
+        "unknown_details" => Err(format!("duplicated section [{name}]")),
+        _ => Err(format!("unknown section [{name}]")),
+    }
+}
+
+fn set_field(
+    details: &mut ErrorDetails,
+    section: &str,
+    path: &[Segment<'_>],
+    value: &str,
+) -> Result<(), String> {
+    use Segment::{Field, Index, Key};
+
+    match (section, path) {
+        ("retry_info", [Field("retry_delay")]) => {
+            details
+                .retry_info
+                .get_or_insert_with(Default::default)
+                .retry_delay = Some(parse_duration(value)?);
+        }
+
+        ("debug_info", path) => {
+            let debug_info = details.debug_info.get_or_insert_with(Default::default);
+            match path {
+                [Field("stack_entries"), Index(i)] => {
+                    *slot(&mut debug_info.stack_entries, *i)? = parse_string(value)?;
+                }
+                [Field("detail")] => debug_info.detail = parse_string(value)?,
+                _ => return Err(unknown_field(section, path)),
+            }
+        }
+
+        ("quota_failure", [Field("violations"), Index(i), field @ ..]) => {
+            let quota_failure = details.quota_failure.get_or_insert_with(Default::default);
+            let violation = slot(&mut quota_failure.violations, *i)?;
+            match field {
+                [Field("subject")] => violation.subject = parse_string(value)?,
+                [Field("description")] => violation.description = parse_string(value)?,
+                [Field("api_service")] => violation.api_service = parse_string(value)?,
+                [Field("quota_metric")] => violation.quota_metric = parse_string(value)?,
+                [Field("quota_id")] => violation.quota_id = parse_string(value)?,
+                [Field("quota_dimensions"), Key(key)] => {
+                    violation
+                        .quota_dimensions
+                        .insert(key.clone(), parse_string(value)?);
+                }
+                [Field("quota_value")] => {
+                    violation.quota_value = value
+                        .parse()
+                        .map_err(|_| format!("invalid integer `{value}`"))?;
+                }
+                [Field("future_quota_value")] => {
+                    violation.future_quota_value = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("invalid integer `{value}`"))?,
+                    );
+                }
+                _ => return Err(unknown_field(section, path)),
+            }
+        }
+
+        ("error_info", path) => {
+            let error_info = details.error_info.get_or_insert_with(Default::default);
+            match path {
+                [Field("reason")] => error_info.reason = parse_string(value)?,
+                [Field("domain")] => error_info.domain = parse_string(value)?,
+                [Field("metadata"), Key(key)] => {
+                    error_info
+                        .metadata
+                        .insert(key.clone(), parse_string(value)?);
+                }
+                _ => return Err(unknown_field(section, path)),
+            }
+        }
+
+        ("precondition_failure", [Field("violations"), Index(i), field]) => {
+            let prec_failure = details
+                .precondition_failure
+                .get_or_insert_with(Default::default);
+            let violation = slot(&mut prec_failure.violations, *i)?;
+            match field {
+                Field("type") => violation.r#type = parse_string(value)?,
+                Field("subject") => violation.subject = parse_string(value)?,
+                Field("description") => violation.description = parse_string(value)?,
+                _ => return Err(unknown_field(section, path)),
+            }
+        }
+
+        ("bad_request", [Field("field_violations"), Index(i), field @ ..]) => {
+            let bad_request = details.bad_request.get_or_insert_with(Default::default);
+            let violation = slot(&mut bad_request.field_violations, *i)?;
+            match field {
+                [Field("field")] => violation.field = parse_string(value)?,
+                [Field("description")] => violation.description = parse_string(value)?,
+                [Field("reason")] => violation.reason = parse_string(value)?,
+                [Field("localized_message"), Field(loc_field)] => {
+                    let loc_message = violation
+                        .localized_message
+                        .get_or_insert_with(LocalizedMessage::default);
+                    match *loc_field {
+                        "locale" => loc_message.locale = parse_string(value)?,
+                        "message" => loc_message.message = parse_string(value)?,
+                        _ => return Err(unknown_field(section, path)),
+                    }
+                }
+                _ => return Err(unknown_field(section, path)),
+            }
+        }
+
+        ("request_info", [Field(field)]) => {
+            let req_info = details.request_info.get_or_insert_with(Default::default);
+            match *field {
+                "request_id" => req_info.request_id = parse_string(value)?,
+                "serving_data" => req_info.serving_data = parse_string(value)?,
+                _ => return Err(unknown_field(section, path)),
+            }
+        }
+
+        ("resource_info", [Field(field)]) => {
+            let res_info = details.resource_info.get_or_insert_with(Default::default);
+            match *field {
+                "resource_type" => res_info.resource_type = parse_string(value)?,
+                "resource_name" => res_info.resource_name = parse_string(value)?,
+                "owner" => res_info.owner = parse_string(value)?,
+                "description" => res_info.description = parse_string(value)?,
+                _ => return Err(unknown_field(section, path)),
+            }
+        }
+
+        ("help", [Field("links"), Index(i), Field(field)]) => {
+            let help = details.help.get_or_insert_with(Default::default);
+            let link = slot(&mut help.links, *i)?;
+            match *field {
+                "description" => link.description = parse_string(value)?,
+                "url" => link.url = parse_string(value)?,
+                _ => return Err(unknown_field(section, path)),
+            }
+        }
+
+        ("localized_message", [Field(field)]) => {
+            let loc_message = details
+                .localized_message
+                .get_or_insert_with(Default::default);
+            match *field {
+                "locale" => loc_message.locale = parse_string(value)?,
+                "message" => loc_message.message = parse_string(value)?,
+                _ => return Err(unknown_field(section, path)),
+            }
+        }
+
+        ("unknown_details", [Index(i), Field(field)]) => {
+            let any: &mut Any = slot(&mut details.unknown_details, *i)?;
+            match *field {
+                "type_url" => any.type_url = parse_string(value)?,
+                "value" => any.value = unhex(&parse_string(value)?)?,
+                _ => return Err(unknown_field(section, path)),
+            }
+        }
+
+        _ => return Err(unknown_field(section, path)),
+    }
+
+    Ok(())
+}
+
+fn unknown_field(section: &str, path: &[Segment<'_>]) -> String {
+    let mut key = String::new();
+
+    for segment in path {
+        match segment {
+            Segment::Field(field) if key.is_empty() => key.push_str(field),
+            Segment::Field(field) => key.push_str(&format!(".{field}")),
+            Segment::Index(index) => key.push_str(&format!("[{index}]")),
+            Segment::Key(map_key) => key.push_str(&format!("[{}]", quote(map_key))),
+        }
+    }
+
+    format!("unknown field `{key}` in section [{section}]")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use prost_types::Any;
+    use tonic::Code;
+
+    use super::super::{ErrorDetails, FieldViolation, LocalizedMessage, QuotaViolation};
+    use super::{details_to_snapshot, parse_snapshot};
+
+    fn all_details() -> ErrorDetails {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("b".to_string(), "2".to_string());
+        metadata.insert("a \"quoted\"".to_string(), "1\n\u{7}".to_string());
+
+        let mut quota_violation = QuotaViolation::new("clientip:127.0.0.1", "rate exceeded");
+        quota_violation
+            .set_quota_value(100)
+            .quota_dimensions
+            .insert("region".into(), "us-east1".into());
+
+        let mut field_violation = FieldViolation::new("name", "is required");
+        field_violation.set_localized_message("pt-BR", "é obrigatório");
+
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_millis(1500)))
+            .set_debug_info(vec!["trace 1".into()], "details")
+            .set_quota_failure(vec![quota_violation])
+            .set_error_info("SOME_INFO", "example.local", metadata)
+            .add_precondition_failure_violation("TOS", "example.local", "not accepted")
+            .set_bad_request(vec![field_violation, FieldViolation::new("age", "")])
+            .set_request_info("request-id", "serving data")
+            .set_resource_info("type", "name", "owner", "description")
+            .add_help_link("link to resource", "resource.example.local")
+            .set_localized_message("en-US", "message for the user");
+
+        err_details.unknown_details.push(Any {
+            type_url: "type.example.local/Custom".into(),
+            value: vec![0x0a, 0xff],
+        });
+
+        err_details
+    }
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let err_details = all_details();
+
+        let snapshot = details_to_snapshot(Code::Aborted, "aborted \"op\"", &err_details);
+
+        println!("snapshot ->\n{snapshot}");
+
+        let expected = r#"code = Aborted
+message = "aborted \"op\""
+
+[retry_info]
+retry_delay = 1.5s
+
+[debug_info]
+stack_entries[0] = "trace 1"
+detail = "details"
+
+[quota_failure]
+violations[0].subject = "clientip:127.0.0.1"
+violations[0].description = "rate exceeded"
+violations[0].api_service = ""
+violations[0].quota_metric = ""
+violations[0].quota_id = ""
+violations[0].quota_dimensions["region"] = "us-east1"
+violations[0].quota_value = 100
+
+[error_info]
+reason = "SOME_INFO"
+domain = "example.local"
+metadata["a \"quoted\""] = "1\n\u{7}"
+metadata["b"] = "2"
+
+[precondition_failure]
+violations[0].type = "TOS"
+violations[0].subject = "example.local"
+violations[0].description = "not accepted"
+
+[bad_request]
+field_violations[0].field = "name"
+field_violations[0].description = "is required"
+field_violations[0].reason = ""
+field_violations[0].localized_message.locale = "pt-BR"
+field_violations[0].localized_message.message = "é obrigatório"
+field_violations[1].field = "age"
+field_violations[1].description = ""
+field_violations[1].reason = ""
+
+[request_info]
+request_id = "request-id"
+serving_data = "serving data"
+
+[resource_info]
+resource_type = "type"
+resource_name = "name"
+owner = "owner"
+description = "description"
+
+[help]
+links[0].description = "link to resource"
+links[0].url = "resource.example.local"
+
+[localized_message]
+locale = "en-US"
+message = "message for the user"
+
+[unknown_details]
+[0].type_url = "type.example.local/Custom"
+[0].value = "0aff"
+"#;
+
+        assert!(
+            snapshot.eq(expected),
+            "snapshot differs from expected result"
+        );
+
+        let (code, message, parsed) = match parse_snapshot(&snapshot) {
+            Ok(parsed) => parsed,
+            Err(err) => panic!("Error parsing snapshot: {}", err),
+        };
+
+        assert!(
+            code == Code::Aborted && message == "aborted \"op\"" && parsed == err_details,
+            "parsed snapshot differs from original details"
+        );
+
+        let mut empty_details = ErrorDetails::new();
+        empty_details.bad_request = Some(Default::default());
+        empty_details.localized_message = Some(LocalizedMessage::default());
+
+        let snapshot = details_to_snapshot(Code::Unknown, "", &empty_details);
+
+        assert!(
+            parse_snapshot(&snapshot).map(|(_, _, details)| details) == Ok(empty_details),
+            "empty details were not kept"
+        );
+    }
+
+    #[test]
+    fn invalid_snapshots() {
+        let cases = [
+            (
+                "message = \"m\"",
+                "invalid snapshot at line 1: missing `code`",
+            ),
+            (
+                "code = Nope\nmessage = \"\"",
+                "invalid snapshot at line 1: unknown code `Nope`",
+            ),
+            (
+                "code = Ok\nmessage = \"\"\n[bad_request]\nfield_violations[1].field = \"f\"",
+                "invalid snapshot at line 4: index 1 is out of order",
+            ),
+            (
+                "code = Ok\nmessage = \"\"\n[help]\n[help]",
+                "invalid snapshot at line 4: duplicated section [help]",
+            ),
+            (
+                "code = Ok\nmessage = \"\"\n[error_info]\nreson = \"r\"",
+                "invalid snapshot at line 4: unknown field `reson` in section [error_info]",
+            ),
+            (
+                "code = Ok\nmessage = \"unterminated",
+                "invalid snapshot at line 2: unterminated string",
+            ),
+        ];
+
+        for (snapshot, expected) in cases {
+            let formatted = match parse_snapshot(snapshot) {
+                Ok(parsed) => panic!("invalid snapshot was parsed: {:?}", parsed),
+                Err(err) => err.to_string(),
+            };
+
+            println!("snapshot error -> {formatted}");
+
+            assert!(
+                formatted.eq(expected),
+                "snapshot error differs from expected result"
+            );
+        }
+    }
+}
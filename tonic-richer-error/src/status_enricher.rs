@@ -0,0 +1,537 @@
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use http_body::{Body, SizeHint};
+use tonic::codegen::{http, BoxFuture, Service};
+use tonic::{Code, Status};
+use tower_layer::Layer;
+
+use super::{ErrorDetails, ErrorDetailsPolicy, WithErrorDetails};
+
+/// Headers that tonic never carries as status metadata, left untouched when
+/// replacing a status.
+const RESERVED_HEADERS: [&str; 4] = ["te", "user-agent", "content-type", "grpc-message-type"];
+
+/// Information about the request that produced an error status, available
+/// to every [`StatusEnricher`].
+#[derive(Clone, Debug)]
+pub struct RequestContext {
+    path: String,
+    headers: http::HeaderMap,
+}
+
+impl RequestContext {
+    /// Creates a new [`RequestContext`] from the path and headers of a
+    /// request.
+    pub fn new(path: impl Into<String>, headers: http::HeaderMap) -> Self {
+        RequestContext {
+            path: path.into(),
+            headers,
+        }
+    }
+
+    /// Returns the request path, in the `/package.Service/Method` form.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the request headers, which hold the gRPC metadata.
+    pub fn headers(&self) -> &http::HeaderMap {
+        &self.headers
+    }
+
+    /// Returns the value of a request header, if present and valid ASCII.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+}
+
+/// Modifies the error statuses returned by a tonic server, usually by adding
+/// error details. Installed with [`StatusEnricherLayer`], so details like
+/// [`crate::Help`] links or [`crate::LocalizedMessage`] can be added
+/// centrally, instead of in every handler.
+///
+/// Implemented by closures with a matching signature, by [`ErrorDetails`],
+/// which merges a copy of itself into the status details, as described in
+/// [`ErrorDetails::merge`], and by [`ErrorDetailsPolicy`], which applies
+/// itself to the status details, as described in [`ErrorDetailsPolicy::apply`].
+/// # Examples
+///
+/// ```
+/// use tonic::Status;
+/// use tonic_richer_error::{RequestContext, StatusEnricher, WithErrorDetails};
+///
+/// struct MethodHelp;
+///
+/// impl StatusEnricher for MethodHelp {
+///     fn enrich(&self, status: Status, ctx: &RequestContext) -> Status {
+///         let url = format!("https://example.local/docs{}", ctx.path());
+///         status.map_error_details(|details| {
+///             details.add_help_link("method documentation", url);
+///         })
+///     }
+/// }
+/// ```
+pub trait StatusEnricher {
+    /// Returns the enriched status.
+    fn enrich(&self, status: Status, ctx: &RequestContext) -> Status;
+}
+
+impl<F> StatusEnricher for F
+where
+    F: Fn(Status, &RequestContext) -> Status,
+{
+    fn enrich(&self, status: Status, ctx: &RequestContext) -> Status {
+        self(status, ctx)
+    }
+}
+
+impl StatusEnricher for ErrorDetails {
+    fn enrich(&self, status: Status, _: &RequestContext) -> Status {
+        status.map_error_details(|details| {
+            details.merge(self.clone());
+        })
+    }
+}
+
+impl StatusEnricher for ErrorDetailsPolicy {
+    fn enrich(&self, status: Status, _: &RequestContext) -> Status {
+        status.map_error_details(|details| self.apply(details))
+    }
+}
+
+type Enricher = Arc<dyn StatusEnricher + Send + Sync>;
+
+/// A tower `Layer` that can be installed on a tonic server, to pass every
+/// error status returned by the service through a stack of
+/// [`StatusEnricher`]s, in the order they were added.
+///
+/// Statuses sent as trailers-only responses (the usual case for errors
+/// returned by unary endpoints) are enriched right away, while statuses sent
+/// in the trailers of streaming responses are enriched by the
+/// [`StatusEnricherBody`] wrapping the response body.
+/// # Examples
+///
+/// ```
+/// use tonic::Status;
+/// use tonic_richer_error::{
+///     ErrorDetails, RequestContext, StatusEnricherLayer, StatusEnricherService, WithErrorDetails,
+/// };
+/// use tower::ServiceBuilder;
+///
+/// // Where `service` is a tonic generated server, like `GreeterServer`
+/// fn with_enrichers<S>(service: S) -> StatusEnricherService<S> {
+///     let layer = StatusEnricherLayer::new()
+///         .enricher(ErrorDetails::with_help_link("support", "https://example.local/support"))
+///         .enricher(|status: Status, ctx: &RequestContext| {
+///             let locale = ctx.header("accept-language").unwrap_or("en-US").to_string();
+///             status.map_error_details(|details| {
+///                 if details.localized_message.is_none() {
+///                     details.set_localized_message(locale, "An error occurred");
+///                 }
+///             })
+///         });
+///
+///     ServiceBuilder::new().layer(layer).service(service)
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct StatusEnricherLayer {
+    enrichers: Vec<Enricher>,
+}
+
+impl StatusEnricherLayer {
+    /// Creates a new [`StatusEnricherLayer`], with no enrichers.
+    pub fn new() -> Self {
+        StatusEnricherLayer::default()
+    }
+
+    /// Appends an enricher to the stack.
+    pub fn enricher(mut self, enricher: impl StatusEnricher + Send + Sync + 'static) -> Self {
+        self.enrichers.push(Arc::new(enricher));
+        self
+    }
+
+    /// Passes a status through every enricher in the stack.
+    fn enrich(&self, status: Status, ctx: &RequestContext) -> Status {
+        self.enrichers
+            .iter()
+            .fold(status, |status, enricher| enricher.enrich(status, ctx))
+    }
+}
+
+impl fmt::Debug for StatusEnricherLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StatusEnricherLayer")
+            .field("enrichers", &self.enrichers.len())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for StatusEnricherLayer {
+    type Service = StatusEnricherService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        StatusEnricherService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service generated by [`StatusEnricherLayer`].
+#[derive(Clone, Debug)]
+pub struct StatusEnricherService<S> {
+    inner: S,
+    layer: StatusEnricherLayer,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for StatusEnricherService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<StatusEnricherBody<ResBody>>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        let ctx = RequestContext::new(req.uri().path(), req.headers().clone());
+
+        let layer = self.layer.clone();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let (mut parts, inner) = future.await?.into_parts();
+
+            // Trailers-only responses carry no trailers to enrich
+            let enrich = if parts.headers.contains_key("grpc-status") {
+                replace_status(&mut parts.headers, |status| layer.enrich(status, &ctx));
+                None
+            } else {
+                Some((layer, ctx))
+            };
+
+            Ok(http::Response::from_parts(
+                parts,
+                StatusEnricherBody { inner, enrich },
+            ))
+        })
+    }
+}
+
+/// Response body generated by [`StatusEnricherService`]. Forwards the data
+/// of the inner body, and passes the error status sent in its trailers, if
+/// any, through the enrichers.
+#[derive(Debug)]
+pub struct StatusEnricherBody<B> {
+    inner: B,
+    enrich: Option<(StatusEnricherLayer, RequestContext)>,
+}
+
+impl<B> Body for StatusEnricherBody<B>
+where
+    B: Body + Unpin,
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        Pin::new(&mut self.get_mut().inner).poll_data(cx)
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        let this = self.get_mut();
+        let mut poll = Pin::new(&mut this.inner).poll_trailers(cx);
+
+        if let Poll::Ready(Ok(Some(trailers))) = &mut poll {
+            if let Some((layer, ctx)) = this.enrich.take() {
+                replace_status(trailers, |status| layer.enrich(status, &ctx));
+            }
+        }
+
+        poll
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Replaces the error status contained in the headers of a trailers-only
+/// response, or in the trailers of a response, if any, with the one returned
+/// by `f`. The other headers are
+/// carried as status metadata, so metadata added, changed or removed by `f`
+/// is reflected in the response.
+pub(crate) fn replace_status(headers: &mut http::HeaderMap, f: impl FnOnce(Status) -> Status) {
+    let status = match Status::from_header_map(headers) {
+        Some(status) if status.code() != Code::Ok => status,
+        _ => return,
+    };
+
+    let response = f(status).to_http();
+    let replaced = response.headers();
+
+    let removed: Vec<http::HeaderName> = headers
+        .keys()
+        .filter(|name| !replaced.contains_key(*name) && !is_reserved(name))
+        .cloned()
+        .collect();
+
+    for name in removed {
+        headers.remove(name);
+    }
+
+    for name in replaced.keys() {
+        if is_reserved(name) {
+            continue;
+        }
+
+        headers.remove(name);
+
+        for value in replaced.get_all(name) {
+            headers.append(name.clone(), value.clone());
+        }
+    }
+}
+
+fn is_reserved(name: &http::HeaderName) -> bool {
+    RESERVED_HEADERS.contains(&name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::convert::Infallible;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use http_body::Body;
+    use tonic::body::BoxBody;
+    use tonic::codegen::{http, BoxFuture, Service};
+    use tonic::{Code, Status};
+    use tower_layer::Layer;
+
+    use super::super::{ErrorDetails, ErrorDetailsPolicy, WithErrorDetails};
+    use super::{RequestContext, StatusEnricherLayer};
+
+    /// Fails every call with a status carrying `BadRequest` details.
+    #[derive(Clone)]
+    struct FailingService;
+
+    impl Service<http::Request<()>> for FailingService {
+        type Response = http::Response<BoxBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: http::Request<()>) -> Self::Future {
+            Box::pin(async move {
+                let mut status = Status::with_error_details(
+                    Code::InvalidArgument,
+                    "bad request",
+                    ErrorDetails::with_bad_request_violation("field", "description"),
+                );
+                status
+                    .metadata_mut()
+                    .insert("x-trace-id", "trace".parse().unwrap());
+                Ok(status.to_http())
+            })
+        }
+    }
+
+    /// Response body of a streaming call, with no data and the given
+    /// trailers.
+    struct TrailersBody(Option<http::HeaderMap>);
+
+    impl Body for TrailersBody {
+        type Data = tonic::codegen::Bytes;
+        type Error = Status;
+
+        fn poll_data(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            Poll::Ready(None)
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+        ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(self.get_mut().0.take()))
+        }
+    }
+
+    /// Answers every call with a streaming response, sending its status in
+    /// the trailers.
+    #[derive(Clone)]
+    struct StreamingService;
+
+    impl Service<http::Request<()>> for StreamingService {
+        type Response = http::Response<TrailersBody>;
+        type Error = Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _: http::Request<()>) -> Self::Future {
+            Box::pin(async move {
+                let status = Status::with_error_details(
+                    Code::Internal,
+                    "internal",
+                    ErrorDetails::with_debug_info(vec!["trace".into()], "detail"),
+                );
+                let trailers = status.to_http().headers().clone();
+                Ok(http::Response::new(TrailersBody(Some(trailers))))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn enrich_statuses() {
+        let mut svc = StatusEnricherLayer::new()
+            .enricher(ErrorDetails::with_help_link(
+                "support",
+                "https://example.local/support",
+            ))
+            .enricher(|status: Status, ctx: &RequestContext| {
+                let locale = ctx.header("accept-language").unwrap_or("en-US").to_string();
+                let message = format!("{} failed", ctx.path());
+                status.map_error_details(|details| {
+                    details.set_localized_message(locale, message);
+                })
+            })
+            .layer(FailingService);
+
+        let req = http::Request::builder()
+            .uri("/example.Greeter/SayHello")
+            .header("accept-language", "pt-BR")
+            .body(())
+            .unwrap();
+
+        let response = svc.call(req).await.unwrap();
+
+        let status = match Status::from_header_map(response.headers()) {
+            Some(status) => status,
+            None => panic!("response does not contain a status"),
+        };
+
+        let formatted = format!(
+            "{:?} {:?} {:?} {:?}",
+            status.code(),
+            status.get_details_bad_request(),
+            status.get_details_help(),
+            status.get_details_localized_message()
+        );
+
+        println!("enriched status -> {formatted}");
+
+        let expected = "InvalidArgument Some(BadRequest { field_violations: [FieldViolation { field: \"field\", description: \"description\", reason: \"\", localized_message: None }] }) Some(Help { links: [HelpLink { description: \"support\", url: \"https://example.local/support\" }] }) Some(LocalizedMessage { locale: \"pt-BR\", message: \"/example.Greeter/SayHello failed\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "enriched status differs from expected result"
+        );
+    }
+
+    #[tokio::test]
+    async fn preserve_status_metadata() {
+        let mut svc = StatusEnricherLayer::new()
+            .enricher(|status: Status, _: &RequestContext| {
+                let mut status = status.map_error_details(|details| {
+                    details.set_request_info("request-id", "");
+                });
+                status
+                    .metadata_mut()
+                    .insert("x-enriched", "true".parse().unwrap());
+                status
+            })
+            .layer(FailingService);
+
+        let req = http::Request::builder()
+            .uri("/example.Greeter/SayHello")
+            .body(())
+            .unwrap();
+
+        let response = svc.call(req).await.unwrap();
+
+        println!("enriched response headers -> {:?}", response.headers());
+
+        assert!(
+            response.headers().get("x-trace-id").is_some(),
+            "original status metadata was lost"
+        );
+
+        assert!(
+            response.headers().get("x-enriched").is_some(),
+            "metadata added by the enricher is missing"
+        );
+
+        let status = match Status::from_header_map(response.headers()) {
+            Some(status) => status,
+            None => panic!("response does not contain a status"),
+        };
+
+        assert!(
+            status.get_details_request_info().is_some(),
+            "enriched status is missing RequestInfo details"
+        );
+    }
+
+    #[tokio::test]
+    async fn enrich_trailers() {
+        let mut svc = StatusEnricherLayer::new()
+            .enricher(ErrorDetailsPolicy::new().redact_debug_info())
+            .layer(StreamingService);
+
+        let req = http::Request::builder()
+            .uri("/example.Greeter/ListFeatures")
+            .body(())
+            .unwrap();
+
+        let mut body = svc.call(req).await.unwrap().into_body();
+
+        let trailers = match body.trailers().await {
+            Ok(Some(trailers)) => trailers,
+            _ => panic!("response does not contain trailers"),
+        };
+
+        let status = match Status::from_header_map(&trailers) {
+            Some(status) => status,
+            None => panic!("trailers do not contain a status"),
+        };
+
+        let formatted = format!("{:?} {:?}", status.code(), status.get_details_debug_info());
+
+        println!("enriched trailers status -> {formatted}");
+
+        let expected = "Internal None";
+
+        assert!(
+            formatted.eq(expected),
+            "enriched trailers status differs from expected result"
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use serde::{de, Deserialize, Serialize};
+use serde_json::Value;
+use tonic::Code;
+
+use super::policy;
+use super::std_messages::*;
+use super::type_url_prefix::canonical_type_url;
+use super::{ErrorDetail, ErrorDetails, FromAny, IntoAny};
+
+const KNOWN_TYPE_URLS: [&str; 12] = [
+    RetryInfo::TYPE_URL,
+    DebugInfo::TYPE_URL,
+    QuotaFailure::TYPE_URL,
+    ErrorInfo::TYPE_URL,
+    PreconditionFailure::TYPE_URL,
+    BadRequest::TYPE_URL,
+    RequestInfo::TYPE_URL,
+    ResourceInfo::TYPE_URL,
+    Help::TYPE_URL,
+    LocalizedMessage::TYPE_URL,
+    HttpRequest::TYPE_URL,
+    HttpResponse::TYPE_URL,
+];
+
+/// JSON representation of `google.rpc.Status`, as produced by gRPC-JSON
+/// transcoders like grpc-gateway and Envoy.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct StatusJson<D> {
+    code: i32,
+    message: String,
+    details: Vec<D>,
+}
+
+impl<D> Default for StatusJson<D> {
+    fn default() -> Self {
+        StatusJson {
+            code: 0,
+            message: String::new(),
+            details: Vec::new(),
+        }
+    }
+}
+
+impl ErrorDetails {
+    /// Generates the canonical JSON representation of a `google.rpc.Status`
+    /// with the given code, message and error details, as used by gRPC-JSON
+    /// transcoders. Each detail is tagged with its type URL at the `@type`
+    /// field, and details with unknown type URLs carry their encoded message
+    /// as base64 text at a `value` field. As in [`crate::WithErrorDetails::with_error_details`], the
+    /// global [`crate::ErrorDetailsPolicy`] is applied.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::Code;
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let err_details = ErrorDetails::with_bad_request_violation("field", "description");
+    ///
+    /// let json = err_details.to_status_json(Code::InvalidArgument, "bad request");
+    ///
+    /// assert_eq!(
+    ///     json,
+    ///     r#"{"code":3,"message":"bad request","details":[{"@type":"type.googleapis.com/google.rpc.BadRequest","fieldViolations":[{"field":"field","description":"description"}]}]}"#
+    /// );
+    /// ```
+    pub fn to_status_json(&self, code: Code, message: impl Into<String>) -> String {
+        let mut details = self.clone();
+        policy::apply_global(&mut details);
+
+        let status = StatusJson {
+            code: code as i32,
+            message: message.into(),
+            details: details.to_details_vec(),
+        };
+
+        // Serializing plain structs and string keyed maps cannot fail
+        serde_json::to_string(&status).unwrap_or_default()
+    }
+
+    /// Parses the canonical JSON representation of a `google.rpc.Status`,
+    /// returning its code, message and error details. Details with unknown
+    /// `@type`s are kept at the `unknown_details` field, and must carry their
+    /// encoded message as base64 text at a `value` field, as generated by
+    /// [`ErrorDetails::to_status_json`]. If some detail is malformed, a
+    /// `serde_json::Error` is returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::Code;
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let json = r#"{
+    ///     "code": 3,
+    ///     "message": "bad request",
+    ///     "details": [{
+    ///         "@type": "type.googleapis.com/google.rpc.BadRequest",
+    ///         "fieldViolations": [{"field": "field", "description": "description"}]
+    ///     }]
+    /// }"#;
+    ///
+    /// let (code, message, err_details) = ErrorDetails::from_status_json(json).unwrap();
+    ///
+    /// assert_eq!(code, Code::InvalidArgument);
+    /// assert!(err_details.has_bad_request_violations());
+    /// ```
+    pub fn from_status_json(json: &str) -> Result<(Code, String, ErrorDetails), serde_json::Error> {
+        let status: StatusJson<Value> = serde_json::from_str(json)?;
+
+        let mut details = ErrorDetails::new();
+
+        for detail in status.details.into_iter() {
+            details.set_detail(ErrorDetail::deserialize(detail)?);
+        }
+
+        Ok((Code::from_i32(status.code), status.message, details))
+    }
+
+    fn to_details_vec(&self) -> Vec<ErrorDetail> {
+        let details = self.clone();
+
+        [
+            details.retry_info.map(ErrorDetail::from),
+            details.debug_info.map(ErrorDetail::from),
+            details.quota_failure.map(ErrorDetail::from),
+            details.error_info.map(ErrorDetail::from),
+            details.precondition_failure.map(ErrorDetail::from),
+            details.bad_request.map(ErrorDetail::from),
+            details.request_info.map(ErrorDetail::from),
+            details.resource_info.map(ErrorDetail::from),
+            details.help.map(ErrorDetail::from),
+            details.localized_message.map(ErrorDetail::from),
+        ]
+        .into_iter()
+        .flatten()
+        // HTTP context details are also kept at `unknown_details`
+        .chain(details.unknown_details.iter().map(|any| {
+            ErrorDetail::from_any(any.into()).unwrap_or_else(|_| ErrorDetail::Other(any.clone()))
+        }))
+        .collect()
+    }
+
+    fn set_detail(&mut self, detail: ErrorDetail) {
+        match detail {
+            ErrorDetail::RetryInfo(retry_info) => self.retry_info = Some(retry_info),
+            ErrorDetail::DebugInfo(debug_info) => self.debug_info = Some(debug_info),
+            ErrorDetail::QuotaFailure(quota_failure) => self.quota_failure = Some(quota_failure),
+            ErrorDetail::ErrorInfo(error_info) => self.error_info = Some(error_info),
+            ErrorDetail::PreconditionFailure(prec_failure) => {
+                self.precondition_failure = Some(prec_failure)
+            }
+            ErrorDetail::BadRequest(bad_req) => self.bad_request = Some(bad_req),
+            ErrorDetail::RequestInfo(req_info) => self.request_info = Some(req_info),
+            ErrorDetail::ResourceInfo(res_info) => self.resource_info = Some(res_info),
+            ErrorDetail::Help(help) => self.help = Some(help),
+            ErrorDetail::LocalizedMessage(loc_message) => {
+                self.localized_message = Some(loc_message)
+            }
+            ErrorDetail::HttpRequest(http_request) => {
+                self.unknown_details.push(http_request.into_any())
+            }
+            ErrorDetail::HttpResponse(http_response) => {
+                self.unknown_details.push(http_response.into_any())
+            }
+            ErrorDetail::Other(any) => self.unknown_details.push(any),
+        }
+    }
+}
+
+impl ErrorDetail {
+    /// Decodes the JSON representation of a `google.protobuf.Any` holding a
+    /// standard error message, as printed by grpcurl and other interop
+    /// tools, with the message type at the `@type` field. Standard messages
+    /// are recognized whatever the prefix of their type URL. If `@type` is
+    /// missing or does not name a standard error message, or if the message
+    /// is malformed, a `serde_json::Error` is returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use tonic_richer_error::ErrorDetail;
+    ///
+    /// let detail = ErrorDetail::from_json_any(json!({
+    ///     "@type": "type.googleapis.com/google.rpc.ErrorInfo",
+    ///     "reason": "API_DISABLED",
+    ///     "domain": "googleapis.com",
+    ///     "metadata": {"service": "pubsub.googleapis.com"}
+    /// }))
+    /// .unwrap();
+    ///
+    /// match detail {
+    ///     ErrorDetail::ErrorInfo(error_info) => assert_eq!(error_info.reason, "API_DISABLED"),
+    ///     _ => panic!("unexpected detail"),
+    /// }
+    /// ```
+    pub fn from_json_any(mut value: Value) -> Result<ErrorDetail, serde_json::Error> {
+        let type_url = match value.get("@type").and_then(Value::as_str) {
+            Some(type_url) => canonical_type_url(type_url).to_string(),
+            None => return Err(de::Error::missing_field("@type")),
+        };
+
+        if !KNOWN_TYPE_URLS.contains(&type_url.as_str()) {
+            return Err(de::Error::custom(format!(
+                "unknown error detail type `{type_url}`"
+            )));
+        }
+
+        value["@type"] = Value::String(type_url);
+
+        ErrorDetail::deserialize(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+    use tonic::Code;
+
+    use super::super::{ErrorDetail, ErrorDetails};
+
+    #[test]
+    fn status_json_round_trip() {
+        let mut metadata = BTreeMap::new();
+        metadata.insert("limitPerRequest".to_string(), "100".into());
+
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .set_error_info("SOME_INFO", "example.local", metadata)
+            .add_precondition_failure_violation("TOS", "example.local", "description")
+            .set_request_info("request-id", "some-request-data")
+            .set_localized_message("en-US", "message for the user");
+
+        let json = err_details.to_status_json(Code::FailedPrecondition, "precondition failed");
+
+        println!("status JSON -> {json}");
+
+        let (code, message, ext_details) = match ErrorDetails::from_status_json(&json) {
+            Ok(parsed) => parsed,
+            Err(err) => panic!("Error parsing status JSON: {:?}", err),
+        };
+
+        assert!(
+            code == Code::FailedPrecondition && message == "precondition failed",
+            "code or message from status JSON differ from original"
+        );
+
+        assert!(
+            format!("{:?}", ext_details).eq(&format!("{:?}", err_details)),
+            "details from status JSON differ from original details"
+        );
+
+        let json = r#"{
+            "code": 8,
+            "details": [
+                {"@type": "type.example.com/custom.Detail", "value": "AQID"},
+                {"@type": "type.googleapis.com/google.rpc.RetryInfo", "retryDelay": "0.250s"}
+            ]
+        }"#;
+
+        let (code, message, ext_details) = match ErrorDetails::from_status_json(json) {
+            Ok(parsed) => parsed,
+            Err(err) => panic!("Error parsing status JSON: {:?}", err),
+        };
+
+        let formatted = format!(
+            "{:?} {:?} {:?} {:?}",
+            code, message, ext_details.retry_info, ext_details.unknown_details
+        );
+
+        println!("status JSON with unknown details -> {formatted}");
+
+        let expected = "ResourceExhausted \"\" Some(RetryInfo { retry_delay: Some(250ms) }) [Any { type_url: \"type.example.com/custom.Detail\", value: [1, 2, 3] }]";
+
+        assert!(
+            formatted.eq(expected),
+            "status JSON with unknown details differs from expected result"
+        );
+
+        let json = ext_details.to_status_json(code, message);
+
+        let expected = r#"{"code":8,"message":"","details":[{"@type":"type.googleapis.com/google.rpc.RetryInfo","retryDelay":"0.250s"},{"@type":"type.example.com/custom.Detail","value":"AQID"}]}"#;
+
+        assert!(
+            json.eq(expected),
+            "unknown details were not kept in status JSON"
+        );
+
+        let json =
+            r#"{"code": 8, "details": [{"@type": "type.example.com/custom.Detail", "value": 1}]}"#;
+
+        assert!(
+            ErrorDetails::from_status_json(json).is_err(),
+            "unknown detail without base64 value was accepted"
+        );
+    }
+
+    #[test]
+    fn error_detail_from_json_any() {
+        let detail = ErrorDetail::from_json_any(serde_json::json!({
+            "@type": "type.internal.example.com/google.rpc.BadRequest",
+            "fieldViolations": [{"field": "email", "description": "must contain an @"}]
+        }));
+
+        let formatted = format!("{:?}", detail);
+
+        let expected = "Ok(BadRequest(BadRequest { field_violations: [FieldViolation { field: \"email\", description: \"must contain an @\", reason: \"\", localized_message: None }] }))";
+
+        assert!(
+            formatted.eq(expected),
+            "detail from JSON Any differs from expected result"
+        );
+
+        let unknown = ErrorDetail::from_json_any(serde_json::json!({
+            "@type": "type.example.com/custom.Detail",
+            "value": 1
+        }));
+
+        assert!(unknown.is_err(), "unknown JSON Any decoded as a detail");
+
+        let untyped = ErrorDetail::from_json_any(serde_json::json!({"reason": "REASON"}));
+
+        assert!(
+            untyped.is_err(),
+            "JSON Any without @type decoded as a detail"
+        );
+
+        let malformed = ErrorDetail::from_json_any(serde_json::json!({
+            "@type": "type.googleapis.com/google.rpc.RetryInfo",
+            "retryDelay": "soon"
+        }));
+
+        assert!(malformed.is_err(), "malformed JSON Any decoded as a detail");
+    }
+}
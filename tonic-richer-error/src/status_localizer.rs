@@ -0,0 +1,196 @@
+use std::fmt;
+use std::sync::Arc;
+
+use tonic::Status;
+
+use super::localized_messages::{parse_accept_language, ACCEPT_LANGUAGE};
+use super::{RequestContext, StatusEnricher, StatusEnricherLayer, WithErrorDetails};
+
+type Translate = Arc<dyn Fn(&str, &str) -> Option<String> + Send + Sync>;
+
+/// [`StatusEnricher`] that sets [`crate::LocalizedMessage`] details on error
+/// statuses, translated to the locale requested by the client. The message
+/// is obtained from a translation callback, called with the
+/// [`crate::ErrorInfo`] reason of the status and a candidate locale. The
+/// candidates are the language ranges of the `accept-language` request
+/// metadata, in decreasing order of their `q` values, followed by the
+/// default locale, if any. The first translation found is used. Statuses
+/// without [`crate::ErrorInfo`] details are left unchanged.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::{StatusEnricherService, StatusLocalizer};
+/// use tower::ServiceBuilder;
+///
+/// fn translate(reason: &str, locale: &str) -> Option<String> {
+///     match (reason, locale) {
+///         ("QUOTA_EXCEEDED", "pt-BR") => Some("Cota excedida".into()),
+///         ("QUOTA_EXCEEDED", "en") => Some("Quota exceeded".into()),
+///         _ => None,
+///     }
+/// }
+///
+/// // Where `service` is a tonic generated server, like `GreeterServer`
+/// fn with_localization<S>(service: S) -> StatusEnricherService<S> {
+///     let layer = StatusLocalizer::new(translate).default_locale("en").layer();
+///
+///     ServiceBuilder::new().layer(layer).service(service)
+/// }
+/// ```
+#[derive(Clone)]
+pub struct StatusLocalizer {
+    translate: Translate,
+    default_locale: Option<String>,
+    keep_existing: bool,
+}
+
+impl StatusLocalizer {
+    /// Creates a new [`StatusLocalizer`], with the given translation
+    /// callback, called with an [`crate::ErrorInfo`] reason and a locale.
+    pub fn new(translate: impl Fn(&str, &str) -> Option<String> + Send + Sync + 'static) -> Self {
+        StatusLocalizer {
+            translate: Arc::new(translate),
+            default_locale: None,
+            keep_existing: false,
+        }
+    }
+
+    /// Sets the locale tried after the ones requested by the client.
+    pub fn default_locale(mut self, locale: impl Into<String>) -> Self {
+        self.default_locale = Some(locale.into());
+        self
+    }
+
+    /// Keeps the [`crate::LocalizedMessage`] details already set by the
+    /// handler, instead of replacing them.
+    pub fn keep_existing(mut self) -> Self {
+        self.keep_existing = true;
+        self
+    }
+
+    /// Creates a [`StatusEnricherLayer`] with this localizer as its only
+    /// enricher.
+    pub fn layer(self) -> StatusEnricherLayer {
+        StatusEnricherLayer::new().enricher(self)
+    }
+}
+
+impl fmt::Debug for StatusLocalizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StatusLocalizer")
+            .field("default_locale", &self.default_locale)
+            .field("keep_existing", &self.keep_existing)
+            .finish()
+    }
+}
+
+impl StatusEnricher for StatusLocalizer {
+    fn enrich(&self, status: Status, ctx: &RequestContext) -> Status {
+        let reason = match status.get_details_error_info() {
+            Some(error_info) => error_info.reason,
+            None => return status,
+        };
+
+        if self.keep_existing && status.get_details_localized_message().is_some() {
+            return status;
+        }
+
+        let ranges = ctx
+            .headers()
+            .get_all(ACCEPT_LANGUAGE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<&str>>()
+            .join(",");
+
+        let translation = parse_accept_language(&ranges)
+            .into_iter()
+            .filter(|locale| *locale != "*")
+            .chain(self.default_locale.as_deref())
+            .find_map(|locale| {
+                (self.translate)(&reason, locale).map(|message| (locale.to_string(), message))
+            });
+
+        match translation {
+            Some((locale, message)) => status.map_error_details(|details| {
+                details.set_localized_message(locale, message);
+            }),
+            None => status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tonic::codegen::http;
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, RequestContext, StatusEnricher, WithErrorDetails};
+    use super::StatusLocalizer;
+
+    fn translate(reason: &str, locale: &str) -> Option<String> {
+        match (reason, locale) {
+            ("QUOTA_EXCEEDED", "pt-BR") => Some("Cota excedida".into()),
+            ("QUOTA_EXCEEDED", "en") => Some("Quota exceeded".into()),
+            _ => None,
+        }
+    }
+
+    fn context(accept_language: &str) -> RequestContext {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("accept-language", accept_language.parse().unwrap());
+        RequestContext::new("/example.Greeter/SayHello", headers)
+    }
+
+    #[test]
+    fn localize_statuses() {
+        let localizer = StatusLocalizer::new(translate).default_locale("en");
+
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_error_info("QUOTA_EXCEEDED", "example.local", Default::default())
+            .set_localized_message("en", "handler message");
+
+        let status = Status::with_error_details(Code::ResourceExhausted, "quota", err_details);
+
+        let results: Vec<String> = [
+            ("fr, pt-BR;q=0.9", &localizer),
+            ("fr", &localizer),
+            ("pt-BR", &localizer.clone().keep_existing()),
+        ]
+        .iter()
+        .map(|(accept_language, localizer)| {
+            let status = localizer.enrich(status.clone(), &context(accept_language));
+            format!("{:?}", status.get_details_localized_message())
+        })
+        .collect();
+
+        println!("localized statuses -> {:?}", results);
+
+        let expected = [
+            "Some(LocalizedMessage { locale: \"pt-BR\", message: \"Cota excedida\" })",
+            "Some(LocalizedMessage { locale: \"en\", message: \"Quota exceeded\" })",
+            "Some(LocalizedMessage { locale: \"en\", message: \"handler message\" })",
+        ];
+
+        assert!(
+            results.eq(&expected),
+            "localized statuses differ from expected result"
+        );
+
+        let status = Status::with_error_details(
+            Code::InvalidArgument,
+            "bad request",
+            ErrorDetails::with_bad_request_violation("field", "description"),
+        );
+
+        let enriched = localizer.enrich(status, &context("pt-BR"));
+
+        assert!(
+            enriched.get_details_localized_message().is_none(),
+            "status without ErrorInfo was localized"
+        );
+    }
+}
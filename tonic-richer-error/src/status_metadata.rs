@@ -0,0 +1,156 @@
+use prost::DecodeError;
+use tonic::metadata::{MetadataMap, MetadataValue};
+use tonic::{Code, Status};
+
+use super::status_ref::StatusRef;
+use super::{Error, ErrorDetails, WithErrorDetails};
+
+impl ErrorDetails {
+    /// Metadata key used by the gRPC implementations to carry the encoded
+    /// `google.rpc.Status`, usually as a trailer.
+    pub const METADATA_KEY: &'static str = "grpc-status-details-bin";
+
+    /// Encodes a `google.rpc.Status` with the given code, message and error
+    /// details, and inserts it in the metadata at
+    /// [`ErrorDetails::METADATA_KEY`], replacing any previous value. Useful
+    /// to interoperate with servers and proxies that only read the details
+    /// from the trailers.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::metadata::MetadataMap;
+    /// use tonic::Code;
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// let mut metadata = MetadataMap::new();
+    ///
+    /// ErrorDetails::with_bad_request_violation("field", "description").insert_into_metadata(
+    ///     Code::InvalidArgument,
+    ///     "bad request",
+    ///     &mut metadata,
+    /// );
+    ///
+    /// assert!(metadata.get_bin(ErrorDetails::METADATA_KEY).is_some());
+    /// ```
+    pub fn insert_into_metadata(
+        self,
+        code: Code,
+        message: impl Into<String>,
+        metadata: &mut MetadataMap,
+    ) {
+        let status = Status::with_error_details(code, message, self);
+
+        metadata.insert_bin(
+            ErrorDetails::METADATA_KEY,
+            MetadataValue::from_bytes(status.details()),
+        );
+    }
+
+    /// Decodes the `google.rpc.Status` found in the metadata at
+    /// [`ErrorDetails::METADATA_KEY`], returning its code, message and error
+    /// details. Returns `Ok(None)` if the key is absent. If the value is not
+    /// valid base64 or some detail is malformed, an [`Error`] is returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic::metadata::MetadataMap;
+    /// use tonic::Code;
+    /// use tonic_richer_error::ErrorDetails;
+    ///
+    /// # let mut metadata = MetadataMap::new();
+    /// # ErrorDetails::with_bad_request_violation("field", "description")
+    /// #     .insert_into_metadata(Code::InvalidArgument, "bad request", &mut metadata);
+    /// // Where `metadata` holds the trailers received from a server
+    /// if let Ok(Some((code, message, err_details))) = ErrorDetails::from_metadata(&metadata) {
+    ///     assert_eq!(code, Code::InvalidArgument);
+    ///     assert!(err_details.has_bad_request_violations());
+    /// }
+    /// ```
+    pub fn from_metadata(
+        metadata: &MetadataMap,
+    ) -> Result<Option<(Code, String, ErrorDetails)>, Error> {
+        let value = match metadata.get_bin(ErrorDetails::METADATA_KEY) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let bytes = value.to_bytes().map_err(|_| {
+            Error::InvalidStatus(DecodeError::new(
+                "invalid base64 value in grpc-status-details-bin",
+            ))
+        })?;
+
+        let status = StatusRef::decode(&bytes).map_err(Error::InvalidStatus)?;
+
+        let code = Code::from_i32(status.code);
+        let message = status.message.to_string();
+
+        let details = Status::with_details(code, message.clone(), bytes)
+            .try_error_details()?
+            .unwrap_or_default();
+
+        Ok(Some((code, message, details)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+    use tonic::metadata::{MetadataMap, MetadataValue};
+    use tonic::Code;
+
+    use super::super::ErrorDetails;
+
+    #[test]
+    fn status_details_metadata() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .add_bad_request_violation("field", "description");
+
+        let mut metadata = MetadataMap::new();
+
+        err_details
+            .clone()
+            .insert_into_metadata(Code::Unavailable, "unavailable", &mut metadata);
+
+        let decoded = match ErrorDetails::from_metadata(&metadata) {
+            Ok(Some(decoded)) => decoded,
+            other => panic!("Error decoding details from metadata: {:?}", other),
+        };
+
+        let formatted = format!("{:?}", decoded);
+
+        println!("details decoded from metadata -> {formatted}");
+
+        let expected = format!(
+            "({:?}, {:?}, {:?})",
+            Code::Unavailable,
+            "unavailable",
+            err_details
+        );
+
+        assert!(
+            formatted.eq(&expected),
+            "details decoded from metadata differ from original"
+        );
+
+        assert!(
+            matches!(ErrorDetails::from_metadata(&MetadataMap::new()), Ok(None)),
+            "details were decoded from empty metadata"
+        );
+
+        let mut metadata = MetadataMap::new();
+        metadata.insert_bin(
+            ErrorDetails::METADATA_KEY,
+            MetadataValue::from_bytes(&[10, 2]),
+        );
+
+        assert!(
+            ErrorDetails::from_metadata(&metadata).is_err(),
+            "malformed details were decoded from metadata"
+        );
+    }
+}
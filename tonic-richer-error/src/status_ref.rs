@@ -0,0 +1,187 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use prost::encoding::{
+    check_wire_type, decode_key, decode_varint, skip_field, DecodeContext, WireType,
+};
+use prost::DecodeError;
+use prost_types::Any;
+
+use super::type_url_prefix::canonical_type_url;
+
+/// Borrowed view of an encoded `google.rpc.Status`. The message and the
+/// details point into the original buffer, so decoding performs no copies.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct StatusRef<'a> {
+    pub(crate) code: i32,
+    pub(crate) message: &'a str,
+    pub(crate) details: Vec<AnyRef<'a>>,
+}
+
+/// Borrowed view of an encoded `google.protobuf.Any`.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct AnyRef<'a> {
+    pub(crate) type_url: &'a str,
+    pub(crate) value: &'a [u8],
+}
+
+impl<'a> StatusRef<'a> {
+    /// Decodes a `google.rpc.Status` without copying the message or the
+    /// details. Unknown fields are skipped.
+    pub(crate) fn decode(buf: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut status = StatusRef::default();
+
+        status.merge(buf)?;
+
+        Ok(status)
+    }
+
+    /// Decodes a `google.rpc.Status` as far as possible. If some part of the
+    /// status is malformed, the fields and details decoded before it are
+    /// returned, alongside the error.
+    #[cfg(feature = "tonic")]
+    pub(crate) fn decode_partial(buf: &'a [u8]) -> (Self, Option<DecodeError>) {
+        let mut status = StatusRef::default();
+
+        let err = status.merge(buf).err();
+
+        (status, err)
+    }
+
+    fn merge(&mut self, mut buf: &'a [u8]) -> Result<(), DecodeError> {
+        while !buf.is_empty() {
+            let (tag, wire_type) = decode_key(&mut buf)?;
+
+            match tag {
+                1 => {
+                    check_wire_type(WireType::Varint, wire_type)?;
+                    self.code = decode_varint(&mut buf)? as i32;
+                }
+                2 => {
+                    check_wire_type(WireType::LengthDelimited, wire_type)?;
+                    self.message = take_str(&mut buf)?;
+                }
+                3 => {
+                    check_wire_type(WireType::LengthDelimited, wire_type)?;
+                    self.details.push(AnyRef::decode(take(&mut buf)?)?);
+                }
+                _ => skip_field(wire_type, tag, &mut buf, DecodeContext::default())?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> AnyRef<'a> {
+    /// Decodes a `google.protobuf.Any` without copying the type URL or the
+    /// value. Unknown fields are skipped.
+    pub(crate) fn decode(mut buf: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut any = AnyRef::default();
+
+        while !buf.is_empty() {
+            let (tag, wire_type) = decode_key(&mut buf)?;
+
+            match tag {
+                1 => {
+                    check_wire_type(WireType::LengthDelimited, wire_type)?;
+                    any.type_url = canonical_type_url(take_str(&mut buf)?);
+                }
+                2 => {
+                    check_wire_type(WireType::LengthDelimited, wire_type)?;
+                    any.value = take(&mut buf)?;
+                }
+                _ => skip_field(wire_type, tag, &mut buf, DecodeContext::default())?,
+            }
+        }
+
+        Ok(any)
+    }
+
+    /// Copies the view into an owned `prost_types::Any`.
+    pub(crate) fn to_any(self) -> Any {
+        Any {
+            type_url: self.type_url.to_string(),
+            value: self.value.to_vec(),
+        }
+    }
+}
+
+impl<'a> From<&'a Any> for AnyRef<'a> {
+    fn from(any: &'a Any) -> Self {
+        AnyRef {
+            type_url: canonical_type_url(&any.type_url),
+            value: &any.value,
+        }
+    }
+}
+
+/// Splits a length-delimited field from the start of the buffer.
+fn take<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8], DecodeError> {
+    let len = decode_varint(buf)?;
+
+    if len > buf.len() as u64 {
+        return Err(DecodeError::new("buffer underflow"));
+    }
+
+    let (field, rest) = buf.split_at(len as usize);
+    *buf = rest;
+
+    Ok(field)
+}
+
+/// Splits a length-delimited UTF-8 string from the start of the buffer.
+fn take_str<'a>(buf: &mut &'a [u8]) -> Result<&'a str, DecodeError> {
+    core::str::from_utf8(take(buf)?)
+        .map_err(|_| DecodeError::new("invalid string value: data is not UTF-8 encoded"))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use prost::Message;
+
+    use super::super::{pb, BadRequest, IntoAny, RetryInfo};
+    use super::StatusRef;
+
+    #[test]
+    fn decode_status_ref() {
+        let status = pb::Status {
+            code: 3,
+            message: "bad request".into(),
+            details: vec![
+                BadRequest::with_violation("field", "description").into_any(),
+                RetryInfo::new(None).into_any(),
+            ],
+        };
+
+        let encoded = status.encode_to_vec();
+
+        let status_ref = match StatusRef::decode(&encoded) {
+            Ok(status_ref) => status_ref,
+            Err(err) => panic!("Error decoding StatusRef: {:?}", err),
+        };
+
+        let decoded = pb::Status {
+            code: status_ref.code,
+            message: status_ref.message.into(),
+            details: status_ref
+                .details
+                .into_iter()
+                .map(|any| any.to_any())
+                .collect(),
+        };
+
+        println!("decoded status -> {:?}", decoded);
+
+        assert!(
+            decoded.eq(&status),
+            "StatusRef differs from original status"
+        );
+
+        assert!(
+            StatusRef::decode(&encoded[..encoded.len() - 1]).is_err(),
+            "truncated status was decoded"
+        );
+    }
+}
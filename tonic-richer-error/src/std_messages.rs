@@ -12,28 +12,39 @@ pub use quota_failure::{QuotaFailure, QuotaViolation};
 
 mod error_info;
 
-pub use error_info::ErrorInfo;
+pub use error_info::{ErrorInfo, ErrorInfoBuilder, InvalidErrorInfo, InvalidMetadataValue, Reason};
 
 mod prec_failure;
 
-pub use prec_failure::{PreconditionFailure, PreconditionViolation};
+pub use prec_failure::{PreconditionFailure, PreconditionKind, PreconditionViolation};
 
 mod bad_request;
 
 pub use bad_request::{BadRequest, FieldViolation};
 
+mod field_path;
+
+pub use field_path::{FieldPath, InvalidFieldPath};
+
 mod request_info;
 
 pub use request_info::RequestInfo;
 
 mod resource_info;
 
-pub use resource_info::ResourceInfo;
+pub use resource_info::{InvalidResourceName, ResourceInfo};
 
 mod help;
 
 pub use help::{Help, HelpLink};
 
+#[cfg(feature = "url")]
+pub use help::InvalidHelpLink;
+
 mod loc_message;
 
 pub use loc_message::LocalizedMessage;
+
+mod http_context;
+
+pub use http_context::{HttpHeader, HttpRequest, HttpResponse};
@@ -1,12 +1,27 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
 use prost::{DecodeError, Message};
 use prost_types::Any;
 
 use super::super::pb;
-use super::super::{FromAny, IntoAny};
+use super::super::type_url_prefix::packed_type_url;
+use super::super::{AnyRef, FromAny, IntoAny};
+use super::{FieldPath, LocalizedMessage};
 
 /// Used at the `field_violations` field of the [`BadRequest`] struct.
 /// Describes a single bad request field.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FieldViolation {
     /// Path leading to a field in the request body. Value should be a
     /// sequence of dot-separated identifiers that identify a protocol buffer
@@ -15,16 +30,57 @@ pub struct FieldViolation {
 
     /// Description of why the field is bad.
     pub description: String,
+
+    /// Reason of the field-level error. Value should be an UPPER_SNAKE_CASE
+    /// identifier, unique within the `ErrorInfo` domain. Empty if not set.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "String::is_empty"))]
+    pub reason: String,
+
+    /// Localized error message for the field-level error, safe to return to
+    /// the API consumer.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub localized_message: Option<LocalizedMessage>,
 }
 
 impl FieldViolation {
-    /// Creates a new [`FieldViolation`] struct.
+    /// Creates a new [`FieldViolation`] struct, with no reason and no
+    /// localized message.
     pub fn new(field: impl Into<String>, description: impl Into<String>) -> Self {
         FieldViolation {
             field: field.into(),
             description: description.into(),
+            ..Default::default()
         }
     }
+
+    /// Sets the reason of the field-level error.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::FieldViolation;
+    ///
+    /// let mut violation = FieldViolation::new("email", "must contain an @");
+    ///
+    /// violation
+    ///     .set_reason("INVALID_EMAIL")
+    ///     .set_localized_message("pt-BR", "deve conter um @");
+    ///
+    /// assert_eq!(violation.reason, "INVALID_EMAIL");
+    /// ```
+    pub fn set_reason(&mut self, reason: impl Into<String>) -> &mut Self {
+        self.reason = reason.into();
+        self
+    }
+
+    /// Sets the localized error message of the field-level error.
+    pub fn set_localized_message(
+        &mut self,
+        locale: impl Into<String>,
+        message: impl Into<String>,
+    ) -> &mut Self {
+        self.localized_message = Some(LocalizedMessage::new(locale, message));
+        self
+    }
 }
 
 /// Used to encode/decode the `BadRequest` standard error message described in
@@ -32,7 +88,13 @@ impl FieldViolation {
 /// on the syntactic aspects of the request.
 ///
 /// [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BadRequest {
     /// Describes all field violations of the request.
     pub field_violations: Vec<FieldViolation>,
@@ -51,10 +113,7 @@ impl BadRequest {
     /// `field_violations`.
     pub fn with_violation(field: impl Into<String>, description: impl Into<String>) -> Self {
         BadRequest {
-            field_violations: vec![FieldViolation {
-                field: field.into(),
-                description: description.into(),
-            }],
+            field_violations: vec![FieldViolation::new(field, description)],
         }
     }
 }
@@ -66,57 +125,273 @@ impl BadRequest {
         field: impl Into<String>,
         description: impl Into<String>,
     ) -> &mut Self {
-        self.field_violations.append(&mut vec![FieldViolation {
-            field: field.into(),
-            description: description.into(),
-        }]);
+        self.field_violations
+            .push(FieldViolation::new(field, description));
         self
     }
 
+    /// Adds a [`FieldViolation`] to [`BadRequest`]'s `field_violations`, with
+    /// the field set to the given [`FieldPath`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{BadRequest, FieldPath};
+    ///
+    /// let items = FieldPath::new("items");
+    ///
+    /// let mut bad_request = BadRequest::new(Vec::new());
+    ///
+    /// bad_request.add_violation_at(&items.index(2).child("sku"), "unknown sku");
+    ///
+    /// assert_eq!(bad_request.field_violations[0].field, "items[2].sku");
+    /// ```
+    pub fn add_violation_at(
+        &mut self,
+        path: &FieldPath,
+        description: impl Into<String>,
+    ) -> &mut Self {
+        self.add_violation(path.as_str(), description)
+    }
+
     /// Returns `true` if [`BadRequest`]'s `field_violations` vector is empty,
     /// and `false` if it is not.
     pub fn is_empty(&self) -> bool {
         self.field_violations.is_empty()
     }
+
+    /// Removes duplicated violations from [`BadRequest`]'s
+    /// `field_violations`, keeping the first occurrence of each, in their
+    /// original order. Useful when aggregating violations from multiple
+    /// validators.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::BadRequest;
+    ///
+    /// let mut bad_request = BadRequest::with_violation("name", "is required");
+    ///
+    /// bad_request
+    ///     .add_violation("email", "is invalid")
+    ///     .add_violation("name", "is required")
+    ///     .dedup();
+    ///
+    /// assert_eq!(bad_request.field_violations.len(), 2);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn dedup(&mut self) -> &mut Self {
+        let mut seen = HashSet::new();
+
+        self.field_violations
+            .retain(|violation| seen.insert(violation.clone()));
+        self
+    }
+
+    /// Sorts [`BadRequest`]'s `field_violations` by field. The sort is
+    /// stable, so violations of the same field keep their relative order.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::BadRequest;
+    ///
+    /// let mut bad_request = BadRequest::with_violation("name", "is required");
+    ///
+    /// bad_request
+    ///     .add_violation("email", "is invalid")
+    ///     .sort_by_field();
+    ///
+    /// assert_eq!(bad_request.field_violations[0].field, "email");
+    /// ```
+    pub fn sort_by_field(&mut self) -> &mut Self {
+        self.field_violations.sort_by(|a, b| a.field.cmp(&b.field));
+        self
+    }
+
+    /// Returns the descriptions of [`BadRequest`]'s `field_violations`,
+    /// grouped by field, in the order the violations were added. Useful to
+    /// render violations next to the corresponding form fields.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::BadRequest;
+    ///
+    /// let mut bad_request = BadRequest::with_violation("name", "is required");
+    ///
+    /// bad_request
+    ///     .add_violation("email", "is invalid")
+    ///     .add_violation("name", "is too short");
+    ///
+    /// let grouped = bad_request.group_by_field();
+    ///
+    /// assert_eq!(grouped["name"], ["is required", "is too short"]);
+    /// assert_eq!(grouped["email"], ["is invalid"]);
+    /// ```
+    pub fn group_by_field(&self) -> BTreeMap<String, Vec<String>> {
+        let mut grouped: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for violation in self.field_violations.iter() {
+            grouped
+                .entry(violation.field.clone())
+                .or_default()
+                .push(violation.description.clone());
+        }
+
+        grouped
+    }
+
+    /// Returns the descriptions of [`BadRequest`]'s `field_violations`,
+    /// grouped by field, with the field paths normalized to the JSON
+    /// (lowerCamelCase) names used by front-end forms, so `user_name` and
+    /// `userName` are bound to the same input.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::BadRequest;
+    ///
+    /// let mut bad_request = BadRequest::with_violation("user_name", "is required");
+    ///
+    /// bad_request
+    ///     .add_violation("userName", "is too short")
+    ///     .add_violation("items[0].unit_price", "must be positive");
+    ///
+    /// let field_map = bad_request.to_field_map();
+    ///
+    /// assert_eq!(field_map["userName"], ["is required", "is too short"]);
+    /// assert_eq!(field_map["items[0].unitPrice"], ["must be positive"]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_field_map(&self) -> HashMap<String, Vec<String>> {
+        let mut field_map: HashMap<String, Vec<String>> = HashMap::new();
+
+        for violation in self.field_violations.iter() {
+            field_map
+                .entry(json_field_path(&violation.field))
+                .or_default()
+                .push(violation.description.clone());
+        }
+
+        field_map
+    }
+
+    /// Returns the violations of [`BadRequest`]'s `field_violations` whose
+    /// field is `path_prefix`, or is nested under it, such as
+    /// `address.city` and `items[2]` for the `address` and `items` prefixes.
+    /// Field paths are compared by their JSON (lowerCamelCase) names, so
+    /// `shipping_address` also matches `shippingAddress.city`.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::BadRequest;
+    ///
+    /// let mut bad_request = BadRequest::with_violation("shipping_address.city", "is required");
+    ///
+    /// bad_request
+    ///     .add_violation("shippingAddressNote", "is too long")
+    ///     .add_violation("shippingAddress.zip_code", "is invalid");
+    ///
+    /// let matched = bad_request.match_field("shippingAddress");
+    ///
+    /// assert_eq!(matched.len(), 2);
+    /// assert_eq!(matched[1].field, "shippingAddress.zip_code");
+    /// ```
+    pub fn match_field(&self, path_prefix: &str) -> Vec<&FieldViolation> {
+        let prefix = json_field_path(path_prefix);
+
+        self.field_violations
+            .iter()
+            .filter(|violation| {
+                let field = json_field_path(&violation.field);
+
+                match field.strip_prefix(prefix.as_str()) {
+                    Some(rest) => rest.is_empty() || rest.starts_with(['.', '[']),
+                    None => false,
+                }
+            })
+            .collect()
+    }
 }
 
-impl IntoAny for BadRequest {
-    fn into_any(self) -> Any {
-        let detail_data = pb::BadRequest {
-            field_violations: self
+/// Converts the identifiers of a field path from snake_case to the
+/// lowerCamelCase used by the protobuf JSON mapping, such as
+/// `items[2].unit_price` to `items[2].unitPrice`. Map keys and indexes
+/// between brackets are kept unchanged.
+fn json_field_path(path: &str) -> String {
+    let mut json_path = String::with_capacity(path.len());
+    let mut in_brackets = false;
+    let mut upper_next = false;
+
+    for c in path.chars() {
+        match c {
+            '[' => in_brackets = true,
+            ']' => in_brackets = false,
+            '_' if !in_brackets => {
+                upper_next = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        if upper_next {
+            json_path.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            json_path.push(c);
+        }
+    }
+
+    json_path
+}
+
+impl From<BadRequest> for pb::BadRequest {
+    fn from(bad_request: BadRequest) -> Self {
+        pb::BadRequest {
+            field_violations: bad_request
                 .field_violations
                 .into_iter()
                 .map(|v| pb::bad_request::FieldViolation {
                     field: v.field,
                     description: v.description,
+                    reason: v.reason,
+                    localized_message: v.localized_message.map(Into::into),
                 })
                 .collect(),
-        };
+        }
+    }
+}
+
+impl IntoAny for BadRequest {
+    fn into_any(self) -> Any {
+        let detail_data = pb::BadRequest::from(self);
 
         Any {
-            type_url: BadRequest::TYPE_URL.to_string(),
+            type_url: packed_type_url(BadRequest::TYPE_URL),
             value: detail_data.encode_to_vec(),
         }
     }
 }
 
-impl FromAny for BadRequest {
-    fn from_any(any: Any) -> Result<Self, DecodeError> {
-        let buf: &[u8] = &any.value;
-        let bad_req = pb::BadRequest::decode(buf)?;
-
-        let bad_req = BadRequest {
-            field_violations: bad_req
+impl From<pb::BadRequest> for BadRequest {
+    fn from(bad_request: pb::BadRequest) -> Self {
+        BadRequest {
+            field_violations: bad_request
                 .field_violations
                 .into_iter()
                 .map(|v| FieldViolation {
                     field: v.field,
                     description: v.description,
+                    reason: v.reason,
+                    localized_message: v.localized_message.map(Into::into),
                 })
                 .collect(),
-        };
+        }
+    }
+}
+
+impl FromAny for BadRequest {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let bad_request = pb::BadRequest::decode(buf)?;
 
-        Ok(bad_req)
+        Ok(bad_request.into())
     }
 }
 
@@ -124,7 +399,9 @@ impl FromAny for BadRequest {
 mod tests {
 
     use super::super::super::{FromAny, IntoAny};
-    use super::BadRequest;
+    #[cfg(feature = "std")]
+    use super::json_field_path;
+    use super::{BadRequest, FieldViolation};
 
     #[test]
     fn gen_bad_request() {
@@ -153,7 +430,7 @@ mod tests {
 
         println!("filled BadRequest -> {formatted}");
 
-        let expected_filled = "BadRequest { field_violations: [FieldViolation { field: \"field_a\", description: \"description_a\" }, FieldViolation { field: \"field_b\", description: \"description_b\" }] }";
+        let expected_filled = "BadRequest { field_violations: [FieldViolation { field: \"field_a\", description: \"description_a\", reason: \"\", localized_message: None }, FieldViolation { field: \"field_b\", description: \"description_b\", reason: \"\", localized_message: None }] }";
 
         assert!(
             formatted.eq(expected_filled),
@@ -161,7 +438,7 @@ mod tests {
         );
 
         assert!(
-            br_details.is_empty() == false,
+            !br_details.is_empty(),
             "filled BadRequest returns 'true' from .is_empty()"
         );
 
@@ -170,14 +447,12 @@ mod tests {
 
         println!("Any generated from BadRequest -> {formatted}");
 
-        let expected = "Any { type_url: \"type.googleapis.com/google.rpc.BadRequest\", value: [10, 24, 10, 7, 102, 105, 101, 108, 100, 95, 97, 18, 13, 100, 101, 115, 99, 114, 105, 112, 116, 105, 111, 110, 95, 97, 10, 24, 10, 7, 102, 105, 101, 108, 100, 95, 98, 18, 13, 100, 101, 115, 99, 114, 105, 112, 116, 105, 111, 110, 95, 98] }";
-
         assert!(
-            formatted.eq(expected),
-            "Any from filled BadRequest differs from expected result"
+            gen_any.type_url.eq(BadRequest::TYPE_URL),
+            "Any from filled BadRequest has unexpected type URL"
         );
 
-        let br_details = match BadRequest::from_any(gen_any) {
+        let br_details = match BadRequest::from_any((&gen_any).into()) {
             Err(error) => panic!("Error generating BadRequest from Any: {:?}", error),
             Ok(from_any) => from_any,
         };
@@ -190,5 +465,129 @@ mod tests {
             formatted.eq(expected_filled),
             "BadRequest from Any differs from expected result"
         );
+
+        let mut violation = FieldViolation::new("email", "must contain an @");
+
+        violation
+            .set_reason("INVALID_EMAIL")
+            .set_localized_message("pt-BR", "deve conter um @");
+
+        let br_details = match BadRequest::from_any(
+            (&BadRequest::new(vec![violation.clone()]).into_any()).into(),
+        ) {
+            Err(error) => panic!("Error generating BadRequest from Any: {:?}", error),
+            Ok(from_any) => from_any,
+        };
+
+        assert!(
+            br_details.field_violations.eq(&[violation]),
+            "FieldViolation reason and localized message were not preserved"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn dedup_sort_and_group_violations() {
+        let mut br_details = BadRequest::with_violation("name", "is required");
+
+        br_details
+            .add_violation("email", "is invalid")
+            .add_violation("name", "is required")
+            .add_violation("age", "must be positive")
+            .add_violation("name", "is too short")
+            .dedup()
+            .sort_by_field();
+
+        let formatted = format!(
+            "{:?}",
+            br_details
+                .field_violations
+                .iter()
+                .map(|v| format!("{}: {}", v.field, v.description))
+                .collect::<Vec<_>>()
+        );
+
+        println!("deduplicated and sorted violations -> {formatted}");
+
+        let expected = "[\"age: must be positive\", \"email: is invalid\", \"name: is required\", \"name: is too short\"]";
+
+        assert!(
+            formatted.eq(expected),
+            "deduplicated and sorted violations differ from expected result"
+        );
+
+        let formatted = format!("{:?}", br_details.group_by_field());
+
+        println!("violations grouped by field -> {formatted}");
+
+        let expected = "{\"age\": [\"must be positive\"], \"email\": [\"is invalid\"], \"name\": [\"is required\", \"is too short\"]}";
+
+        assert!(
+            formatted.eq(expected),
+            "violations grouped by field differ from expected result"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn bind_violations_to_form_fields() {
+        let paths: Vec<String> = [
+            "user_name",
+            "userName",
+            "items[0].unit_price",
+            "labels[\"some_key\"].value_text",
+        ]
+        .iter()
+        .map(|path| json_field_path(path))
+        .collect();
+
+        println!("JSON field paths -> {:?}", paths);
+
+        let expected = [
+            "userName",
+            "userName",
+            "items[0].unitPrice",
+            "labels[\"some_key\"].valueText",
+        ];
+
+        assert!(
+            paths.eq(&expected),
+            "JSON field paths differ from expected result"
+        );
+
+        let mut br_details = BadRequest::with_violation("user_name", "is required");
+
+        br_details
+            .add_violation("userName", "is too short")
+            .add_violation("items[0].unit_price", "must be positive")
+            .add_violation("items_count", "must match items");
+
+        let mut field_map: Vec<(String, Vec<String>)> =
+            br_details.to_field_map().into_iter().collect();
+        field_map.sort();
+
+        let formatted = format!("{:?}", field_map);
+
+        println!("field map -> {formatted}");
+
+        let expected = "[(\"itemsCount\", [\"must match items\"]), (\"items[0].unitPrice\", [\"must be positive\"]), (\"userName\", [\"is required\", \"is too short\"])]";
+
+        assert!(
+            formatted.eq(expected),
+            "field map differs from expected result"
+        );
+
+        let matched: Vec<&str> = br_details
+            .match_field("items")
+            .iter()
+            .map(|violation| violation.field.as_str())
+            .collect();
+
+        println!("violations matching 'items' -> {:?}", matched);
+
+        assert!(
+            matched.eq(&["items[0].unit_price"]),
+            "violations matching prefix differ from expected result"
+        );
     }
 }
@@ -1,14 +1,29 @@
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::backtrace::Backtrace;
+
 use prost::{DecodeError, Message};
 use prost_types::Any;
 
 use super::super::pb;
-use super::super::{FromAny, IntoAny};
+use super::super::type_url_prefix::packed_type_url;
+use super::super::{AnyRef, FromAny, IntoAny};
 
 /// Used to encode/decode the `DebugInfo` standard error message described in
 /// [error_details.proto]. Describes additional debugging info.
 ///
 /// [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DebugInfo {
     /// Stack trace entries indicating where the error occurred.
     pub stack_entries: Vec<String>,
@@ -24,12 +39,62 @@ impl DebugInfo {
     /// Creates a new [`DebugInfo`] struct.
     pub fn new(stack_entries: Vec<String>, detail: impl Into<String>) -> Self {
         DebugInfo {
-            stack_entries: stack_entries,
+            stack_entries,
             detail: detail.into(),
         }
     }
 }
 
+impl DebugInfo {
+    /// Creates a new [`DebugInfo`] struct, with `stack_entries` obtained from
+    /// a `std::backtrace::Backtrace` of the current thread. Each stack entry
+    /// holds a frame symbol, followed by its source location if available.
+    /// The backtrace is captured even if `RUST_BACKTRACE` is not set.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::DebugInfo;
+    ///
+    /// let debug_info = DebugInfo::from_backtrace("details about the error");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_backtrace(detail: impl Into<String>) -> Self {
+        let backtrace = Backtrace::force_capture().to_string();
+
+        DebugInfo {
+            stack_entries: split_backtrace(&backtrace),
+            detail: detail.into(),
+        }
+    }
+
+    /// Keeps only the first `max_depth` stack entries, replacing the
+    /// remaining ones by a single entry informing how many frames were
+    /// redacted. Can be used to avoid sending deep stack traces to clients.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::DebugInfo;
+    ///
+    /// let mut debug_info = DebugInfo::new(
+    ///     vec!["trace 3".into(), "trace 2".into(), "trace 1".into()],
+    ///     "details about the error",
+    /// );
+    ///
+    /// debug_info.redact_below(1);
+    ///
+    /// assert_eq!(debug_info.stack_entries, vec!["trace 3", "... 2 frames redacted"]);
+    /// ```
+    pub fn redact_below(&mut self, max_depth: usize) -> &mut Self {
+        if self.stack_entries.len() > max_depth {
+            let redacted = self.stack_entries.len() - max_depth;
+            self.stack_entries.truncate(max_depth);
+            self.stack_entries
+                .push(format!("... {redacted} frames redacted"));
+        }
+        self
+    }
+}
+
 impl DebugInfo {
     /// Returns `true` if [`DebugInfo`] fields are empty, and `false` if they
     /// are not.
@@ -38,31 +103,71 @@ impl DebugInfo {
     }
 }
 
+/// Splits the `Display` output of a `std::backtrace::Backtrace` into one
+/// entry per frame, joining each symbol with its source location.
+#[cfg(feature = "std")]
+fn split_backtrace(backtrace: &str) -> Vec<String> {
+    let mut entries: Vec<String> = Vec::new();
+
+    for line in backtrace.lines() {
+        let line = line.trim();
+
+        if let Some(location) = line.strip_prefix("at ") {
+            if let Some(entry) = entries.last_mut() {
+                entry.push_str(" at ");
+                entry.push_str(location);
+            }
+            continue;
+        }
+
+        let symbol = match line.split_once(": ") {
+            Some((index, symbol)) if index.chars().all(|c| c.is_ascii_digit()) => symbol,
+            _ => line,
+        };
+
+        if !symbol.is_empty() {
+            entries.push(symbol.to_string());
+        }
+    }
+
+    entries
+}
+
+impl From<DebugInfo> for pb::DebugInfo {
+    fn from(debug_info: DebugInfo) -> Self {
+        pb::DebugInfo {
+            stack_entries: debug_info.stack_entries,
+            detail: debug_info.detail,
+        }
+    }
+}
+
 impl IntoAny for DebugInfo {
     fn into_any(self) -> Any {
-        let detail_data = pb::DebugInfo {
-            stack_entries: self.stack_entries,
-            detail: self.detail,
-        };
+        let detail_data = pb::DebugInfo::from(self);
 
         Any {
-            type_url: DebugInfo::TYPE_URL.to_string(),
+            type_url: packed_type_url(DebugInfo::TYPE_URL),
             value: detail_data.encode_to_vec(),
         }
     }
 }
 
-impl FromAny for DebugInfo {
-    fn from_any(any: Any) -> Result<Self, DecodeError> {
-        let buf: &[u8] = &any.value;
-        let debug_info = pb::DebugInfo::decode(buf)?;
-
-        let debug_info = DebugInfo {
+impl From<pb::DebugInfo> for DebugInfo {
+    fn from(debug_info: pb::DebugInfo) -> Self {
+        DebugInfo {
             stack_entries: debug_info.stack_entries,
             detail: debug_info.detail,
-        };
+        }
+    }
+}
 
-        Ok(debug_info)
+impl FromAny for DebugInfo {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let debug_info = pb::DebugInfo::decode(buf)?;
+
+        Ok(debug_info.into())
     }
 }
 
@@ -70,6 +175,8 @@ impl FromAny for DebugInfo {
 mod tests {
 
     use super::super::super::{FromAny, IntoAny};
+    #[cfg(feature = "std")]
+    use super::split_backtrace;
     use super::DebugInfo;
 
     #[test]
@@ -99,15 +206,12 @@ mod tests {
 
         println!("Any generated from DebugInfo -> {formatted}");
 
-        let expected =
-            "Any { type_url: \"type.googleapis.com/google.rpc.DebugInfo\", value: [10, 7, 116, 114, 97, 99, 101, 32, 51, 10, 7, 116, 114, 97, 99, 101, 32, 50, 10, 7, 116, 114, 97, 99, 101, 32, 49, 18, 23, 100, 101, 116, 97, 105, 108, 115, 32, 97, 98, 111, 117, 116, 32, 116, 104, 101, 32, 101, 114, 114, 111, 114] }";
-
         assert!(
-            formatted.eq(expected),
-            "Any from filled DebugInfo differs from expected result"
+            gen_any.type_url.eq(DebugInfo::TYPE_URL),
+            "Any from filled DebugInfo has unexpected type URL"
         );
 
-        let br_details = match DebugInfo::from_any(gen_any) {
+        let br_details = match DebugInfo::from_any((&gen_any).into()) {
             Err(error) => panic!("Error generating DebugInfo from Any: {:?}", error),
             Ok(from_any) => from_any,
         };
@@ -121,4 +225,32 @@ mod tests {
             "DebugInfo from Any differs from expected result"
         );
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn debug_info_from_backtrace() {
+        let backtrace = "   0: service::handler\n             at ./src/handler.rs:10:5\n   1: service::main\n             at ./src/main.rs:3:1\n   2: std::rt::lang_start\n";
+
+        let mut debug_info = DebugInfo::new(split_backtrace(backtrace), "details");
+
+        debug_info.redact_below(2);
+
+        let formatted = format!("{:?}", debug_info.stack_entries);
+
+        println!("stack entries from backtrace -> {formatted}");
+
+        let expected = "[\"service::handler at ./src/handler.rs:10:5\", \"service::main at ./src/main.rs:3:1\", \"... 1 frames redacted\"]";
+
+        assert!(
+            formatted.eq(expected),
+            "stack entries from backtrace differ from expected result"
+        );
+
+        let debug_info = DebugInfo::from_backtrace("details");
+
+        assert!(
+            !debug_info.stack_entries.is_empty(),
+            "captured backtrace has no stack entries"
+        );
+    }
 }
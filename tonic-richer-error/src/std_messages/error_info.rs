@@ -1,17 +1,29 @@
-use std::collections::HashMap;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
+use core::time::Duration;
 
 use prost::{DecodeError, Message};
 use prost_types::Any;
 
 use super::super::pb;
-use super::super::{FromAny, IntoAny};
+use super::super::type_url_prefix::packed_type_url;
+use super::super::{AnyRef, FromAny, IntoAny};
 
 /// Used to encode/decode the `ErrorInfo` standard error message described in
 /// [error_details.proto]. Describes the cause of the error with structured
 /// details.
 ///
 /// [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ErrorInfo {
     /// Reason of the error. Should be a constant value that identifies the
     /// proximate cause of the error. Error reasons should be unique within a
@@ -25,7 +37,11 @@ pub struct ErrorInfo {
 
     /// Additional structured details about this error. Keys should match
     /// `/[a-zA-Z0-9-_]/` and be limited to 64 characters in length.
-    pub metadata: HashMap<String, String>,
+    ///
+    /// Stored in a `BTreeMap`, with or without the `std` feature. Previous
+    /// releases used a `HashMap`, so code building the metadata must switch
+    /// map types when upgrading.
+    pub metadata: BTreeMap<String, String>,
 }
 
 impl ErrorInfo {
@@ -36,12 +52,12 @@ impl ErrorInfo {
     pub fn new(
         reason: impl Into<String>,
         domain: impl Into<String>,
-        metadata: HashMap<String, String>,
+        metadata: BTreeMap<String, String>,
     ) -> Self {
         ErrorInfo {
             reason: reason.into(),
             domain: domain.into(),
-            metadata: metadata,
+            metadata,
         }
     }
 }
@@ -54,47 +70,449 @@ impl ErrorInfo {
     }
 }
 
+impl ErrorInfo {
+    /// Creates a new [`ErrorInfoBuilder`], which validates the reason, domain
+    /// and metadata keys before building the [`ErrorInfo`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorInfo;
+    ///
+    /// let error_info = ErrorInfo::builder("QUOTA_EXCEEDED", "example.local")
+    ///     .metadata("limit", 100)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(ErrorInfo::builder("quota exceeded", "example.local").build().is_err());
+    /// ```
+    pub fn builder(reason: impl Into<String>, domain: impl Into<String>) -> ErrorInfoBuilder {
+        ErrorInfoBuilder {
+            reason: reason.into(),
+            domain: domain.into(),
+            metadata: BTreeMap::new(),
+        }
+    }
+}
+
+impl ErrorInfo {
+    /// Parses the metadata value at `key` into any type implementing
+    /// `FromStr`, such as integers or booleans. Returns `Ok(None)` if the key
+    /// is not present, and an [`InvalidMetadataValue`] error, describing the
+    /// key, value and cause, if the value cannot be parsed.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorInfo;
+    ///
+    /// let error_info = ErrorInfo::builder("QUOTA_EXCEEDED", "example.local")
+    ///     .metadata("limit", 100)
+    ///     .metadata("burst", "many")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(error_info.metadata_parsed::<u32>("limit"), Ok(Some(100)));
+    /// assert_eq!(error_info.metadata_parsed::<u32>("window"), Ok(None));
+    /// assert!(error_info.metadata_parsed::<u32>("burst").is_err());
+    /// ```
+    pub fn metadata_parsed<T>(&self, key: &str) -> Result<Option<T>, InvalidMetadataValue>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.parse_metadata(key, |value| {
+            value.parse().map_err(|err: T::Err| err.to_string())
+        })
+    }
+
+    /// Parses the metadata value at `key` as a duration. Accepts the
+    /// protobuf JSON form, such as `"1.5s"`, values with `ms`, `m` or `h`
+    /// units, such as `"500ms"`, and plain numbers of seconds. Returns
+    /// `Ok(None)` if the key is not present, and an [`InvalidMetadataValue`]
+    /// error if the value is not a valid, non-negative duration.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tonic_richer_error::ErrorInfo;
+    ///
+    /// let error_info = ErrorInfo::builder("TOKEN_EXPIRED", "example.local")
+    ///     .metadata("grace_period", "1.5s")
+    ///     .metadata("lockout", "15m")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     error_info.metadata_duration("grace_period"),
+    ///     Ok(Some(Duration::from_millis(1500)))
+    /// );
+    /// assert_eq!(
+    ///     error_info.metadata_duration("lockout"),
+    ///     Ok(Some(Duration::from_secs(900)))
+    /// );
+    /// ```
+    pub fn metadata_duration(&self, key: &str) -> Result<Option<Duration>, InvalidMetadataValue> {
+        self.parse_metadata(key, parse_duration)
+    }
+
+    /// Parses the `limit` metadata entry, commonly set on rate limit and
+    /// size errors, as an integer.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorInfo;
+    ///
+    /// let error_info = ErrorInfo::builder("TOO_MANY_ITEMS", "example.local")
+    ///     .metadata("limit", 50)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(error_info.limit(), Ok(Some(50)));
+    /// ```
+    pub fn limit(&self) -> Result<Option<u64>, InvalidMetadataValue> {
+        self.metadata_parsed("limit")
+    }
+
+    /// Parses the `quota_limit_value` metadata entry, set by Google APIs on
+    /// quota errors, as an integer.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ErrorInfo;
+    ///
+    /// let error_info = ErrorInfo::builder("RATE_LIMIT_EXCEEDED", "googleapis.com")
+    ///     .metadata("quota_limit", "ReadRequestsPerMinutePerProject")
+    ///     .metadata("quota_limit_value", 300)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(error_info.quota_limit_value(), Ok(Some(300)));
+    /// ```
+    pub fn quota_limit_value(&self) -> Result<Option<u64>, InvalidMetadataValue> {
+        self.metadata_parsed("quota_limit_value")
+    }
+
+    /// Parses the `ttl` metadata entry as a duration, as described in
+    /// [`ErrorInfo::metadata_duration`].
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tonic_richer_error::ErrorInfo;
+    ///
+    /// let error_info = ErrorInfo::builder("LEASE_HELD", "example.local")
+    ///     .metadata("ttl", "30s")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(error_info.ttl(), Ok(Some(Duration::from_secs(30))));
+    /// ```
+    pub fn ttl(&self) -> Result<Option<Duration>, InvalidMetadataValue> {
+        self.metadata_duration("ttl")
+    }
+
+    fn parse_metadata<T>(
+        &self,
+        key: &str,
+        parse: impl FnOnce(&str) -> Result<T, String>,
+    ) -> Result<Option<T>, InvalidMetadataValue> {
+        let value = match self.metadata.get(key) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        parse(value.trim())
+            .map(Some)
+            .map_err(|reason| InvalidMetadataValue {
+                key: key.to_string(),
+                value: value.clone(),
+                reason,
+            })
+    }
+}
+
+/// Error reason, validated at compile time when used in constants. Must be
+/// UPPER_SNAKE_CASE, start with a letter and be at most 63 characters long,
+/// as described in [AIP-193]. Can be shared across services to keep reasons
+/// consistent.
+///
+/// [AIP-193]: https://google.aip.dev/193
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::{ErrorInfo, Reason};
+///
+/// pub const QUOTA_EXCEEDED: Reason = Reason::new("QUOTA_EXCEEDED");
+///
+/// let error_info = ErrorInfo::builder(QUOTA_EXCEEDED, "example.local")
+///     .build()
+///     .unwrap();
+///
+/// assert!(QUOTA_EXCEEDED == error_info.reason.as_str());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Reason(&'static str);
+
+impl Reason {
+    /// Creates a new [`Reason`]. Panics if the reason is invalid, which
+    /// results in a compile error when used in constants.
+    pub const fn new(reason: &'static str) -> Self {
+        assert!(is_valid_reason(reason), "invalid ErrorInfo reason");
+        Reason(reason)
+    }
+
+    /// Returns the reason as a string slice.
+    pub const fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl From<Reason> for String {
+    fn from(reason: Reason) -> Self {
+        reason.0.to_string()
+    }
+}
+
+impl PartialEq<str> for Reason {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Reason {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Builds an [`ErrorInfo`], validating its fields as described in
+/// [AIP-193]. Created by [`ErrorInfo::builder`].
+///
+/// [AIP-193]: https://google.aip.dev/193
+#[derive(Clone, Debug)]
+pub struct ErrorInfoBuilder {
+    reason: String,
+    domain: String,
+    metadata: BTreeMap<String, String>,
+}
+
+impl ErrorInfoBuilder {
+    /// Adds a metadata entry. The value is converted to a string.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.metadata.insert(key.into(), value.to_string());
+        self
+    }
+
+    /// Validates the fields and builds the [`ErrorInfo`]. Returns an
+    /// [`InvalidErrorInfo`] error describing the first invalid field found.
+    pub fn build(self) -> Result<ErrorInfo, InvalidErrorInfo> {
+        if !is_valid_reason(&self.reason) {
+            return Err(InvalidErrorInfo::Reason(self.reason));
+        }
+
+        if !is_valid_domain(&self.domain) {
+            return Err(InvalidErrorInfo::Domain(self.domain));
+        }
+
+        if let Some(key) = self.metadata.keys().find(|key| !is_valid_metadata_key(key)) {
+            return Err(InvalidErrorInfo::MetadataKey(key.clone()));
+        }
+
+        Ok(ErrorInfo::new(self.reason, self.domain, self.metadata))
+    }
+}
+
+/// Error returned by [`ErrorInfoBuilder::build`] when a field of the
+/// [`ErrorInfo`] is invalid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvalidErrorInfo {
+    /// The reason is not UPPER_SNAKE_CASE, or is longer than 63 characters.
+    Reason(String),
+
+    /// The domain is not a valid lowercase domain name.
+    Domain(String),
+
+    /// A metadata key contains characters other than `[a-zA-Z0-9-_]`, or is
+    /// longer than 64 characters.
+    MetadataKey(String),
+}
+
+impl fmt::Display for InvalidErrorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidErrorInfo::Reason(reason) => write!(
+                f,
+                "invalid ErrorInfo reason `{reason}`, expected UPPER_SNAKE_CASE with at most 63 characters"
+            ),
+            InvalidErrorInfo::Domain(domain) => write!(
+                f,
+                "invalid ErrorInfo domain `{domain}`, expected a lowercase domain name"
+            ),
+            InvalidErrorInfo::MetadataKey(key) => write!(
+                f,
+                "invalid ErrorInfo metadata key `{key}`, expected [a-zA-Z0-9-_] with at most 64 characters"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidErrorInfo {}
+
+/// Error returned by the [`ErrorInfo`] metadata accessors, such as
+/// [`ErrorInfo::metadata_parsed`], when a metadata value cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidMetadataValue {
+    /// Key of the metadata entry.
+    pub key: String,
+
+    /// Value that could not be parsed.
+    pub value: String,
+
+    /// Description of why the value could not be parsed.
+    pub reason: String,
+}
+
+impl fmt::Display for InvalidMetadataValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid ErrorInfo metadata value `{}` at `{}`: {}",
+            self.value, self.key, self.reason
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidMetadataValue {}
+
+/// Parses a duration such as `"1.5s"`, `"500ms"`, `"15m"`, `"1h"` or `"30"`.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let (number, unit_secs) = if let Some(number) = value.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = value.strip_suffix('s') {
+        (number, 1.0)
+    } else if let Some(number) = value.strip_suffix('m') {
+        (number, 60.0)
+    } else if let Some(number) = value.strip_suffix('h') {
+        (number, 3600.0)
+    } else {
+        (value, 1.0)
+    };
+
+    let valid_number = !number.is_empty()
+        && number.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+        && number.bytes().filter(|b| *b == b'.').count() <= 1;
+
+    if !valid_number {
+        return Err("expected a non-negative duration, such as \"1.5s\" or \"500ms\"".into());
+    }
+
+    let number: f64 = number.parse().map_err(|err| format!("{err}"))?;
+
+    Duration::try_from_secs_f64(number * unit_secs).map_err(|err| err.to_string())
+}
+
+const fn is_valid_reason(reason: &str) -> bool {
+    let bytes = reason.as_bytes();
+
+    if bytes.is_empty() || bytes.len() > 63 || !bytes[0].is_ascii_uppercase() {
+        return false;
+    }
+
+    if bytes[bytes.len() - 1] == b'_' {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if !(b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_') {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+fn is_valid_domain(domain: &str) -> bool {
+    !domain.is_empty()
+        && domain.len() <= 253
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        })
+}
+
+fn is_valid_metadata_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 64
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+impl From<ErrorInfo> for pb::ErrorInfo {
+    fn from(error_info: ErrorInfo) -> Self {
+        pb::ErrorInfo {
+            reason: error_info.reason,
+            domain: error_info.domain,
+            metadata: error_info.metadata.into_iter().collect(),
+        }
+    }
+}
+
 impl IntoAny for ErrorInfo {
     fn into_any(self) -> Any {
-        let detail_data = pb::ErrorInfo {
-            reason: self.reason,
-            domain: self.domain,
-            metadata: self.metadata,
-        };
+        let detail_data = pb::ErrorInfo::from(self);
 
         Any {
-            type_url: ErrorInfo::TYPE_URL.to_string(),
+            type_url: packed_type_url(ErrorInfo::TYPE_URL),
             value: detail_data.encode_to_vec(),
         }
     }
 }
 
+impl From<pb::ErrorInfo> for ErrorInfo {
+    fn from(error_info: pb::ErrorInfo) -> Self {
+        ErrorInfo {
+            reason: error_info.reason,
+            domain: error_info.domain,
+            metadata: error_info.metadata.into_iter().collect(),
+        }
+    }
+}
+
 impl FromAny for ErrorInfo {
-    fn from_any(any: Any) -> Result<Self, DecodeError> {
-        let buf: &[u8] = &any.value;
-        let debug_info = pb::ErrorInfo::decode(buf)?;
-
-        let debug_info = ErrorInfo {
-            reason: debug_info.reason,
-            domain: debug_info.domain,
-            metadata: debug_info.metadata,
-        };
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let error_info = pb::ErrorInfo::decode(buf)?;
 
-        Ok(debug_info)
+        Ok(error_info.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use std::collections::HashMap;
+    use std::collections::BTreeMap;
 
     use super::super::super::{FromAny, IntoAny};
-    use super::ErrorInfo;
+    use super::{ErrorInfo, InvalidErrorInfo, InvalidMetadataValue, Reason};
 
     #[test]
     fn gen_error_info() {
-        let mut metadata = HashMap::new();
+        let mut metadata = BTreeMap::new();
         metadata.insert("instanceLimitPerRequest".to_string(), "100".into());
 
         let error_info = ErrorInfo::new("SOME_INFO", "mydomain.com", metadata);
@@ -116,15 +534,12 @@ mod tests {
 
         println!("Any generated from ErrorInfo -> {formatted}");
 
-        let expected =
-            "Any { type_url: \"type.googleapis.com/google.rpc.ErrorInfo\", value: [10, 9, 83, 79, 77, 69, 95, 73, 78, 70, 79, 18, 12, 109, 121, 100, 111, 109, 97, 105, 110, 46, 99, 111, 109, 26, 30, 10, 23, 105, 110, 115, 116, 97, 110, 99, 101, 76, 105, 109, 105, 116, 80, 101, 114, 82, 101, 113, 117, 101, 115, 116, 18, 3, 49, 48, 48] }";
-
         assert!(
-            formatted.eq(expected),
-            "Any from filled ErrorInfo differs from expected result"
+            gen_any.type_url.eq(ErrorInfo::TYPE_URL),
+            "Any from filled ErrorInfo has unexpected type URL"
         );
 
-        let br_details = match ErrorInfo::from_any(gen_any) {
+        let br_details = match ErrorInfo::from_any((&gen_any).into()) {
             Err(error) => panic!("Error generating ErrorInfo from Any: {:?}", error),
             Ok(from_any) => from_any,
         };
@@ -138,4 +553,97 @@ mod tests {
             "ErrorInfo from Any differs from expected result"
         );
     }
+
+    #[test]
+    fn build_error_info() {
+        const QUOTA_EXCEEDED: Reason = Reason::new("QUOTA_EXCEEDED");
+
+        let error_info = ErrorInfo::builder(QUOTA_EXCEEDED, "pubsub.example.local")
+            .metadata("limit", 100)
+            .build();
+
+        let formatted = format!("{:?}", error_info);
+
+        println!("built ErrorInfo -> {formatted}");
+
+        let expected = "Ok(ErrorInfo { reason: \"QUOTA_EXCEEDED\", domain: \"pubsub.example.local\", metadata: {\"limit\": \"100\"} })";
+
+        assert!(
+            formatted.eq(expected),
+            "built ErrorInfo differs from expected result"
+        );
+
+        let results = [
+            ErrorInfo::builder("QuotaExceeded", "example.local").build(),
+            ErrorInfo::builder("QUOTA_", "example.local").build(),
+            ErrorInfo::builder("A".repeat(64), "example.local").build(),
+            ErrorInfo::builder("QUOTA", "Example.local").build(),
+            ErrorInfo::builder("QUOTA", "example..local").build(),
+            ErrorInfo::builder("QUOTA", "example.local")
+                .metadata("a key", "value")
+                .build(),
+        ];
+
+        let errors: Vec<InvalidErrorInfo> = results.into_iter().filter_map(Result::err).collect();
+
+        println!("ErrorInfo builder errors -> {:?}", errors);
+
+        assert!(errors.len() == 6, "invalid ErrorInfo fields were accepted");
+
+        assert!(
+            errors[5].to_string().eq("invalid ErrorInfo metadata key `a key`, expected [a-zA-Z0-9-_] with at most 64 characters"),
+            "ErrorInfo builder error message differs from expected result"
+        );
+    }
+
+    #[test]
+    fn parse_error_info_metadata() {
+        let error_info = ErrorInfo::builder("QUOTA_EXCEEDED", "example.local")
+            .metadata("limit", 100)
+            .metadata("quota_limit_value", "-1")
+            .metadata("ttl", "0.250s")
+            .metadata("window", "1h")
+            .metadata("timeout", "soon")
+            .build()
+            .unwrap();
+
+        let formatted = format!(
+            "{:?} {:?} {:?} {:?} {:?}",
+            error_info.limit(),
+            error_info.ttl(),
+            error_info.metadata_duration("window"),
+            error_info.metadata_parsed::<bool>("missing"),
+            error_info.quota_limit_value(),
+        );
+
+        println!("parsed ErrorInfo metadata -> {formatted}");
+
+        let expected = "Ok(Some(100)) Ok(Some(250ms)) Ok(Some(3600s)) Ok(None) Err(InvalidMetadataValue { key: \"quota_limit_value\", value: \"-1\", reason: \"invalid digit found in string\" })";
+
+        assert!(
+            formatted.eq(expected),
+            "parsed ErrorInfo metadata differs from expected result"
+        );
+
+        let err = match error_info.metadata_duration("timeout") {
+            Err(err) => err,
+            Ok(parsed) => panic!("invalid duration was parsed as {:?}", parsed),
+        };
+
+        let expected = InvalidMetadataValue {
+            key: "timeout".into(),
+            value: "soon".into(),
+            reason: "expected a non-negative duration, such as \"1.5s\" or \"500ms\"".into(),
+        };
+
+        assert!(
+            err.eq(&expected),
+            "invalid duration error differs from expected result"
+        );
+
+        assert!(
+            err.to_string().eq("invalid ErrorInfo metadata value `soon` at `timeout`: expected a non-negative duration, such as \"1.5s\" or \"500ms\""),
+            "invalid duration error message differs from expected result"
+        );
+    }
 }
@@ -0,0 +1,214 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+
+/// Builds the path of a request field, following the AIP-style notation
+/// expected at the `field` field of [`FieldViolation`](super::FieldViolation),
+/// such as `book.author.name` or `items[2].sku`. Used with
+/// [`BadRequest::add_violation_at`](super::BadRequest::add_violation_at), so
+/// large request validators construct consistent nested field references.
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::FieldPath;
+///
+/// let items = FieldPath::new("items");
+///
+/// let sku = items.index(2).child("sku");
+///
+/// assert_eq!(sku.as_str(), "items[2].sku");
+/// assert!(sku.is_valid());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FieldPath {
+    path: String,
+}
+
+impl FieldPath {
+    /// Creates a new [`FieldPath`], pointing to a top-level field of the
+    /// request.
+    pub fn new(field: impl Into<String>) -> Self {
+        FieldPath { path: field.into() }
+    }
+
+    /// Parses a [`FieldPath`] from its string form. If the path is not
+    /// valid, as described in [`FieldPath::is_valid`], an
+    /// [`InvalidFieldPath`] error is returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::FieldPath;
+    ///
+    /// assert!(FieldPath::parse("book.authors[0].name").is_ok());
+    /// assert!(FieldPath::parse("book..name").is_err());
+    /// ```
+    pub fn parse(path: &str) -> Result<Self, InvalidFieldPath> {
+        let path = FieldPath::new(path);
+
+        if path.is_valid() {
+            Ok(path)
+        } else {
+            Err(InvalidFieldPath(path.path))
+        }
+    }
+
+    /// Returns the path of a field nested in the one pointed to by this
+    /// path.
+    pub fn child(&self, field: &str) -> Self {
+        if self.path.is_empty() {
+            return FieldPath::new(field);
+        }
+
+        FieldPath {
+            path: format!("{}.{field}", self.path),
+        }
+    }
+
+    /// Returns the path of an element of the repeated field pointed to by
+    /// this path.
+    pub fn index(&self, index: usize) -> Self {
+        FieldPath {
+            path: format!("{}[{index}]", self.path),
+        }
+    }
+
+    /// Returns the path in its string form.
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns `true` if the path is a non-empty sequence of dot-separated
+    /// identifiers, each optionally followed by `[n]` indices, and `false`
+    /// otherwise. Identifiers must start with an ASCII letter or `_`, and
+    /// contain only ASCII alphanumeric characters or `_`.
+    pub fn is_valid(&self) -> bool {
+        !self.path.is_empty() && self.path.split('.').all(is_valid_segment)
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.path)
+    }
+}
+
+impl From<FieldPath> for String {
+    fn from(path: FieldPath) -> Self {
+        path.path
+    }
+}
+
+/// Error returned by [`FieldPath::parse`], holding the invalid path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidFieldPath(pub String);
+
+impl fmt::Display for InvalidFieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid field path `{}`, expected dot-separated identifiers with optional [n] indices",
+            self.0
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidFieldPath {}
+
+/// Checks a single path segment, such as `items[2]`.
+fn is_valid_segment(segment: &str) -> bool {
+    let (name, mut indices) = match segment.find('[') {
+        Some(start) => segment.split_at(start),
+        None => (segment, ""),
+    };
+
+    let mut chars = name.chars();
+
+    let valid_name = match chars.next() {
+        Some(first) => {
+            (first.is_ascii_alphabetic() || first == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    };
+
+    if !valid_name {
+        return false;
+    }
+
+    while !indices.is_empty() {
+        let index = match indices
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+        {
+            Some((index, rest)) => {
+                indices = rest;
+                index
+            }
+            None => return false,
+        };
+
+        if index.is_empty() || !index.chars().all(|c| c.is_ascii_digit()) {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::BadRequest;
+    use super::FieldPath;
+
+    #[test]
+    fn gen_field_paths() {
+        let book = FieldPath::new("book");
+
+        let mut br_details = BadRequest::new(Vec::new());
+
+        br_details
+            .add_violation_at(&book.child("author").child("name"), "must not be empty")
+            .add_violation_at(&book.child("items").index(2).child("sku"), "unknown sku");
+
+        let formatted = format!("{:?}", br_details);
+
+        println!("BadRequest with field paths -> {formatted}");
+
+        let expected = "BadRequest { field_violations: [FieldViolation { field: \"book.author.name\", description: \"must not be empty\", reason: \"\", localized_message: None }, FieldViolation { field: \"book.items[2].sku\", description: \"unknown sku\", reason: \"\", localized_message: None }] }";
+
+        assert!(
+            formatted.eq(expected),
+            "BadRequest with field paths differs from expected result"
+        );
+
+        let results: Vec<bool> = [
+            "book",
+            "_id",
+            "items[0][1].sku",
+            "",
+            "book.",
+            "1book",
+            "items[]",
+            "items[a]",
+            "items[0",
+            "items[0]x",
+        ]
+        .iter()
+        .map(|path| FieldPath::parse(path).is_ok())
+        .collect();
+
+        println!("field path validation results -> {:?}", results);
+
+        assert!(
+            results.eq(&[true, true, true, false, false, false, false, false, false, false]),
+            "field path validation differs from expected result"
+        );
+
+        assert!(
+            FieldPath::default().child("book").as_str() == "book",
+            "child of empty path differs from expected result"
+        );
+    }
+}
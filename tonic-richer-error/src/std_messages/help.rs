@@ -1,11 +1,26 @@
+use alloc::string::String;
+#[cfg(feature = "url")]
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "url")]
+use core::fmt;
+
 use prost::{DecodeError, Message};
 use prost_types::Any;
 
 use super::super::pb;
-use super::super::{FromAny, IntoAny};
+use super::super::type_url_prefix::packed_type_url;
+use super::super::{AnyRef, FromAny, IntoAny};
 
 /// Used at the `links` field of the [`Help`] struct. Describes a URL link.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct HelpLink {
     /// Description of what the link offers.
     pub description: String,
@@ -22,14 +37,98 @@ impl HelpLink {
             url: url.into(),
         }
     }
+
+    /// Creates a new [`HelpLink`] struct, checking that `url` is a well-formed
+    /// absolute `https` URL. Otherwise, an [`InvalidHelpLink`] error is
+    /// returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::HelpLink;
+    ///
+    /// assert!(HelpLink::parse("docs", "https://docs.example.local/errors").is_ok());
+    /// assert!(HelpLink::parse("docs", "http://docs.example.local/errors").is_err());
+    /// assert!(HelpLink::parse("docs", "docs.example.local/errors").is_err());
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn parse(
+        description: impl Into<String>,
+        url: impl Into<String>,
+    ) -> Result<Self, InvalidHelpLink> {
+        let link = HelpLink::new(description, url);
+
+        link.validate()?;
+
+        Ok(link)
+    }
+
+    /// Checks that the link's `url` is a well-formed absolute `https` URL, as
+    /// described in [`HelpLink::parse`].
+    #[cfg(feature = "url")]
+    pub fn validate(&self) -> Result<(), InvalidHelpLink> {
+        match url::Url::parse(&self.url) {
+            Ok(parsed) if parsed.scheme() == "https" => Ok(()),
+            Ok(_) => Err(InvalidHelpLink::NotHttps {
+                url: self.url.clone(),
+            }),
+            Err(err) => Err(InvalidHelpLink::Malformed {
+                url: self.url.clone(),
+                reason: err.to_string(),
+            }),
+        }
+    }
 }
 
+/// Error returned by [`HelpLink::parse`] and [`HelpLink::validate`].
+#[cfg(feature = "url")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvalidHelpLink {
+    /// The URL could not be parsed.
+    Malformed {
+        /// The rejected URL.
+        url: String,
+
+        /// Description of the parsing error.
+        reason: String,
+    },
+
+    /// The URL is well-formed, but does not use the `https` scheme.
+    NotHttps {
+        /// The rejected URL.
+        url: String,
+    },
+}
+
+#[cfg(feature = "url")]
+impl fmt::Display for InvalidHelpLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidHelpLink::Malformed { url, reason } => {
+                write!(f, "malformed help link URL `{url}`: {reason}")
+            }
+            InvalidHelpLink::NotHttps { url } => {
+                write!(f, "help link URL `{url}` does not use https")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "url")]
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidHelpLink {}
+
 /// Used to encode/decode the `Help` standard error message described in
 /// [error_details.proto]. Provides links to documentation or for performing
 /// an out-of-band action.
 ///
 /// [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Help {
     /// Links pointing to additional information on how to handle the error.
     pub links: Vec<HelpLink>,
@@ -76,10 +175,10 @@ impl Help {
     }
 }
 
-impl IntoAny for Help {
-    fn into_any(self) -> Any {
-        let detail_data = pb::Help {
-            links: self
+impl From<Help> for pb::Help {
+    fn from(help: Help) -> Self {
+        pb::Help {
+            links: help
                 .links
                 .into_iter()
                 .map(|v| pb::help::Link {
@@ -87,21 +186,24 @@ impl IntoAny for Help {
                     url: v.url,
                 })
                 .collect(),
-        };
+        }
+    }
+}
+
+impl IntoAny for Help {
+    fn into_any(self) -> Any {
+        let detail_data = pb::Help::from(self);
 
         Any {
-            type_url: Help::TYPE_URL.to_string(),
+            type_url: packed_type_url(Help::TYPE_URL),
             value: detail_data.encode_to_vec(),
         }
     }
 }
 
-impl FromAny for Help {
-    fn from_any(any: Any) -> Result<Self, DecodeError> {
-        let buf: &[u8] = &any.value;
-        let help = pb::Help::decode(buf)?;
-
-        let quota_failure = Help {
+impl From<pb::Help> for Help {
+    fn from(help: pb::Help) -> Self {
+        Help {
             links: help
                 .links
                 .into_iter()
@@ -110,9 +212,16 @@ impl FromAny for Help {
                     url: v.url,
                 })
                 .collect(),
-        };
+        }
+    }
+}
 
-        Ok(quota_failure)
+impl FromAny for Help {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let help = pb::Help::decode(buf)?;
+
+        Ok(help.into())
     }
 }
 
@@ -156,7 +265,7 @@ mod tests {
         );
 
         assert!(
-            help.is_empty() == false,
+            !help.is_empty(),
             "filled Help returns 'true' from .is_empty()"
         );
 
@@ -166,14 +275,12 @@ mod tests {
 
         println!("Any generated from Help -> {formatted}");
 
-        let expected = "Any { type_url: \"type.googleapis.com/google.rpc.Help\", value: [10, 46, 10, 18, 108, 105, 110, 107, 32, 116, 111, 32, 114, 101, 115, 111, 117, 114, 99, 101, 32, 97, 18, 24, 114, 101, 115, 111, 117, 114, 99, 101, 45, 97, 46, 101, 120, 97, 109, 112, 108, 101, 46, 108, 111, 99, 97, 108, 10, 46, 10, 18, 108, 105, 110, 107, 32, 116, 111, 32, 114, 101, 115, 111, 117, 114, 99, 101, 32, 98, 18, 24, 114, 101, 115, 111, 117, 114, 99, 101, 45, 98, 46, 101, 120, 97, 109, 112, 108, 101, 46, 108, 111, 99, 97, 108] }";
-
         assert!(
-            formatted.eq(expected),
-            "Any from filled Help differs from expected result"
+            gen_any.type_url.eq(Help::TYPE_URL),
+            "Any from filled Help has unexpected type URL"
         );
 
-        let br_details = match Help::from_any(gen_any) {
+        let br_details = match Help::from_any((&gen_any).into()) {
             Err(error) => panic!("Error generating Help from Any: {:?}", error),
             Ok(from_any) => from_any,
         };
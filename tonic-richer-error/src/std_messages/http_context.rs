@@ -0,0 +1,448 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use prost::{DecodeError, Message};
+use prost_types::Any;
+
+use super::super::pb;
+use super::super::type_url_prefix::{canonical_type_url, packed_type_url};
+use super::super::{AnyRef, ErrorDetails, FromAny, IntoAny};
+
+/// Used at the `headers` field of the [`HttpRequest`] and [`HttpResponse`]
+/// structs. Describes a single HTTP header.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HttpHeader {
+    /// The HTTP header key. It is case insensitive.
+    pub key: String,
+
+    /// The HTTP header value.
+    pub value: String,
+}
+
+impl HttpHeader {
+    /// Creates a new [`HttpHeader`] struct.
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        HttpHeader {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Used to encode/decode the `HttpRequest` message described in [http.proto].
+/// Carries the HTTP request received by a proxy or ingress, so it can be
+/// reported alongside the gRPC error. The request body is not carried.
+///
+/// [http.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/http.proto
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::HttpRequest;
+///
+/// let mut http_request = HttpRequest::new("GET", "https://example.local/v1/users/1");
+///
+/// http_request.add_header("x-forwarded-for", "127.0.0.1");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HttpRequest {
+    /// The HTTP request method.
+    pub method: String,
+
+    /// The HTTP request URI.
+    pub uri: String,
+
+    /// Subset of the HTTP request headers. Multiple headers with the same key
+    /// may be present.
+    pub headers: Vec<HttpHeader>,
+}
+
+impl HttpRequest {
+    /// Type URL of the `HttpRequest` message type.
+    pub const TYPE_URL: &'static str = "type.googleapis.com/google.rpc.HttpRequest";
+
+    /// Creates a new [`HttpRequest`] struct, with no headers.
+    pub fn new(method: impl Into<String>, uri: impl Into<String>) -> Self {
+        HttpRequest {
+            method: method.into(),
+            uri: uri.into(),
+            headers: Vec::new(),
+        }
+    }
+}
+
+impl HttpRequest {
+    /// Adds an [`HttpHeader`] to [`HttpRequest`]'s `headers`.
+    pub fn add_header(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.headers.push(HttpHeader::new(key, value));
+        self
+    }
+
+    /// Returns `true` if [`HttpRequest`] fields are empty, and `false` if
+    /// they are not.
+    pub fn is_empty(&self) -> bool {
+        self.method.is_empty() && self.uri.is_empty() && self.headers.is_empty()
+    }
+}
+
+impl From<HttpRequest> for pb::HttpRequest {
+    fn from(http_request: HttpRequest) -> Self {
+        pb::HttpRequest {
+            method: http_request.method,
+            uri: http_request.uri,
+            headers: http_request
+                .headers
+                .into_iter()
+                .map(pb::HttpHeader::from)
+                .collect(),
+            body: Vec::new(),
+        }
+    }
+}
+
+impl IntoAny for HttpRequest {
+    fn into_any(self) -> Any {
+        let detail_data = pb::HttpRequest::from(self);
+
+        Any {
+            type_url: packed_type_url(HttpRequest::TYPE_URL),
+            value: detail_data.encode_to_vec(),
+        }
+    }
+}
+
+impl From<pb::HttpRequest> for HttpRequest {
+    fn from(http_request: pb::HttpRequest) -> Self {
+        HttpRequest {
+            method: http_request.method,
+            uri: http_request.uri,
+            headers: http_request
+                .headers
+                .into_iter()
+                .map(HttpHeader::from)
+                .collect(),
+        }
+    }
+}
+
+impl FromAny for HttpRequest {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let http_request = pb::HttpRequest::decode(buf)?;
+
+        Ok(http_request.into())
+    }
+}
+
+/// Used to encode/decode the `HttpResponse` message described in
+/// [http.proto]. Carries the HTTP response produced by a proxy or ingress, so
+/// it can be reported alongside the gRPC error. The response body is not
+/// carried.
+///
+/// [http.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/http.proto
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::HttpResponse;
+///
+/// let mut http_response = HttpResponse::new(503, "Service Unavailable");
+///
+/// http_response.add_header("retry-after", "120");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HttpResponse {
+    /// The HTTP status code, such as `200` or `404`.
+    pub status: i32,
+
+    /// The HTTP reason phrase, such as `"OK"` or `"Not Found"`.
+    pub reason: String,
+
+    /// Subset of the HTTP response headers. Multiple headers with the same
+    /// key may be present.
+    pub headers: Vec<HttpHeader>,
+}
+
+impl HttpResponse {
+    /// Type URL of the `HttpResponse` message type.
+    pub const TYPE_URL: &'static str = "type.googleapis.com/google.rpc.HttpResponse";
+
+    /// Creates a new [`HttpResponse`] struct, with no headers.
+    pub fn new(status: i32, reason: impl Into<String>) -> Self {
+        HttpResponse {
+            status,
+            reason: reason.into(),
+            headers: Vec::new(),
+        }
+    }
+}
+
+impl HttpResponse {
+    /// Adds an [`HttpHeader`] to [`HttpResponse`]'s `headers`.
+    pub fn add_header(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.headers.push(HttpHeader::new(key, value));
+        self
+    }
+
+    /// Returns `true` if [`HttpResponse`] fields are empty, and `false` if
+    /// they are not.
+    pub fn is_empty(&self) -> bool {
+        self.status == 0 && self.reason.is_empty() && self.headers.is_empty()
+    }
+}
+
+impl From<HttpResponse> for pb::HttpResponse {
+    fn from(http_response: HttpResponse) -> Self {
+        pb::HttpResponse {
+            status: http_response.status,
+            reason: http_response.reason,
+            headers: http_response
+                .headers
+                .into_iter()
+                .map(pb::HttpHeader::from)
+                .collect(),
+            body: Vec::new(),
+        }
+    }
+}
+
+impl IntoAny for HttpResponse {
+    fn into_any(self) -> Any {
+        let detail_data = pb::HttpResponse::from(self);
+
+        Any {
+            type_url: packed_type_url(HttpResponse::TYPE_URL),
+            value: detail_data.encode_to_vec(),
+        }
+    }
+}
+
+impl From<pb::HttpResponse> for HttpResponse {
+    fn from(http_response: pb::HttpResponse) -> Self {
+        HttpResponse {
+            status: http_response.status,
+            reason: http_response.reason,
+            headers: http_response
+                .headers
+                .into_iter()
+                .map(HttpHeader::from)
+                .collect(),
+        }
+    }
+}
+
+impl FromAny for HttpResponse {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let http_response = pb::HttpResponse::decode(buf)?;
+
+        Ok(http_response.into())
+    }
+}
+
+impl From<HttpHeader> for pb::HttpHeader {
+    fn from(header: HttpHeader) -> Self {
+        pb::HttpHeader {
+            key: header.key,
+            value: header.value,
+        }
+    }
+}
+
+impl From<pb::HttpHeader> for HttpHeader {
+    fn from(header: pb::HttpHeader) -> Self {
+        HttpHeader {
+            key: header.key,
+            value: header.value,
+        }
+    }
+}
+
+/// [`HttpRequest`] and [`HttpResponse`] details are kept at the
+/// `unknown_details` field instead of dedicated fields. They are not part of
+/// the standard error message set the other fields mirror, and since every
+/// [`ErrorDetails`] field is public, adding fields would break code that
+/// builds the struct with a literal.
+impl ErrorDetails {
+    /// Set [`HttpRequest`] details, replacing any previous ones. Since it is
+    /// not a standard error message, it is stored at the `unknown_details`
+    /// field. Can be chained with other `.set_` and `.add_` [`ErrorDetails`]
+    /// methods.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetails, HttpRequest};
+    ///
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details.set_http_request(HttpRequest::new("POST", "/v1/orders"));
+    ///
+    /// assert_eq!(err_details.get_http_request().unwrap().method, "POST");
+    /// ```
+    pub fn set_http_request(&mut self, http_request: HttpRequest) -> &mut Self {
+        self.unknown_details
+            .retain(|any| canonical_type_url(&any.type_url) != HttpRequest::TYPE_URL);
+        self.unknown_details.push(http_request.into_any());
+        self
+    }
+
+    /// Set [`HttpResponse`] details, replacing any previous ones. Since it is
+    /// not a standard error message, it is stored at the `unknown_details`
+    /// field. Can be chained with other `.set_` and `.add_` [`ErrorDetails`]
+    /// methods.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{ErrorDetails, HttpResponse};
+    ///
+    /// let mut err_details = ErrorDetails::new();
+    ///
+    /// err_details.set_http_response(HttpResponse::new(502, "Bad Gateway"));
+    ///
+    /// assert_eq!(err_details.get_http_response().unwrap().status, 502);
+    /// ```
+    pub fn set_http_response(&mut self, http_response: HttpResponse) -> &mut Self {
+        self.unknown_details
+            .retain(|any| canonical_type_url(&any.type_url) != HttpResponse::TYPE_URL);
+        self.unknown_details.push(http_response.into_any());
+        self
+    }
+
+    /// Returns the first [`HttpRequest`] details found at the
+    /// `unknown_details` field, if any. If some `prost::DecodeError` occurs,
+    /// returns `None`.
+    pub fn get_http_request(&self) -> Option<HttpRequest> {
+        self.unknown_details
+            .iter()
+            .filter(|any| canonical_type_url(&any.type_url) == HttpRequest::TYPE_URL)
+            .find_map(|any| HttpRequest::from_any(any.into()).ok())
+    }
+
+    /// Returns the first [`HttpResponse`] details found at the
+    /// `unknown_details` field, if any. If some `prost::DecodeError` occurs,
+    /// returns `None`.
+    pub fn get_http_response(&self) -> Option<HttpResponse> {
+        self.unknown_details
+            .iter()
+            .filter(|any| canonical_type_url(&any.type_url) == HttpResponse::TYPE_URL)
+            .find_map(|any| HttpResponse::from_any(any.into()).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    #[cfg(feature = "tonic")]
+    use tonic::{Code, Status};
+
+    #[cfg(feature = "tonic")]
+    use super::super::super::{ErrorDetail, ErrorDetails, WithErrorDetails};
+    use super::super::super::{FromAny, IntoAny};
+    use super::{HttpRequest, HttpResponse};
+
+    #[test]
+    fn gen_http_context() {
+        let mut http_request = HttpRequest::new("GET", "/v1/users");
+        http_request.add_header("host", "example.local");
+
+        let mut http_response = HttpResponse::new(404, "Not Found");
+        http_response.add_header("content-type", "text/plain");
+
+        let formatted = format!("{:?} {:?}", http_request, http_response);
+
+        println!("filled HTTP context -> {formatted}");
+
+        let expected_filled = "HttpRequest { method: \"GET\", uri: \"/v1/users\", headers: [HttpHeader { key: \"host\", value: \"example.local\" }] } HttpResponse { status: 404, reason: \"Not Found\", headers: [HttpHeader { key: \"content-type\", value: \"text/plain\" }] }";
+
+        assert!(
+            formatted.eq(expected_filled),
+            "filled HTTP context differs from expected result"
+        );
+
+        let req_any = http_request.into_any();
+
+        let formatted = format!("{:?}", req_any);
+
+        println!("Any generated from HttpRequest -> {formatted}");
+
+        assert!(
+            req_any.type_url.eq(HttpRequest::TYPE_URL),
+            "Any from filled HttpRequest has unexpected type URL"
+        );
+
+        let res_any = http_response.clone().into_any();
+
+        let decoded = match (
+            HttpRequest::from_any((&req_any).into()),
+            HttpResponse::from_any((&res_any).into()),
+        ) {
+            (Ok(http_request), Ok(http_response)) => (http_request, http_response),
+            (req, res) => panic!("Error generating HTTP context from Any: {req:?} {res:?}"),
+        };
+
+        let formatted = format!("{:?} {:?}", decoded.0, decoded.1);
+
+        println!("HTTP context generated from Any -> {formatted}");
+
+        assert!(
+            formatted.eq(expected_filled),
+            "HTTP context from Any differs from expected result"
+        );
+    }
+
+    #[cfg(feature = "tonic")]
+    #[test]
+    fn http_context_in_status() {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_http_request(HttpRequest::new("GET", "/v1/users"))
+            .set_http_request(HttpRequest::new("DELETE", "/v1/users/1"))
+            .set_http_response(HttpResponse::new(403, "Forbidden"));
+
+        let status = Status::with_error_details(Code::PermissionDenied, "forbidden", err_details);
+
+        let ext_details = status.error_details();
+
+        let formatted = format!(
+            "{:?} {:?}",
+            ext_details.get_http_request(),
+            ext_details.get_http_response()
+        );
+
+        println!("HTTP context extracted from status -> {formatted}");
+
+        let expected = "Some(HttpRequest { method: \"DELETE\", uri: \"/v1/users/1\", headers: [] }) Some(HttpResponse { status: 403, reason: \"Forbidden\", headers: [] })";
+
+        assert!(
+            formatted.eq(expected),
+            "HTTP context extracted from status differs from expected result"
+        );
+
+        let details_vec = status.get_error_details_vec();
+
+        assert!(
+            matches!(
+                details_vec.as_slice(),
+                [ErrorDetail::HttpRequest(_), ErrorDetail::HttpResponse(_)]
+            ),
+            "HTTP context was not extracted as ErrorDetail variants"
+        );
+    }
+}
@@ -1,15 +1,24 @@
+use alloc::string::String;
+
 use prost::{DecodeError, Message};
 use prost_types::Any;
 
 use super::super::pb;
-use super::super::{FromAny, IntoAny};
+use super::super::type_url_prefix::packed_type_url;
+use super::super::{AnyRef, FromAny, IntoAny};
 
 /// Used to encode/decode the `LocalizedMessage` standard error message
 /// described in [error_details.proto]. Provides a localized error message
 /// that is safe to return to the user.
 ///
 /// [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LocalizedMessage {
     /// Locale used, following the specification defined in [BCP 47]. For
     /// example: "en-US", "fr-CH" or "es-MX".
@@ -42,31 +51,41 @@ impl LocalizedMessage {
     }
 }
 
+impl From<LocalizedMessage> for pb::LocalizedMessage {
+    fn from(loc_message: LocalizedMessage) -> Self {
+        pb::LocalizedMessage {
+            locale: loc_message.locale,
+            message: loc_message.message,
+        }
+    }
+}
+
 impl IntoAny for LocalizedMessage {
     fn into_any(self) -> Any {
-        let detail_data = pb::LocalizedMessage {
-            locale: self.locale,
-            message: self.message,
-        };
+        let detail_data = pb::LocalizedMessage::from(self);
 
         Any {
-            type_url: LocalizedMessage::TYPE_URL.to_string(),
+            type_url: packed_type_url(LocalizedMessage::TYPE_URL),
             value: detail_data.encode_to_vec(),
         }
     }
 }
 
-impl FromAny for LocalizedMessage {
-    fn from_any(any: Any) -> Result<Self, DecodeError> {
-        let buf: &[u8] = &any.value;
-        let req_info = pb::LocalizedMessage::decode(buf)?;
+impl From<pb::LocalizedMessage> for LocalizedMessage {
+    fn from(loc_message: pb::LocalizedMessage) -> Self {
+        LocalizedMessage {
+            locale: loc_message.locale,
+            message: loc_message.message,
+        }
+    }
+}
 
-        let debug_info = LocalizedMessage {
-            locale: req_info.locale,
-            message: req_info.message,
-        };
+impl FromAny for LocalizedMessage {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let loc_message = pb::LocalizedMessage::decode(buf)?;
 
-        Ok(debug_info)
+        Ok(loc_message.into())
     }
 }
 
@@ -98,15 +117,12 @@ mod tests {
 
         println!("Any generated from LocalizedMessage -> {formatted}");
 
-        let expected =
-            "Any { type_url: \"type.googleapis.com/google.rpc.LocalizedMessage\", value: [10, 5, 101, 110, 45, 85, 83, 18, 20, 109, 101, 115, 115, 97, 103, 101, 32, 102, 111, 114, 32, 116, 104, 101, 32, 117, 115, 101, 114] }";
-
         assert!(
-            formatted.eq(expected),
-            "Any from filled LocalizedMessage differs from expected result"
+            gen_any.type_url.eq(LocalizedMessage::TYPE_URL),
+            "Any from filled LocalizedMessage has unexpected type URL"
         );
 
-        let br_details = match LocalizedMessage::from_any(gen_any) {
+        let br_details = match LocalizedMessage::from_any((&gen_any).into()) {
             Err(error) => panic!("Error generating LocalizedMessage from Any: {:?}", error),
             Ok(from_any) => from_any,
         };
@@ -1,12 +1,24 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
 use prost::{DecodeError, Message};
 use prost_types::Any;
 
 use super::super::pb;
-use super::super::{FromAny, IntoAny};
+use super::super::type_url_prefix::packed_type_url;
+use super::super::{AnyRef, FromAny, IntoAny};
 
 /// Used at the `violations` field of the [`PreconditionFailure`] struct.
 /// Describes a single precondition failure.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PreconditionViolation {
     /// Type of the PreconditionFailure. At [error_details.proto], the usage
     /// of a service-specific enum type is recommended. For example, "TOS" for
@@ -35,6 +47,103 @@ impl PreconditionViolation {
             description: description.into(),
         }
     }
+
+    /// Returns the [`PreconditionKind`] matching the violation's `type`.
+    pub fn kind(&self) -> PreconditionKind {
+        PreconditionKind::from_type(&self.r#type)
+    }
+}
+
+/// Typed values for the `type` field of [`PreconditionViolation`], so the
+/// same precondition is always reported with the same UPPER_SNAKE_CASE
+/// string. Service-specific types can be used through
+/// [`PreconditionKind::Custom`].
+/// # Examples
+///
+/// ```
+/// use tonic_richer_error::PreconditionKind;
+///
+/// assert_eq!(PreconditionKind::Tos.to_string(), "TOS");
+/// assert_eq!(PreconditionKind::custom("billingAccount").to_string(), "BILLING_ACCOUNT");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PreconditionKind {
+    /// A "Terms of Service" violation, sent as `TOS`.
+    Tos,
+
+    /// The resource is not in the state required by the request, sent as
+    /// `STATE`.
+    State,
+
+    /// A quota verification that must be completed before the request,
+    /// sent as `QUOTA_VERIFICATION`.
+    QuotaVerification,
+
+    /// A service-specific type, normalized to UPPER_SNAKE_CASE when sent.
+    Custom(String),
+}
+
+impl PreconditionKind {
+    /// Creates a [`PreconditionKind::Custom`] kind.
+    pub fn custom(kind: impl Into<String>) -> Self {
+        PreconditionKind::Custom(kind.into())
+    }
+
+    /// Returns the kind matching a `type` string. Unknown types are kept at
+    /// [`PreconditionKind::Custom`].
+    pub fn from_type(r#type: &str) -> Self {
+        match r#type {
+            "TOS" => PreconditionKind::Tos,
+            "STATE" => PreconditionKind::State,
+            "QUOTA_VERIFICATION" => PreconditionKind::QuotaVerification,
+            other => PreconditionKind::Custom(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for PreconditionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreconditionKind::Tos => f.write_str("TOS"),
+            PreconditionKind::State => f.write_str("STATE"),
+            PreconditionKind::QuotaVerification => f.write_str("QUOTA_VERIFICATION"),
+            PreconditionKind::Custom(kind) => f.write_str(&to_upper_snake(kind)),
+        }
+    }
+}
+
+impl From<PreconditionKind> for String {
+    fn from(kind: PreconditionKind) -> Self {
+        kind.to_string()
+    }
+}
+
+/// Converts a string such as `billingAccount` or `billing-account` into
+/// `BILLING_ACCOUNT`.
+fn to_upper_snake(value: &str) -> String {
+    let mut converted = String::with_capacity(value.len());
+    let mut prev_lower = false;
+
+    for c in value.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && prev_lower {
+                converted.push('_');
+            }
+            converted.push(c.to_ascii_uppercase());
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !converted.is_empty() && !converted.ends_with('_') {
+                converted.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+
+    while converted.ends_with('_') {
+        converted.pop();
+    }
+
+    converted
 }
 
 /// Used to encode/decode the `PreconditionFailure` standard error message
@@ -42,7 +151,13 @@ impl PreconditionViolation {
 /// failed.
 ///
 /// [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PreconditionFailure {
     /// Describes all precondition violations of the request.
     pub violations: Vec<PreconditionViolation>,
@@ -54,9 +169,7 @@ impl PreconditionFailure {
 
     /// Creates a new [`PreconditionFailure`] struct.
     pub fn new(violations: Vec<PreconditionViolation>) -> Self {
-        PreconditionFailure {
-            violations: violations,
-        }
+        PreconditionFailure { violations }
     }
 
     /// Creates a new [`PreconditionFailure`] struct with a single
@@ -93,6 +206,32 @@ impl PreconditionFailure {
         self
     }
 
+    /// Adds a [`PreconditionViolation`] to [`PreconditionFailure`]'s
+    /// `violations` vector, with its `type` set from a [`PreconditionKind`].
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::{PreconditionFailure, PreconditionKind};
+    ///
+    /// let mut prec_failure = PreconditionFailure::new(Vec::new());
+    ///
+    /// prec_failure.add_typed_violation(
+    ///     PreconditionKind::Tos,
+    ///     "example.local",
+    ///     "terms of service not accepted",
+    /// );
+    ///
+    /// assert_eq!(prec_failure.violations[0].r#type, "TOS");
+    /// ```
+    pub fn add_typed_violation(
+        &mut self,
+        kind: PreconditionKind,
+        subject: impl Into<String>,
+        description: impl Into<String>,
+    ) -> &mut Self {
+        self.add_violation(kind, subject, description)
+    }
+
     /// Returns `true` if [`PreconditionFailure`]'s `violations` vector is
     /// empty, and `false` if it is not.
     pub fn is_empty(&self) -> bool {
@@ -100,10 +239,10 @@ impl PreconditionFailure {
     }
 }
 
-impl IntoAny for PreconditionFailure {
-    fn into_any(self) -> Any {
-        let detail_data = pb::PreconditionFailure {
-            violations: self
+impl From<PreconditionFailure> for pb::PreconditionFailure {
+    fn from(prec_failure: PreconditionFailure) -> Self {
+        pb::PreconditionFailure {
+            violations: prec_failure
                 .violations
                 .into_iter()
                 .map(|v| pb::precondition_failure::Violation {
@@ -112,22 +251,25 @@ impl IntoAny for PreconditionFailure {
                     description: v.description,
                 })
                 .collect(),
-        };
+        }
+    }
+}
+
+impl IntoAny for PreconditionFailure {
+    fn into_any(self) -> Any {
+        let detail_data = pb::PreconditionFailure::from(self);
 
         Any {
-            type_url: PreconditionFailure::TYPE_URL.to_string(),
+            type_url: packed_type_url(PreconditionFailure::TYPE_URL),
             value: detail_data.encode_to_vec(),
         }
     }
 }
 
-impl FromAny for PreconditionFailure {
-    fn from_any(any: Any) -> Result<Self, DecodeError> {
-        let buf: &[u8] = &any.value;
-        let precondition_failure = pb::PreconditionFailure::decode(buf)?;
-
-        let precondition_failure = PreconditionFailure {
-            violations: precondition_failure
+impl From<pb::PreconditionFailure> for PreconditionFailure {
+    fn from(prec_failure: pb::PreconditionFailure) -> Self {
+        PreconditionFailure {
+            violations: prec_failure
                 .violations
                 .into_iter()
                 .map(|v| PreconditionViolation {
@@ -136,9 +278,16 @@ impl FromAny for PreconditionFailure {
                     description: v.description,
                 })
                 .collect(),
-        };
+        }
+    }
+}
+
+impl FromAny for PreconditionFailure {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let prec_failure = pb::PreconditionFailure::decode(buf)?;
 
-        Ok(precondition_failure)
+        Ok(prec_failure.into())
     }
 }
 
@@ -146,7 +295,7 @@ impl FromAny for PreconditionFailure {
 mod tests {
 
     use super::super::super::{FromAny, IntoAny};
-    use super::PreconditionFailure;
+    use super::{PreconditionFailure, PreconditionKind};
 
     #[test]
     fn gen_prec_failure() {
@@ -183,7 +332,7 @@ mod tests {
         );
 
         assert!(
-            prec_failure.is_empty() == false,
+            !prec_failure.is_empty(),
             "filled PreconditionFailure returns 'true' from .is_empty()"
         );
 
@@ -193,14 +342,12 @@ mod tests {
 
         println!("Any generated from PreconditionFailure -> {formatted}");
 
-        let expected = "Any { type_url: \"type.googleapis.com/google.rpc.PreconditionFailure\", value: [10, 51, 10, 3, 84, 79, 83, 18, 13, 101, 120, 97, 109, 112, 108, 101, 46, 108, 111, 99, 97, 108, 26, 29, 84, 101, 114, 109, 115, 32, 111, 102, 32, 115, 101, 114, 118, 105, 99, 101, 32, 110, 111, 116, 32, 97, 99, 99, 101, 112, 116, 101, 100, 10, 36, 10, 3, 70, 78, 70, 18, 13, 101, 120, 97, 109, 112, 108, 101, 46, 108, 111, 99, 97, 108, 26, 14, 70, 105, 108, 101, 32, 110, 111, 116, 32, 102, 111, 117, 110, 100] }";
-
         assert!(
-            formatted.eq(expected),
-            "Any from filled PreconditionFailure differs from expected result"
+            gen_any.type_url.eq(PreconditionFailure::TYPE_URL),
+            "Any from filled PreconditionFailure has unexpected type URL"
         );
 
-        let br_details = match PreconditionFailure::from_any(gen_any) {
+        let br_details = match PreconditionFailure::from_any((&gen_any).into()) {
             Err(error) => panic!("Error generating PreconditionFailure from Any: {:?}", error),
             Ok(from_any) => from_any,
         };
@@ -214,4 +361,65 @@ mod tests {
             "PreconditionFailure from Any differs from expected result"
         );
     }
+
+    #[test]
+    fn gen_typed_prec_failure() {
+        let mut prec_failure = PreconditionFailure::new(Vec::new());
+
+        prec_failure
+            .add_typed_violation(PreconditionKind::Tos, "example.local", "description")
+            .add_typed_violation(PreconditionKind::State, "books/b", "description")
+            .add_typed_violation(
+                PreconditionKind::QuotaVerification,
+                "project",
+                "description",
+            )
+            .add_typed_violation(
+                PreconditionKind::custom("billingAccount"),
+                "project",
+                "description",
+            )
+            .add_typed_violation(
+                PreconditionKind::custom(" region-lock "),
+                "project",
+                "description",
+            );
+
+        let types: Vec<&str> = prec_failure
+            .violations
+            .iter()
+            .map(|violation| violation.r#type.as_str())
+            .collect();
+
+        println!("typed PreconditionFailure types -> {:?}", types);
+
+        assert!(
+            types.eq(&[
+                "TOS",
+                "STATE",
+                "QUOTA_VERIFICATION",
+                "BILLING_ACCOUNT",
+                "REGION_LOCK"
+            ]),
+            "typed PreconditionFailure types differ from expected result"
+        );
+
+        let kinds: Vec<PreconditionKind> = prec_failure
+            .violations
+            .iter()
+            .map(|violation| violation.kind())
+            .collect();
+
+        let formatted = format!("{:?}", kinds);
+
+        println!("PreconditionViolation kinds -> {formatted}");
+
+        let expected =
+            "[Tos, State, QuotaVerification, Custom(\"BILLING_ACCOUNT\"), Custom(\"REGION_LOCK\")]";
+
+        assert!(
+            formatted.eq(expected),
+            "PreconditionViolation kinds differ from expected result"
+        );
+    }
 }
@@ -1,35 +1,149 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::time::Duration;
+
 use prost::{DecodeError, Message};
 use prost_types::Any;
 
 use super::super::pb;
-use super::super::{FromAny, IntoAny};
+use super::super::type_url_prefix::packed_type_url;
+use super::super::{AnyRef, FromAny, IntoAny};
 
 /// Used at the `violations` field of the [`QuotaFailure`] struct. Describes a
 /// single quota violation.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct QuotaViolation {
     /// Subject on which the quota check failed.
     pub subject: String,
 
     /// Description of why the quota check failed.
     pub description: String,
+
+    /// API service from which the violation originates, such as
+    /// `compute.googleapis.com`. Empty if not set.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "String::is_empty"))]
+    pub api_service: String,
+
+    /// Metric of the violated quota, such as
+    /// `compute.googleapis.com/cpus_per_vm_family`. Empty if not set.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "String::is_empty"))]
+    pub quota_metric: String,
+
+    /// Identifier of the violated quota, unique within the API service.
+    /// Empty if not set.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "String::is_empty"))]
+    pub quota_id: String,
+
+    /// Dimensions on which the violated quota is enforced, such as `region`.
+    /// Empty for global quotas.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "BTreeMap::is_empty"))]
+    pub quota_dimensions: BTreeMap<String, String>,
+
+    /// Quota value enforced at the time of the violation. Zero if not set.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub quota_value: i64,
+
+    /// Quota value being rolled out at the time of the violation, which will
+    /// be enforced in place of `quota_value` once the rollout completes.
+    /// `None` if no rollout is in progress.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub future_quota_value: Option<i64>,
 }
 
 impl QuotaViolation {
-    /// Creates a new [`QuotaViolation`] struct.
+    /// Creates a new [`QuotaViolation`] struct, with no quota metadata.
     pub fn new(subject: impl Into<String>, description: impl Into<String>) -> Self {
         QuotaViolation {
             subject: subject.into(),
             description: description.into(),
+            ..Default::default()
         }
     }
+
+    /// Sets the API service from which the violation originates.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::QuotaViolation;
+    ///
+    /// let mut violation = QuotaViolation::new("project:example", "CPU quota exceeded");
+    ///
+    /// violation
+    ///     .set_api_service("compute.googleapis.com")
+    ///     .set_quota_metric("compute.googleapis.com/cpus_per_vm_family")
+    ///     .set_quota_id("CPUS-PER-VM-FAMILY-per-project-region")
+    ///     .add_quota_dimension("region", "us-central1")
+    ///     .set_quota_value(10)
+    ///     .set_future_quota_value(20);
+    ///
+    /// assert_eq!(violation.quota_dimensions["region"], "us-central1");
+    /// ```
+    pub fn set_api_service(&mut self, api_service: impl Into<String>) -> &mut Self {
+        self.api_service = api_service.into();
+        self
+    }
+
+    /// Sets the metric of the violated quota.
+    pub fn set_quota_metric(&mut self, quota_metric: impl Into<String>) -> &mut Self {
+        self.quota_metric = quota_metric.into();
+        self
+    }
+
+    /// Sets the identifier of the violated quota.
+    pub fn set_quota_id(&mut self, quota_id: impl Into<String>) -> &mut Self {
+        self.quota_id = quota_id.into();
+        self
+    }
+
+    /// Adds a dimension on which the violated quota is enforced, replacing
+    /// any previous value of the same dimension.
+    pub fn add_quota_dimension(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> &mut Self {
+        self.quota_dimensions.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the quota value enforced at the time of the violation.
+    pub fn set_quota_value(&mut self, quota_value: i64) -> &mut Self {
+        self.quota_value = quota_value;
+        self
+    }
+
+    /// Sets the quota value being rolled out at the time of the violation.
+    pub fn set_future_quota_value(&mut self, future_quota_value: i64) -> &mut Self {
+        self.future_quota_value = Some(future_quota_value);
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+fn is_zero(value: &i64) -> bool {
+    *value == 0
 }
 
 /// Used to encode/decode the `QuotaFailure` standard error message described
 /// in [error_details.proto]. Describes how a quota check failed.
 ///
 /// [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct QuotaFailure {
     /// Describes all quota violations.
     pub violations: Vec<QuotaViolation>,
@@ -41,19 +155,14 @@ impl QuotaFailure {
 
     /// Creates a new [`QuotaFailure`] struct.
     pub fn new(violations: Vec<QuotaViolation>) -> Self {
-        QuotaFailure {
-            violations: violations,
-        }
+        QuotaFailure { violations }
     }
 
     /// Creates a new [`QuotaFailure`] struct with a single [`QuotaViolation`]
     /// in `violations`.
     pub fn with_violation(subject: impl Into<String>, description: impl Into<String>) -> Self {
         QuotaFailure {
-            violations: vec![QuotaViolation {
-                subject: subject.into(),
-                description: description.into(),
-            }],
+            violations: vec![QuotaViolation::new(subject, description)],
         }
     }
 }
@@ -65,10 +174,8 @@ impl QuotaFailure {
         subject: impl Into<String>,
         description: impl Into<String>,
     ) -> &mut Self {
-        self.violations.append(&mut vec![QuotaViolation {
-            subject: subject.into(),
-            description: description.into(),
-        }]);
+        self.violations
+            .push(QuotaViolation::new(subject, description));
         self
     }
 
@@ -79,43 +186,141 @@ impl QuotaFailure {
     }
 }
 
-impl IntoAny for QuotaFailure {
-    fn into_any(self) -> Any {
-        let detail_data = pb::QuotaFailure {
-            violations: self
+impl QuotaFailure {
+    /// Creates a new [`QuotaFailure`] struct with a single [`QuotaViolation`],
+    /// describing a rate limit of `limit` requests per `window` exceeded by
+    /// `subject`.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tonic_richer_error::QuotaFailure;
+    ///
+    /// let quota_failure =
+    ///     QuotaFailure::rate_limited("clientip:127.0.0.1", 100, Duration::from_secs(60));
+    ///
+    /// assert_eq!(
+    ///     quota_failure.violations[0].description,
+    ///     "rate limit of 100 requests per minute exceeded"
+    /// );
+    /// ```
+    pub fn rate_limited(subject: impl Into<String>, limit: u64, window: Duration) -> Self {
+        QuotaFailure::with_violation(
+            subject,
+            format!(
+                "rate limit of {limit} requests per {} exceeded",
+                format_window(window)
+            ),
+        )
+    }
+
+    /// Creates a new [`QuotaFailure`] struct with a single [`QuotaViolation`],
+    /// describing a quota of `limit` for `metric` exceeded by the `current`
+    /// usage. The metric is used as the violation subject.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::QuotaFailure;
+    ///
+    /// let quota_failure = QuotaFailure::exceeds_quota("storage_bytes", 1024, 2048);
+    ///
+    /// assert_eq!(
+    ///     quota_failure.violations[0].description,
+    ///     "quota of 1024 for storage_bytes exceeded, current usage is 2048"
+    /// );
+    /// ```
+    pub fn exceeds_quota(metric: impl Into<String>, limit: u64, current: u64) -> Self {
+        let metric: String = metric.into();
+        let description =
+            format!("quota of {limit} for {metric} exceeded, current usage is {current}");
+        QuotaFailure::with_violation(metric, description)
+    }
+}
+
+/// Formats a rate limit window using the largest whole time unit, like
+/// `"minute"` or `"5 seconds"`.
+fn format_window(window: Duration) -> String {
+    const UNITS: [(u64, &str); 4] = [
+        (86_400, "day"),
+        (3_600, "hour"),
+        (60, "minute"),
+        (1, "second"),
+    ];
+
+    let (count, unit) = match window.subsec_nanos() {
+        0 if window.as_secs() > 0 => UNITS
+            .iter()
+            .find(|(secs, _)| window.as_secs().is_multiple_of(*secs))
+            .map(|(secs, unit)| (window.as_secs() / secs, *unit))
+            .unwrap_or((window.as_secs(), "second")),
+        _ => (window.as_millis() as u64, "millisecond"),
+    };
+
+    match count {
+        1 => unit.to_string(),
+        _ => format!("{count} {unit}s"),
+    }
+}
+
+impl From<QuotaFailure> for pb::QuotaFailure {
+    fn from(quota_failure: QuotaFailure) -> Self {
+        pb::QuotaFailure {
+            violations: quota_failure
                 .violations
                 .into_iter()
                 .map(|v| pb::quota_failure::Violation {
                     subject: v.subject,
                     description: v.description,
+                    api_service: v.api_service,
+                    quota_metric: v.quota_metric,
+                    quota_id: v.quota_id,
+                    quota_dimensions: v.quota_dimensions.into_iter().collect(),
+                    quota_value: v.quota_value,
+                    future_quota_value: v.future_quota_value,
                 })
                 .collect(),
-        };
+        }
+    }
+}
+
+impl IntoAny for QuotaFailure {
+    fn into_any(self) -> Any {
+        let detail_data = pb::QuotaFailure::from(self);
 
         Any {
-            type_url: QuotaFailure::TYPE_URL.to_string(),
+            type_url: packed_type_url(QuotaFailure::TYPE_URL),
             value: detail_data.encode_to_vec(),
         }
     }
 }
 
-impl FromAny for QuotaFailure {
-    fn from_any(any: Any) -> Result<Self, DecodeError> {
-        let buf: &[u8] = &any.value;
-        let quota_failure = pb::QuotaFailure::decode(buf)?;
-
-        let quota_failure = QuotaFailure {
+impl From<pb::QuotaFailure> for QuotaFailure {
+    fn from(quota_failure: pb::QuotaFailure) -> Self {
+        QuotaFailure {
             violations: quota_failure
                 .violations
                 .into_iter()
                 .map(|v| QuotaViolation {
                     subject: v.subject,
                     description: v.description,
+                    api_service: v.api_service,
+                    quota_metric: v.quota_metric,
+                    quota_id: v.quota_id,
+                    quota_dimensions: v.quota_dimensions.into_iter().collect(),
+                    quota_value: v.quota_value,
+                    future_quota_value: v.future_quota_value,
                 })
                 .collect(),
-        };
+        }
+    }
+}
+
+impl FromAny for QuotaFailure {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let quota_failure = pb::QuotaFailure::decode(buf)?;
 
-        Ok(quota_failure)
+        Ok(quota_failure.into())
     }
 }
 
@@ -123,7 +328,9 @@ impl FromAny for QuotaFailure {
 mod tests {
 
     use super::super::super::{FromAny, IntoAny};
-    use super::QuotaFailure;
+    use std::time::Duration;
+
+    use super::{QuotaFailure, QuotaViolation};
 
     #[test]
     fn gen_quota_failure() {
@@ -152,7 +359,7 @@ mod tests {
 
         println!("filled QuotaFailure -> {formatted}");
 
-        let expected_filled = "QuotaFailure { violations: [QuotaViolation { subject: \"clientip:<ip address>\", description: \"description a\" }, QuotaViolation { subject: \"project:<project id>\", description: \"description b\" }] }";
+        let expected_filled = "QuotaFailure { violations: [QuotaViolation { subject: \"clientip:<ip address>\", description: \"description a\", api_service: \"\", quota_metric: \"\", quota_id: \"\", quota_dimensions: {}, quota_value: 0, future_quota_value: None }, QuotaViolation { subject: \"project:<project id>\", description: \"description b\", api_service: \"\", quota_metric: \"\", quota_id: \"\", quota_dimensions: {}, quota_value: 0, future_quota_value: None }] }";
 
         assert!(
             formatted.eq(expected_filled),
@@ -160,7 +367,7 @@ mod tests {
         );
 
         assert!(
-            quota_failure.is_empty() == false,
+            !quota_failure.is_empty(),
             "filled QuotaFailure returns 'true' from .is_empty()"
         );
 
@@ -170,14 +377,12 @@ mod tests {
 
         println!("Any generated from QuotaFailure -> {formatted}");
 
-        let expected = "Any { type_url: \"type.googleapis.com/google.rpc.QuotaFailure\", value: [10, 38, 10, 21, 99, 108, 105, 101, 110, 116, 105, 112, 58, 60, 105, 112, 32, 97, 100, 100, 114, 101, 115, 115, 62, 18, 13, 100, 101, 115, 99, 114, 105, 112, 116, 105, 111, 110, 32, 97, 10, 37, 10, 20, 112, 114, 111, 106, 101, 99, 116, 58, 60, 112, 114, 111, 106, 101, 99, 116, 32, 105, 100, 62, 18, 13, 100, 101, 115, 99, 114, 105, 112, 116, 105, 111, 110, 32, 98] }";
-
         assert!(
-            formatted.eq(expected),
-            "Any from filled QuotaFailure differs from expected result"
+            gen_any.type_url.eq(QuotaFailure::TYPE_URL),
+            "Any from filled QuotaFailure has unexpected type URL"
         );
 
-        let br_details = match QuotaFailure::from_any(gen_any) {
+        let br_details = match QuotaFailure::from_any((&gen_any).into()) {
             Err(error) => panic!("Error generating QuotaFailure from Any: {:?}", error),
             Ok(from_any) => from_any,
         };
@@ -191,4 +396,71 @@ mod tests {
             "QuotaFailure from Any differs from expected result"
         );
     }
+
+    #[test]
+    fn gen_quota_failure_helpers() {
+        let descriptions: Vec<String> = [
+            Duration::from_secs(1),
+            Duration::from_secs(300),
+            Duration::from_secs(7_200),
+            Duration::from_secs(86_400),
+            Duration::from_secs(90),
+            Duration::from_millis(1_500),
+        ]
+        .into_iter()
+        .map(|window| {
+            QuotaFailure::rate_limited("user:1", 10, window).violations[0]
+                .description
+                .clone()
+        })
+        .collect();
+
+        println!("rate limit descriptions -> {:?}", descriptions);
+
+        let expected = [
+            "rate limit of 10 requests per second exceeded",
+            "rate limit of 10 requests per 5 minutes exceeded",
+            "rate limit of 10 requests per 2 hours exceeded",
+            "rate limit of 10 requests per day exceeded",
+            "rate limit of 10 requests per 90 seconds exceeded",
+            "rate limit of 10 requests per 1500 milliseconds exceeded",
+        ];
+
+        assert!(
+            descriptions.eq(&expected),
+            "rate limit descriptions differ from expected result"
+        );
+
+        let formatted = format!("{:?}", QuotaFailure::exceeds_quota("cpus", 8, 12));
+
+        let expected = "QuotaFailure { violations: [QuotaViolation { subject: \"cpus\", description: \"quota of 8 for cpus exceeded, current usage is 12\", api_service: \"\", quota_metric: \"\", quota_id: \"\", quota_dimensions: {}, quota_value: 0, future_quota_value: None }] }";
+
+        assert!(
+            formatted.eq(expected),
+            "QuotaFailure from exceeds_quota differs from expected result"
+        );
+
+        let mut violation = QuotaViolation::new("project:example", "CPU quota exceeded");
+
+        violation
+            .set_api_service("compute.googleapis.com")
+            .set_quota_metric("compute.googleapis.com/cpus_per_vm_family")
+            .set_quota_id("CPUS-PER-VM-FAMILY-per-project-region")
+            .add_quota_dimension("region", "us-central1")
+            .add_quota_dimension("vm_family", "n1")
+            .set_quota_value(10)
+            .set_future_quota_value(20);
+
+        let qf_details = match QuotaFailure::from_any(
+            (&QuotaFailure::new(vec![violation.clone()]).into_any()).into(),
+        ) {
+            Err(error) => panic!("Error generating QuotaFailure from Any: {:?}", error),
+            Ok(from_any) => from_any,
+        };
+
+        assert!(
+            qf_details.violations.eq(&[violation]),
+            "QuotaViolation quota metadata was not preserved"
+        );
+    }
 }
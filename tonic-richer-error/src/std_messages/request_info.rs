@@ -1,15 +1,24 @@
+use alloc::string::String;
+
 use prost::{DecodeError, Message};
 use prost_types::Any;
 
 use super::super::pb;
-use super::super::{FromAny, IntoAny};
+use super::super::type_url_prefix::packed_type_url;
+use super::super::{AnyRef, FromAny, IntoAny};
 
 /// Used to encode/decode the `RequestInfo` standard error message described
 /// in [error_details.proto]. Contains metadata about the request that
 /// clients can attach when providing feedback.
 ///
 /// [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RequestInfo {
     /// An opaque string that should only be interpreted by the service that
     /// generated it. For example, an id used to identify requests in the logs.
@@ -41,31 +50,41 @@ impl RequestInfo {
     }
 }
 
+impl From<RequestInfo> for pb::RequestInfo {
+    fn from(req_info: RequestInfo) -> Self {
+        pb::RequestInfo {
+            request_id: req_info.request_id,
+            serving_data: req_info.serving_data,
+        }
+    }
+}
+
 impl IntoAny for RequestInfo {
     fn into_any(self) -> Any {
-        let detail_data = pb::RequestInfo {
-            request_id: self.request_id,
-            serving_data: self.serving_data,
-        };
+        let detail_data = pb::RequestInfo::from(self);
 
         Any {
-            type_url: RequestInfo::TYPE_URL.to_string(),
+            type_url: packed_type_url(RequestInfo::TYPE_URL),
             value: detail_data.encode_to_vec(),
         }
     }
 }
 
-impl FromAny for RequestInfo {
-    fn from_any(any: Any) -> Result<Self, DecodeError> {
-        let buf: &[u8] = &any.value;
-        let req_info = pb::RequestInfo::decode(buf)?;
-
-        let debug_info = RequestInfo {
+impl From<pb::RequestInfo> for RequestInfo {
+    fn from(req_info: pb::RequestInfo) -> Self {
+        RequestInfo {
             request_id: req_info.request_id,
             serving_data: req_info.serving_data,
-        };
+        }
+    }
+}
 
-        Ok(debug_info)
+impl FromAny for RequestInfo {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let req_info = pb::RequestInfo::decode(buf)?;
+
+        Ok(req_info.into())
     }
 }
 
@@ -97,15 +116,12 @@ mod tests {
 
         println!("Any generated from RequestInfo -> {formatted}");
 
-        let expected =
-            "Any { type_url: \"type.googleapis.com/google.rpc.RequestInfo\", value: [10, 7, 115, 111, 109, 101, 45, 105, 100, 18, 9, 115, 111, 109, 101, 45, 100, 97, 116, 97] }";
-
         assert!(
-            formatted.eq(expected),
-            "Any from filled RequestInfo differs from expected result"
+            gen_any.type_url.eq(RequestInfo::TYPE_URL),
+            "Any from filled RequestInfo has unexpected type URL"
         );
 
-        let br_details = match RequestInfo::from_any(gen_any) {
+        let br_details = match RequestInfo::from_any((&gen_any).into()) {
             Err(error) => panic!("Error generating RequestInfo from Any: {:?}", error),
             Ok(from_any) => from_any,
         };
@@ -1,14 +1,27 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
 use prost::{DecodeError, Message};
 use prost_types::Any;
 
 use super::super::pb;
-use super::super::{FromAny, IntoAny};
+use super::super::type_url_prefix::packed_type_url;
+use super::super::{AnyRef, FromAny, IntoAny};
 
 /// Used to encode/decode the `ResourceInfo` standard error message described
 /// in [error_details.proto]. Describes the resource that is being accessed.
 ///
 /// [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ResourceInfo {
     /// Type of resource being accessed.
     pub resource_type: String,
@@ -41,6 +54,114 @@ impl ResourceInfo {
             description: description.into(),
         }
     }
+
+    /// Creates a new [`ResourceInfo`] struct from an [AIP-122] resource name,
+    /// such as `projects/p/books/b`. The `resource_type` is set to the
+    /// collection ID of the resource, `books` in the previous example. Full
+    /// resource names, such as `//library.example.local/books/b`, and
+    /// singleton resources, such as `users/u/settings`, are also accepted.
+    /// If the name is malformed, an [`InvalidResourceName`] error is
+    /// returned.
+    ///
+    /// [AIP-122]: https://google.aip.dev/122
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::ResourceInfo;
+    ///
+    /// let res_info = ResourceInfo::for_resource_name("projects/p/books/b").unwrap();
+    ///
+    /// assert_eq!(res_info.resource_type, "books");
+    /// assert_eq!(res_info.resource_name, "projects/p/books/b");
+    ///
+    /// assert!(ResourceInfo::for_resource_name("projects//books/b").is_err());
+    /// ```
+    pub fn for_resource_name(name: &str) -> Result<Self, InvalidResourceName> {
+        let collection = collection_id(name)?;
+
+        Ok(ResourceInfo::new(collection, name, "", ""))
+    }
+
+    /// Similar to [`ResourceInfo::for_resource_name`], but looks up the
+    /// `resource_type` in a map from collection IDs to resource types, such
+    /// as `books` to `library.example.local/Book`. Falls back to the
+    /// collection ID if it is not in the map.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use tonic_richer_error::ResourceInfo;
+    ///
+    /// let types = HashMap::from([("books".to_string(), "library.example.local/Book".to_string())]);
+    ///
+    /// let res_info = ResourceInfo::for_resource_name_with_types("shelves/s/books/b", &types).unwrap();
+    ///
+    /// assert_eq!(res_info.resource_type, "library.example.local/Book");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn for_resource_name_with_types(
+        name: &str,
+        types: &HashMap<String, String>,
+    ) -> Result<Self, InvalidResourceName> {
+        let collection = collection_id(name)?;
+
+        let resource_type = types
+            .get(collection)
+            .map(String::as_str)
+            .unwrap_or(collection);
+
+        Ok(ResourceInfo::new(resource_type, name, "", ""))
+    }
+}
+
+/// Error returned by [`ResourceInfo::for_resource_name`], holding the invalid
+/// resource name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidResourceName(pub String);
+
+impl fmt::Display for InvalidResourceName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid resource name `{}`, expected alternating collection IDs and resource IDs",
+            self.0
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidResourceName {}
+
+/// Returns the collection ID of the resource, such as `books` for
+/// `projects/p/books/b`, or `settings` for the singleton `users/u/settings`.
+fn collection_id(name: &str) -> Result<&str, InvalidResourceName> {
+    let invalid = || InvalidResourceName(name.to_string());
+
+    let relative = match name.strip_prefix("//") {
+        Some(full) => full.split_once('/').map(|(_, rest)| rest).unwrap_or(""),
+        None => name,
+    };
+
+    let segments: Vec<&str> = relative.split('/').collect();
+
+    let valid_collection = |id: &str| {
+        id.starts_with(|c: char| c.is_ascii_lowercase())
+            && id.chars().all(|c| c.is_ascii_alphanumeric())
+    };
+
+    if segments.len() < 2 || segments.iter().any(|segment| segment.is_empty()) {
+        return Err(invalid());
+    }
+
+    if !segments.iter().step_by(2).all(|id| valid_collection(id)) {
+        return Err(invalid());
+    }
+
+    // The last chunk is either a `collection/id` pair, or a singleton.
+    match segments.chunks(2).last() {
+        Some(chunk) => Ok(chunk[0]),
+        None => Err(invalid()),
+    }
 }
 
 impl ResourceInfo {
@@ -54,41 +175,54 @@ impl ResourceInfo {
     }
 }
 
+impl From<ResourceInfo> for pb::ResourceInfo {
+    fn from(res_info: ResourceInfo) -> Self {
+        pb::ResourceInfo {
+            resource_type: res_info.resource_type,
+            resource_name: res_info.resource_name,
+            owner: res_info.owner,
+            description: res_info.description,
+        }
+    }
+}
+
 impl IntoAny for ResourceInfo {
     fn into_any(self) -> Any {
-        let detail_data = pb::ResourceInfo {
-            resource_type: self.resource_type,
-            resource_name: self.resource_name,
-            owner: self.owner,
-            description: self.description,
-        };
+        let detail_data = pb::ResourceInfo::from(self);
 
         Any {
-            type_url: ResourceInfo::TYPE_URL.to_string(),
+            type_url: packed_type_url(ResourceInfo::TYPE_URL),
             value: detail_data.encode_to_vec(),
         }
     }
 }
 
-impl FromAny for ResourceInfo {
-    fn from_any(any: Any) -> Result<Self, DecodeError> {
-        let buf: &[u8] = &any.value;
-        let res_info = pb::ResourceInfo::decode(buf)?;
-
-        let debug_info = ResourceInfo {
+impl From<pb::ResourceInfo> for ResourceInfo {
+    fn from(res_info: pb::ResourceInfo) -> Self {
+        ResourceInfo {
             resource_type: res_info.resource_type,
             resource_name: res_info.resource_name,
             owner: res_info.owner,
             description: res_info.description,
-        };
+        }
+    }
+}
 
-        Ok(debug_info)
+impl FromAny for ResourceInfo {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let res_info = pb::ResourceInfo::decode(buf)?;
+
+        Ok(res_info.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
 
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+
     use super::super::super::{FromAny, IntoAny};
     use super::ResourceInfo;
 
@@ -114,15 +248,12 @@ mod tests {
 
         println!("Any generated from ResourceInfo -> {formatted}");
 
-        let expected =
-            "Any { type_url: \"type.googleapis.com/google.rpc.ResourceInfo\", value: [10, 13, 114, 101, 115, 111, 117, 114, 99, 101, 45, 116, 121, 112, 101, 18, 13, 114, 101, 115, 111, 117, 114, 99, 101, 45, 110, 97, 109, 101, 26, 5, 111, 119, 110, 101, 114, 34, 11, 100, 101, 115, 99, 114, 105, 112, 116, 105, 111, 110] }";
-
         assert!(
-            formatted.eq(expected),
-            "Any from filled ResourceInfo differs from expected result"
+            gen_any.type_url.eq(ResourceInfo::TYPE_URL),
+            "Any from filled ResourceInfo has unexpected type URL"
         );
 
-        let br_details = match ResourceInfo::from_any(gen_any) {
+        let br_details = match ResourceInfo::from_any((&gen_any).into()) {
             Err(error) => panic!("Error generating ResourceInfo from Any: {:?}", error),
             Ok(from_any) => from_any,
         };
@@ -136,4 +267,58 @@ mod tests {
             "ResourceInfo from Any differs from expected result"
         );
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn gen_resource_info_from_names() {
+        let types = HashMap::from([(
+            "books".to_string(),
+            "library.example.local/Book".to_string(),
+        )]);
+
+        let results: Vec<String> = [
+            "projects/p/books/b",
+            "//library.example.local/shelves/s/books/b",
+            "users/u/settings",
+            "shelves/s",
+            "shelves",
+            "",
+            "shelves//books/b",
+            "Shelves/s/books/b",
+            "//library.example.local",
+        ]
+        .iter()
+        .map(
+            |name| match ResourceInfo::for_resource_name_with_types(name, &types) {
+                Ok(res_info) => res_info.resource_type,
+                Err(err) => format!("Err({})", err.0),
+            },
+        )
+        .collect();
+
+        let formatted = format!("{:?}", results);
+
+        println!("ResourceInfo from names -> {formatted}");
+
+        let expected = "[\"library.example.local/Book\", \"library.example.local/Book\", \"settings\", \"shelves\", \"Err(shelves)\", \"Err()\", \"Err(shelves//books/b)\", \"Err(Shelves/s/books/b)\", \"Err(//library.example.local)\"]";
+
+        assert!(
+            formatted.eq(expected),
+            "ResourceInfo from names differs from expected result"
+        );
+
+        let res_info = match ResourceInfo::for_resource_name("projects/p/books/b") {
+            Ok(res_info) => res_info,
+            Err(err) => panic!("Error parsing resource name: {err}"),
+        };
+
+        let formatted = format!("{:?}", res_info);
+
+        let expected = "ResourceInfo { resource_type: \"books\", resource_name: \"projects/p/books/b\", owner: \"\", description: \"\" }";
+
+        assert!(
+            formatted.eq(expected),
+            "ResourceInfo from name differs from expected result"
+        );
+    }
 }
@@ -1,11 +1,11 @@
-use std::convert::TryFrom;
-use std::time;
+use core::time;
 
 use prost::{DecodeError, Message};
 use prost_types::Any;
 
 use super::super::pb;
-use super::super::{FromAny, IntoAny};
+use super::super::type_url_prefix::packed_type_url;
+use super::super::{AnyRef, FromAny, IntoAny};
 
 /// Used to encode/decode the `RetryInfo` standard error message described in
 /// [error_details.proto]. Describes when the clients can retry a failed
@@ -14,9 +14,23 @@ use super::super::{FromAny, IntoAny};
 /// `retry_delay`'s become 0.
 ///
 /// [error_details.proto]: https://github.com/googleapis/googleapis/blob/master/google/rpc/error_details.proto
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "camelCase")
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RetryInfo {
     /// Informs the amout of time that clients should wait before retrying.
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "duration_serde", skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(
+        feature = "arbitrary",
+        arbitrary(with = super::super::arbitrary_details::retry_delay)
+    )]
     pub retry_delay: Option<time::Duration>,
 }
 
@@ -41,9 +55,7 @@ impl RetryInfo {
             }
             None => None,
         };
-        RetryInfo {
-            retry_delay: retry_delay,
-        }
+        RetryInfo { retry_delay }
     }
 }
 
@@ -53,11 +65,34 @@ impl RetryInfo {
     pub fn is_empty(&self) -> bool {
         self.retry_delay.is_none()
     }
+
+    /// Sleeps for the `retry_delay` informed by the server. Returns
+    /// immediately if no delay is set.
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tonic_richer_error::RetryInfo;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let retry_info = RetryInfo::new(Some(Duration::from_millis(10)));
+    ///
+    /// // Wait before retrying the call
+    /// retry_info.wait().await;
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn wait(&self) {
+        if let Some(delay) = self.retry_delay {
+            tokio::time::sleep(delay).await;
+        }
+    }
 }
 
-impl IntoAny for RetryInfo {
-    fn into_any(self) -> Any {
-        let retry_delay = match self.retry_delay {
+impl From<RetryInfo> for pb::RetryInfo {
+    fn from(retry_info: RetryInfo) -> Self {
+        let retry_delay = match retry_info.retry_delay {
             Some(duration) => {
                 // If duration is too large, uses max `prost_types::Duration`
                 let duration = match prost_types::Duration::try_from(duration) {
@@ -72,22 +107,23 @@ impl IntoAny for RetryInfo {
             None => None,
         };
 
-        let detail_data = pb::RetryInfo {
-            retry_delay: retry_delay,
-        };
+        pb::RetryInfo { retry_delay }
+    }
+}
+
+impl IntoAny for RetryInfo {
+    fn into_any(self) -> Any {
+        let detail_data = pb::RetryInfo::from(self);
 
         Any {
-            type_url: RetryInfo::TYPE_URL.to_string(),
+            type_url: packed_type_url(RetryInfo::TYPE_URL),
             value: detail_data.encode_to_vec(),
         }
     }
 }
 
-impl FromAny for RetryInfo {
-    fn from_any(any: Any) -> Result<Self, DecodeError> {
-        let buf: &[u8] = &any.value;
-        let retry_info = pb::RetryInfo::decode(buf)?;
-
+impl From<pb::RetryInfo> for RetryInfo {
+    fn from(retry_info: pb::RetryInfo) -> Self {
         let retry_delay = match retry_info.retry_delay {
             Some(duration) => {
                 // Negative retry_delays become 0
@@ -97,11 +133,84 @@ impl FromAny for RetryInfo {
             None => None,
         };
 
-        let retry_info = RetryInfo {
-            retry_delay: retry_delay,
+        RetryInfo { retry_delay }
+    }
+}
+
+impl FromAny for RetryInfo {
+    fn from_any(any: AnyRef<'_>) -> Result<Self, DecodeError> {
+        let buf: &[u8] = any.value;
+        let retry_info = pb::RetryInfo::decode(buf)?;
+
+        Ok(retry_info.into())
+    }
+}
+
+/// Serializes `retry_delay` following the JSON mapping of
+/// `google.protobuf.Duration`, as a string of seconds with an `s` suffix, such
+/// as `"1.5s"`.
+#[cfg(feature = "serde")]
+mod duration_serde {
+    use std::time;
+
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S>(
+        duration: &Option<time::Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let duration = match duration {
+            Some(duration) => duration,
+            None => return serializer.serialize_none(),
+        };
+
+        let secs = duration.as_secs();
+        let nanos = duration.subsec_nanos();
+
+        let formatted = if nanos == 0 {
+            format!("{secs}s")
+        } else if nanos % 1_000_000 == 0 {
+            format!("{secs}.{:03}s", nanos / 1_000_000)
+        } else if nanos % 1_000 == 0 {
+            format!("{secs}.{:06}s", nanos / 1_000)
+        } else {
+            format!("{secs}.{nanos:09}s")
         };
 
-        Ok(retry_info)
+        serializer.serialize_str(&formatted)
+    }
+
+    pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<Option<time::Duration>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let formatted = match Option::<String>::deserialize(deserializer)? {
+            Some(formatted) => formatted,
+            None => return Ok(None),
+        };
+
+        let invalid = || de::Error::custom(format!("invalid duration: {formatted:?}"));
+
+        let value = formatted.strip_suffix('s').ok_or_else(invalid)?;
+
+        // Negative retry_delays become 0
+        if value.starts_with('-') {
+            return Ok(Some(time::Duration::ZERO));
+        }
+
+        let (secs, frac) = value.split_once('.').unwrap_or((value, ""));
+
+        if frac.len() > 9 || !frac.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+
+        let secs: u64 = secs.parse().map_err(|_| invalid())?;
+        let nanos: u32 = format!("{frac:0<9}").parse().map_err(|_| invalid())?;
+
+        Ok(Some(time::Duration::new(secs, nanos)))
     }
 }
 
@@ -110,7 +219,7 @@ mod tests {
 
     use core::time::Duration;
 
-    use super::super::super::{FromAny, IntoAny};
+    use super::super::super::{pb, FromAny, IntoAny};
     use super::RetryInfo;
 
     #[test]
@@ -129,7 +238,7 @@ mod tests {
         );
 
         assert!(
-            error_info.is_empty() == false,
+            !error_info.is_empty(),
             "filled RetryInfo returns 'false' from .has_retry_delay()"
         );
 
@@ -139,15 +248,12 @@ mod tests {
 
         println!("Any generated from RetryInfo -> {formatted}");
 
-        let expected =
-            "Any { type_url: \"type.googleapis.com/google.rpc.RetryInfo\", value: [10, 13, 8, 128, 188, 174, 206, 151, 9, 16, 255, 147, 235, 220, 3] }";
-
         assert!(
-            formatted.eq(expected),
-            "Any from filled RetryInfo differs from expected result"
+            gen_any.type_url.eq(RetryInfo::TYPE_URL),
+            "Any from filled RetryInfo has unexpected type URL"
         );
 
-        let br_details = match RetryInfo::from_any(gen_any) {
+        let br_details = match RetryInfo::from_any((&gen_any).into()) {
             Err(error) => panic!("Error generating RetryInfo from Any: {:?}", error),
             Ok(from_any) => from_any,
         };
@@ -161,4 +267,40 @@ mod tests {
             "RetryInfo from Any differs from expected result"
         );
     }
+
+    #[test]
+    fn convert_pb_retry_info() {
+        let pb_retry_info = pb::RetryInfo {
+            retry_delay: Some(prost_types::Duration {
+                seconds: -5,
+                nanos: 0,
+            }),
+        };
+
+        let retry_info = RetryInfo::from(pb_retry_info);
+
+        let formatted = format!("{:?}", retry_info);
+
+        println!("RetryInfo from pb -> {formatted}");
+
+        let expected = "RetryInfo { retry_delay: Some(0ns) }";
+
+        assert!(
+            formatted.eq(expected),
+            "RetryInfo from pb differs from expected result"
+        );
+
+        let pb_retry_info = pb::RetryInfo::from(RetryInfo::new(Some(Duration::from_millis(1500))));
+
+        let formatted = format!("{:?}", pb_retry_info);
+
+        println!("pb from RetryInfo -> {formatted}");
+
+        let expected = "RetryInfo { retry_delay: Some(Duration { seconds: 1, nanos: 500000000 }) }";
+
+        assert!(
+            formatted.eq(expected),
+            "pb from RetryInfo differs from expected result"
+        );
+    }
 }
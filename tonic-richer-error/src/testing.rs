@@ -0,0 +1,310 @@
+//! Assertion helpers for tests of server handlers that return
+//! `tonic::Status` with error details. The helpers panic with messages that
+//! describe the details actually found in the status.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use tonic::{Code, Status};
+//! use tonic_richer_error::testing::{
+//!     assert_retry_info_at_least, assert_status_has_bad_request_violation, contains,
+//! };
+//! use tonic_richer_error::{ErrorDetails, WithErrorDetails};
+//!
+//! let mut err_details = ErrorDetails::new();
+//!
+//! err_details
+//!     .add_bad_request_violation("email", "email is not a valid address")
+//!     .set_retry_info(Some(Duration::from_secs(5)));
+//!
+//! let status = Status::with_error_details(Code::InvalidArgument, "bad request", err_details);
+//!
+//! assert_status_has_bad_request_violation(&status, "email", contains("valid address"));
+//! assert_retry_info_at_least(&status, Duration::from_secs(1));
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use tonic::{Code, Status};
+
+use super::WithErrorDetails;
+
+/// Describes the expected text of a detail field, such as a violation
+/// description. Plain strings are converted into exact matchers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TextMatcher {
+    /// Matches any text.
+    Any,
+
+    /// Matches text equal to the given string.
+    Exactly(String),
+
+    /// Matches text containing the given string.
+    Contains(String),
+}
+
+impl TextMatcher {
+    /// Returns `true` if the text is matched.
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            TextMatcher::Any => true,
+            TextMatcher::Exactly(expected) => text == expected,
+            TextMatcher::Contains(expected) => text.contains(expected.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for TextMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextMatcher::Any => write!(f, "any text"),
+            TextMatcher::Exactly(expected) => write!(f, "{expected:?}"),
+            TextMatcher::Contains(expected) => write!(f, "text containing {expected:?}"),
+        }
+    }
+}
+
+impl From<&str> for TextMatcher {
+    fn from(expected: &str) -> Self {
+        TextMatcher::Exactly(expected.into())
+    }
+}
+
+impl From<String> for TextMatcher {
+    fn from(expected: String) -> Self {
+        TextMatcher::Exactly(expected)
+    }
+}
+
+/// Creates a [`TextMatcher`] that matches any text.
+pub fn any() -> TextMatcher {
+    TextMatcher::Any
+}
+
+/// Creates a [`TextMatcher`] that matches text containing `expected`.
+pub fn contains(expected: impl Into<String>) -> TextMatcher {
+    TextMatcher::Contains(expected.into())
+}
+
+/// Asserts that the status has the given code.
+#[track_caller]
+pub fn assert_status_code(status: &Status, code: Code) {
+    assert!(
+        status.code() == code,
+        "expected status code {:?}, found {:?} (message: {:?})",
+        code,
+        status.code(),
+        status.message()
+    );
+}
+
+/// Asserts that the status has [`crate::BadRequest`] details with a
+/// violation for `field`, whose description is matched by `description`.
+#[track_caller]
+pub fn assert_status_has_bad_request_violation(
+    status: &Status,
+    field: &str,
+    description: impl Into<TextMatcher>,
+) {
+    let description = description.into();
+
+    let bad_request = match status.get_details_bad_request() {
+        Some(bad_request) => bad_request,
+        None => panic!(
+            "expected BadRequest details with a violation for field {:?}, found no BadRequest details",
+            field
+        ),
+    };
+
+    let found = bad_request
+        .field_violations
+        .iter()
+        .any(|v| v.field == field && description.matches(&v.description));
+
+    assert!(
+        found,
+        "expected BadRequest violation for field {:?} with description {}, found violations: {:?}",
+        field, description, bad_request.field_violations
+    );
+}
+
+/// Asserts that the status has [`crate::QuotaFailure`] details with a
+/// violation for `subject`, whose description is matched by `description`.
+#[track_caller]
+pub fn assert_status_has_quota_violation(
+    status: &Status,
+    subject: &str,
+    description: impl Into<TextMatcher>,
+) {
+    let description = description.into();
+
+    let quota_failure = match status.get_details_quota_failure() {
+        Some(quota_failure) => quota_failure,
+        None => panic!(
+            "expected QuotaFailure details with a violation for subject {:?}, found no QuotaFailure details",
+            subject
+        ),
+    };
+
+    let found = quota_failure
+        .violations
+        .iter()
+        .any(|v| v.subject == subject && description.matches(&v.description));
+
+    assert!(
+        found,
+        "expected QuotaFailure violation for subject {:?} with description {}, found violations: {:?}",
+        subject, description, quota_failure.violations
+    );
+}
+
+/// Asserts that the status has [`crate::PreconditionFailure`] details with a
+/// violation of type `violation_type` for `subject`.
+#[track_caller]
+pub fn assert_status_has_precondition_violation(
+    status: &Status,
+    violation_type: &str,
+    subject: &str,
+) {
+    let prec_failure = match status.get_details_precondition_failure() {
+        Some(prec_failure) => prec_failure,
+        None => panic!(
+            "expected PreconditionFailure details with a {:?} violation for subject {:?}, found no PreconditionFailure details",
+            violation_type, subject
+        ),
+    };
+
+    let found = prec_failure
+        .violations
+        .iter()
+        .any(|v| v.r#type == violation_type && v.subject == subject);
+
+    assert!(
+        found,
+        "expected PreconditionFailure {:?} violation for subject {:?}, found violations: {:?}",
+        violation_type, subject, prec_failure.violations
+    );
+}
+
+/// Asserts that the status has [`crate::ErrorInfo`] details with the given
+/// reason.
+#[track_caller]
+pub fn assert_error_info_reason(status: &Status, reason: &str) {
+    match status.get_details_error_info() {
+        Some(error_info) => assert!(
+            error_info.reason == reason,
+            "expected ErrorInfo reason {:?}, found {:?}",
+            reason,
+            error_info.reason
+        ),
+        None => panic!(
+            "expected ErrorInfo details with reason {:?}, found no ErrorInfo details",
+            reason
+        ),
+    }
+}
+
+/// Asserts that the status has [`crate::RetryInfo`] details with a
+/// `retry_delay` of at least `min_delay`.
+#[track_caller]
+pub fn assert_retry_info_at_least(status: &Status, min_delay: Duration) {
+    let retry_info = match status.get_details_retry_info() {
+        Some(retry_info) => retry_info,
+        None => panic!(
+            "expected RetryInfo details with a delay of at least {:?}, found no RetryInfo details",
+            min_delay
+        ),
+    };
+
+    match retry_info.retry_delay {
+        Some(delay) => assert!(
+            delay >= min_delay,
+            "expected RetryInfo delay of at least {:?}, found {:?}",
+            min_delay,
+            delay
+        ),
+        None => panic!(
+            "expected RetryInfo delay of at least {:?}, found no delay",
+            min_delay
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::panic::catch_unwind;
+    use std::time::Duration;
+    use tonic::{Code, Status};
+
+    use super::super::{ErrorDetails, WithErrorDetails};
+    use super::{
+        any, assert_error_info_reason, assert_retry_info_at_least, assert_status_code,
+        assert_status_has_bad_request_violation, assert_status_has_precondition_violation,
+        assert_status_has_quota_violation, contains,
+    };
+
+    fn gen_status() -> Status {
+        let mut err_details = ErrorDetails::new();
+
+        err_details
+            .set_retry_info(Some(Duration::from_secs(5)))
+            .add_quota_failure_violation("clientip:<ip address>", "rate limit exceeded")
+            .set_error_info("SOME_INFO", "example.local", Default::default())
+            .add_precondition_failure_violation("TOS", "example.local", "description")
+            .add_bad_request_violation("email", "email is not a valid address");
+
+        Status::with_error_details(Code::InvalidArgument, "bad request", err_details)
+    }
+
+    #[test]
+    fn passing_assertions() {
+        let status = gen_status();
+
+        assert_status_code(&status, Code::InvalidArgument);
+        assert_status_has_bad_request_violation(&status, "email", "email is not a valid address");
+        assert_status_has_bad_request_violation(&status, "email", contains("valid"));
+        assert_status_has_quota_violation(&status, "clientip:<ip address>", any());
+        assert_status_has_precondition_violation(&status, "TOS", "example.local");
+        assert_error_info_reason(&status, "SOME_INFO");
+        assert_retry_info_at_least(&status, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn failing_assertions() {
+        let panic_message = |result: std::thread::Result<()>| match result {
+            Ok(()) => String::from("did not panic"),
+            Err(err) => match err.downcast::<String>() {
+                Ok(message) => *message,
+                Err(_) => String::from("non-string panic"),
+            },
+        };
+
+        let messages = [
+            panic_message(catch_unwind(|| {
+                assert_status_has_bad_request_violation(&gen_status(), "email", contains("empty"))
+            })),
+            panic_message(catch_unwind(|| {
+                assert_retry_info_at_least(&gen_status(), Duration::from_secs(10))
+            })),
+            panic_message(catch_unwind(|| {
+                assert_error_info_reason(&Status::internal("internal"), "SOME_INFO")
+            })),
+        ];
+
+        println!("assertion panic messages -> {:?}", messages);
+
+        let expected = [
+            "expected BadRequest violation for field \"email\" with description text containing \"empty\", found violations: [FieldViolation { field: \"email\", description: \"email is not a valid address\", reason: \"\", localized_message: None }]",
+            "expected RetryInfo delay of at least 10s, found 5s",
+            "expected ErrorInfo details with reason \"SOME_INFO\", found no ErrorInfo details",
+        ];
+
+        assert!(
+            messages.eq(&expected),
+            "assertion panic messages differ from expected result"
+        );
+    }
+}
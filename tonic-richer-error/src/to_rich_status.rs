@@ -0,0 +1,374 @@
+#[cfg(any(feature = "std-io", feature = "sqlx", feature = "reqwest"))]
+use tonic::Code;
+use tonic::Status;
+
+#[cfg(feature = "reqwest")]
+use super::http_to_code;
+#[cfg(feature = "sqlx")]
+use super::{ErrorDetails, WithErrorDetails};
+
+/// Used to convert errors of other libraries, or of the application itself,
+/// into a `tonic::Status` with a sensible code and error details. The
+/// `std-io`, `sqlx` and `reqwest` features add implementations for the
+/// errors of the corresponding libraries.
+/// # Examples
+///
+/// ```
+/// use tonic::{Code, Status};
+/// use tonic_richer_error::{ErrorDetails, ToRichStatus, WithErrorDetails};
+///
+/// struct OutOfStock(String);
+///
+/// impl ToRichStatus for OutOfStock {
+///     fn to_rich_status(&self) -> Status {
+///         let mut err_details = ErrorDetails::new();
+///
+///         err_details.add_precondition_failure_violation("STOCK", &self.0, "out of stock");
+///
+///         Status::with_error_details(Code::FailedPrecondition, "out of stock", err_details)
+///     }
+/// }
+///
+/// let status = OutOfStock("items/A-1".into()).to_rich_status();
+///
+/// assert_eq!(status.code(), Code::FailedPrecondition);
+/// ```
+pub trait ToRichStatus {
+    /// Converts the error into a `tonic::Status` with error details.
+    fn to_rich_status(&self) -> Status;
+}
+
+/// Extension trait for `Result`s whose error implements [`ToRichStatus`],
+/// so server handlers can `?` their way to rich statuses.
+/// # Examples
+///
+/// ```
+/// use tonic::Status;
+/// use tonic_richer_error::{IntoRichResult, ToRichStatus};
+///
+/// struct NotReady;
+///
+/// impl ToRichStatus for NotReady {
+///     fn to_rich_status(&self) -> Status {
+///         Status::unavailable("not ready")
+///     }
+/// }
+///
+/// fn handler() -> Result<(), Status> {
+///     Err(NotReady).into_rich_result()?;
+///     Ok(())
+/// }
+///
+/// assert_eq!(handler().unwrap_err().message(), "not ready");
+/// ```
+pub trait IntoRichResult<T> {
+    /// Converts the error, if any, into a `tonic::Status` with
+    /// [`ToRichStatus::to_rich_status`].
+    #[allow(clippy::result_large_err)]
+    fn into_rich_result(self) -> Result<T, Status>;
+}
+
+impl<T, E: ToRichStatus> IntoRichResult<T> for Result<T, E> {
+    #[allow(clippy::result_large_err)]
+    fn into_rich_result(self) -> Result<T, Status> {
+        self.map_err(|err| err.to_rich_status())
+    }
+}
+
+/// Maps `std::io::Error`s to the code matching their kind, such as
+/// `Code::NotFound` for `ErrorKind::NotFound` and `Code::Unavailable` for
+/// connection failures.
+#[cfg(feature = "std-io")]
+impl ToRichStatus for std::io::Error {
+    fn to_rich_status(&self) -> Status {
+        use std::io::ErrorKind;
+
+        let code = match self.kind() {
+            ErrorKind::NotFound => Code::NotFound,
+            ErrorKind::PermissionDenied => Code::PermissionDenied,
+            ErrorKind::AlreadyExists => Code::AlreadyExists,
+            ErrorKind::InvalidInput | ErrorKind::InvalidData => Code::InvalidArgument,
+            ErrorKind::TimedOut => Code::DeadlineExceeded,
+            ErrorKind::Interrupted => Code::Aborted,
+            ErrorKind::Unsupported => Code::Unimplemented,
+            ErrorKind::OutOfMemory => Code::ResourceExhausted,
+            ErrorKind::UnexpectedEof => Code::DataLoss,
+            ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::AddrNotAvailable
+            | ErrorKind::BrokenPipe
+            | ErrorKind::WouldBlock => Code::Unavailable,
+            _ => Code::Internal,
+        };
+
+        Status::new(code, self.to_string())
+    }
+}
+
+/// Maps `sqlx::Error`s to rich statuses. Unique violations become
+/// `Code::AlreadyExists` with [`crate::ResourceInfo`] details, foreign key
+/// violations become `Code::FailedPrecondition` with
+/// [`crate::PreconditionFailure`] details, and not-null and check violations
+/// become `Code::InvalidArgument` with [`crate::BadRequest`] details. Missing
+/// rows become `Code::NotFound`, and pool and connection failures
+/// `Code::Unavailable`. Other errors become `Code::Internal`, without
+/// exposing the database message.
+#[cfg(feature = "sqlx")]
+impl ToRichStatus for sqlx::Error {
+    fn to_rich_status(&self) -> Status {
+        use sqlx::error::ErrorKind;
+
+        let db_err = match self {
+            sqlx::Error::Database(db_err) => db_err,
+            sqlx::Error::RowNotFound => return Status::new(Code::NotFound, "row not found"),
+            sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::WorkerCrashed
+            | sqlx::Error::Io(_) => {
+                return Status::new(Code::Unavailable, "database unavailable");
+            }
+            _ => return Status::new(Code::Internal, "database error"),
+        };
+
+        let table = db_err.table().unwrap_or_default();
+        let constraint = db_err.constraint().unwrap_or_default();
+
+        let mut err_details = ErrorDetails::new();
+
+        let (code, message) = match db_err.kind() {
+            ErrorKind::UniqueViolation => {
+                err_details.set_resource_info(table, constraint, "", db_err.message());
+                (Code::AlreadyExists, "resource already exists")
+            }
+            ErrorKind::ForeignKeyViolation => {
+                err_details.add_precondition_failure_violation(
+                    "FOREIGN_KEY",
+                    constraint,
+                    db_err.message(),
+                );
+                (
+                    Code::FailedPrecondition,
+                    "referenced resource does not exist",
+                )
+            }
+            ErrorKind::NotNullViolation | ErrorKind::CheckViolation => {
+                err_details.add_bad_request_violation(constraint, db_err.message());
+                (Code::InvalidArgument, "invalid argument")
+            }
+            _ => return Status::new(Code::Internal, "database error"),
+        };
+
+        Status::with_error_details(code, message, err_details)
+    }
+}
+
+/// Maps `reqwest::Error`s from calls to upstream services to rich
+/// statuses. Timeouts become `Code::DeadlineExceeded`, connection failures
+/// `Code::Unavailable`, and error responses the code matching their HTTP
+/// status. Other errors become `Code::Internal`.
+#[cfg(feature = "reqwest")]
+impl ToRichStatus for reqwest::Error {
+    fn to_rich_status(&self) -> Status {
+        let code = if self.is_timeout() {
+            Code::DeadlineExceeded
+        } else if self.is_connect() {
+            Code::Unavailable
+        } else if let Some(status) = self.status() {
+            http_to_code(status)
+        } else {
+            Code::Internal
+        };
+
+        Status::new(code, format!("upstream error: {self}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tonic::{Code, Status};
+
+    use super::{IntoRichResult, ToRichStatus};
+
+    struct NotReady;
+
+    impl ToRichStatus for NotReady {
+        fn to_rich_status(&self) -> Status {
+            Status::unavailable("not ready")
+        }
+    }
+
+    #[test]
+    fn into_rich_result() {
+        let result: Result<(), NotReady> = Err(NotReady);
+
+        let status = match result.into_rich_result() {
+            Ok(_) => panic!("into_rich_result returned Ok for an error"),
+            Err(status) => status,
+        };
+
+        assert!(
+            status.code() == Code::Unavailable,
+            "into_rich_result did not convert the error"
+        );
+    }
+
+    #[cfg(feature = "std-io")]
+    #[test]
+    fn io_error_statuses() {
+        use std::io::{Error, ErrorKind};
+
+        let cases = [
+            (ErrorKind::NotFound, Code::NotFound),
+            (ErrorKind::PermissionDenied, Code::PermissionDenied),
+            (ErrorKind::InvalidData, Code::InvalidArgument),
+            (ErrorKind::TimedOut, Code::DeadlineExceeded),
+            (ErrorKind::ConnectionRefused, Code::Unavailable),
+            (ErrorKind::Other, Code::Internal),
+        ];
+
+        for (kind, code) in cases {
+            let status = Error::new(kind, "io failure").to_rich_status();
+
+            println!("{:?} -> {:?}", kind, status);
+
+            assert!(status.code() == code, "io error status has unexpected code");
+        }
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn sqlx_error_statuses() {
+        use std::borrow::Cow;
+        use std::error::Error as StdError;
+        use std::fmt;
+
+        use sqlx::error::{DatabaseError, ErrorKind};
+
+        use super::super::WithErrorDetails;
+
+        #[derive(Debug)]
+        struct ConstraintError {
+            unique: bool,
+        }
+
+        impl fmt::Display for ConstraintError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("constraint violated")
+            }
+        }
+
+        impl StdError for ConstraintError {}
+
+        impl DatabaseError for ConstraintError {
+            fn message(&self) -> &str {
+                "constraint violated"
+            }
+
+            fn code(&self) -> Option<Cow<'_, str>> {
+                None
+            }
+
+            fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+                self
+            }
+
+            fn as_error_mut(&mut self) -> &mut (dyn StdError + Send + Sync + 'static) {
+                self
+            }
+
+            fn into_error(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static> {
+                self
+            }
+
+            fn constraint(&self) -> Option<&str> {
+                Some("users_email_key")
+            }
+
+            fn table(&self) -> Option<&str> {
+                Some("users")
+            }
+
+            fn kind(&self) -> ErrorKind {
+                if self.unique {
+                    ErrorKind::UniqueViolation
+                } else {
+                    ErrorKind::ForeignKeyViolation
+                }
+            }
+        }
+
+        let unique =
+            sqlx::Error::Database(Box::new(ConstraintError { unique: true })).to_rich_status();
+
+        println!("unique violation -> {:?}", unique);
+
+        let res_info = unique.get_details_resource_info();
+
+        assert!(
+            unique.code() == Code::AlreadyExists
+                && res_info.is_some_and(|info| info.resource_type == "users"),
+            "unique violation status differs from expected result"
+        );
+
+        let foreign_key =
+            sqlx::Error::Database(Box::new(ConstraintError { unique: false })).to_rich_status();
+
+        assert!(
+            foreign_key.code() == Code::FailedPrecondition
+                && foreign_key.get_details_precondition_failure().is_some(),
+            "foreign key violation status differs from expected result"
+        );
+
+        let not_found = sqlx::Error::RowNotFound.to_rich_status();
+
+        assert!(
+            not_found.code() == Code::NotFound,
+            "row not found status differs from expected result"
+        );
+
+        let timed_out = sqlx::Error::PoolTimedOut.to_rich_status();
+
+        assert!(
+            timed_out.code() == Code::Unavailable,
+            "pool timed out status differs from expected result"
+        );
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[test]
+    fn reqwest_error_statuses() {
+        use tonic::codegen::http;
+
+        let response = http::Response::builder()
+            .status(429)
+            .body("rate exceeded")
+            .unwrap();
+
+        let err = match reqwest::Response::from(response).error_for_status() {
+            Ok(_) => panic!("error_for_status returned Ok for a 429 response"),
+            Err(err) => err,
+        };
+
+        let status = err.to_rich_status();
+
+        println!("429 response -> {:?}", status);
+
+        assert!(
+            status.code() == Code::ResourceExhausted,
+            "429 response status has unexpected code"
+        );
+
+        let err = match reqwest::Client::new().get("not a url").build() {
+            Ok(_) => panic!("request with invalid URL was built"),
+            Err(err) => err,
+        };
+
+        assert!(
+            err.to_rich_status().code() == Code::Internal,
+            "builder error status has unexpected code"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt;
+
+#[cfg(feature = "tonic")]
+use prost_types::Any;
+#[cfg(feature = "tonic")]
+use tonic::Code;
+
+#[cfg(feature = "tonic")]
+use super::std_messages::*;
+#[cfg(feature = "tonic")]
+use super::type_url_prefix::canonical_type_url;
+
+/// Emits a `DEBUG` event describing a `tonic::Status` generated with error
+/// details. Each standard error message has its own field, set to `true` if
+/// present, and details with unknown type URLs are counted.
+#[cfg(feature = "tonic")]
+pub(crate) fn status_built(code: Code, message: &str, details: &[Any]) {
+    let has = |type_url: &str| {
+        details
+            .iter()
+            .any(|any| canonical_type_url(&any.type_url) == type_url)
+    };
+
+    let known = [
+        RetryInfo::TYPE_URL,
+        DebugInfo::TYPE_URL,
+        QuotaFailure::TYPE_URL,
+        ErrorInfo::TYPE_URL,
+        PreconditionFailure::TYPE_URL,
+        BadRequest::TYPE_URL,
+        RequestInfo::TYPE_URL,
+        ResourceInfo::TYPE_URL,
+        Help::TYPE_URL,
+        LocalizedMessage::TYPE_URL,
+        HttpRequest::TYPE_URL,
+        HttpResponse::TYPE_URL,
+    ];
+
+    let unknown_details = details
+        .iter()
+        .filter(|any| !known.contains(&canonical_type_url(&any.type_url)))
+        .count();
+
+    tracing::debug!(
+        code = ?code,
+        status_message = message,
+        retry_info = has(RetryInfo::TYPE_URL),
+        debug_info = has(DebugInfo::TYPE_URL),
+        quota_failure = has(QuotaFailure::TYPE_URL),
+        error_info = has(ErrorInfo::TYPE_URL),
+        precondition_failure = has(PreconditionFailure::TYPE_URL),
+        bad_request = has(BadRequest::TYPE_URL),
+        request_info = has(RequestInfo::TYPE_URL),
+        resource_info = has(ResourceInfo::TYPE_URL),
+        help = has(Help::TYPE_URL),
+        localized_message = has(LocalizedMessage::TYPE_URL),
+        http_request = has(HttpRequest::TYPE_URL),
+        http_response = has(HttpResponse::TYPE_URL),
+        unknown_details,
+        "generated status with error details"
+    );
+}
+
+/// Emits a `WARN` event for malformed error details that were discarded
+/// during extraction.
+pub(crate) fn decode_failed(target: &str, err: &dyn fmt::Display) {
+    tracing::warn!(
+        target_detail = target,
+        error = %err,
+        "discarded malformed error details"
+    );
+}
+
+#[cfg(all(test, feature = "tonic"))]
+mod tests {
+
+    use std::sync::{Arc, Mutex};
+    use tonic::{Code, Status};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Level, Metadata};
+
+    use super::super::{ErrorDetails, HttpRequest, WithErrorDetails};
+
+    /// Records the level and message of every event, followed by the HTTP
+    /// request and unknown details fields, if any.
+    #[derive(Clone, Default)]
+    struct Recorder {
+        events: Arc<Mutex<Vec<(Level, String)>>>,
+    }
+
+    struct MessageVisitor<'a>(&'a mut String);
+
+    impl tracing::field::Visit for MessageVisitor<'_> {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            match field.name() {
+                "message" => *self.0 = format!("{value:?}{}", self.0),
+                "http_request" | "unknown_details" => {
+                    self.0.push_str(&format!(" {}={value:?}", field.name()))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    impl tracing::Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut message = String::new();
+            event.record(&mut MessageVisitor(&mut message));
+            self.events
+                .lock()
+                .unwrap()
+                .push((*event.metadata().level(), message));
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn emit_events() {
+        let recorder = Recorder::default();
+
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let status = Status::with_error_details(
+                Code::InvalidArgument,
+                "bad request",
+                ErrorDetails::with_bad_request_violation("field", "description")
+                    .set_http_request(HttpRequest::new("POST", "/v1/orders"))
+                    .clone(),
+            );
+
+            assert!(status.get_details_bad_request().is_some());
+
+            let malformed = Status::with_details(Code::Internal, "malformed", vec![1, 2, 3].into());
+
+            assert!(malformed.get_details_bad_request().is_none());
+        });
+
+        let formatted = format!("{:?}", recorder.events.lock().unwrap());
+
+        println!("recorded events -> {formatted}");
+
+        let expected = "[(Level(Debug), \"generated status with error details http_request=true unknown_details=0\"), (Level(Warn), \"discarded malformed error details\")]";
+
+        assert!(
+            formatted.eq(expected),
+            "recorded events differ from expected result"
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+
+use super::std_messages::*;
+
+#[cfg(feature = "std")]
+static GLOBAL_PREFIX: RwLock<TypeUrlPrefix> = RwLock::new(TypeUrlPrefix::standard());
+
+/// Incremented whenever a prefix is installed, so threads can tell when
+/// their copy of the global prefix is outdated.
+#[cfg(feature = "std")]
+static GLOBAL_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "std")]
+thread_local! {
+    static LOCAL_PREFIX: RefCell<(usize, TypeUrlPrefix)> =
+        const { RefCell::new((0, TypeUrlPrefix::standard())) };
+}
+
+/// Type URLs of the standard error messages, with the standard prefix.
+const STANDARD_TYPE_URLS: [&str; 12] = [
+    RetryInfo::TYPE_URL,
+    DebugInfo::TYPE_URL,
+    QuotaFailure::TYPE_URL,
+    ErrorInfo::TYPE_URL,
+    PreconditionFailure::TYPE_URL,
+    BadRequest::TYPE_URL,
+    RequestInfo::TYPE_URL,
+    ResourceInfo::TYPE_URL,
+    Help::TYPE_URL,
+    LocalizedMessage::TYPE_URL,
+    HttpRequest::TYPE_URL,
+    HttpResponse::TYPE_URL,
+];
+
+/// Prefix of the type URLs used when packing the standard error messages,
+/// `type.googleapis.com/` by default. Can be changed for deployments that
+/// serve their message types from a private registry. Once installed with
+/// [`TypeUrlPrefix::install`], the prefix is used whenever error details are
+/// encoded.
+///
+/// When unpacking, the standard error messages are recognized by the last
+/// segment of their type URL, such as `google.rpc.BadRequest`, whatever the
+/// prefix.
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use tonic_richer_error::{BadRequest, ErrorDetails, TypeUrlPrefix};
+///
+/// let prefix = TypeUrlPrefix::new("type.internal.example.com");
+///
+/// assert_eq!(
+///     prefix.type_url(BadRequest::TYPE_URL),
+///     "type.internal.example.com/google.rpc.BadRequest"
+/// );
+///
+/// prefix.install();
+///
+/// let encoded = ErrorDetails::with_bad_request_violation("field", "description")
+///     .encode_status(3, "bad request");
+///
+/// let err_details = ErrorDetails::decode_status(&encoded).unwrap();
+///
+/// assert!(err_details.bad_request.is_some());
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeUrlPrefix {
+    prefix: Cow<'static, str>,
+}
+
+impl TypeUrlPrefix {
+    /// The standard type URL prefix.
+    pub const STANDARD: &'static str = "type.googleapis.com/";
+
+    /// Creates a [`TypeUrlPrefix`] with the standard prefix.
+    pub const fn standard() -> Self {
+        TypeUrlPrefix {
+            prefix: Cow::Borrowed(TypeUrlPrefix::STANDARD),
+        }
+    }
+
+    /// Creates a [`TypeUrlPrefix`] with a custom prefix. A trailing `/` is
+    /// appended if missing.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        let mut prefix: String = prefix.into();
+
+        if !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+
+        TypeUrlPrefix {
+            prefix: Cow::Owned(prefix),
+        }
+    }
+
+    /// Returns the prefix, including the trailing `/`.
+    pub fn as_str(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns `true` if this is the standard prefix.
+    pub fn is_standard(&self) -> bool {
+        self.prefix == TypeUrlPrefix::STANDARD
+    }
+
+    /// Returns the given type URL, with its prefix replaced by this one.
+    pub fn type_url(&self, type_url: &str) -> String {
+        format!("{}{}", self.prefix, type_name(type_url))
+    }
+
+    /// Installs the prefix globally, replacing the previously installed one.
+    #[cfg(feature = "std")]
+    pub fn install(self) {
+        let mut prefix = match GLOBAL_PREFIX.write() {
+            Ok(prefix) => prefix,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        *prefix = self;
+        GLOBAL_GENERATION.fetch_add(1, Ordering::Release);
+    }
+
+    /// Returns a copy of the globally installed prefix.
+    #[cfg(feature = "std")]
+    pub fn global() -> Self {
+        match GLOBAL_PREFIX.read() {
+            Ok(prefix) => prefix.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+}
+
+impl Default for TypeUrlPrefix {
+    fn default() -> Self {
+        TypeUrlPrefix::standard()
+    }
+}
+
+/// Returns the last segment of a type URL, such as `google.rpc.BadRequest`.
+fn type_name(type_url: &str) -> &str {
+    match type_url.rfind('/') {
+        Some(index) => &type_url[index + 1..],
+        None => type_url,
+    }
+}
+
+/// Returns the type URL of a standard error message, with the globally
+/// installed prefix. Each thread keeps a copy of the prefix, only reading the
+/// global one again after a new prefix is installed. Without the `std`
+/// feature, no prefix can be installed, so the standard one is used.
+pub(crate) fn packed_type_url(type_url: &'static str) -> String {
+    #[cfg(feature = "std")]
+    {
+        let generation = GLOBAL_GENERATION.load(Ordering::Acquire);
+
+        let packed = LOCAL_PREFIX.with(|local| {
+            let mut local = local.borrow_mut();
+
+            if local.0 != generation {
+                *local = (generation, TypeUrlPrefix::global());
+            }
+
+            (!local.1.is_standard()).then(|| local.1.type_url(type_url))
+        });
+
+        if let Some(packed) = packed {
+            return packed;
+        }
+    }
+
+    type_url.to_string()
+}
+
+/// Maps the type URL of a standard error message with any prefix to its
+/// standard form. Other type URLs are returned unchanged.
+pub(crate) fn canonical_type_url(type_url: &str) -> &str {
+    if type_url.starts_with(TypeUrlPrefix::STANDARD) {
+        return type_url;
+    }
+
+    let name = type_name(type_url);
+
+    STANDARD_TYPE_URLS
+        .into_iter()
+        .find(|standard| type_name(standard) == name)
+        .unwrap_or(type_url)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use prost_types::Any;
+
+    use super::super::{BadRequest, ErrorDetail, FromAny, IntoAny};
+    use super::{canonical_type_url, TypeUrlPrefix};
+
+    #[test]
+    fn custom_type_url_prefix() {
+        let prefix = TypeUrlPrefix::new("type.internal.example.com");
+
+        let any = BadRequest::with_violation("field", "description").into_any();
+
+        let any = Any {
+            type_url: prefix.type_url(&any.type_url),
+            value: any.value,
+        };
+
+        println!("type URL with custom prefix -> {}", any.type_url);
+
+        assert!(
+            any.type_url == "type.internal.example.com/google.rpc.BadRequest",
+            "type URL with custom prefix differs from expected result"
+        );
+
+        let detail = match ErrorDetail::from_any((&any).into()) {
+            Ok(detail) => detail,
+            Err(err) => panic!("Error decoding detail with custom prefix: {:?}", err),
+        };
+
+        let formatted = format!("{:?}", detail);
+
+        let expected = "BadRequest(BadRequest { field_violations: [FieldViolation { field: \"field\", description: \"description\", reason: \"\", localized_message: None }] })";
+
+        assert!(
+            formatted.eq(expected),
+            "detail with custom prefix differs from expected result"
+        );
+
+        assert!(
+            canonical_type_url("type.internal.example.com/example.QuotaPlan")
+                == "type.internal.example.com/example.QuotaPlan",
+            "unknown type URL was changed"
+        );
+
+        #[cfg(feature = "std")]
+        assert!(
+            TypeUrlPrefix::global().is_standard(),
+            "default prefix is not the standard one"
+        );
+    }
+}
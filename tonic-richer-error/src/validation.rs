@@ -0,0 +1,121 @@
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use super::std_messages::{BadRequest, FieldViolation};
+
+impl From<&ValidationErrors> for BadRequest {
+    /// Generates a [`BadRequest`] with one [`FieldViolation`] per failed
+    /// field. Nested fields are joined with dots, and list items are indexed
+    /// with brackets, as in `items[1].sku`. The description is the error
+    /// message, or the error code if no message was set. Violations are sorted
+    /// by field path.
+    /// # Examples
+    ///
+    /// ```
+    /// use tonic_richer_error::BadRequest;
+    /// use validator::{ValidationError, ValidationErrors};
+    ///
+    /// let mut errors = ValidationErrors::new();
+    /// errors.add("email", ValidationError::new("email"));
+    ///
+    /// let bad_request = BadRequest::from(errors);
+    ///
+    /// assert_eq!(bad_request.field_violations[0].field, "email");
+    /// ```
+    fn from(errors: &ValidationErrors) -> Self {
+        let mut violations = Vec::new();
+
+        collect_violations(errors, "", &mut violations);
+
+        violations.sort_by(|a, b| a.field.cmp(&b.field));
+
+        BadRequest::new(violations)
+    }
+}
+
+impl From<ValidationErrors> for BadRequest {
+    fn from(errors: ValidationErrors) -> Self {
+        BadRequest::from(&errors)
+    }
+}
+
+/// Recursively walks the validation errors, pushing one [`FieldViolation`]
+/// per failed field.
+fn collect_violations(
+    errors: &ValidationErrors,
+    prefix: &str,
+    violations: &mut Vec<FieldViolation>,
+) {
+    for (field, kind) in errors.errors().iter() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{prefix}.{field}")
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                let description = field_errors
+                    .iter()
+                    .map(|err| match &err.message {
+                        Some(message) => message.to_string(),
+                        None => err.code.to_string(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join("; ");
+
+                violations.push(FieldViolation::new(path, description));
+            }
+            ValidationErrorsKind::Struct(nested) => {
+                collect_violations(nested, &path, violations);
+            }
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items.iter() {
+                    collect_violations(nested, &format!("{path}[{index}]"), violations);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+    use super::super::BadRequest;
+
+    #[test]
+    fn gen_bad_request_from_validation_errors() {
+        let mut item_errors = ValidationErrors::new();
+        item_errors.add("sku", ValidationError::new("range"));
+
+        let mut address_errors = ValidationErrors::new();
+        let mut city_error = ValidationError::new("length");
+        city_error.message = Some("city is too long".into());
+        address_errors.add("city", city_error);
+
+        let mut errors = ValidationErrors::new();
+        errors.add("email", ValidationError::new("email"));
+        errors.add("email", ValidationError::new("length"));
+        errors.errors_mut().insert(
+            "address",
+            ValidationErrorsKind::Struct(Box::new(address_errors)),
+        );
+        errors.errors_mut().insert(
+            "items",
+            ValidationErrorsKind::List(BTreeMap::from([(1, Box::new(item_errors))])),
+        );
+
+        let formatted = format!("{:?}", BadRequest::from(errors));
+
+        println!("BadRequest generated from ValidationErrors -> {formatted}");
+
+        let expected = "BadRequest { field_violations: [FieldViolation { field: \"address.city\", description: \"city is too long\", reason: \"\", localized_message: None }, FieldViolation { field: \"email\", description: \"email; length\", reason: \"\", localized_message: None }, FieldViolation { field: \"items[1].sku\", description: \"range\", reason: \"\", localized_message: None }] }";
+
+        assert!(
+            formatted.eq(expected),
+            "BadRequest from ValidationErrors differs from expected result"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "json")]
+
+// Installing a policy changes global state, so this test runs in its own
+// binary, isolated from the unit tests.
+
+use std::collections::BTreeMap;
+
+use tonic::Code;
+use tonic_richer_error::{ErrorDetail, ErrorDetails, ErrorDetailsPolicy, ErrorInfo, RpcStatus};
+
+#[test]
+fn encode_with_installed_policy() {
+    ErrorDetailsPolicy::new()
+        .redact_debug_info()
+        .deny_metadata_key("internal_host")
+        .install();
+
+    let metadata = BTreeMap::from([
+        ("internal_host".to_string(), "db-7.internal".to_string()),
+        ("quota_limit".to_string(), "100".to_string()),
+    ]);
+
+    let mut err_details = ErrorDetails::new();
+
+    err_details
+        .set_error_info("QUOTA_EXCEEDED", "example.local", metadata.clone())
+        .set_debug_info(vec!["main.rs:1".into()], "stack trace");
+
+    let json = err_details.to_status_json(Code::ResourceExhausted, "quota exceeded");
+
+    let expected = r#"{"code":8,"message":"quota exceeded","details":[{"@type":"type.googleapis.com/google.rpc.ErrorInfo","reason":"QUOTA_EXCEEDED","domain":"example.local","metadata":{"quota_limit":"100"}}]}"#;
+
+    assert!(
+        json.eq(expected),
+        "JSON status with installed policy differs from expected result"
+    );
+
+    let status = RpcStatus::new(
+        8,
+        "quota exceeded",
+        vec![
+            ErrorInfo::new("QUOTA_EXCEEDED", "example.local", metadata).into(),
+            ErrorDetail::DebugInfo(err_details.debug_info.clone().unwrap()),
+        ],
+    );
+
+    let decoded = match RpcStatus::decode(&status.encode_to_vec()) {
+        Ok(decoded) => decoded,
+        Err(err) => panic!("Error decoding RpcStatus: {:?}", err),
+    };
+
+    let formatted = format!("{:?}", decoded.details);
+
+    let expected = "[ErrorInfo(ErrorInfo { reason: \"QUOTA_EXCEEDED\", domain: \"example.local\", metadata: {\"quota_limit\": \"100\"} })]";
+
+    assert!(
+        formatted.eq(expected),
+        "RpcStatus encoded with installed policy differs from expected result"
+    );
+
+    ErrorDetailsPolicy::new().install();
+}
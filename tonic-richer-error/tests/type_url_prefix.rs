@@ -0,0 +1,112 @@
+#![cfg(feature = "tonic")]
+
+// Installing a prefix changes global state, so this test runs in its own
+// binary, isolated from the unit tests.
+
+use prost::Message;
+use tonic::{Code, Status};
+use tonic_richer_error::{
+    BadRequest, CustomErrorDetail, ErrorDetails, HttpRequest, HttpResponse, TypeUrlPrefix,
+    WithErrorDetails,
+};
+
+#[derive(Clone, PartialEq, Message)]
+struct QuotaPlan {
+    #[prost(string, tag = "1")]
+    plan: String,
+}
+
+impl CustomErrorDetail for QuotaPlan {
+    const TYPE_URL: &'static str = "type.example.com/example.QuotaPlan";
+}
+
+/// Returns the type URL `HttpRequest` details are packed with.
+fn http_request_type_url() -> String {
+    let mut err_details = ErrorDetails::new();
+
+    err_details.set_http_request(HttpRequest::new("GET", "/v1/orders"));
+
+    err_details.unknown_details[0].type_url.clone()
+}
+
+#[test]
+fn read_details_with_installed_prefix() {
+    TypeUrlPrefix::new("type.internal.example.com").install();
+
+    let mut err_details = ErrorDetails::new();
+
+    err_details
+        .set_http_request(HttpRequest::new("GET", "/v1/orders"))
+        .set_http_request(HttpRequest::new("POST", "/v1/orders"))
+        .set_http_response(HttpResponse::new(503, "Service Unavailable"))
+        .set_http_response(HttpResponse::new(502, "Bad Gateway"))
+        .add_custom(QuotaPlan {
+            plan: "free".into(),
+        });
+
+    let type_urls: Vec<&str> = err_details
+        .unknown_details
+        .iter()
+        .map(|any| any.type_url.as_str())
+        .collect();
+
+    let expected = [
+        "type.internal.example.com/google.rpc.HttpRequest",
+        "type.internal.example.com/google.rpc.HttpResponse",
+        "type.example.com/example.QuotaPlan",
+    ];
+
+    assert!(
+        type_urls.eq(&expected),
+        "details with installed prefix were duplicated or not prefixed"
+    );
+
+    assert!(
+        err_details
+            .get_http_request()
+            .is_some_and(|http_request| http_request.method == "POST"),
+        "HTTP request not read back with installed prefix"
+    );
+
+    assert!(
+        err_details
+            .get_http_response()
+            .is_some_and(|http_response| http_response.status == 502),
+        "HTTP response not read back with installed prefix"
+    );
+
+    assert!(
+        err_details
+            .get_custom::<QuotaPlan>()
+            .is_some_and(|quota_plan| quota_plan.plan == "free"),
+        "custom detail not read back with installed prefix"
+    );
+
+    let status = Status::with_error_details(
+        Code::InvalidArgument,
+        "bad request",
+        ErrorDetails::with_bad_request_violation("field", "description"),
+    );
+
+    assert!(
+        status
+            .error_details_iter()
+            .next()
+            .is_some_and(|detail| detail.is::<BadRequest>()),
+        "raw detail not matched with installed prefix"
+    );
+
+    let type_url = std::thread::spawn(http_request_type_url).join().unwrap();
+
+    assert!(
+        type_url == "type.internal.example.com/google.rpc.HttpRequest",
+        "installed prefix not used by another thread"
+    );
+
+    TypeUrlPrefix::standard().install();
+
+    assert!(
+        http_request_type_url() == HttpRequest::TYPE_URL,
+        "standard prefix not used after being installed again"
+    );
+}
@@ -0,0 +1,12 @@
+#!/bin/sh
+# Encodes each `.txtpb` vector source into its `.bin` golden vector with
+# `protoc --encode`, which uses the reference C++ protobuf implementation.
+set -e
+cd "$(dirname "$0")"
+PROTOC="${PROTOC:-protoc}"
+INCLUDE="$(dirname "$(dirname "$(command -v "$PROTOC")")")/include"
+for source in *.txtpb; do
+    "$PROTOC" -I../proto -I"$INCLUDE" --encode=google.rpc.Status \
+        ../proto/status.proto ../proto/error_details.proto ../proto/http.proto \
+        < "$source" > "${source%.txtpb}.bin"
+done